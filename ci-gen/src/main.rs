@@ -3,6 +3,7 @@ use std::fs::File;
 use std::io::Read;
 use std::io::Write;
 
+use crate::actions::cargo_build;
 use crate::actions::cargo_check;
 use crate::actions::cargo_doc;
 use crate::actions::cargo_miri_setup;
@@ -203,6 +204,36 @@ fn miri_test_job() -> Job {
     }
 }
 
+fn no_std_job() -> Job {
+    let target = "thumbv7em-none-eabi";
+    let mut steps = Vec::new();
+    steps.push(checkout_sources());
+    steps.push(rust_install_toolchain(RustToolchain::Stable));
+    steps.push(Step::run(
+        "Install target",
+        &format!("rustup target add {}", target),
+    ));
+    steps.push(cargo_build(
+        "cargo build",
+        &format!("-p protobuf-no-std-test --target {}", target),
+    ));
+    steps.push(cargo_test(
+        "cargo test",
+        "-p protobuf --no-default-features --lib",
+    ));
+    steps.push(cargo_test(
+        "cargo test no-std-test",
+        "-p protobuf-no-std-test",
+    ));
+    Job {
+        id: "no-std".to_owned(),
+        name: "no_std".to_owned(),
+        runs_on: LINUX.ghwf.to_owned(),
+        steps,
+        ..Job::default()
+    }
+}
+
 // https://github.com/megalinter/megalinter
 fn super_linter_job() -> Job {
     let mut steps = Vec::new();
@@ -267,6 +298,8 @@ fn jobs() -> Yaml {
 
     r.push(miri_test_job());
 
+    r.push(no_std_job());
+
     r.push(super_linter_job());
 
     r.push(rustfmt_job());
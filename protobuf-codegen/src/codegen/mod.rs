@@ -244,6 +244,8 @@ impl Codegen {
         parser.inputs(&self.inputs);
         parser.includes(&self.includes);
         parser.protoc_extra_args(&self.protoc_extra_args);
+        // Comments are emitted as doc comments in generated code.
+        parser.include_source_info();
 
         if self.capture_stderr {
             parser.capture_stderr();
@@ -12,6 +12,30 @@ use protobuf::reflect::MessageDescriptor;
 use protobuf::reflect::OneofDescriptor;
 
 /// Dynamic callback to customize code generation.
+///
+/// Returned `Customize` is applied on top of customize of the parent element,
+/// so options can be selected per file, message, field etc.
+///
+/// # Examples
+///
+/// Use `bytes::Bytes` only for some fields:
+///
+/// ```
+/// use protobuf::reflect::FieldDescriptor;
+/// use protobuf_codegen::Customize;
+/// use protobuf_codegen::CustomizeCallback;
+///
+/// struct LargeBlobs;
+///
+/// impl CustomizeCallback for LargeBlobs {
+///     fn field(&self, field: &FieldDescriptor) -> Customize {
+///         Customize::default().tokio_bytes(field.name().ends_with("_blob"))
+///     }
+/// }
+///
+/// let mut codegen = protobuf_codegen::Codegen::new();
+/// codegen.customize_callback(LargeBlobs);
+/// ```
 pub trait CustomizeCallback: 'static {
     fn file(&self, file: &FileDescriptor) -> Customize {
         let _ = file;
@@ -94,6 +118,30 @@ pub struct Customize {
     pub(crate) generate_accessors: Option<bool>,
     /// When false, `get_` is not generated even if `syntax = "proto2"`
     pub(crate) generate_getter: Option<bool>,
+    /// Generate `foo_opt()` returning `None` for unset fields
+    pub(crate) generate_opt_getter: Option<bool>,
+    /// Generate `FooBuilder` for each message `Foo`
+    pub(crate) generate_builders: Option<bool>,
+    /// Generate `FOO_FIELD_NUMBER` constants for each message field
+    pub(crate) generate_field_numbers: Option<bool>,
+    /// Derive `serde::Serialize` and `serde::Deserialize`
+    pub(crate) serde_derive: Option<bool>,
+    /// Mark generated enums `#[non_exhaustive]`
+    pub(crate) enum_non_exhaustive: Option<bool>,
+    /// Mark generated enums `#[repr(i32)]`
+    pub(crate) enum_repr_i32: Option<bool>,
+    /// Derive `Debug` for generated messages
+    pub(crate) derive_debug: Option<bool>,
+    /// Derive `Hash` and `Eq` for generated messages
+    pub(crate) derive_hash: Option<bool>,
+    /// Store recursive singular message fields as `ArcMessageField`
+    pub(crate) arc_recursive_fields: Option<bool>,
+    /// Store singular message fields with `[lazy = true]` as `LazyMessage`
+    pub(crate) lazy_message_fields: Option<bool>,
+    /// Prefix of generated message and enum names
+    pub(crate) type_name_prefix: Option<String>,
+    /// Suffix of generated message and enum names
+    pub(crate) type_name_suffix: Option<String>,
     /// Use `bytes::Bytes` for `bytes` fields
     pub(crate) tokio_bytes: Option<bool>,
     /// Use `bytes::Bytes` for `string` fields
@@ -106,6 +154,8 @@ pub struct Customize {
     ///
     /// This option will likely be on by default in rust-protobuf version 3.
     pub(crate) gen_mod_rs: Option<bool>,
+    /// Write all generated modules into single `mod.rs`.
+    pub(crate) gen_single_file: Option<bool>,
     /// Used internally to generate protos bundled in protobuf crate
     /// like `descriptor.proto`
     pub(crate) inside_protobuf: Option<bool>,
@@ -138,11 +188,175 @@ impl Customize {
         self
     }
 
+    /// Generate `foo_opt()` accessors for fields with explicit presence
+    /// (proto2 optional fields, proto3 `optional` fields and oneof fields).
+    ///
+    /// The accessor returns `None` when the field is not set,
+    /// and the same value as `foo()` wrapped in `Some` otherwise,
+    /// so it replaces a pair of `has_foo()` and `foo()` calls.
+    /// For message fields it is equivalent to `foo.as_ref()`.
+    ///
+    /// Accessors are generated in addition to other accessors,
+    /// disable other accessors with
+    /// [`generate_getter`](Self::generate_getter) if needed.
+    pub fn generate_opt_getter(mut self, generate_opt_getter: bool) -> Self {
+        self.generate_opt_getter = Some(generate_opt_getter);
+        self
+    }
+
+    /// Generate `FooBuilder` struct for each message `Foo`.
+    ///
+    /// Builder has a chained setter for each field
+    /// and `build()` function which fails if required fields are not set.
+    pub fn generate_builders(mut self, generate_builders: bool) -> Self {
+        self.generate_builders = Some(generate_builders);
+        self
+    }
+
+    /// Generate an associated constant with the number of each message field,
+    /// e. g. `pub const FOO_BAR_FIELD_NUMBER: u32 = 3;` for field `foo_bar`,
+    /// like `protoc` does for C++ and Java.
+    pub fn generate_field_numbers(mut self, generate_field_numbers: bool) -> Self {
+        self.generate_field_numbers = Some(generate_field_numbers);
+        self
+    }
+
+    /// Derive `serde::Serialize` and `serde::Deserialize` for generated
+    /// messages, enums and oneofs.
+    ///
+    /// Message fields are renamed to `camelCase`, unset optional fields
+    /// are not serialized, oneofs are serialized as externally tagged enums,
+    /// unknown fields are skipped.
+    ///
+    /// Generated code requires `serde` crate with `derive` feature
+    /// and `with-serde` feature of `protobuf` crate.
+    ///
+    /// Note this is not
+    /// [protobuf JSON mapping](https://developers.google.com/protocol-buffers/docs/proto3#json),
+    /// which is implemented by `protobuf-json-mapping` crate.
+    pub fn serde_derive(mut self, serde_derive: bool) -> Self {
+        self.serde_derive = Some(serde_derive);
+        self
+    }
+
+    /// Mark generated enums `#[non_exhaustive]`.
+    ///
+    /// Proto3 enums are open, so new values may be added to the schema,
+    /// and code matching on generated enum should have a catch-all arm.
+    /// Values not known to generated code are preserved in
+    /// `EnumOrUnknown` fields regardless of this option,
+    /// no `Unknown(i32)` variant is generated.
+    ///
+    /// The option is ignored for proto2 enums, which are closed:
+    /// unknown values of proto2 enum fields are stored in unknown fields.
+    pub fn enum_non_exhaustive(mut self, enum_non_exhaustive: bool) -> Self {
+        self.enum_non_exhaustive = Some(enum_non_exhaustive);
+        self
+    }
+
+    /// Mark generated enums `#[repr(i32)]`.
+    ///
+    /// Enum variants always have discriminants equal to proto numbers,
+    /// so with this option `Foo::BAR as i32` is the proto number of `BAR`,
+    /// and enum values can be passed over FFI as C `int32_t`.
+    /// Code generation fails for enums with `allow_alias`,
+    /// which variants have no discriminants.
+    pub fn enum_repr_i32(mut self, enum_repr_i32: bool) -> Self {
+        self.enum_repr_i32 = Some(enum_repr_i32);
+        self
+    }
+
+    /// Derive `Debug` for generated messages, default is true.
+    ///
+    /// Derived `Debug` for messages with many fields takes noticeable
+    /// compile time and code size. When this option is false,
+    /// `Debug` is implemented manually and prints only the message name.
+    /// Such messages can still be printed with `Display`
+    /// which uses text format.
+    ///
+    /// `Clone` and `PartialEq` are always derived,
+    /// because they are required by `Message` trait.
+    pub fn derive_debug(mut self, derive_debug: bool) -> Self {
+        self.derive_debug = Some(derive_debug);
+        self
+    }
+
+    /// Derive `Hash` and `Eq` for generated messages, so they can be used
+    /// as `HashMap` keys.
+    ///
+    /// Code generation fails if a message contains `float`, `double`,
+    /// map or lazy fields, or message fields whose type does not derive `Hash`:
+    /// messages not generated with this option, and well known types.
+    pub fn derive_hash(mut self, derive_hash: bool) -> Self {
+        self.derive_hash = Some(derive_hash);
+        self
+    }
+
+    /// Store recursive singular message fields as
+    /// [`ArcMessageField`](protobuf::ArcMessageField) instead of `MessageField`.
+    ///
+    /// A field is recursive if the containing message can be reached
+    /// from the field type, e. g. `Tree parent = 1;` field of message `Tree`.
+    /// Cloning such a field is cheap, and subtrees can be shared between messages.
+    /// Mutation of a shared subtree clones it.
+    ///
+    /// Accessors are generated like for `MessageField` fields,
+    /// `mut_` accessor clones the subtree if it is shared.
+    pub fn arc_recursive_fields(mut self, arc_recursive_fields: bool) -> Self {
+        self.arc_recursive_fields = Some(arc_recursive_fields);
+        self
+    }
+
+    /// Store singular message fields with `[lazy = true]` option as
+    /// [`LazyMessage`](protobuf::LazyMessage) instead of `MessageField`.
+    ///
+    /// Such fields are decoded on first access.
+    /// Accessors are not generated for these fields,
+    /// `LazyMessage` functions can be used instead.
+    pub fn lazy_message_fields(mut self, lazy_message_fields: bool) -> Self {
+        self.lazy_message_fields = Some(lazy_message_fields);
+        self
+    }
+
+    /// Prepend given prefix to names of generated messages and enums.
+    ///
+    /// This is useful when types from several files are reexported
+    /// from a single module, and some of them have the same name.
+    ///
+    /// The option applies to whole file: to be consistent in references
+    /// from other files, it is read only from `Customize` passed to codegen
+    /// and from `rustproto.type_name_prefix_all` file option,
+    /// but not from `CustomizeCallback`.
+    /// Files of `google.protobuf` package are bundled in `protobuf` crate,
+    /// so their names are not changed.
+    pub fn type_name_prefix(mut self, type_name_prefix: &str) -> Self {
+        self.type_name_prefix = Some(type_name_prefix.to_owned());
+        self
+    }
+
+    /// Append given suffix to names of generated messages and enums.
+    ///
+    /// See [`type_name_prefix`](Self::type_name_prefix) for details.
+    pub fn type_name_suffix(mut self, type_name_suffix: &str) -> Self {
+        self.type_name_suffix = Some(type_name_suffix.to_owned());
+        self
+    }
+
+    /// Use `bytes::Bytes` instead of `Vec<u8>` for `bytes` fields.
+    ///
+    /// Parsing from `Bytes` with `parse_from_tokio_bytes` does not copy
+    /// these fields. The option can be enabled for individual fields with
+    /// [`CustomizeCallback::field`] or `rustproto.tokio_bytes_field` option.
+    ///
+    /// Generated code requires `with-bytes` feature of `protobuf` crate.
     pub fn tokio_bytes(mut self, tokio_bytes: bool) -> Self {
         self.tokio_bytes = Some(tokio_bytes);
         self
     }
 
+    /// Use `protobuf::Chars` instead of `String` for `string` fields.
+    ///
+    /// Like [`tokio_bytes`](Self::tokio_bytes), can be enabled per field.
     pub fn tokio_bytes_for_string(mut self, tokio_bytes_for_string: bool) -> Self {
         self.tokio_bytes_for_string = Some(tokio_bytes_for_string);
         self
@@ -165,6 +379,17 @@ impl Customize {
         self
     }
 
+    /// Generate single `mod.rs` file with modules for all `.proto` files inline,
+    /// instead of a file per `.proto` file.
+    ///
+    /// This is convenient to include generated code from cargo output directory
+    /// with a single `include!`. When this option is set,
+    /// [`gen_mod_rs`](Self::gen_mod_rs) is ignored.
+    pub fn gen_single_file(mut self, gen_single_file: bool) -> Self {
+        self.gen_single_file = Some(gen_single_file);
+        self
+    }
+
     /// Generate code bundled in protobuf crate. Regular users don't need this option.
     pub fn inside_protobuf(mut self, inside_protobuf: bool) -> Self {
         self.inside_protobuf = Some(inside_protobuf);
@@ -182,6 +407,42 @@ impl Customize {
         if let Some(v) = that.generate_getter {
             self.generate_getter = Some(v);
         }
+        if let Some(v) = that.generate_opt_getter {
+            self.generate_opt_getter = Some(v);
+        }
+        if let Some(v) = that.generate_builders {
+            self.generate_builders = Some(v);
+        }
+        if let Some(v) = that.generate_field_numbers {
+            self.generate_field_numbers = Some(v);
+        }
+        if let Some(v) = that.serde_derive {
+            self.serde_derive = Some(v);
+        }
+        if let Some(v) = that.enum_non_exhaustive {
+            self.enum_non_exhaustive = Some(v);
+        }
+        if let Some(v) = that.enum_repr_i32 {
+            self.enum_repr_i32 = Some(v);
+        }
+        if let Some(v) = that.derive_debug {
+            self.derive_debug = Some(v);
+        }
+        if let Some(v) = that.derive_hash {
+            self.derive_hash = Some(v);
+        }
+        if let Some(v) = that.arc_recursive_fields {
+            self.arc_recursive_fields = Some(v);
+        }
+        if let Some(v) = that.lazy_message_fields {
+            self.lazy_message_fields = Some(v);
+        }
+        if let Some(v) = &that.type_name_prefix {
+            self.type_name_prefix = Some(v.clone());
+        }
+        if let Some(v) = &that.type_name_suffix {
+            self.type_name_suffix = Some(v.clone());
+        }
         if let Some(v) = that.tokio_bytes {
             self.tokio_bytes = Some(v);
         }
@@ -194,6 +455,9 @@ impl Customize {
         if let Some(v) = that.gen_mod_rs {
             self.gen_mod_rs = Some(v);
         }
+        if let Some(v) = that.gen_single_file {
+            self.gen_single_file = Some(v);
+        }
         if let Some(v) = that.inside_protobuf {
             self.inside_protobuf = Some(v);
         }
@@ -228,6 +492,30 @@ impl Customize {
                 r.generate_accessors = Some(parse_bool(v)?);
             } else if n == "generate_getter" {
                 r.generate_getter = Some(parse_bool(v)?);
+            } else if n == "generate_opt_getter" {
+                r.generate_opt_getter = Some(parse_bool(v)?);
+            } else if n == "generate_builders" {
+                r.generate_builders = Some(parse_bool(v)?);
+            } else if n == "generate_field_numbers" {
+                r.generate_field_numbers = Some(parse_bool(v)?);
+            } else if n == "serde_derive" {
+                r.serde_derive = Some(parse_bool(v)?);
+            } else if n == "enum_non_exhaustive" {
+                r.enum_non_exhaustive = Some(parse_bool(v)?);
+            } else if n == "enum_repr_i32" {
+                r.enum_repr_i32 = Some(parse_bool(v)?);
+            } else if n == "derive_debug" {
+                r.derive_debug = Some(parse_bool(v)?);
+            } else if n == "derive_hash" {
+                r.derive_hash = Some(parse_bool(v)?);
+            } else if n == "arc_recursive_fields" {
+                r.arc_recursive_fields = Some(parse_bool(v)?);
+            } else if n == "lazy_message_fields" {
+                r.lazy_message_fields = Some(parse_bool(v)?);
+            } else if n == "type_name_prefix" {
+                r.type_name_prefix = Some(v.to_owned());
+            } else if n == "type_name_suffix" {
+                r.type_name_suffix = Some(v.to_owned());
             } else if n == "tokio_bytes" {
                 r.tokio_bytes = Some(parse_bool(v)?);
             } else if n == "tokio_bytes_for_string" {
@@ -236,6 +524,8 @@ impl Customize {
                 r.lite_runtime = Some(parse_bool(v)?);
             } else if n == "gen_mod_rs" {
                 r.gen_mod_rs = Some(parse_bool(v)?);
+            } else if n == "gen_single_file" {
+                r.gen_single_file = Some(parse_bool(v)?);
             } else if n == "inside_protobuf" {
                 r.inside_protobuf = Some(parse_bool(v)?);
             } else if n == "lite" {
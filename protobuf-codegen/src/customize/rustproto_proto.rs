@@ -10,44 +10,100 @@ pub(crate) fn customize_from_rustproto_for_message(source: &MessageOptions) -> C
     let before = None;
     let generate_accessors = rustproto::exts::generate_accessors.get(source);
     let generate_getter = rustproto::exts::generate_getter.get(source);
+    let generate_opt_getter = rustproto::exts::generate_opt_getter.get(source);
+    let generate_builders = rustproto::exts::generate_builders.get(source);
+    let generate_field_numbers = rustproto::exts::generate_field_numbers.get(source);
+    let type_name_prefix = None;
+    let type_name_suffix = None;
     let tokio_bytes = rustproto::exts::tokio_bytes.get(source);
     let tokio_bytes_for_string = rustproto::exts::tokio_bytes_for_string.get(source);
+    let serde_derive = None;
+    let enum_non_exhaustive = None;
+    let enum_repr_i32 = None;
+    let derive_debug = rustproto::exts::derive_debug.get(source);
+    let derive_hash = rustproto::exts::derive_hash.get(source);
+    let arc_recursive_fields = rustproto::exts::arc_recursive_fields.get(source);
+    let lazy_message_fields = rustproto::exts::lazy_message_fields.get(source);
     let lite_runtime = None;
     let gen_mod_rs = None;
+    let gen_single_file = None;
     let inside_protobuf = None;
     Customize {
         before,
         generate_accessors,
         generate_getter,
+        generate_opt_getter,
+        generate_builders,
+        generate_field_numbers,
+        serde_derive,
+        enum_non_exhaustive,
+        enum_repr_i32,
+        derive_debug,
+        derive_hash,
+        arc_recursive_fields,
+        lazy_message_fields,
+        type_name_prefix,
+        type_name_suffix,
         tokio_bytes,
         tokio_bytes_for_string,
         lite_runtime,
         gen_mod_rs,
+        gen_single_file,
         inside_protobuf,
     }
 }
 
-pub(crate) fn customize_from_rustproto_for_enum(_source: &EnumOptions) -> Customize {
-    Customize::default()
+pub(crate) fn customize_from_rustproto_for_enum(source: &EnumOptions) -> Customize {
+    Customize {
+        enum_non_exhaustive: rustproto::exts::enum_non_exhaustive.get(source),
+        enum_repr_i32: rustproto::exts::enum_repr_i32.get(source),
+        ..Customize::default()
+    }
 }
 
 pub(crate) fn customize_from_rustproto_for_field(source: &FieldOptions) -> Customize {
     let before = None;
     let generate_accessors = rustproto::exts::generate_accessors_field.get(source);
     let generate_getter = rustproto::exts::generate_getter_field.get(source);
+    let generate_opt_getter = rustproto::exts::generate_opt_getter_field.get(source);
+    let generate_builders = None;
+    let generate_field_numbers = None;
+    let type_name_prefix = None;
+    let type_name_suffix = None;
     let tokio_bytes = rustproto::exts::tokio_bytes_field.get(source);
     let tokio_bytes_for_string = rustproto::exts::tokio_bytes_for_string_field.get(source);
+    let serde_derive = None;
+    let enum_non_exhaustive = None;
+    let enum_repr_i32 = None;
+    let derive_debug = None;
+    let derive_hash = None;
+    let arc_recursive_fields = None;
+    let lazy_message_fields = rustproto::exts::lazy_message_field.get(source);
     let lite_runtime = None;
     let gen_mod_rs = None;
+    let gen_single_file = None;
     let inside_protobuf = None;
     Customize {
         before,
         generate_accessors,
         generate_getter,
+        generate_opt_getter,
+        generate_builders,
+        generate_field_numbers,
+        serde_derive,
+        enum_non_exhaustive,
+        enum_repr_i32,
+        derive_debug,
+        derive_hash,
+        arc_recursive_fields,
+        lazy_message_fields,
+        type_name_prefix,
+        type_name_suffix,
         tokio_bytes,
         tokio_bytes_for_string,
         lite_runtime,
         gen_mod_rs,
+        gen_single_file,
         inside_protobuf,
     }
 }
@@ -56,19 +112,45 @@ pub(crate) fn customize_from_rustproto_for_file(source: &FileOptions) -> Customi
     let before = None;
     let generate_accessors = rustproto::exts::generate_accessors_all.get(source);
     let generate_getter = rustproto::exts::generate_getter_all.get(source);
+    let generate_opt_getter = rustproto::exts::generate_opt_getter_all.get(source);
+    let generate_builders = rustproto::exts::generate_builders_all.get(source);
+    let generate_field_numbers = rustproto::exts::generate_field_numbers_all.get(source);
+    let type_name_prefix = rustproto::exts::type_name_prefix_all.get(source);
+    let type_name_suffix = rustproto::exts::type_name_suffix_all.get(source);
     let tokio_bytes = rustproto::exts::tokio_bytes_all.get(source);
     let tokio_bytes_for_string = rustproto::exts::tokio_bytes_for_string_all.get(source);
+    let serde_derive = None;
+    let enum_non_exhaustive = rustproto::exts::enum_non_exhaustive_all.get(source);
+    let enum_repr_i32 = rustproto::exts::enum_repr_i32_all.get(source);
+    let derive_debug = rustproto::exts::derive_debug_all.get(source);
+    let derive_hash = rustproto::exts::derive_hash_all.get(source);
+    let arc_recursive_fields = rustproto::exts::arc_recursive_fields_all.get(source);
+    let lazy_message_fields = rustproto::exts::lazy_message_fields_all.get(source);
     let lite_runtime = rustproto::exts::lite_runtime_all.get(source);
     let gen_mod_rs = None;
+    let gen_single_file = None;
     let inside_protobuf = None;
     Customize {
         before,
         generate_accessors,
         generate_getter,
+        generate_opt_getter,
+        generate_builders,
+        generate_field_numbers,
+        serde_derive,
+        enum_non_exhaustive,
+        enum_repr_i32,
+        derive_debug,
+        derive_hash,
+        arc_recursive_fields,
+        lazy_message_fields,
+        type_name_prefix,
+        type_name_suffix,
         tokio_bytes,
         tokio_bytes_for_string,
         lite_runtime,
         inside_protobuf,
         gen_mod_rs,
+        gen_single_file,
     }
 }
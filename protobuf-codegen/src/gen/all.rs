@@ -10,6 +10,7 @@ use crate::customize::ctx::CustomizeElemCtx;
 use crate::customize::CustomizeCallback;
 use crate::gen::file::gen_file;
 use crate::gen::mod_rs::gen_mod_rs;
+use crate::gen::mod_rs::gen_mod_rs_single_file;
 use crate::gen::scope::RootScope;
 use crate::gen::well_known_types::gen_well_known_types_mod;
 use crate::Customize;
@@ -25,6 +26,7 @@ pub(crate) fn gen_all(
 
     let root_scope = RootScope {
         file_descriptors: &file_descriptors,
+        customize,
     };
 
     let mut results: Vec<compiler_plugin::GenResult> = Vec::new();
@@ -35,6 +37,9 @@ pub(crate) fn gen_all(
 
     let mut mods = Vec::new();
 
+    let gen_single_file = customize.gen_single_file.unwrap_or(false);
+    let mut single_file_mods = Vec::new();
+
     let customize = CustomizeElemCtx {
         for_elem: customize.clone(),
         for_children: customize.clone(),
@@ -48,15 +53,21 @@ pub(crate) fn gen_all(
             files_map.keys()
         ));
         let gen_file_result = gen_file(file, &files_map, &root_scope, &customize, parser)?;
-        results.push(gen_file_result.compiler_plugin_result);
-        mods.push(gen_file_result.mod_name);
+        if gen_single_file {
+            single_file_mods.push(gen_file_result);
+        } else {
+            results.push(gen_file_result.compiler_plugin_result);
+            mods.push(gen_file_result.mod_name);
+        }
     }
 
     if customize.for_elem.inside_protobuf.unwrap_or(false) {
         results.push(gen_well_known_types_mod());
     }
 
-    if customize.for_elem.gen_mod_rs.unwrap_or(true) {
+    if gen_single_file {
+        results.push(gen_mod_rs_single_file(&single_file_mods));
+    } else if customize.for_elem.gen_mod_rs.unwrap_or(true) {
         results.push(gen_mod_rs(&mods));
     }
 
@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 
 use protobuf::descriptor::*;
+use protobuf::reflect::Syntax;
 
 use crate::customize::ctx::CustomizeElemCtx;
 use crate::customize::rustproto_proto::customize_from_rustproto_for_enum;
@@ -17,6 +18,14 @@ use crate::gen::scope::EnumValueWithContext;
 use crate::gen::scope::EnumWithScope;
 use crate::gen::scope::RootScope;
 use crate::gen::scope::WithScope;
+use crate::gen::serde::serde_derive_enabled;
+use crate::gen::serde::SERDE_DERIVE;
+
+#[derive(Debug, thiserror::Error)]
+enum EnumGenError {
+    #[error("cannot generate `#[repr(i32)]` for enum `{0}`, because it has `allow_alias` option")]
+    ReprI32WithAllowAlias(String),
+}
 
 #[derive(Clone)]
 pub(crate) struct EnumValueGen<'a> {
@@ -69,7 +78,7 @@ impl<'a> EnumGen<'a> {
         _root_scope: &RootScope,
         path: &'a [i32],
         info: Option<&'a SourceCodeInfo>,
-    ) -> EnumGen<'a> {
+    ) -> anyhow::Result<EnumGen<'a>> {
         let customize = customize.child(
             &customize_from_rustproto_for_enum(enum_with_scope.en.proto().options.get_or_default()),
             &enum_with_scope.en,
@@ -83,14 +92,26 @@ impl<'a> EnumGen<'a> {
                 == file_options::OptimizeMode::LITE_RUNTIME
         });
 
-        EnumGen {
+        let enum_gen = EnumGen {
             enum_with_scope,
             type_name: enum_with_scope.rust_name().to_path(),
             lite_runtime,
             customize,
             path,
             info,
+        };
+        // Variants of enums with aliases have no discriminants.
+        if enum_gen.repr_i32() && enum_gen.allow_alias() {
+            return Err(EnumGenError::ReprI32WithAllowAlias(
+                enum_with_scope.en.full_name().to_owned(),
+            )
+            .into());
         }
+        Ok(enum_gen)
+    }
+
+    fn repr_i32(&self) -> bool {
+        self.customize.for_elem.enum_repr_i32.unwrap_or(false)
     }
 
     fn allow_alias(&self) -> bool {
@@ -102,6 +123,13 @@ impl<'a> EnumGen<'a> {
             .allow_alias()
     }
 
+    /// Only proto3 enums are open, values of proto2 enums
+    /// not known when generating code are stored in unknown fields.
+    fn non_exhaustive(&self) -> bool {
+        self.customize.for_elem.enum_non_exhaustive.unwrap_or(false)
+            && self.enum_with_scope.file_descriptor().syntax() == Syntax::Proto3
+    }
+
     fn values_all(&self) -> Vec<EnumValueGen> {
         let mut r = Vec::new();
         for p in self.enum_with_scope.values() {
@@ -132,6 +160,8 @@ impl<'a> EnumGen<'a> {
         }
         w.write_line("");
         self.write_impl_enum(w);
+        w.write_line("");
+        self.write_impl_try_from_i32(w);
         if !self.lite_runtime {
             w.write_line("");
             self.write_impl_enum_full(w);
@@ -166,15 +196,36 @@ impl<'a> EnumGen<'a> {
         } else {
             w.comment("Note: you cannot use pattern matching for enums with allow_alias option");
         }
+        if serde_derive_enabled(&self.customize.for_elem) {
+            derive.extend(SERDE_DERIVE);
+        }
         w.derive(&derive);
+        if self.non_exhaustive() {
+            w.write_line("#[non_exhaustive]");
+        }
+        if self.repr_i32() {
+            w.write_line("#[repr(i32)]");
+        }
         let ref type_name = self.type_name;
         write_protoc_insertion_point_for_enum(
             w,
             &self.customize.for_elem,
             &self.enum_with_scope.en,
         );
+        static VALUE_NUMBER: protobuf::rt::Lazy<i32> = protobuf::rt::Lazy::new();
+        let value_number = *VALUE_NUMBER.get(|| {
+            protobuf::reflect::MessageDescriptor::for_type::<EnumDescriptorProto>()
+                .field_by_name("value")
+                .expect("`value` must exist")
+                .proto()
+                .number()
+        });
+
         w.expr_block(&format!("pub enum {}", type_name), |w| {
-            for value in self.values_all() {
+            for (id, value) in self.values_all().into_iter().enumerate() {
+                let mut path = self.path.to_vec();
+                path.extend_from_slice(&[value_number, id as i32]);
+                w.all_documentation(self.info, &path);
                 write_protoc_insertion_point_for_enum_value(
                     w,
                     &self.customize.for_children,
@@ -214,6 +265,19 @@ impl<'a> EnumGen<'a> {
         });
     }
 
+    fn write_impl_enum_fn_name(&self, w: &mut CodeWriter) {
+        w.def_fn("name(&self) -> &'static str", |w| {
+            w.match_expr("*self", |w| {
+                for value in self.values_all() {
+                    w.case_expr(
+                        format!("{}", value.rust_name_outer()),
+                        format!("\"{}\"", value.value.proto.name()),
+                    );
+                }
+            });
+        });
+    }
+
     fn write_impl_enum_const_name(&self, w: &mut CodeWriter) {
         w.write_line(&format!(
             "const NAME: &'static str = \"{}\";",
@@ -251,7 +315,7 @@ impl<'a> EnumGen<'a> {
             ),
             |w| {
                 w.match_expr("str", |w| {
-                    let values = self.values_unique();
+                    let values = self.values_all();
                     for value in values {
                         w.write_line(&format!(
                             "\"{}\" => ::std::option::Option::Some({}),",
@@ -284,6 +348,8 @@ impl<'a> EnumGen<'a> {
                 w.write_line("");
                 self.write_impl_enum_fn_value(w);
                 w.write_line("");
+                self.write_impl_enum_fn_name(w);
+                w.write_line("");
                 self.write_impl_enum_fn_from_i32(w);
                 w.write_line("");
                 self.write_impl_enum_fn_from_str(w);
@@ -293,6 +359,30 @@ impl<'a> EnumGen<'a> {
         );
     }
 
+    fn write_impl_try_from_i32(&self, w: &mut CodeWriter) {
+        let protobuf_crate = protobuf_crate_path(&self.customize.for_elem);
+        w.impl_for_block(
+            "::std::convert::TryFrom<i32>",
+            self.type_name.to_string(),
+            |w| {
+                w.write_line(format!(
+                    "type Error = {}::UnknownEnumValue;",
+                    protobuf_crate
+                ));
+                w.write_line("");
+                w.def_fn(
+                    "try_from(value: i32) -> ::std::result::Result<Self, Self::Error>",
+                    |w| {
+                        w.write_line(format!(
+                            "<Self as {}::Enum>::from_i32(value).ok_or({}::UnknownEnumValue(value))",
+                            protobuf_crate, protobuf_crate
+                        ));
+                    },
+                );
+            },
+        );
+    }
+
     fn write_impl_enum_full(&self, w: &mut CodeWriter) {
         let ref type_name = self.type_name;
         w.impl_for_block(
@@ -435,3 +525,47 @@ impl<'a> EnumGen<'a> {
         );
     }
 }
+
+#[cfg(test)]
+mod test {
+    use protobuf::descriptor::FileDescriptorSet;
+    use protobuf::text_format;
+    use protobuf_parse::ProtoPathBuf;
+
+    use crate::customize::CustomizeCallbackDefault;
+    use crate::gen::all::gen_all;
+    use crate::Customize;
+
+    fn gen_with_repr_i32(enum_type: &str) -> anyhow::Result<()> {
+        let fds: FileDescriptorSet = text_format::parse_from_str(&format!(
+            r#"file {{ name: "a.proto" package: "a" enum_type {{ {} }} }}"#,
+            enum_type
+        ))
+        .unwrap();
+        gen_all(
+            &fds.file,
+            "test",
+            &[ProtoPathBuf::new("a.proto".to_owned()).unwrap()],
+            &Customize::default().enum_repr_i32(true),
+            &CustomizeCallbackDefault,
+        )
+        .map(drop)
+    }
+
+    #[test]
+    fn repr_i32_allow_alias() {
+        gen_with_repr_i32(
+            r#"name: "E" value { name: "A" number: 0 } value { name: "B" number: -1 }"#,
+        )
+        .unwrap();
+
+        let err = gen_with_repr_i32(
+            r#"name: "E" options { allow_alias: true } value { name: "A" number: 0 } value { name: "B" number: 0 }"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            "cannot generate `#[repr(i32)]` for enum `a.E`, because it has `allow_alias` option",
+            err.to_string()
+        );
+    }
+}
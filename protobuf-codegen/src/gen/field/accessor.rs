@@ -142,9 +142,19 @@ impl FieldGen<'_> {
     fn accessor_fn_singular_with_flag(
         &self,
         elem: &FieldElem,
-        _option_kind: OptionKind,
+        option_kind: OptionKind,
     ) -> AccessorFn {
         match elem {
+            FieldElem::Message(m) if option_kind == OptionKind::LazyMessage => AccessorFn {
+                name: "make_lazy_message_field_accessor".to_owned(),
+                type_params: vec![format!("{}", m.rust_name_relative(&self.file_and_mod()))],
+                callback_params: self.make_accessor_fns_lambda(),
+            },
+            FieldElem::Message(m) if option_kind == OptionKind::ArcMessage => AccessorFn {
+                name: "make_arc_message_field_accessor".to_owned(),
+                type_params: vec![format!("{}", m.rust_name_relative(&self.file_and_mod()))],
+                callback_params: self.make_accessor_fns_lambda(),
+            },
             FieldElem::Message(m) => AccessorFn {
                 name: "make_message_field_accessor".to_owned(),
                 type_params: vec![format!("{}", m.rust_name_relative(&self.file_and_mod()))],
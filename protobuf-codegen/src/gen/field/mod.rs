@@ -6,10 +6,14 @@ mod singular;
 mod tag;
 pub(crate) mod type_ext;
 
+use std::collections::HashSet;
+
 use protobuf::descriptor::field_descriptor_proto::Type;
 use protobuf::descriptor::*;
+use protobuf::reflect::FieldDescriptor;
 use protobuf::reflect::ReflectValueRef;
 use protobuf::reflect::RuntimeFieldType;
+use protobuf::reflect::RuntimeType;
 use protobuf::reflect::Syntax;
 use protobuf::rt;
 use protobuf::rt::WireType;
@@ -46,6 +50,7 @@ use crate::gen::rust_types_values::RustValueTyped;
 use crate::gen::scope::FieldWithContext;
 use crate::gen::scope::MessageWithScope;
 use crate::gen::scope::RootScope;
+use crate::gen::serde::write_serde_attr;
 
 fn field_type_protobuf_name<'a>(field: &'a FieldDescriptorProto) -> &'a str {
     if field.has_type_name() {
@@ -55,6 +60,34 @@ fn field_type_protobuf_name<'a>(field: &'a FieldDescriptorProto) -> &'a str {
     }
 }
 
+/// Singular message field is recursive if the containing message
+/// can be reached from the field type through message fields.
+fn is_recursive_message_field(field: &FieldDescriptor) -> bool {
+    let containing = field.containing_message();
+    let mut stack = match field.runtime_field_type() {
+        RuntimeFieldType::Singular(RuntimeType::Message(m)) => vec![m],
+        _ => return false,
+    };
+    let mut visited = HashSet::new();
+    while let Some(m) = stack.pop() {
+        if m == containing {
+            return true;
+        }
+        if !visited.insert(m.full_name().to_owned()) {
+            continue;
+        }
+        for f in m.fields() {
+            match f.runtime_field_type() {
+                RuntimeFieldType::Singular(RuntimeType::Message(m))
+                | RuntimeFieldType::Repeated(RuntimeType::Message(m))
+                | RuntimeFieldType::Map(_, RuntimeType::Message(m)) => stack.push(m),
+                _ => {}
+            }
+        }
+    }
+    false
+}
+
 #[derive(Clone)]
 pub struct MapField<'a> {
     _message: MessageWithScope<'a>,
@@ -132,6 +165,7 @@ pub(crate) struct FieldGen<'a> {
     pub kind: FieldKind<'a>,
     pub generate_accessors: bool,
     pub generate_getter: bool,
+    generate_opt_getter: bool,
     customize: Customize,
     path: Vec<i32>,
     info: Option<&'a SourceCodeInfo>,
@@ -158,14 +192,28 @@ impl<'a> FieldGen<'a> {
             && field.field.proto().label() != field_descriptor_proto::Label::LABEL_REPEATED
             && field.field.proto().type_() != Type::TYPE_MESSAGE;
 
-        let generate_accessors = customize
-            .generate_accessors
-            .unwrap_or(field_may_have_custom_default_value)
-            || field.is_oneof();
+        let lazy = field.field.proto().label() != field_descriptor_proto::Label::LABEL_REPEATED
+            && field.field.proto().type_() == Type::TYPE_MESSAGE
+            && !field.is_oneof()
+            && customize.lazy_message_fields.unwrap_or(false)
+            && field.field.proto().options.get_or_default().lazy();
+
+        let arc = !lazy
+            && field.field.proto().label() != field_descriptor_proto::Label::LABEL_REPEATED
+            && !field.is_oneof()
+            && customize.arc_recursive_fields.unwrap_or(false)
+            && is_recursive_message_field(&field.field);
+
+        // Lazy fields are accessed with `LazyMessage` functions.
+        let generate_accessors = !lazy
+            && (customize
+                .generate_accessors
+                .unwrap_or(field_may_have_custom_default_value)
+                || field.is_oneof());
 
         let default_generate_getter = generate_accessors || field_may_have_custom_default_value;
-        let generate_getter =
-            customize.generate_getter.unwrap_or(default_generate_getter) || field.is_oneof();
+        let generate_getter = !lazy
+            && (customize.generate_getter.unwrap_or(default_generate_getter) || field.is_oneof());
 
         let kind = match field.field.runtime_field_type() {
             RuntimeFieldType::Map(..) => {
@@ -197,15 +245,20 @@ impl<'a> FieldGen<'a> {
                 if let Some(oneof) = field.oneof() {
                     FieldKind::Oneof(OneofField::parse(&oneof, &field.field, elem, root_scope))
                 } else {
-                    let flag = if field.message.scope.file_scope.syntax() == Syntax::Proto3
-                        && field.field.proto().type_() != field_descriptor_proto::Type::TYPE_MESSAGE
-                        && !field.field.proto().proto3_optional()
-                    {
+                    // Proto3 fields without `optional` and editions fields
+                    // with implicit presence are stored without flag.
+                    let flag = if !field.field.has_presence() {
                         SingularFieldFlag::WithoutFlag
                     } else {
                         let required = field.field.proto().label()
                             == field_descriptor_proto::Label::LABEL_REQUIRED;
                         let option_kind = match field.field.proto().type_() {
+                            field_descriptor_proto::Type::TYPE_MESSAGE if lazy => {
+                                OptionKind::LazyMessage
+                            }
+                            field_descriptor_proto::Type::TYPE_MESSAGE if arc => {
+                                OptionKind::ArcMessage
+                            }
                             field_descriptor_proto::Type::TYPE_MESSAGE => OptionKind::MessageField,
                             _ => OptionKind::Option,
                         };
@@ -220,6 +273,8 @@ impl<'a> FieldGen<'a> {
             }
         };
 
+        let generate_opt_getter = !lazy && customize.generate_opt_getter.unwrap_or(false);
+
         Ok(FieldGen {
             syntax: field.message.message.file_descriptor().syntax(),
             rust_name: rust_field_name_for_protobuf_field_name(&field.field.name()),
@@ -229,6 +284,7 @@ impl<'a> FieldGen<'a> {
             kind,
             generate_accessors,
             generate_getter,
+            generate_opt_getter,
             customize,
             path,
             info,
@@ -254,6 +310,32 @@ impl<'a> FieldGen<'a> {
         }
     }
 
+    pub(crate) fn is_lazy_message(&self) -> bool {
+        matches!(
+            self.kind,
+            FieldKind::Singular(SingularField {
+                flag: SingularFieldFlag::WithFlag {
+                    option_kind: OptionKind::LazyMessage,
+                    ..
+                },
+                ..
+            })
+        )
+    }
+
+    fn is_arc_message(&self) -> bool {
+        matches!(
+            self.kind,
+            FieldKind::Singular(SingularField {
+                flag: SingularFieldFlag::WithFlag {
+                    option_kind: OptionKind::ArcMessage,
+                    ..
+                },
+                ..
+            })
+        )
+    }
+
     fn is_repeated_packed(&self) -> bool {
         match self.kind {
             FieldKind::Repeated(RepeatedField { packed: true, .. }) => true,
@@ -470,7 +552,7 @@ impl<'a> FieldGen<'a> {
     pub(crate) fn reconstruct_def(&self) -> String {
         let prefix = match (self.proto_field.field.proto().label(), self.syntax) {
             (field_descriptor_proto::Label::LABEL_REPEATED, _) => "repeated ",
-            (_, Syntax::Proto3) => "",
+            (_, Syntax::Proto3 | Syntax::Editions) => "",
             (field_descriptor_proto::Label::LABEL_OPTIONAL, _) => "optional ",
             (field_descriptor_proto::Label::LABEL_REQUIRED, _) => "required ",
         };
@@ -575,6 +657,32 @@ impl<'a> FieldGen<'a> {
             w.all_documentation(self.info, &self.path);
 
             write_protoc_insertion_point_for_field(w, &self.customize, &self.proto_field.field);
+            if let FieldKind::Singular(SingularField {
+                flag: SingularFieldFlag::WithFlag { option_kind, .. },
+                ..
+            }) = self.kind
+            {
+                let is_none = match option_kind {
+                    OptionKind::Option => "::std::option::Option::is_none".to_owned(),
+                    OptionKind::MessageField => format!(
+                        "{}::MessageField::is_none",
+                        protobuf_crate_path(&self.customize)
+                    ),
+                    OptionKind::LazyMessage => format!(
+                        "{}::LazyMessage::is_none",
+                        protobuf_crate_path(&self.customize)
+                    ),
+                    OptionKind::ArcMessage => format!(
+                        "{}::ArcMessageField::is_none",
+                        protobuf_crate_path(&self.customize)
+                    ),
+                };
+                write_serde_attr(
+                    w,
+                    &self.customize,
+                    &format!("skip_serializing_if = \"{}\"", is_none),
+                );
+            }
             w.field_decl_vis(
                 Visibility::Public,
                 &self.rust_name.to_string(),
@@ -721,8 +829,12 @@ impl<'a> FieldGen<'a> {
     }
 
     fn write_self_field_assign(&self, w: &mut CodeWriter, value: &str) {
-        let self_field = self.self_field();
-        w.write_line(&format!("{} = {};", self_field, value));
+        self.write_field_assign(w, "self", value);
+    }
+
+    // `message` is an expression of message type, e. g. `self`
+    fn write_field_assign(&self, w: &mut CodeWriter, message: &str, value: &str) {
+        w.write_line(format!("{}.{} = {};", message, self.rust_name, value));
     }
 
     fn write_self_field_assign_some(&self, w: &mut CodeWriter, s: &SingularField, value: &str) {
@@ -742,9 +854,10 @@ impl<'a> FieldGen<'a> {
         }
     }
 
-    fn write_self_field_assign_value_singular(
+    fn write_field_assign_value_singular(
         &self,
         w: &mut CodeWriter,
+        message: &str,
         s: &SingularField,
         value: &RustValueTyped,
     ) {
@@ -766,10 +879,10 @@ impl<'a> FieldGen<'a> {
                 option_kind.wrap_value(&converted.value, &self.customize)
             }
         };
-        self.write_self_field_assign(w, &wrapped);
+        self.write_field_assign(w, message, &wrapped);
     }
 
-    fn write_self_field_assign_value(&self, w: &mut CodeWriter, value: &RustValueTyped) {
+    fn write_field_assign_value(&self, w: &mut CodeWriter, message: &str, value: &RustValueTyped) {
         match self.kind {
             FieldKind::Repeated(..) | FieldKind::Map(..) => {
                 let converted = value.into_type(
@@ -782,10 +895,10 @@ impl<'a> FieldGen<'a> {
                     ),
                     &self.customize,
                 );
-                self.write_self_field_assign(w, &converted.value);
+                self.write_field_assign(w, message, &converted.value);
             }
             FieldKind::Singular(ref s) => {
-                self.write_self_field_assign_value_singular(w, s, value);
+                self.write_field_assign_value_singular(w, message, s, value);
             }
             FieldKind::Oneof(..) => unreachable!(),
         }
@@ -896,7 +1009,14 @@ impl<'a> FieldGen<'a> {
         w: &mut CodeWriter,
     ) {
         let read_fn = match &r.elem {
-            FieldElem::Message(..) => "read_message",
+            FieldElem::Message(..) => {
+                w.write_line(format!(
+                    "{}::rt::read_repeated_message_into_vec(is, &mut self.{})?;",
+                    protobuf_crate_path(&self.customize),
+                    self.rust_name,
+                ));
+                return;
+            }
             FieldElem::Primitive(Type::TYPE_STRING, PrimitiveTypeVariant::Default) => "read_string",
             FieldElem::Primitive(Type::TYPE_STRING, PrimitiveTypeVariant::TokioBytes) => {
                 "read_tokio_chars"
@@ -941,12 +1061,28 @@ impl<'a> FieldGen<'a> {
                 typed
             };
 
-            w.write_line(&format!(
+            let variant_path = o.variant_path(&self.proto_field.message.scope.rust_path_to_file());
+            let set = format!(
                 "self.{} = ::std::option::Option::Some({}({}));",
-                o.oneof_field_name,
-                o.variant_path(&self.proto_field.message.scope.rust_path_to_file()),
-                maybe_boxed.value
-            ));
+                o.oneof_field_name, variant_path, maybe_boxed.value
+            );
+            match o.elem {
+                // Message is merged into previously read one if the same variant is set.
+                FieldElem::Message(..) => {
+                    w.match_block(format!("self.{}", o.oneof_field_name), |w| {
+                        w.case_expr(
+                            format!("::std::option::Option::Some({}(ref mut v))", variant_path),
+                            if o.boxed {
+                                "is.merge_message(&mut **v)?"
+                            } else {
+                                "is.merge_message(v)?"
+                            },
+                        );
+                        w.case_block("_", |w| w.write_line(&set));
+                    });
+                }
+                _ => w.write_line(&set),
+            }
         })
     }
 
@@ -994,6 +1130,20 @@ impl<'a> FieldGen<'a> {
     // Write `merge_from` part for this singular field
     fn write_merge_from_singular_case_block(&self, s: &SingularField, w: &mut CodeWriter) {
         w.case_block(&format!("{}", self.tag()), |w| match s.elem {
+            FieldElem::Message(..) if self.is_lazy_message() => {
+                w.write_line(format!(
+                    "{}::rt::read_lazy_message_into_field(is, &mut self.{})?;",
+                    protobuf_crate_path(&self.customize),
+                    self.rust_name,
+                ));
+            }
+            FieldElem::Message(..) if self.is_arc_message() => {
+                w.write_line(format!(
+                    "{}::rt::read_arc_message_into_field(is, &mut self.{})?;",
+                    protobuf_crate_path(&self.customize),
+                    self.rust_name,
+                ));
+            }
             FieldElem::Message(..) => {
                 w.write_line(&format!(
                     "{}::rt::read_singular_message_into_field(is, &mut self.{})?;",
@@ -1103,7 +1253,12 @@ impl<'a> FieldGen<'a> {
         os: &str,
         w: &mut CodeWriter,
     ) {
-        self.for_each_map_entry(key, value, w, |k, v, w| {
+        let entries = format!(
+            "{}::rt::map_entries(&{}, {os}.is_deterministic())",
+            protobuf_crate_path(&self.customize),
+            self.self_field(),
+        );
+        self.for_each_map_entry(&entries, key, value, w, |k, v, w| {
             w.write_line("let mut entry_size = 0;");
             key.write_element_size(
                 1,
@@ -1133,6 +1288,15 @@ impl<'a> FieldGen<'a> {
 
     pub(crate) fn write_message_write_field(&self, os: &str, w: &mut CodeWriter) {
         match &self.kind {
+            FieldKind::Singular(..) if self.is_lazy_message() => {
+                w.write_line(format!(
+                    "{}::rt::write_lazy_message_field_with_cached_size({}, &{}, {})?;",
+                    protobuf_crate_path(&self.customize),
+                    self.proto_field.number(),
+                    self.self_field(),
+                    os,
+                ));
+            }
             FieldKind::Singular(s @ SingularField { elem, .. }) => {
                 self.write_if_let_self_field_is_some(s, w, |v, w| {
                     self.write_write_element(&elem, w, os, &v);
@@ -1168,12 +1332,13 @@ impl<'a> FieldGen<'a> {
 
     fn for_each_map_entry(
         &self,
+        entries: &str,
         key: &FieldElem,
         value: &FieldElem,
         w: &mut CodeWriter,
         cb: impl FnOnce(&RustValueTyped, &RustValueTyped, &mut CodeWriter),
     ) {
-        w.for_stmt(&format!("&{}", self.self_field()), "(k, v)", move |w| {
+        w.for_stmt(entries, "(k, v)", move |w| {
             let k = RustValueTyped {
                 value: "k".to_owned(),
                 rust_type: key.rust_storage_elem_type(&self.file_and_mod()).wrap_ref(),
@@ -1195,7 +1360,8 @@ impl<'a> FieldGen<'a> {
         value: &FieldElem<'a>,
         w: &mut CodeWriter,
     ) {
-        self.for_each_map_entry(key, value, w, |k, v, w| {
+        let entries = format!("&{}", self.self_field());
+        self.for_each_map_entry(&entries, key, value, w, |k, v, w| {
                 w.write_line("let mut entry_size = 0;");
                 key.write_element_size(1, k, HowToGetMessageSize::Compute, "entry_size", &self.customize, w);
                 value.write_element_size(2, v, HowToGetMessageSize::Compute, "entry_size", &self.customize, w);
@@ -1208,6 +1374,15 @@ impl<'a> FieldGen<'a> {
 
     pub(crate) fn write_message_compute_field_size(&self, sum_var: &str, w: &mut CodeWriter) {
         match &self.kind {
+            FieldKind::Singular(..) if self.is_lazy_message() => {
+                w.write_line(format!(
+                    "{} += {}::rt::lazy_message_field_size({}, &{});",
+                    sum_var,
+                    protobuf_crate_path(&self.customize),
+                    self.proto_field.number(),
+                    self.self_field(),
+                ));
+            }
             FieldKind::Singular(s @ SingularField { elem, .. }) => {
                 self.write_if_let_self_field_is_some(s, w, |v, w| {
                     self.write_element_size(&elem, w, v, sum_var)
@@ -1427,6 +1602,66 @@ impl<'a> FieldGen<'a> {
         });
     }
 
+    fn write_message_field_get_opt(&self, w: &mut CodeWriter) {
+        let get_xxx_return_type = self.getter_return_type();
+        let fn_def = format!(
+            "{}_opt(&self) -> ::std::option::Option<{}>",
+            self.rust_name,
+            get_xxx_return_type.to_code(&self.customize)
+        );
+
+        w.pub_fn(&fn_def, |w| match self.kind {
+            FieldKind::Oneof(ref o) => {
+                let (refv, vtype) = if !o.elem.is_copy() {
+                    (
+                        "ref v",
+                        o.elem
+                            .rust_storage_elem_type(&self.file_and_mod())
+                            .ref_type(),
+                    )
+                } else {
+                    ("v", o.elem.rust_storage_elem_type(&self.file_and_mod()))
+                };
+                w.match_expr(format!("self.{}", o.oneof_field_name), |w| {
+                    w.case_expr(
+                        format!(
+                            "::std::option::Option::Some({}({}))",
+                            o.variant_path(&self.proto_field.message.scope.rust_path_to_file()),
+                            refv
+                        ),
+                        format!(
+                            "::std::option::Option::Some({})",
+                            vtype.into_target(&get_xxx_return_type, "v", &self.customize)
+                        ),
+                    );
+                    w.case_expr("_", "::std::option::Option::None");
+                });
+            }
+            FieldKind::Singular(SingularField {
+                ref elem,
+                flag: SingularFieldFlag::WithFlag { .. },
+            }) => match elem {
+                FieldElem::Message(..) => {
+                    w.write_line(format!("{}.as_ref()", self.self_field()));
+                }
+                FieldElem::Enum(..) => {
+                    w.write_line(format!(
+                        "{}.map(|e| e.enum_value_or({}))",
+                        self.self_field(),
+                        self.xxx_default_value_rust()
+                    ));
+                }
+                _ if elem.is_copy() => w.write_line(self.self_field()),
+                // `String`, `Vec<u8>`, `Chars` and `Bytes` dereference
+                // to getter return type.
+                _ => w.write_line(format!("{}.as_deref()", self.self_field())),
+            },
+            FieldKind::Singular(..) | FieldKind::Repeated(..) | FieldKind::Map(..) => {
+                unreachable!()
+            }
+        });
+    }
+
     fn has_has(&self) -> bool {
         match self.kind {
             FieldKind::Repeated(..) | FieldKind::Map(..) => false,
@@ -1496,6 +1731,44 @@ impl<'a> FieldGen<'a> {
         );
     }
 
+    // Assign `v` of type `set_xxx_param_type` to the field of `message`
+    fn write_field_set_value(
+        &self,
+        w: &mut CodeWriter,
+        message: &str,
+        set_xxx_param_type: &RustType,
+    ) {
+        let value_typed = RustValueTyped {
+            value: "v".to_owned(),
+            rust_type: set_xxx_param_type.clone(),
+        };
+        match self.kind {
+            FieldKind::Oneof(ref oneof) => {
+                let v = set_xxx_param_type.into_target(
+                    &oneof.rust_type(
+                        &self
+                            .proto_field
+                            .message
+                            .scope
+                            .file_and_mod(self.customize.clone()),
+                    ),
+                    "v",
+                    &self.customize,
+                );
+                w.write_line(format!(
+                    "{}.{} = ::std::option::Option::Some({}({}));",
+                    message,
+                    oneof.oneof_field_name,
+                    oneof.variant_path(&self.proto_field.message.scope.rust_path_to_file()),
+                    v
+                ));
+            }
+            _ => {
+                self.write_field_assign_value(w, message, &value_typed);
+            }
+        }
+    }
+
     fn write_message_field_set(&self, w: &mut CodeWriter) {
         let set_xxx_param_type = self.set_xxx_param_type(
             &self
@@ -1511,35 +1784,28 @@ impl<'a> FieldGen<'a> {
                 self.set_name(),
                 set_xxx_param_type.to_code(&self.customize)
             ),
+            |w| self.write_field_set_value(w, "self", &set_xxx_param_type),
+        );
+    }
+
+    // Chained setter of `FooBuilder`, which stores the message in `message` field
+    pub(crate) fn write_builder_setter(&self, w: &mut CodeWriter) {
+        let set_xxx_param_type = self.set_xxx_param_type(
+            &self
+                .proto_field
+                .message
+                .scope
+                .file_and_mod(self.customize.clone()),
+        );
+        w.pub_fn(
+            &format!(
+                "{}(mut self, v: {}) -> Self",
+                self.rust_name,
+                set_xxx_param_type.to_code(&self.customize)
+            ),
             |w| {
-                let value_typed = RustValueTyped {
-                    value: "v".to_owned(),
-                    rust_type: set_xxx_param_type.clone(),
-                };
-                match self.kind {
-                    FieldKind::Oneof(ref oneof) => {
-                        let v = set_xxx_param_type.into_target(
-                            &oneof.rust_type(
-                                &self
-                                    .proto_field
-                                    .message
-                                    .scope
-                                    .file_and_mod(self.customize.clone()),
-                            ),
-                            "v",
-                            &self.customize,
-                        );
-                        w.write_line(&format!(
-                            "self.{} = ::std::option::Option::Some({}({}))",
-                            oneof.oneof_field_name,
-                            oneof.variant_path(&self.proto_field.message.scope.rust_path_to_file()),
-                            v
-                        ));
-                    }
-                    _ => {
-                        self.write_self_field_assign_value(w, &value_typed);
-                    }
-                }
+                self.write_field_set_value(w, "self.message", &set_xxx_param_type);
+                w.write_line("self");
             },
         );
     }
@@ -1552,9 +1818,11 @@ impl<'a> FieldGen<'a> {
     ) {
         let self_field = self.self_field();
         match option_kind {
-            OptionKind::MessageField => {
+            // Arc message is cloned if it is shared.
+            OptionKind::MessageField | OptionKind::ArcMessage => {
                 w.write_line(&format!("{}.mut_or_insert_default()", self_field))
             }
+            OptionKind::LazyMessage => unreachable!("no accessors for lazy message field"),
             OptionKind::Option => {
                 self.write_if_self_field_is_none(w, |w| {
                     self.write_self_field_assign_default(
@@ -1799,7 +2067,10 @@ impl<'a> FieldGen<'a> {
     }
 
     pub(crate) fn write_message_single_field_accessors(&self, w: &mut CodeWriter) {
-        if self.generate_accessors || self.generate_getter {
+        // Only fields with explicit presence have `None` value.
+        let generate_opt_getter = self.generate_opt_getter && self.has_has();
+
+        if self.generate_accessors || self.generate_getter || generate_opt_getter {
             w.write_line("");
             let reconstruct_def = self.reconstruct_def();
             w.comment(&(reconstruct_def + ";"));
@@ -1810,6 +2081,11 @@ impl<'a> FieldGen<'a> {
             self.write_message_field_get(w);
         }
 
+        if generate_opt_getter {
+            w.write_line("");
+            self.write_message_field_get_opt(w);
+        }
+
         if !self.generate_accessors {
             return;
         }
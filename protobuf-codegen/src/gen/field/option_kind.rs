@@ -9,6 +9,10 @@ pub enum OptionKind {
     Option,
     /// Field is `SingularPtrField<T>`
     MessageField,
+    /// Field is `LazyMessage<T>`
+    LazyMessage,
+    /// Field is `ArcMessageField<T>`
+    ArcMessage,
 }
 
 impl OptionKind {
@@ -17,6 +21,8 @@ impl OptionKind {
         match self {
             OptionKind::Option => RustType::Option(element_type),
             OptionKind::MessageField => RustType::MessageField(element_type),
+            OptionKind::LazyMessage => RustType::LazyMessage(element_type),
+            OptionKind::ArcMessage => RustType::ArcMessageField(element_type),
         }
     }
 
@@ -25,12 +31,17 @@ impl OptionKind {
         match self {
             OptionKind::Option => RustType::Option(Box::new(element_type.ref_type())),
             OptionKind::MessageField => RustType::MessageField(Box::new(element_type.ref_type())),
+            OptionKind::ArcMessage => RustType::Option(Box::new(element_type.ref_type())),
+            OptionKind::LazyMessage => unreachable!("lazy message field cannot be used as option"),
         }
     }
 
     fn _as_option_ref(&self, v: &str) -> String {
         match self {
-            OptionKind::Option | OptionKind::MessageField => format!("{}.as_ref()", v),
+            OptionKind::Option | OptionKind::MessageField | OptionKind::ArcMessage => {
+                format!("{}.as_ref()", v)
+            }
+            OptionKind::LazyMessage => unreachable!("lazy message field cannot be used as option"),
         }
     }
 
@@ -54,6 +65,16 @@ impl OptionKind {
                 protobuf_crate_path(customize),
                 value
             ),
+            OptionKind::LazyMessage => format!(
+                "{}::LazyMessage::some({})",
+                protobuf_crate_path(customize),
+                value
+            ),
+            OptionKind::ArcMessage => format!(
+                "{}::ArcMessageField::some({})",
+                protobuf_crate_path(customize),
+                value
+            ),
         }
     }
 }
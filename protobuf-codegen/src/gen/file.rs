@@ -15,7 +15,6 @@ use crate::gen::file_descriptor::write_file_descriptor_data;
 use crate::gen::inside::protobuf_crate_path;
 use crate::gen::message::MessageGen;
 use crate::gen::paths::proto_path_to_rust_mod;
-use crate::gen::scope::FileScope;
 use crate::gen::scope::RootScope;
 use crate::proto_name_to_rs;
 
@@ -48,7 +47,7 @@ pub(crate) fn gen_file(
 
     let customize = parent_customize.child(&customize_from_proto, file_descriptor);
 
-    let file_scope = FileScope { file_descriptor };
+    let file_scope = root_scope.file_scope(file_descriptor);
     let scope = file_scope.to_scope();
 
     let lite_runtime = customize.for_elem.lite_runtime.unwrap_or(false);
@@ -125,7 +124,7 @@ pub(crate) fn gen_file(
                 root_scope,
                 &path,
                 file_descriptor.proto().source_code_info.as_ref(),
-            )
+            )?
             .write(w);
         }
 
@@ -133,7 +132,7 @@ pub(crate) fn gen_file(
 
         if !lite_runtime {
             w.write_line("");
-            write_file_descriptor_data(file_descriptor, &customize.for_elem, w);
+            write_file_descriptor_data(file_descriptor, root_scope, &customize.for_elem, w);
         }
 
         Ok(())
@@ -7,7 +7,7 @@ use crate::gen::code_writer::CodeWriter;
 use crate::gen::inside::protobuf_crate_path;
 use crate::gen::paths::proto_path_to_fn_file_descriptor;
 use crate::gen::rust::snippets::expr_vec_with_capacity_const;
-use crate::gen::scope::FileScope;
+use crate::gen::scope::RootScope;
 use crate::gen::scope::Scope;
 use crate::gen::scope::WithScope;
 use crate::Customize;
@@ -31,8 +31,24 @@ fn escape_byte(s: &mut String, b: u8) {
     }
 }
 
+fn is_empty_weak_dependency(file_descriptor: &FileDescriptor, index: usize) -> bool {
+    if !file_descriptor
+        .proto()
+        .weak_dependency
+        .contains(&(index as i32))
+    {
+        return false;
+    }
+    let dep = file_descriptor.deps()[index].proto();
+    dep.message_type.is_empty()
+        && dep.enum_type.is_empty()
+        && dep.extension.is_empty()
+        && dep.service.is_empty()
+}
+
 fn write_generate_file_descriptor(
     file_descriptor: &FileDescriptor,
+    root_scope: &RootScope,
     customize: &Customize,
     w: &mut CodeWriter,
 ) {
@@ -41,14 +57,24 @@ fn write_generate_file_descriptor(
         "let mut deps = {vec_with_capacity};",
         vec_with_capacity = expr_vec_with_capacity_const(deps.len())
     ));
-    for f in deps {
+    for (i, f) in deps.iter().enumerate() {
+        if is_empty_weak_dependency(file_descriptor, i) {
+            // Weak dependency may be absent (e. g. placeholder created by parser),
+            // and it does not declare anything, so its generated module is not needed.
+            w.write_line(format!(
+                "deps.push({}::rt::weak_dependency_placeholder(\"{}\"));",
+                protobuf_crate_path(customize),
+                f
+            ));
+            continue;
+        }
         w.write_line(&format!(
             "deps.push({}().clone());",
             proto_path_to_fn_file_descriptor(f, customize)
         ));
     }
 
-    let scope = FileScope { file_descriptor };
+    let scope = root_scope.file_scope(file_descriptor);
 
     let messages = scope.find_messages_except_map();
     w.write_line(&format!(
@@ -89,6 +115,7 @@ fn write_generate_file_descriptor(
 
 fn write_file_descriptor(
     file_descriptor: &FileDescriptor,
+    root_scope: &RootScope,
     customize: &Customize,
     w: &mut CodeWriter,
 ) {
@@ -118,7 +145,7 @@ fn write_file_descriptor(
                     w.block(
                         "let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {",
                         "});",
-                        |w| write_generate_file_descriptor(file_descriptor, customize, w),
+                        |w| write_generate_file_descriptor(file_descriptor, root_scope, customize, w),
                     );
                     w.write_line(&format!(
                         "{protobuf_crate}::reflect::FileDescriptor::new_generated_2(generated_file_descriptor)",
@@ -132,6 +159,7 @@ fn write_file_descriptor(
 
 pub(crate) fn write_file_descriptor_data(
     file: &FileDescriptor,
+    root_scope: &RootScope,
     customize: &Customize,
     w: &mut CodeWriter,
 ) {
@@ -167,7 +195,7 @@ pub(crate) fn write_file_descriptor_data(
     w.write_line("");
     write_file_descriptor_proto(&customize, w);
     w.write_line("");
-    write_file_descriptor(file, &customize, w);
+    write_file_descriptor(file, root_scope, customize, w);
 }
 
 fn write_file_descriptor_proto(customize: &Customize, w: &mut CodeWriter) {
@@ -206,3 +234,35 @@ pub(crate) fn file_descriptor_call_expr(scope: &Scope) -> String {
             .append_ident("file_descriptor".into())
     )
 }
+
+#[cfg(test)]
+mod test {
+    use protobuf::descriptor::FileDescriptorSet;
+    use protobuf::reflect::FileDescriptor;
+    use protobuf::text_format;
+
+    use super::is_empty_weak_dependency;
+
+    #[test]
+    fn empty_weak_dependency() {
+        let fds: FileDescriptorSet = text_format::parse_from_str(
+            r#"
+                file { name: "empty.proto" }
+                file { name: "b.proto" message_type { name: "B" } }
+                file {
+                    name: "a.proto"
+                    dependency: ["empty.proto", "b.proto", "empty.proto"]
+                    weak_dependency: [0, 1]
+                }
+            "#,
+        )
+        .unwrap();
+        let files = FileDescriptor::new_dynamic_fds(fds.file, &[]).unwrap();
+        let a = &files[2];
+        assert!(is_empty_weak_dependency(a, 0));
+        // Weak, but declares a message.
+        assert!(!is_empty_weak_dependency(a, 1));
+        // Empty, but not weak.
+        assert!(!is_empty_weak_dependency(a, 2));
+    }
+}
@@ -7,10 +7,12 @@ use protobuf_parse::snake_case;
 
 use crate::customize::ctx::CustomizeElemCtx;
 use crate::customize::ctx::SpecialFieldPseudoDescriptor;
+use crate::customize::rustproto_proto::customize_from_rustproto_for_file;
 use crate::customize::rustproto_proto::customize_from_rustproto_for_message;
 use crate::gen::code_writer::*;
 use crate::gen::descriptor::write_fn_descriptor;
 use crate::gen::enums::*;
+use crate::gen::field::elem::FieldElem;
 use crate::gen::field::FieldGen;
 use crate::gen::field::FieldKind;
 use crate::gen::file_and_mod::FileAndMod;
@@ -28,8 +30,22 @@ use crate::gen::rust_types_values::*;
 use crate::gen::scope::MessageWithScope;
 use crate::gen::scope::RootScope;
 use crate::gen::scope::WithScope;
+use crate::gen::serde::serde_derive_enabled;
+use crate::gen::serde::write_serde_attr;
+use crate::gen::serde::SERDE_DERIVE;
+use crate::gen::well_known_types::WELL_KNOWN_TYPES_PROTO_FILE_FULL_NAMES;
 use crate::Customize;
 
+#[derive(Debug, thiserror::Error)]
+enum MessageGenError {
+    #[error("cannot generate builder for message `{0}`, because field name `{1}` conflicts with builder function")]
+    BuilderFieldNameConflict(String, String),
+    #[error("cannot derive `Hash` for message `{0}`, because field `{1}` is {2}")]
+    UnhashableField(String, String, &'static str),
+    #[error("cannot derive `Hash` for message `{0}`, because type `{2}` of field `{1}` does not derive `Hash`")]
+    UnhashableMessageField(String, String, String),
+}
+
 /// Protobuf message Rust type name
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct RustTypeMessage(pub RustIdentWithPath);
@@ -57,6 +73,62 @@ impl RustTypeMessage {
     }
 }
 
+/// Methods of `Message`, `MessageFull`, `MessageDyn` and derived traits
+/// implemented by generated messages. Inherent methods with these names
+/// would shadow trait methods.
+const MESSAGE_TRAIT_METHOD_NAMES: &[&str] = &[
+    // `Message`
+    "is_initialized",
+    "merge_from",
+    "parse_from",
+    "write_to_with_cached_sizes",
+    "compute_size",
+    "cached_size",
+    "write_to",
+    "write_length_delimited_to",
+    "write_length_delimited_to_vec",
+    "merge_from_bytes",
+    "parse_from_reader",
+    "parse_from_bytes",
+    "parse_length_delimited_from_bytes",
+    "parse_from_tokio_bytes",
+    "uninitialized_field_path",
+    "check_initialized",
+    "write_to_writer",
+    "write_to_vec",
+    "write_to_bytes",
+    "write_length_delimited_to_writer",
+    "write_length_delimited_to_bytes",
+    "special_fields",
+    "mut_special_fields",
+    "unknown_fields",
+    "mut_unknown_fields",
+    "clear",
+    "default_instance",
+    // `MessageFull`
+    "descriptor",
+    "reflect_eq",
+    // `MessageDyn`
+    "descriptor_dyn",
+    "merge_from_dyn",
+    "write_to_with_cached_sizes_dyn",
+    "compute_size_dyn",
+    "is_initialized_dyn",
+    "uninitialized_field_path_dyn",
+    "clear_dyn",
+    "special_fields_dyn",
+    "mut_special_fields_dyn",
+    // Derived and `std` traits
+    "clone",
+    "clone_from",
+    "eq",
+    "ne",
+    "default",
+    "fmt",
+    "hash",
+    "to_string",
+];
+
 /// Message info for codegen
 pub(crate) struct MessageGen<'a> {
     file_descriptor: &'a FileDescriptor,
@@ -343,13 +415,77 @@ impl<'a> MessageGen<'a> {
         }
     }
 
+    /// Oneof accessor is not generated if its name clashes with a field accessor
+    /// or a method of a trait implemented by the message.
+    fn oneof_accessor_name_is_free(&self, oneof: &OneofGen) -> bool {
+        let name = oneof.oneof.field_name().to_string();
+        if name == "new"
+            || name == "generated_message_descriptor_data"
+            || MESSAGE_TRAIT_METHOD_NAMES.contains(&name.as_str())
+        {
+            return false;
+        }
+        self.fields.iter().all(|f| {
+            let field = f.rust_name.get();
+            name != field
+                && ["has_", "set_", "mut_", "take_", "clear_"]
+                    .iter()
+                    .all(|prefix| name != format!("{}{}", prefix, field))
+                && name != format!("{}_opt", field)
+        })
+    }
+
+    fn write_oneof_accessors(&self, w: &mut CodeWriter) {
+        for oneof in self.oneofs() {
+            if !self.oneof_accessor_name_is_free(&oneof) {
+                continue;
+            }
+            w.write_line("");
+            w.write_line(format!(
+                "/// Field of oneof `{}` which is set, if any.",
+                oneof.oneof.oneof.name()
+            ));
+            w.pub_fn(
+                &format!(
+                    "{}(&self) -> ::std::option::Option<&{}>",
+                    oneof.oneof.field_name(),
+                    oneof.enum_type().to_code(&self.customize.for_elem)
+                ),
+                |w| {
+                    w.write_line(format!("self.{}.as_ref()", oneof.oneof.field_name()));
+                },
+            );
+        }
+    }
+
+    fn write_field_numbers(&self, w: &mut CodeWriter) {
+        w.write_line("");
+        for field in &self.fields {
+            w.pub_const(
+                &format!("{}_FIELD_NUMBER", field.proto_field.name().to_uppercase()),
+                "u32",
+                &field.proto_field.number().to_string(),
+            );
+        }
+    }
+
     fn write_impl_self(&self, w: &mut CodeWriter) {
         w.impl_self_block(&format!("{}", self.rust_name()), |w| {
             w.pub_fn(&format!("new() -> {}", self.rust_name()), |w| {
                 w.write_line("::std::default::Default::default()");
             });
 
+            if self
+                .customize
+                .for_elem
+                .generate_field_numbers
+                .unwrap_or(false)
+            {
+                self.write_field_numbers(w);
+            }
+
             self.write_field_accessors(w);
+            self.write_oneof_accessors(w);
 
             if !self.lite_runtime {
                 w.write_line("");
@@ -358,6 +494,24 @@ impl<'a> MessageGen<'a> {
         });
     }
 
+    fn write_impl_from_oneof(&self, w: &mut CodeWriter, oneof: &OneofGen) {
+        let oneof_type = oneof.enum_type().to_code(&self.customize.for_elem);
+        w.impl_for_block(
+            format!("::std::convert::From<{}>", oneof_type),
+            format!("{}", self.rust_name()),
+            |w| {
+                w.def_fn(&format!("from(v: {}) -> Self", oneof_type), |w| {
+                    w.write_line("let mut m = Self::new();");
+                    w.write_line(format!(
+                        "m.{} = ::std::option::Option::Some(v);",
+                        oneof.oneof.field_name()
+                    ));
+                    w.write_line("m");
+                });
+            },
+        );
+    }
+
     fn write_unknown_fields(&self, w: &mut CodeWriter) {
         let sig = format!(
             "special_fields(&self) -> &{}::SpecialFields",
@@ -467,6 +621,13 @@ impl<'a> MessageGen<'a> {
                         continue;
                     }
 
+                    if f.is_lazy_message() {
+                        w.if_stmt(format!("!self.{}.is_initialized()", f.rust_name), |w| {
+                            w.write_line("return false;");
+                        });
+                        continue;
+                    }
+
                     f.write_for_self_field(w, "v", |w, _t| {
                         w.if_stmt("!v.is_initialized()", |w| {
                             w.write_line("return false;");
@@ -478,6 +639,78 @@ impl<'a> MessageGen<'a> {
         });
     }
 
+    fn write_uninitialized_field_path(&self, w: &mut CodeWriter) {
+        let protobuf_crate = protobuf_crate_path(&self.customize.for_elem);
+        w.def_fn(
+            "uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String>",
+            |w| {
+                for f in self.required_fields() {
+                    f.write_if_self_field_is_none(w, |w| {
+                        w.write_line(format!(
+                            "return ::std::option::Option::Some(\"{}\".to_owned());",
+                            f.proto_field.field.name()
+                        ));
+                    });
+                }
+
+                for f in self.message_fields() {
+                    let name = f.proto_field.field.name();
+                    let write_return = |w: &mut CodeWriter, path_format: &str| {
+                        w.if_let_stmt(
+                            "::std::option::Option::Some(path)",
+                            &format!("{}::Message::uninitialized_field_path(v)", protobuf_crate),
+                            |w| {
+                                w.write_line(format!(
+                                    "return ::std::option::Option::Some({});",
+                                    path_format
+                                ));
+                            },
+                        );
+                    };
+                    match f.kind {
+                        FieldKind::Map(..) => {
+                            // Map values are not checked by `is_initialized` either.
+                            continue;
+                        }
+                        FieldKind::Repeated(..) => {
+                            w.for_stmt(
+                                format!("self.{}.iter().enumerate()", f.rust_name),
+                                "(i, v)",
+                                |w| {
+                                    write_return(
+                                        w,
+                                        &format!("format!(\"{}[{{}}].{{}}\", i, path)", name),
+                                    )
+                                },
+                            );
+                        }
+                        _ if f.is_lazy_message() => {
+                            // Lazy field is checked only if it is decoded.
+                            w.if_stmt(format!("!self.{}.is_initialized()", f.rust_name), |w| {
+                                w.if_let_stmt(
+                                    "::std::result::Result::Ok(::std::option::Option::Some(v))",
+                                    &format!("self.{}.get()", f.rust_name),
+                                    |w| {
+                                        write_return(
+                                            w,
+                                            &format!("format!(\"{}.{{}}\", path)", name),
+                                        )
+                                    },
+                                );
+                            });
+                        }
+                        _ => {
+                            f.write_for_self_field(w, "v", |w, _t| {
+                                write_return(w, &format!("format!(\"{}.{{}}\", path)", name));
+                            });
+                        }
+                    }
+                }
+                w.write_line("::std::option::Option::None");
+            },
+        );
+    }
+
     fn write_impl_message(&self, w: &mut CodeWriter) {
         w.impl_for_block(
             &format!("{}::Message", protobuf_crate_path(&self.customize.for_elem),),
@@ -489,6 +722,10 @@ impl<'a> MessageGen<'a> {
                 ));
                 w.write_line("");
                 self.write_is_initialized(w);
+                if !self.message.message.is_initialized_is_always_true() {
+                    w.write_line("");
+                    self.write_uninitialized_field_path(w);
+                }
                 w.write_line("");
                 self.write_merge_from(w);
                 w.write_line("");
@@ -567,6 +804,88 @@ impl<'a> MessageGen<'a> {
         self.fields.len() <= 500
     }
 
+    fn derive_debug(&self) -> bool {
+        self.customize.for_elem.derive_debug.unwrap_or(true)
+    }
+
+    fn derive_hash(&self) -> bool {
+        self.customize.for_elem.derive_hash.unwrap_or(false)
+    }
+
+    /// Customize of given message, computed the same way as when the message is generated.
+    fn customize_for_message(&self, message: &MessageDescriptor) -> Customize {
+        let root = CustomizeElemCtx {
+            for_elem: self.root_scope.customize.clone(),
+            for_children: self.root_scope.customize.clone(),
+            callback: self.customize.callback,
+        };
+        let file = message.file_descriptor();
+        let mut customize = root.child(
+            &customize_from_rustproto_for_file(file.proto().options.get_or_default()),
+            file,
+        );
+        let mut messages = vec![message.clone()];
+        while let Some(enclosing) = messages.last().unwrap().enclosing_message() {
+            messages.push(enclosing);
+        }
+        for m in messages.iter().rev() {
+            customize = customize.child(
+                &customize_from_rustproto_for_message(m.proto().options.get_or_default()),
+                m,
+            );
+        }
+        customize.for_elem
+    }
+
+    /// Message type derives `Hash` when generated with the same options.
+    fn message_derives_hash(&self, message: &MessageDescriptor) -> bool {
+        let file_name = message.file_descriptor().proto().name();
+        // Messages from `protobuf` crate do not derive `Hash`.
+        let in_protobuf_crate = file_name == "rustproto.proto"
+            || file_name == "google/protobuf/descriptor.proto"
+            || WELL_KNOWN_TYPES_PROTO_FILE_FULL_NAMES.contains(&file_name);
+        if in_protobuf_crate && self.customize.for_elem.inside_protobuf != Some(true) {
+            return false;
+        }
+        self.customize_for_message(message)
+            .derive_hash
+            .unwrap_or(false)
+    }
+
+    fn check_derive_hash(&self) -> anyhow::Result<()> {
+        for field in &self.fields {
+            let unhashable = match field.kind {
+                FieldKind::Map(..) => "a map",
+                _ if field.is_lazy_message() => "a lazy message",
+                _ => match field.elem() {
+                    FieldElem::Message(m) => {
+                        if self.message_derives_hash(&m.message.message) {
+                            continue;
+                        }
+                        return Err(MessageGenError::UnhashableMessageField(
+                            self.message.message.full_name().to_owned(),
+                            field.proto_field.field.name().to_owned(),
+                            m.message.message.full_name().to_owned(),
+                        )
+                        .into());
+                    }
+                    _ => match field.proto_type {
+                        field_descriptor_proto::Type::TYPE_FLOAT
+                        | field_descriptor_proto::Type::TYPE_DOUBLE => "a floating point number",
+                        _ => continue,
+                    },
+                },
+            };
+            return Err(MessageGenError::UnhashableField(
+                self.message.message.full_name().to_owned(),
+                field.proto_field.field.name().to_owned(),
+                unhashable,
+            )
+            .into());
+        }
+        Ok(())
+    }
+
     fn write_struct(&self, w: &mut CodeWriter) {
         write_protoc_insertion_point_for_message(
             w,
@@ -577,8 +896,22 @@ impl<'a> MessageGen<'a> {
         if self.supports_derive_partial_eq() {
             derive.push("PartialEq");
         }
-        derive.extend(&["Clone", "Default", "Debug"]);
+        derive.extend(&["Clone", "Default"]);
+        if self.derive_debug() {
+            derive.push("Debug");
+        }
+        if self.derive_hash() {
+            derive.extend(&["Eq", "Hash"]);
+        }
+        if serde_derive_enabled(&self.customize.for_elem) {
+            derive.extend(SERDE_DERIVE);
+        }
         w.derive(&derive);
+        write_serde_attr(
+            w,
+            &self.customize.for_elem,
+            "default, rename_all = \"camelCase\"",
+        );
 
         w.pub_struct(&format!("{}", self.rust_name()), |w| {
             if !self.fields_except_oneof().is_empty() {
@@ -590,6 +923,11 @@ impl<'a> MessageGen<'a> {
             if !self.oneofs().is_empty() {
                 w.comment("message oneof groups");
                 for oneof in self.oneofs() {
+                    write_serde_attr(
+                        w,
+                        &self.customize.for_elem,
+                        "skip_serializing_if = \"::std::option::Option::is_none\"",
+                    );
                     w.field_decl_vis(
                         Visibility::Public,
                         &oneof.oneof.field_name().to_string(),
@@ -616,6 +954,7 @@ impl<'a> MessageGen<'a> {
                 &self.message_descriptor,
                 "special_fields",
             );
+            write_serde_attr(w, &self.customize.for_elem, "skip");
             w.pub_field_decl(
                 "special_fields",
                 &format!(
@@ -626,6 +965,56 @@ impl<'a> MessageGen<'a> {
         });
     }
 
+    fn builder_rust_name(&self) -> RustIdent {
+        RustIdent::new(&format!("{}Builder", self.rust_name()))
+    }
+
+    fn write_builder(&self, w: &mut CodeWriter) -> anyhow::Result<()> {
+        let fields = self.fields_except_group();
+        for f in &fields {
+            if f.rust_name.get() == "new" || f.rust_name.get() == "build" {
+                return Err(MessageGenError::BuilderFieldNameConflict(
+                    self.message.message.full_name().to_owned(),
+                    f.rust_name.to_string(),
+                )
+                .into());
+            }
+        }
+
+        w.write_line(format!("/// Builder for [`{}`].", self.rust_name()));
+        w.derive(&["Clone", "Default", "Debug"]);
+        w.pub_struct(format!("{}", self.builder_rust_name()), |w| {
+            w.field_decl("message", &format!("{}", self.rust_name()));
+        });
+        w.write_line("");
+        w.impl_self_block(format!("{}", self.builder_rust_name()), |w| {
+            w.pub_fn(&format!("new() -> {}", self.builder_rust_name()), |w| {
+                w.write_line("::std::default::Default::default()");
+            });
+            for f in &fields {
+                w.write_line("");
+                f.write_builder_setter(w);
+            }
+            w.write_line("");
+            w.write_line("/// Return the message, or an error if required fields are not set.");
+            w.pub_fn(
+                &format!(
+                    "build(self) -> {}::Result<{}>",
+                    protobuf_crate_path(&self.customize.for_elem),
+                    self.rust_name()
+                ),
+                |w| {
+                    w.write_line(format!(
+                        "{}::Message::check_initialized(&self.message)?;",
+                        protobuf_crate_path(&self.customize.for_elem)
+                    ));
+                    w.write_line("::std::result::Result::Ok(self.message)");
+                },
+            );
+        });
+        Ok(())
+    }
+
     fn write_impl_default_for_amp(&self, w: &mut CodeWriter) {
         w.impl_args_for_block(
             &["'a"],
@@ -656,10 +1045,34 @@ impl<'a> MessageGen<'a> {
         );
     }
 
+    fn write_impl_debug(&self, w: &mut CodeWriter) {
+        w.impl_for_block("::std::fmt::Debug", format!("{}", self.rust_name()), |w| {
+            w.def_fn(
+                "fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result",
+                |w| {
+                    w.comment("`Debug` is not derived, see `derive_debug` option");
+                    w.write_line(format!(
+                        "f.debug_struct(\"{}\").finish_non_exhaustive()",
+                        self.rust_name()
+                    ));
+                },
+            );
+        });
+    }
+
     pub fn write(&self, w: &mut CodeWriter) -> anyhow::Result<()> {
+        if self.derive_hash() {
+            self.check_derive_hash()?;
+        }
+
         w.all_documentation(self.info, self.path);
         self.write_struct(w);
 
+        if !self.derive_debug() {
+            w.write_line("");
+            self.write_impl_debug(w);
+        }
+
         w.write_line("");
         self.write_impl_default_for_amp(w);
 
@@ -670,6 +1083,10 @@ impl<'a> MessageGen<'a> {
 
         w.write_line("");
         self.write_impl_self(w);
+        for oneof in &self.oneofs() {
+            w.write_line("");
+            self.write_impl_from_oneof(w, oneof);
+        }
         w.write_line("");
         self.write_impl_message(w);
         if !self.lite_runtime {
@@ -684,6 +1101,11 @@ impl<'a> MessageGen<'a> {
             self.write_impl_value(w);
         }
 
+        if self.customize.for_elem.generate_builders.unwrap_or(false) {
+            w.write_line("");
+            self.write_builder(w)?;
+        }
+
         let mod_name = message_name_to_nested_mod_name(&self.message.message.name());
 
         let oneofs = self.oneofs();
@@ -773,6 +1195,8 @@ impl<'a> MessageGen<'a> {
                         &path,
                         self.info,
                     )
+                    // TODO: do not unwrap.
+                    .unwrap()
                     .write(w);
                 }
             });
@@ -785,3 +1209,153 @@ pub(crate) fn message_name_to_nested_mod_name(message_name: &str) -> RustIdent {
     let mod_name = snake_case(message_name);
     RustIdent::new(&mod_name)
 }
+
+#[cfg(test)]
+mod test {
+    use protobuf::descriptor::FileDescriptorSet;
+    use protobuf::reflect::MessageDescriptor;
+    use protobuf::text_format;
+    use protobuf_parse::ProtoPathBuf;
+
+    use crate::customize::CustomizeCallback;
+    use crate::customize::CustomizeCallbackDefault;
+    use crate::gen::all::gen_all;
+    use crate::Customize;
+
+    fn gen_file_with_derive_hash(
+        file: &str,
+        callback: &dyn CustomizeCallback,
+    ) -> anyhow::Result<()> {
+        let fds: FileDescriptorSet = text_format::parse_from_str(file).unwrap();
+        gen_all(
+            &fds.file,
+            "test",
+            &[ProtoPathBuf::new("a.proto".to_owned()).unwrap()],
+            &Customize::default().derive_hash(true),
+            callback,
+        )
+        .map(drop)
+    }
+
+    fn gen_with_derive_hash(message: &str) -> anyhow::Result<()> {
+        gen_file_with_derive_hash(
+            &format!(
+                r#"file {{ name: "a.proto" package: "a" message_type {{ {} }} }}"#,
+                message
+            ),
+            &CustomizeCallbackDefault,
+        )
+    }
+
+    #[test]
+    fn derive_hash_unhashable_field() {
+        gen_with_derive_hash(
+            r#"name: "A" field { name: "i" number: 1 label: LABEL_OPTIONAL type: TYPE_INT32 }"#,
+        )
+        .unwrap();
+
+        let err = gen_with_derive_hash(
+            r#"name: "A" field { name: "d" number: 1 label: LABEL_REPEATED type: TYPE_DOUBLE }"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            "cannot derive `Hash` for message `a.A`, because field `d` is a floating point number",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn derive_hash_message_field() {
+        gen_file_with_derive_hash(
+            r#"file {
+                name: "a.proto" package: "a"
+                message_type { name: "B" }
+                message_type {
+                    name: "A"
+                    field { name: "b" number: 1 label: LABEL_REPEATED type: TYPE_MESSAGE type_name: ".a.B" }
+                    field { name: "a" number: 2 label: LABEL_OPTIONAL type: TYPE_MESSAGE type_name: ".a.A" }
+                }
+            }"#,
+            &CustomizeCallbackDefault,
+        )
+        .unwrap();
+
+        struct NoHashForB;
+        impl CustomizeCallback for NoHashForB {
+            fn message(&self, message: &MessageDescriptor) -> Customize {
+                Customize::default().derive_hash(message.name() != "B")
+            }
+        }
+
+        let err = gen_file_with_derive_hash(
+            r#"file {
+                name: "a.proto" package: "a"
+                message_type { name: "B" }
+                message_type {
+                    name: "A"
+                    field { name: "b" number: 1 label: LABEL_OPTIONAL type: TYPE_MESSAGE type_name: ".a.B" oneof_index: 0 }
+                    oneof_decl { name: "o" }
+                }
+            }"#,
+            &NoHashForB,
+        )
+        .unwrap_err();
+        assert_eq!(
+            "cannot derive `Hash` for message `a.A`, because type `a.B` of field `b` does not derive `Hash`",
+            err.to_string()
+        );
+
+        let err = gen_file_with_derive_hash(
+            r#"
+            file {
+                name: "google/protobuf/duration.proto" package: "google.protobuf"
+                message_type { name: "Duration" }
+            }
+            file {
+                name: "a.proto" package: "a" dependency: "google/protobuf/duration.proto"
+                message_type {
+                    name: "A"
+                    field { name: "d" number: 1 label: LABEL_OPTIONAL type: TYPE_MESSAGE type_name: ".google.protobuf.Duration" }
+                }
+            }"#,
+            &CustomizeCallbackDefault,
+        )
+        .unwrap_err();
+        assert_eq!(
+            "cannot derive `Hash` for message `a.A`, because type `google.protobuf.Duration` of field `d` does not derive `Hash`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn oneof_accessor_name_clash() {
+        let fds: FileDescriptorSet = text_format::parse_from_str(
+            r#"file {
+                name: "a.proto" package: "a"
+                message_type {
+                    name: "A"
+                    field { name: "x" number: 1 label: LABEL_OPTIONAL type: TYPE_INT32 oneof_index: 0 }
+                    field { name: "y" number: 2 label: LABEL_OPTIONAL type: TYPE_INT32 oneof_index: 1 }
+                    field { name: "z" number: 3 label: LABEL_OPTIONAL type: TYPE_INT32 oneof_index: 2 }
+                    field { name: "set_z" number: 4 label: LABEL_OPTIONAL type: TYPE_INT32 }
+                    oneof_decl { name: "clear" }
+                    oneof_decl { name: "choice" }
+                    oneof_decl { name: "has_z" }
+                }
+            }"#,
+        )
+        .unwrap();
+        let files = gen_all(
+            &fds.file,
+            "test",
+            &[ProtoPathBuf::new("a.proto".to_owned()).unwrap()],
+            &Customize::default(),
+            &CustomizeCallbackDefault,
+        )
+        .unwrap();
+        let content = String::from_utf8(files[0].content.clone()).unwrap();
+        assert!(content.contains("pub fn choice(&self)"));
+        assert!(!content.contains("pub fn clear(&self)"));
+        assert_eq!(1, content.matches("pub fn has_z(&self)").count());
+    }
+}
@@ -17,5 +17,6 @@ pub(crate) mod protoc_insertion_point;
 pub(crate) mod rust;
 pub(crate) mod rust_types_values;
 pub(crate) mod scope;
+pub(crate) mod serde;
 pub(crate) mod strx;
 pub(crate) mod well_known_types;
@@ -1,5 +1,8 @@
+use std::str;
+
 use crate::compiler_plugin;
 use crate::gen::code_writer::CodeWriter;
+use crate::gen::file::GenFileResult;
 
 pub(crate) fn gen_mod_rs(mods: &[String]) -> compiler_plugin::GenResult {
     let v = CodeWriter::with_no_error(|w| {
@@ -16,3 +19,24 @@ pub(crate) fn gen_mod_rs(mods: &[String]) -> compiler_plugin::GenResult {
         content: v.into_bytes(),
     }
 }
+
+/// `mod.rs` with generated files inlined as modules.
+pub(crate) fn gen_mod_rs_single_file(files: &[GenFileResult]) -> compiler_plugin::GenResult {
+    let v = CodeWriter::with_no_error(|w| {
+        w.comment(&format!("{}generated", "@"));
+        let mut files: Vec<&GenFileResult> = files.iter().collect();
+        files.sort_by(|a, b| a.mod_name.cmp(&b.mod_name));
+        for file in files {
+            let content = str::from_utf8(&file.compiler_plugin_result.content)
+                .expect("generated code is UTF-8");
+            w.write_line("");
+            // Cross-file references are `super::<mod>::<Type>`,
+            // so they resolve the same way as with separate files.
+            w.pub_mod(&file.mod_name, |w| w.write_text(content));
+        }
+    });
+    compiler_plugin::GenResult {
+        name: "mod.rs".to_owned(),
+        content: v.into_bytes(),
+    }
+}
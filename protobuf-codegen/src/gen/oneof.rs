@@ -29,6 +29,9 @@ use crate::gen::scope::OneofVariantWithContext;
 use crate::gen::scope::OneofWithContext;
 use crate::gen::scope::RootScope;
 use crate::gen::scope::WithScope;
+use crate::gen::serde::serde_derive_enabled;
+use crate::gen::serde::write_serde_attr;
+use crate::gen::serde::SERDE_DERIVE;
 
 // oneof one { ... }
 #[derive(Clone)]
@@ -223,8 +226,9 @@ impl<'a> OneofGen<'a> {
             .collect()
     }
 
-    pub fn full_storage_type(&self) -> RustType {
-        RustType::Option(Box::new(RustType::Oneof(
+    /// Oneof enum type, relative to the message module.
+    pub fn enum_type(&self) -> RustType {
+        RustType::Oneof(
             self.type_name_relative(
                 &self
                     .oneof
@@ -234,7 +238,11 @@ impl<'a> OneofGen<'a> {
                     .relative_mod,
             )
             .clone(),
-        )))
+        )
+    }
+
+    pub fn full_storage_type(&self) -> RustType {
+        RustType::Option(Box::new(self.enum_type()))
     }
 
     fn file_and_mod(&self) -> FileAndMod {
@@ -250,8 +258,15 @@ impl<'a> OneofGen<'a> {
     }
 
     fn write_enum(&self, w: &mut CodeWriter) {
-        let derive = vec!["Clone", "PartialEq", "Debug"];
+        let mut derive = vec!["Clone", "PartialEq", "Debug"];
+        if self.customize.for_elem.derive_hash.unwrap_or(false) {
+            derive.extend(&["Eq", "Hash"]);
+        }
+        if serde_derive_enabled(&self.customize.for_elem) {
+            derive.extend(SERDE_DERIVE);
+        }
         w.derive(&derive);
+        write_serde_attr(w, &self.customize.for_elem, "rename_all = \"camelCase\"");
         w.write_line("#[non_exhaustive]");
         write_protoc_insertion_point_for_oneof(w, &self.customize.for_elem, &self.oneof.oneof);
         w.pub_enum(&self.oneof.rust_name().ident.to_string(), |w| {
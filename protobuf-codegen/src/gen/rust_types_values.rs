@@ -41,6 +41,8 @@ pub(crate) enum RustType {
     Str,
     Option(Box<RustType>),
     MessageField(Box<RustType>),
+    LazyMessage(Box<RustType>),
+    ArcMessageField(Box<RustType>),
     // Box<T>
     Uniq(Box<RustType>),
     // &T
@@ -86,6 +88,16 @@ impl RustType {
                 protobuf_crate_path(customize),
                 param.to_code(customize)
             ),
+            RustType::LazyMessage(ref param) => format!(
+                "{}::LazyMessage<{}>",
+                protobuf_crate_path(customize),
+                param.to_code(customize)
+            ),
+            RustType::ArcMessageField(ref param) => format!(
+                "{}::ArcMessageField<{}>",
+                protobuf_crate_path(customize),
+                param.to_code(customize)
+            ),
             RustType::Uniq(ref param) => format!("::std::boxed::Box<{}>", param.to_code(customize)),
             RustType::Ref(ref param) => format!("&{}", param.to_code(customize)),
             RustType::Message(ref name) => format!("{}", name),
@@ -228,6 +240,15 @@ impl RustType {
             RustType::MessageField(..) => {
                 format!("{}::MessageField::none()", protobuf_crate_path(customize))
             }
+            RustType::LazyMessage(..) => {
+                format!("{}::LazyMessage::none()", protobuf_crate_path(customize))
+            }
+            RustType::ArcMessageField(..) => {
+                format!(
+                    "{}::ArcMessageField::none()",
+                    protobuf_crate_path(customize)
+                )
+            }
             RustType::Message(ref name) => format!("{}::new()", name),
             RustType::Ref(ref m) if m.is_message() => match **m {
                 RustType::Message(ref name) => name.default_instance(customize),
@@ -266,6 +287,8 @@ impl RustType {
             | RustType::Chars
             | RustType::String
             | RustType::MessageField(..)
+            | RustType::LazyMessage(..)
+            | RustType::ArcMessageField(..)
             | RustType::HashMap(..) => format!("{}.clear()", v),
             RustType::Bool
             | RustType::Float(..)
@@ -402,7 +425,7 @@ impl RustType {
     pub fn elem_type(&self) -> RustType {
         match self {
             &RustType::Option(ref ty) => (**ty).clone(),
-            &RustType::MessageField(ref ty) => (**ty).clone(),
+            &RustType::MessageField(ref ty) | &RustType::ArcMessageField(ref ty) => (**ty).clone(),
             x => panic!("cannot get elem type of {:?}", x),
         }
     }
@@ -412,7 +435,8 @@ impl RustType {
         match self {
             &RustType::Vec(ref ty)
             | &RustType::Option(ref ty)
-            | &RustType::MessageField(ref ty) => RustType::Ref(ty.clone()),
+            | &RustType::MessageField(ref ty)
+            | &RustType::ArcMessageField(ref ty) => RustType::Ref(ty.clone()),
             x => panic!("cannot iterate {:?}", x),
         }
     }
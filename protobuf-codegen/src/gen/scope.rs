@@ -12,6 +12,7 @@ use protobuf_parse::ProtobufIdentRef;
 use protobuf_parse::ProtobufRelPath;
 use protobuf_parse::ProtobufRelPathRef;
 
+use crate::customize::rustproto_proto::customize_from_rustproto_for_file;
 use crate::customize::Customize;
 use crate::gen::field::rust_field_name_for_protobuf_field_name;
 use crate::gen::file_and_mod::FileAndMod;
@@ -25,15 +26,22 @@ use crate::gen::strx::capitalize;
 
 pub(crate) struct RootScope<'a> {
     pub file_descriptors: &'a [FileDescriptor],
+    /// Customize passed to codegen.
+    pub customize: &'a Customize,
 }
 
 impl<'a> RootScope<'a> {
+    pub fn file_scope(&self, file_descriptor: &'a FileDescriptor) -> FileScope<'a> {
+        FileScope {
+            file_descriptor,
+            customize: self.customize,
+        }
+    }
+
     fn packages(&'a self) -> Vec<FileScope<'a>> {
         self.file_descriptors
             .iter()
-            .map(|fd| FileScope {
-                file_descriptor: fd,
-            })
+            .map(|fd| self.file_scope(fd))
             .collect()
     }
 
@@ -67,6 +75,8 @@ impl<'a> RootScope<'a> {
 #[derive(Clone, Debug)]
 pub(crate) struct FileScope<'a> {
     pub file_descriptor: &'a FileDescriptor,
+    /// Customize passed to codegen, not specific to this file.
+    customize: &'a Customize,
 }
 
 impl<'a> Deref for FileScope<'a> {
@@ -78,6 +88,24 @@ impl<'a> Deref for FileScope<'a> {
 }
 
 impl<'a> FileScope<'a> {
+    /// Rust name of message or enum declared in this file.
+    fn rust_type_name(&self, name: &str) -> RustIdent {
+        // Types from `google.protobuf` are referenced from `protobuf` crate.
+        if self.file_descriptor.package() == "google.protobuf" {
+            return RustIdent::new(name);
+        }
+        let mut customize = self.customize.clone();
+        customize.update_with(&customize_from_rustproto_for_file(
+            self.proto().options.get_or_default(),
+        ));
+        RustIdent::new(&format!(
+            "{}{}{}",
+            customize.type_name_prefix.as_deref().unwrap_or(""),
+            name,
+            customize.type_name_suffix.as_deref().unwrap_or(""),
+        ))
+    }
+
     fn package(&self) -> ProtobufAbsPath {
         ProtobufAbsPath::package_from_file_descriptor(self.file_descriptor)
     }
@@ -315,8 +343,9 @@ pub(crate) trait WithScope<'a> {
 
     // rust type name of this descriptor
     fn rust_name(&self) -> RustIdent {
-        let rust_name = capitalize(&self.name());
-        RustIdent::new(&rust_name)
+        self.scope()
+            .file_scope
+            .rust_type_name(&capitalize(self.name()))
     }
 
     fn rust_name_to_file(&self) -> RustIdentWithPath {
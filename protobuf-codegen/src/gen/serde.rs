@@ -0,0 +1,16 @@
+use crate::customize::Customize;
+use crate::gen::code_writer::CodeWriter;
+
+/// Derives added to generated types when `serde_derive` option is enabled.
+pub(crate) const SERDE_DERIVE: &[&str] = &["::serde::Serialize", "::serde::Deserialize"];
+
+pub(crate) fn serde_derive_enabled(customize: &Customize) -> bool {
+    customize.serde_derive.unwrap_or(false)
+}
+
+/// Write `#[serde(...)]` attribute if `serde_derive` option is enabled.
+pub(crate) fn write_serde_attr(w: &mut CodeWriter, customize: &Customize, attr: &str) {
+    if serde_derive_enabled(customize) {
+        w.write_line(format!("#[serde({})]", attr));
+    }
+}
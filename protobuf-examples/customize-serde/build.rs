@@ -34,4 +34,12 @@ fn main() {
         .inputs(&["src/customize_example.proto"])
         .customize_callback(GenSerde)
         .run_from_script();
+
+    // Or derive serde for all generated types with built-in option.
+    Codegen::new()
+        .cargo_out_dir("protos_serde_derive")
+        .include("src")
+        .inputs(["src/serde_derive_example.proto"])
+        .customize(Customize::default().serde_derive(true))
+        .run_from_script();
 }
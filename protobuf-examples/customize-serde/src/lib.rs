@@ -10,6 +10,9 @@
 //! This crate is an example how to inject serde annotations into generated code.
 //!
 //! Annotations are configured from `build.rs`.
+//!
+//! Alternatively, `Customize::serde_derive` option generates serde derives
+//! for all messages, enums and oneofs, see [`serde_derive`] module.
 
 use std::fmt::Formatter;
 use std::marker::PhantomData;
@@ -19,6 +22,11 @@ use protobuf::EnumOrUnknown;
 
 include!(concat!(env!("OUT_DIR"), "/protos/mod.rs"));
 
+/// Code generated with `Customize::serde_derive` option.
+pub mod serde_derive {
+    include!(concat!(env!("OUT_DIR"), "/protos_serde_derive/mod.rs"));
+}
+
 fn serialize_enum_or_unknown<E: EnumFull, S: serde::Serializer>(
     e: &Option<EnumOrUnknown<E>>,
     s: S,
@@ -100,4 +108,40 @@ mod test {
 
         // TODO: add deserialization test
     }
+
+    #[test]
+    fn test_serde_derive() {
+        use crate::serde_derive::serde_derive_example::plant::Habitat;
+        use crate::serde_derive::serde_derive_example::Color;
+        use crate::serde_derive::serde_derive_example::Plant;
+
+        let mut plant = Plant::new();
+        plant.plant_name = "Fern".to_owned();
+        plant.color = Color::COLOR_GREEN.into();
+        plant.seed.mut_or_insert_default().name = "spore".to_owned();
+        plant.tags = vec!["shade".to_owned()];
+        plant.counts.insert("leaves".to_owned(), 12);
+        plant.habitat = Some(Habitat::GardenName("Kew".to_owned()));
+
+        let json = serde_json::to_string(&plant).unwrap();
+        assert_eq!(
+            concat!(
+                "{\"plantName\":\"Fern\",\"color\":\"COLOR_GREEN\",",
+                "\"seed\":{\"name\":\"spore\"},\"tags\":[\"shade\"],",
+                "\"counts\":{\"leaves\":12},\"habitat\":{\"gardenName\":\"Kew\"}}",
+            ),
+            json
+        );
+        assert_eq!(plant, serde_json::from_str::<Plant>(&json).unwrap());
+
+        // Missing fields are set to default, unknown enum values are preserved.
+        let plant: Plant = serde_json::from_str("{\"heightCm\":3,\"color\":17}").unwrap();
+        assert_eq!(Some(3), plant.height_cm);
+        assert_eq!(17, plant.color.value());
+        assert!(plant.seed.is_none());
+        assert_eq!(
+            "{\"plantName\":\"\",\"heightCm\":3,\"color\":17,\"tags\":[],\"counts\":{}}",
+            serde_json::to_string(&plant).unwrap()
+        );
+    }
 }
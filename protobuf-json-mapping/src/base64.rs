@@ -1,23 +1,31 @@
 //! Copy-pasted from the internet
-/// Available encoding character sets
-#[derive(Clone, Copy, Debug)]
-enum _CharacterSet {
-    /// The standard character set (uses `+` and `/`)
-    _Standard,
-    /// The URL safe character set (uses `-` and `_`)
-    _UrlSafe,
+
+/// Character set used to print `bytes` fields.
+///
+/// Both character sets, with or without padding, are accepted by the parser.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BytesBase64 {
+    /// The standard character set (uses `+` and `/`) with `=` padding,
+    /// as required by protobuf JSON specification.
+    #[default]
+    Standard,
+    /// The URL safe character set (uses `-` and `_`) without padding.
+    UrlSafeNoPad,
 }
 
 static STANDARD_CHARS: &'static [u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
                                         abcdefghijklmnopqrstuvwxyz\
                                         0123456789+/";
 
-static _URLSAFE_CHARS: &'static [u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
+static URLSAFE_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
                                        abcdefghijklmnopqrstuvwxyz\
                                        0123456789-_";
 
-pub fn encode(input: &[u8]) -> String {
-    let bytes = STANDARD_CHARS;
+pub fn encode(input: &[u8], char_set: BytesBase64) -> String {
+    let bytes = match char_set {
+        BytesBase64::Standard => STANDARD_CHARS,
+        BytesBase64::UrlSafeNoPad => URLSAFE_CHARS,
+    };
 
     let len = input.len();
 
@@ -68,6 +76,12 @@ pub fn encode(input: &[u8]) -> String {
         }
     }
 
+    if char_set == BytesBase64::UrlSafeNoPad {
+        while out_bytes.last() == Some(&b'=') {
+            out_bytes.pop();
+        }
+    }
+
     // `out_bytes` vec is prepopulated with `=` symbols and then only updated
     // with base64 chars, so this unsafe is safe.
     unsafe { String::from_utf8_unchecked(out_bytes) }
@@ -171,18 +185,26 @@ mod tests {
 
     #[test]
     fn test_encode_basic() {
-        assert_eq!(encode(b""), "");
-        assert_eq!(encode(b"f"), "Zg==");
-        assert_eq!(encode(b"fo"), "Zm8=");
-        assert_eq!(encode(b"foo"), "Zm9v");
-        assert_eq!(encode(b"foob"), "Zm9vYg==");
-        assert_eq!(encode(b"fooba"), "Zm9vYmE=");
-        assert_eq!(encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(encode(b"", BytesBase64::Standard), "");
+        assert_eq!(encode(b"f", BytesBase64::Standard), "Zg==");
+        assert_eq!(encode(b"fo", BytesBase64::Standard), "Zm8=");
+        assert_eq!(encode(b"foo", BytesBase64::Standard), "Zm9v");
+        assert_eq!(encode(b"foob", BytesBase64::Standard), "Zm9vYg==");
+        assert_eq!(encode(b"fooba", BytesBase64::Standard), "Zm9vYmE=");
+        assert_eq!(encode(b"foobar", BytesBase64::Standard), "Zm9vYmFy");
     }
 
     #[test]
     fn test_encode_standard_safe() {
-        assert_eq!(encode(&[251, 255]), "+/8=");
+        assert_eq!(encode(&[251, 255], BytesBase64::Standard), "+/8=");
+    }
+
+    #[test]
+    fn test_encode_url_safe_no_pad() {
+        assert_eq!(encode(&[251, 255], BytesBase64::UrlSafeNoPad), "-_8");
+        assert_eq!(encode(b"foob", BytesBase64::UrlSafeNoPad), "Zm9vYg");
+        assert_eq!(encode(b"foobar", BytesBase64::UrlSafeNoPad), "Zm9vYmFy");
+        assert_eq!(encode(b"", BytesBase64::UrlSafeNoPad), "");
     }
 
     #[test]
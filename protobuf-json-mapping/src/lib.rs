@@ -6,8 +6,12 @@ mod float;
 mod parse;
 mod print;
 mod rfc_3339;
+mod type_registry;
+#[cfg(feature = "serde_json")]
+mod value;
 mod well_known_wrapper;
 
+pub use self::base64::BytesBase64;
 pub use self::parse::merge_from_str;
 pub use self::parse::merge_from_str_with_options;
 pub use self::parse::parse_dyn_from_str;
@@ -18,5 +22,20 @@ pub use self::parse::ParseError;
 pub use self::parse::ParseOptions;
 pub use self::print::print_to_string;
 pub use self::print::print_to_string_with_options;
+pub use self::print::print_to_writer;
 pub use self::print::PrintError;
 pub use self::print::PrintOptions;
+pub use self::type_registry::TypeRegistry;
+#[cfg(feature = "serde_json")]
+pub use self::value::to_json_value;
+#[cfg(feature = "serde_json")]
+pub use self::value::to_json_value_with_options;
+
+/// Default JSON name of a field, for example, `fooBar` for `foo_bar`.
+///
+/// The name is used by printer and parser unless the field specifies
+/// `json_name` option. Like in `protoc`, underscores are dropped
+/// and a letter following an underscore is capitalized.
+pub fn json_name(proto_field_name: &str) -> String {
+    protobuf_support::json_name::json_name(proto_field_name)
+}
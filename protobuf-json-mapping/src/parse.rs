@@ -42,6 +42,8 @@ use super::base64;
 use super::float;
 use super::rfc_3339;
 use crate::base64::FromBase64Error;
+use crate::type_registry;
+use crate::type_registry::TypeRegistry;
 use crate::well_known_wrapper::WellKnownWrapper;
 
 #[derive(Debug, thiserror::Error)]
@@ -72,10 +74,26 @@ enum ParseErrorWithoutLocInner {
     ExpectingNumber,
     #[error("Unexpected token")]
     UnexpectedToken,
-    #[error("Any parsing is not implemented")]
-    AnyParsingIsNotImplemented,
+    #[error("Any object without `@type` field")]
+    AnyTypeUrlMissing,
+    #[error("Any type `{}` is not found in type registry", .0)]
+    AnyTypeNotFound(String),
+    #[error(transparent)]
+    AnyPack(protobuf::Error),
     #[error("Message not initialized")]
     MessageNotInitialized,
+    #[error("`null` is not allowed as repeated field element or map value")]
+    NullElement,
+}
+
+/// `null` is parsed as a value of `google.protobuf.Value`
+/// and `google.protobuf.NullValue`, and means absence of value for other types.
+fn null_is_value(t: &RuntimeType) -> bool {
+    match t {
+        RuntimeType::Enum(e) => e.is::<NullValue>(),
+        RuntimeType::Message(m) => *m == Value::descriptor(),
+        _ => false,
+    }
 }
 
 /// JSON parse error.
@@ -420,12 +438,26 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Read repeated field element or map value,
+    /// which cannot be `null` unless `null` is a valid value of the type.
+    fn read_element_value(&mut self, t: &RuntimeType) -> ParseResultWithoutLoc<ReflectValueBox> {
+        if !null_is_value(t) && self.tokenizer.lookahead_is_ident("null")? {
+            return Err(ParseErrorWithoutLoc(ParseErrorWithoutLocInner::NullElement));
+        }
+        self.read_value(t)
+    }
+
     fn merge_singular_field(
         &mut self,
         message: &mut dyn MessageDyn,
         field: &FieldDescriptor,
         t: &RuntimeType,
     ) -> ParseResultWithoutLoc<()> {
+        // `null` means the field is not set, including wrapper fields.
+        if !null_is_value(t) && self.tokenizer.next_ident_if_eq("null")? {
+            field.clear_field(message);
+            return Ok(());
+        }
         field.set_singular_field(message, self.read_value(t)?);
         Ok(())
     }
@@ -463,7 +495,7 @@ impl<'a> Parser<'a> {
         repeated.clear();
 
         self.read_list(|s| {
-            repeated.push(s.read_value(t)?);
+            repeated.push(s.read_element_value(t)?);
             Ok(())
         })
     }
@@ -537,7 +569,7 @@ impl<'a> Parser<'a> {
         self.read_map(
             |ss, s| ss.parse_key(s, kt),
             |s, k| {
-                let v = s.read_value(vt)?;
+                let v = s.read_element_value(vt)?;
                 map.insert(k, v);
                 Ok(())
             },
@@ -656,8 +688,6 @@ impl<'a> Parser<'a> {
             return self.merge_wk_struct(value);
         }
 
-        let descriptor = message.descriptor_dyn();
-
         self.tokenizer.next_symbol_expect_eq('{', "object")?;
         let mut first = true;
         while !self.tokenizer.next_symbol_if_eq('}')? {
@@ -667,27 +697,36 @@ impl<'a> Parser<'a> {
             first = false;
 
             let field_name = self.read_string()?;
-            // Proto3 JSON parsers are required to accept both
-            // the converted `lowerCamelCase` name and the proto field name.
-            match descriptor.field_by_name_or_json_name(&field_name) {
-                Some(field) => {
-                    self.tokenizer.next_symbol_expect_eq(':', "object")?;
-                    self.merge_field(message, &field)?;
-                }
-                None if self.parse_options.ignore_unknown_fields => {
-                    self.tokenizer.next_symbol_expect_eq(':', "object")?;
-                    self.skip_json_value()?;
-                }
-                None => {
-                    return Err(ParseErrorWithoutLoc(
-                        ParseErrorWithoutLocInner::UnknownFieldName(field_name),
-                    ))
-                }
-            };
+            self.merge_object_field(message, field_name)?;
         }
         Ok(())
     }
 
+    fn merge_object_field(
+        &mut self,
+        message: &mut dyn MessageDyn,
+        field_name: String,
+    ) -> ParseResultWithoutLoc<()> {
+        // Proto3 JSON parsers are required to accept both
+        // the converted `lowerCamelCase` name and the proto field name.
+        match message
+            .descriptor_dyn()
+            .field_by_name_or_json_name(&field_name)
+        {
+            Some(field) => {
+                self.tokenizer.next_symbol_expect_eq(':', "object")?;
+                self.merge_field(message, &field)
+            }
+            None if self.parse_options.ignore_unknown_fields => {
+                self.tokenizer.next_symbol_expect_eq(':', "object")?;
+                self.skip_json_value()
+            }
+            None => Err(ParseErrorWithoutLoc(
+                ParseErrorWithoutLocInner::UnknownFieldName(field_name),
+            )),
+        }
+    }
+
     fn merge_wk_duration(&mut self, duration: &mut Duration) -> ParseResultWithoutLoc<()> {
         let s = self.read_string()?;
         let mut lexer = Lexer::new(&s, ParserLanguage::Json);
@@ -815,12 +854,80 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
-    fn merge_wk_any(&mut self, _value: &mut Any) -> ParseResultWithoutLoc<()> {
-        Err(ParseErrorWithoutLoc(
-            ParseErrorWithoutLocInner::AnyParsingIsNotImplemented,
+    /// Find `@type` field of `Any` object without consuming it.
+    fn lookahead_any_type_url(&self) -> ParseResultWithoutLoc<String> {
+        let mut type_url = None;
+        let mut lookahead = self.clone();
+        lookahead.read_map(
+            |_, k| Ok(k),
+            |s, k| {
+                if k == "@type" {
+                    type_url = Some(s.read_string()?);
+                    Ok(())
+                } else {
+                    s.skip_json_value()
+                }
+            },
+        )?;
+        type_url.ok_or(ParseErrorWithoutLoc(
+            ParseErrorWithoutLocInner::AnyTypeUrlMissing,
         ))
     }
 
+    fn merge_wk_any(&mut self, any: &mut Any) -> ParseResultWithoutLoc<()> {
+        // `@type` is not necessarily the first field,
+        // but it is needed to know how to parse other fields.
+        let type_url = self.lookahead_any_type_url()?;
+        let descriptor = match self
+            .parse_options
+            .type_registry
+            .find_message_by_type_url(&type_url)
+        {
+            Some(descriptor) => descriptor.clone(),
+            None => {
+                return Err(ParseErrorWithoutLoc(
+                    ParseErrorWithoutLocInner::AnyTypeNotFound(type_url),
+                ))
+            }
+        };
+        let value_wrapped = type_registry::is_any_value_wrapped(&descriptor);
+
+        let mut message = descriptor.new_instance();
+
+        self.tokenizer.next_symbol_expect_eq('{', "object")?;
+        let mut first = true;
+        while !self.tokenizer.next_symbol_if_eq('}')? {
+            if !first {
+                self.tokenizer.next_symbol_expect_eq(',', "object")?;
+            }
+            first = false;
+
+            let field_name = self.read_string()?;
+            if field_name == "@type" {
+                self.tokenizer.next_symbol_expect_eq(':', "object")?;
+                self.read_string()?;
+            } else if value_wrapped && field_name == "value" {
+                self.tokenizer.next_symbol_expect_eq(':', "object")?;
+                self.merge_inner(&mut *message)?;
+            } else if value_wrapped && self.parse_options.ignore_unknown_fields {
+                self.tokenizer.next_symbol_expect_eq(':', "object")?;
+                self.skip_json_value()?;
+            } else if value_wrapped {
+                return Err(ParseErrorWithoutLoc(
+                    ParseErrorWithoutLocInner::UnknownFieldName(field_name),
+                ));
+            } else {
+                self.merge_object_field(&mut *message, field_name)?;
+            }
+        }
+
+        any.value = message
+            .write_to_bytes_dyn()
+            .map_err(|e| ParseErrorWithoutLoc(ParseErrorWithoutLocInner::AnyPack(e)))?;
+        any.type_url = type_url;
+        Ok(())
+    }
+
     fn read_wk_value(&mut self) -> ParseResultWithoutLoc<Value> {
         let mut v = Value::new();
         self.merge_wk_value(&mut v)?;
@@ -853,8 +960,12 @@ pub struct ParseOptions {
     /// Ignore unknown fields when parsing.
     ///
     /// When `true` fields with unknown names are ignored.
-    /// When `false` parser returns an error on unknown field.
+    /// When `false` (default) parser returns an error on unknown field.
     pub ignore_unknown_fields: bool,
+    /// Types of messages packed into `google.protobuf.Any`.
+    ///
+    /// Parsing `Any` with type not found in the registry is an error.
+    pub type_registry: TypeRegistry,
     /// Prevent initializing `ParseOptions` enumerating all field.
     pub _future_options: (),
 }
@@ -1,5 +1,6 @@
 use std::fmt;
 use std::fmt::Write as fmt_Write;
+use std::io;
 
 use protobuf::reflect::EnumDescriptor;
 use protobuf::reflect::EnumValueDescriptor;
@@ -31,18 +32,27 @@ use protobuf::well_known_types::wrappers::UInt64Value;
 use protobuf::MessageDyn;
 
 use crate::base64;
+use crate::base64::BytesBase64;
 use crate::float;
 use crate::rfc_3339::TmUtc;
+use crate::type_registry;
+use crate::type_registry::TypeRegistry;
 use crate::well_known_wrapper::WellKnownWrapper;
 
 #[derive(Debug, thiserror::Error)]
-enum PrintErrorInner {
+pub(crate) enum PrintErrorInner {
     #[error(transparent)]
     Fmt(fmt::Error),
-    #[error("JSON printing of Any is not implemented")]
-    AnyPrintingIsNotImplemented,
+    #[error(transparent)]
+    Io(io::Error),
+    #[error("Any type `{0}` is not found in type registry")]
+    AnyTypeNotFound(String),
+    #[error(transparent)]
+    AnyUnpack(protobuf::Error),
     #[error("Negative nanoseconds in timestamp")]
     TimestampNegativeNanos,
+    #[error("Invalid number of fractional digits: {0}, allowed values are 0, 3, 6 and 9")]
+    InvalidFractionDigits(u8),
     #[error("Unknown struct value kind")]
     UnknownStructValueKind,
 }
@@ -50,7 +60,7 @@ enum PrintErrorInner {
 /// Print to JSON error.
 #[derive(Debug, thiserror::Error)]
 #[error(transparent)]
-pub struct PrintError(PrintErrorInner);
+pub struct PrintError(pub(crate) PrintErrorInner);
 
 impl From<fmt::Error> for PrintError {
     fn from(e: fmt::Error) -> Self {
@@ -60,8 +70,167 @@ impl From<fmt::Error> for PrintError {
 
 pub type PrintResult<T> = Result<T, PrintError>;
 
-struct Printer {
-    buf: String,
+/// Receiver of JSON values produced by [`Printer`].
+///
+/// Objects and lists are reported as begin and end events,
+/// object members are preceded by [`JsonSink::key`].
+pub(crate) trait JsonSink {
+    fn begin_object(&mut self) -> PrintResult<()>;
+    fn key(&mut self, key: &str) -> PrintResult<()>;
+    fn end_object(&mut self) -> PrintResult<()>;
+    fn begin_list(&mut self) -> PrintResult<()>;
+    fn end_list(&mut self) -> PrintResult<()>;
+    fn null(&mut self) -> PrintResult<()>;
+    fn bool(&mut self, v: bool) -> PrintResult<()>;
+    fn u64(&mut self, v: u64) -> PrintResult<()>;
+    fn i64(&mut self, v: i64) -> PrintResult<()>;
+    /// Finite float, non-finite values are passed as strings.
+    fn f32(&mut self, v: f32) -> PrintResult<()>;
+    /// Finite float, non-finite values are passed as strings.
+    fn f64(&mut self, v: f64) -> PrintResult<()>;
+    fn str(&mut self, v: &str) -> PrintResult<()>;
+}
+
+/// Sink which writes JSON text.
+struct TextSink<'a> {
+    out: &'a mut dyn fmt::Write,
+    pretty: bool,
+    indent: usize,
+    /// For each open object or list, whether nothing is printed in it yet.
+    first: Vec<bool>,
+    /// Object key is just printed, so the value needs no separator.
+    after_key: bool,
+}
+
+impl<'a> TextSink<'a> {
+    fn print_newline_and_indent(&mut self) -> fmt::Result {
+        let indent = self.first.len() * self.indent;
+        write!(self.out, "\n{:indent$}", "", indent = indent)
+    }
+
+    /// Print separator before object member or list element.
+    fn print_comma_but_first(&mut self) -> fmt::Result {
+        let first = match self.first.last_mut() {
+            Some(first) => first,
+            None => return Ok(()),
+        };
+        if *first {
+            *first = false;
+        } else if self.pretty {
+            write!(self.out, ",")?;
+        } else {
+            write!(self.out, ", ")?;
+        }
+        if self.pretty {
+            self.print_newline_and_indent()?;
+        }
+        Ok(())
+    }
+
+    fn before_value(&mut self) -> fmt::Result {
+        if self.after_key {
+            self.after_key = false;
+            Ok(())
+        } else {
+            self.print_comma_but_first()
+        }
+    }
+
+    /// Print opening bracket of object or list.
+    fn print_open(&mut self, bracket: char) -> PrintResult<()> {
+        self.before_value()?;
+        self.first.push(true);
+        Ok(write!(self.out, "{}", bracket)?)
+    }
+
+    /// Print closing bracket of object or list.
+    fn print_close(&mut self, bracket: char) -> PrintResult<()> {
+        let empty = self.first.pop().expect("unbalanced close");
+        if self.pretty && !empty {
+            self.print_newline_and_indent()?;
+        }
+        Ok(write!(self.out, "{}", bracket)?)
+    }
+
+    fn print_str(&mut self, v: &str) -> fmt::Result {
+        write!(self.out, "\"")?;
+        for c in v.chars() {
+            match c {
+                '"' => write!(self.out, "\\\""),
+                '\\' => write!(self.out, "\\\\"),
+                '\n' => write!(self.out, "\\n"),
+                '\r' => write!(self.out, "\\r"),
+                '\t' => write!(self.out, "\\t"),
+                c if c.is_control() => write!(self.out, "\\u{:04x}", c as u32),
+                c => write!(self.out, "{}", c),
+            }?;
+        }
+        write!(self.out, "\"")
+    }
+
+    fn print_scalar(&mut self, v: fmt::Arguments) -> PrintResult<()> {
+        self.before_value()?;
+        Ok(self.out.write_fmt(v)?)
+    }
+}
+
+impl<'a> JsonSink for TextSink<'a> {
+    fn begin_object(&mut self) -> PrintResult<()> {
+        self.print_open('{')
+    }
+
+    fn key(&mut self, key: &str) -> PrintResult<()> {
+        self.print_comma_but_first()?;
+        self.print_str(key)?;
+        write!(self.out, ": ")?;
+        self.after_key = true;
+        Ok(())
+    }
+
+    fn end_object(&mut self) -> PrintResult<()> {
+        self.print_close('}')
+    }
+
+    fn begin_list(&mut self) -> PrintResult<()> {
+        self.print_open('[')
+    }
+
+    fn end_list(&mut self) -> PrintResult<()> {
+        self.print_close(']')
+    }
+
+    fn null(&mut self) -> PrintResult<()> {
+        self.print_scalar(format_args!("null"))
+    }
+
+    fn bool(&mut self, v: bool) -> PrintResult<()> {
+        self.print_scalar(format_args!("{}", v))
+    }
+
+    fn u64(&mut self, v: u64) -> PrintResult<()> {
+        self.print_scalar(format_args!("{}", v))
+    }
+
+    fn i64(&mut self, v: i64) -> PrintResult<()> {
+        self.print_scalar(format_args!("{}", v))
+    }
+
+    fn f32(&mut self, v: f32) -> PrintResult<()> {
+        self.print_scalar(format_args!("{:?}", v))
+    }
+
+    fn f64(&mut self, v: f64) -> PrintResult<()> {
+        self.print_scalar(format_args!("{:?}", v))
+    }
+
+    fn str(&mut self, v: &str) -> PrintResult<()> {
+        self.before_value()?;
+        Ok(self.print_str(v)?)
+    }
+}
+
+struct Printer<'a> {
+    sink: &'a mut dyn JsonSink,
     print_options: PrintOptions,
 }
 
@@ -74,16 +243,18 @@ trait JsonFloat: fmt::Display + fmt::Debug + PrintableToJson {
     fn is_pos_infinity(&self) -> bool;
     fn is_neg_infinity(&self) -> bool;
 
-    fn print_to_json_impl(&self, w: &mut String) -> PrintResult<()> {
-        Ok(if self.is_nan() {
-            write!(w, "\"{}\"", float::PROTOBUF_JSON_NAN)?
+    /// Print non-finite value as string, return `false` if value is finite.
+    fn print_non_finite(&self, w: &mut dyn JsonSink) -> PrintResult<bool> {
+        if self.is_nan() {
+            w.str(float::PROTOBUF_JSON_NAN)?;
         } else if self.is_pos_infinity() {
-            write!(w, "\"{}\"", float::PROTOBUF_JSON_INF)?
+            w.str(float::PROTOBUF_JSON_INF)?;
         } else if self.is_neg_infinity() {
-            write!(w, "\"{}\"", float::PROTOBUF_JSON_MINUS_INF)?
+            w.str(float::PROTOBUF_JSON_MINUS_INF)?;
         } else {
-            write!(w, "{:?}", self)?
-        })
+            return Ok(false);
+        }
+        Ok(true)
     }
 }
 
@@ -103,7 +274,10 @@ impl JsonFloat for f32 {
 
 impl PrintableToJson for f32 {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        Ok(self.print_to_json_impl(&mut w.buf)?)
+        if self.print_non_finite(w.sink)? {
+            return Ok(());
+        }
+        w.sink.f32(*self)
     }
 }
 
@@ -123,58 +297,56 @@ impl JsonFloat for f64 {
 
 impl PrintableToJson for f64 {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        self.print_to_json_impl(&mut w.buf)
+        if self.print_non_finite(w.sink)? {
+            return Ok(());
+        }
+        w.sink.f64(*self)
     }
 }
 
 impl PrintableToJson for u64 {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        // 64-bit integers are quoted by default
-        Ok(write!(w.buf, "\"{}\"", self)?)
+        if w.print_options.int64_as_number {
+            w.sink.u64(*self)
+        } else {
+            // 64-bit integers are quoted by default
+            w.sink.str(&self.to_string())
+        }
     }
 }
 
 impl PrintableToJson for i64 {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        // 64-bit integers are quoted by default
-        Ok(write!(w.buf, "\"{}\"", self)?)
+        if w.print_options.int64_as_number {
+            w.sink.i64(*self)
+        } else {
+            // 64-bit integers are quoted by default
+            w.sink.str(&self.to_string())
+        }
     }
 }
 
 impl PrintableToJson for u32 {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        Ok(write!(w.buf, "{}", self)?)
+        w.sink.u64(*self as _)
     }
 }
 
 impl PrintableToJson for i32 {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        Ok(write!(w.buf, "{}", self)?)
+        w.sink.i64(*self as _)
     }
 }
 
 impl PrintableToJson for bool {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        Ok(write!(w.buf, "{}", self)?)
+        w.sink.bool(*self)
     }
 }
 
 impl PrintableToJson for str {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        write!(w.buf, "\"")?;
-        for c in self.chars() {
-            match c {
-                '"' => write!(w.buf, "\\\""),
-                '\\' => write!(w.buf, "\\\\"),
-                '\n' => write!(w.buf, "\\n"),
-                '\r' => write!(w.buf, "\\r"),
-                '\t' => write!(w.buf, "\\t"),
-                c if c.is_control() => write!(w.buf, "\\u{:04x}", c as u32),
-                c => write!(w.buf, "{}", c),
-            }?;
-        }
-        write!(w.buf, "\"")?;
-        Ok(())
+        w.sink.str(self)
     }
 }
 
@@ -186,7 +358,7 @@ impl PrintableToJson for String {
 
 impl PrintableToJson for [u8] {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        let encoded = base64::encode(self);
+        let encoded = base64::encode(self, w.print_options.bytes_base64);
         encoded.print_to_json(w)
     }
 }
@@ -215,26 +387,57 @@ impl<'a> PrintableToJson for ReflectValueRef<'a> {
     }
 }
 
+/// Validate [`PrintOptions::timestamp_fraction_digits`], 9 digits by default.
+fn fraction_digits(print_options: &PrintOptions) -> PrintResult<u32> {
+    match print_options.timestamp_fraction_digits {
+        None => Ok(9),
+        Some(digits @ (0 | 3 | 6 | 9)) => Ok(digits as u32),
+        Some(digits) => Err(PrintError(PrintErrorInner::InvalidFractionDigits(digits))),
+    }
+}
+
+pub(crate) fn duration_to_string(
+    duration: &Duration,
+    print_options: &PrintOptions,
+) -> PrintResult<String> {
+    let digits = fraction_digits(print_options)?;
+    let sign = if duration.seconds < 0 || duration.nanos < 0 {
+        "-"
+    } else {
+        ""
+    };
+    let mut s = format!("{}{}", sign, duration.seconds.unsigned_abs());
+    if digits != 0 {
+        let fraction = duration.nanos.unsigned_abs() / 10u32.pow(9 - digits);
+        write!(s, ".{:0width$}", fraction, width = digits as usize)?;
+    }
+    s.push('s');
+    Ok(s)
+}
+
+pub(crate) fn timestamp_to_string(
+    timestamp: &Timestamp,
+    print_options: &PrintOptions,
+) -> PrintResult<String> {
+    let digits = fraction_digits(print_options)?;
+    if timestamp.nanos < 0 {
+        return Err(PrintError(PrintErrorInner::TimestampNegativeNanos));
+    }
+    let tm_utc = TmUtc::from_protobuf_timestamp(timestamp.seconds, timestamp.nanos as u32);
+    Ok(format!("{:.prec$}", tm_utc, prec = digits as usize))
+}
+
 impl PrintableToJson for Duration {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        let sign = if self.seconds >= 0 { "" } else { "-" };
-        Ok(write!(
-            w.buf,
-            "\"{}{}.{:09}s\"",
-            sign,
-            self.seconds.abs(),
-            self.nanos.abs()
-        )?)
+        let s = duration_to_string(self, &w.print_options)?;
+        w.print_printable(&s)
     }
 }
 
 impl PrintableToJson for Timestamp {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        if self.nanos < 0 {
-            return Err(PrintError(PrintErrorInner::TimestampNegativeNanos));
-        }
-        let tm_utc = TmUtc::from_protobuf_timestamp(self.seconds, self.nanos as u32);
-        w.print_printable(&tm_utc.to_string())
+        let s = timestamp_to_string(self, &w.print_options)?;
+        w.print_printable(&s)
     }
 }
 
@@ -245,8 +448,34 @@ impl PrintableToJson for FieldMask {
 }
 
 impl PrintableToJson for Any {
-    fn print_to_json(&self, _w: &mut Printer) -> PrintResult<()> {
-        Err(PrintError(PrintErrorInner::AnyPrintingIsNotImplemented))
+    fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
+        let descriptor = match w
+            .print_options
+            .type_registry
+            .find_message_by_type_url(&self.type_url)
+        {
+            Some(descriptor) => descriptor.clone(),
+            None => {
+                return Err(PrintError(PrintErrorInner::AnyTypeNotFound(
+                    self.type_url.clone(),
+                )))
+            }
+        };
+        let message = match self.unpack_dyn(&descriptor) {
+            Ok(message) => message.unwrap(),
+            Err(e) => return Err(PrintError(PrintErrorInner::AnyUnpack(e))),
+        };
+
+        w.sink.begin_object()?;
+        w.sink.key("@type")?;
+        w.print_printable(&self.type_url)?;
+        if type_registry::is_any_value_wrapped(&descriptor) {
+            w.sink.key("value")?;
+            w.print_message(&MessageRef::from(&*message))?;
+        } else {
+            w.print_message_fields(&MessageRef::from(&*message))?;
+        }
+        w.sink.end_object()
     }
 }
 
@@ -293,70 +522,49 @@ impl<'a, P: PrintableToJson> PrintableToJson for &'a P {
 }
 
 trait ObjectKey {
-    fn print_object_key(&self, w: &mut Printer) -> PrintResult<()>;
+    fn object_key(&self, w: &Printer) -> String;
 }
 
 impl<'a> ObjectKey for ReflectValueRef<'a> {
-    fn print_object_key(&self, w: &mut Printer) -> PrintResult<()> {
+    fn object_key(&self, w: &Printer) -> String {
         match self {
-            ReflectValueRef::String(v) => return w.print_printable::<str>(v),
-            ReflectValueRef::Bytes(v) => return w.print_printable::<[u8]>(v),
-            // do not quote, because printable is quoted
-            ReflectValueRef::U64(v) => return w.print_printable(v),
-            ReflectValueRef::I64(v) => return w.print_printable(v),
+            ReflectValueRef::String(v) => v.to_string(),
+            ReflectValueRef::Bytes(v) => base64::encode(v, w.print_options.bytes_base64),
             ReflectValueRef::Enum(d, v) if !w.print_options.enum_values_int => {
-                return w.print_enum(d, *v)
+                match d.value_by_number(*v) {
+                    Some(value) => value.name().to_owned(),
+                    None => v.to_string(),
+                }
             }
-            _ => {}
-        }
-
-        write!(w.buf, "\"")?;
-
-        match self {
-            ReflectValueRef::U32(v) => w.print_printable(v),
-            ReflectValueRef::I32(v) => w.print_printable(v),
-            ReflectValueRef::Bool(v) => w.print_printable(v),
-            ReflectValueRef::Enum(d, v) if w.print_options.enum_values_int => w.print_enum(d, *v),
-            ReflectValueRef::Enum(..)
-            | ReflectValueRef::U64(_)
-            | ReflectValueRef::I64(_)
-            | ReflectValueRef::String(_)
-            | ReflectValueRef::Bytes(_) => unreachable!(),
+            ReflectValueRef::Enum(_, v) => v.to_string(),
+            ReflectValueRef::U32(v) => v.to_string(),
+            ReflectValueRef::I32(v) => v.to_string(),
+            // Map keys are always quoted, regardless of `int64_as_number`
+            ReflectValueRef::U64(v) => v.to_string(),
+            ReflectValueRef::I64(v) => v.to_string(),
+            ReflectValueRef::Bool(v) => v.to_string(),
             ReflectValueRef::F32(_) | ReflectValueRef::F64(_) | ReflectValueRef::Message(_) => {
                 panic!("cannot be object key")
             }
-        }?;
-
-        write!(w.buf, "\"")?;
-
-        Ok(())
+        }
     }
 }
 
 impl ObjectKey for String {
-    fn print_object_key(&self, w: &mut Printer) -> PrintResult<()> {
-        w.print_printable(self)
+    fn object_key(&self, _w: &Printer) -> String {
+        self.clone()
     }
 }
 
 impl<'a, O: ObjectKey> ObjectKey for &'a O {
-    fn print_object_key(&self, w: &mut Printer) -> PrintResult<()> {
-        (*self).print_object_key(w)
+    fn object_key(&self, w: &Printer) -> String {
+        (*self).object_key(w)
     }
 }
 
-impl Printer {
-    fn print_comma_but_first(&mut self, first: &mut bool) -> fmt::Result {
-        if *first {
-            *first = false;
-            Ok(())
-        } else {
-            write!(self.buf, ", ")
-        }
-    }
-
+impl<'a> Printer<'a> {
     fn print_json_null(&mut self) -> PrintResult<()> {
-        Ok(write!(self.buf, "null")?)
+        self.sink.null()
     }
 
     fn print_printable<F: PrintableToJson + ?Sized>(&mut self, f: &F) -> PrintResult<()> {
@@ -368,15 +576,11 @@ impl Printer {
         I: IntoIterator,
         I::Item: PrintableToJson,
     {
-        write!(self.buf, "[")?;
-        for (i, item) in items.into_iter().enumerate() {
-            if i != 0 {
-                write!(self.buf, ", ")?;
-            }
+        self.sink.begin_list()?;
+        for item in items {
             self.print_printable(&item)?;
         }
-        write!(self.buf, "]")?;
-        Ok(())
+        self.sink.end_list()
     }
 
     fn print_repeated(&mut self, repeated: &ReflectRepeatedRef) -> PrintResult<()> {
@@ -389,17 +593,13 @@ impl Printer {
         K: ObjectKey,
         V: PrintableToJson,
     {
-        write!(self.buf, "{{")?;
-        for (i, (k, v)) in items.into_iter().enumerate() {
-            if i != 0 {
-                write!(self.buf, ", ")?;
-            }
-            k.print_object_key(self)?;
-            write!(self.buf, ": ")?;
+        self.sink.begin_object()?;
+        for (k, v) in items {
+            let key = k.object_key(self);
+            self.sink.key(&key)?;
             self.print_printable(&v)?;
         }
-        write!(self.buf, "}}")?;
-        Ok(())
+        self.sink.end_object()
     }
 
     fn print_map(&mut self, map: &ReflectMapRef) -> PrintResult<()> {
@@ -413,7 +613,7 @@ impl Printer {
             if self.print_options.enum_values_int {
                 self.print_printable(&value.value())
             } else {
-                Ok(write!(self.buf, "\"{}\"", value.name())?)
+                self.sink.str(value.name())
             }
         }
     }
@@ -468,10 +668,14 @@ impl Printer {
     }
 
     fn print_regular_message(&mut self, message: &MessageRef) -> Result<(), PrintError> {
+        self.sink.begin_object()?;
+        self.print_message_fields(message)?;
+        self.sink.end_object()
+    }
+
+    fn print_message_fields(&mut self, message: &MessageRef) -> Result<(), PrintError> {
         let descriptor = message.descriptor_dyn();
 
-        write!(self.buf, "{{")?;
-        let mut first = true;
         for field in descriptor.fields() {
             let json_field_name = if self.print_options.proto_field_name {
                 field.name()
@@ -497,35 +701,30 @@ impl Printer {
 
                             if !is_message && !is_oneof {
                                 let v = field.get_singular_field_or_default(&**message);
-                                self.print_comma_but_first(&mut first)?;
-                                write!(self.buf, "\"{}\": ", json_field_name)?;
+                                self.sink.key(json_field_name)?;
                                 self.print_printable(&v)?;
                             }
                         }
                     }
                     Some(v) => {
-                        self.print_comma_but_first(&mut first)?;
-                        write!(self.buf, "\"{}\": ", json_field_name)?;
+                        self.sink.key(json_field_name)?;
                         self.print_printable(&v)?;
                     }
                 },
                 ReflectFieldRef::Repeated(v) => {
                     if !v.is_empty() || self.print_options.always_output_default_values {
-                        self.print_comma_but_first(&mut first)?;
-                        write!(self.buf, "\"{}\": ", json_field_name)?;
+                        self.sink.key(json_field_name)?;
                         self.print_repeated(&v)?;
                     }
                 }
                 ReflectFieldRef::Map(v) => {
                     if !v.is_empty() || self.print_options.always_output_default_values {
-                        self.print_comma_but_first(&mut first)?;
-                        write!(self.buf, "\"{}\": ", json_field_name)?;
+                        self.sink.key(json_field_name)?;
                         self.print_map(&v)?;
                     }
                 }
             }
         }
-        write!(self.buf, "}}")?;
         Ok(())
     }
 
@@ -552,35 +751,150 @@ impl Printer {
 ///     ..Default::default()
 /// };
 /// ```
-#[derive(Default, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct PrintOptions {
     /// Use ints instead of strings for enums.
     ///
     /// Note both string or int can be parsed.
+    /// Enum values unknown to the enum descriptor are always printed as ints.
     pub enum_values_int: bool,
     /// Use protobuf field names instead of `lowerCamelCase` which is used by default.
     /// Note both names are supported when JSON is parsed.
+    ///
+    /// This is `preserving_proto_field_name` option of C++ implementation.
     pub proto_field_name: bool,
     /// Output field default values.
     pub always_output_default_values: bool,
+    /// Print 64-bit integers as JSON numbers instead of strings.
+    ///
+    /// Canonical encoding uses strings, because some JSON parsers
+    /// (e. g. JavaScript) lose precision of large numbers.
+    /// Note both strings and numbers can be parsed.
+    pub int64_as_number: bool,
+    /// Types of messages packed into `google.protobuf.Any`.
+    ///
+    /// Printing `Any` with type not found in the registry is an error.
+    pub type_registry: TypeRegistry,
+    /// Print multi-line JSON with each field and list element on a separate line.
+    pub pretty: bool,
+    /// Number of spaces per nesting level in pretty mode, default is 2.
+    pub indent: usize,
+    /// Character set of base64 encoding of `bytes` fields.
+    ///
+    /// Canonical encoding is standard base64 with padding.
+    /// Note both standard and URL safe encodings can be parsed.
+    pub bytes_base64: BytesBase64,
+    /// Number of fractional digits of seconds in `Timestamp` and `Duration`,
+    /// allowed values are 0, 3, 6 and 9, other values are printing errors.
+    ///
+    /// Nanoseconds which do not fit are truncated, not rounded.
+    /// By default 9 digits are printed.
+    pub timestamp_fraction_digits: Option<u8>,
     /// Prevent initializing `PrintOptions` enumerating all field.
     pub _future_options: (),
 }
 
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions {
+            enum_values_int: false,
+            proto_field_name: false,
+            always_output_default_values: false,
+            int64_as_number: false,
+            type_registry: TypeRegistry::default(),
+            pretty: false,
+            indent: 2,
+            bytes_base64: BytesBase64::Standard,
+            timestamp_fraction_digits: None,
+            _future_options: (),
+        }
+    }
+}
+
 /// Serialize message to JSON according to protobuf specification.
 pub fn print_to_string_with_options(
     message: &dyn MessageDyn,
     print_options: &PrintOptions,
 ) -> PrintResult<String> {
-    let mut printer = Printer {
-        buf: String::new(),
-        print_options: print_options.clone(),
-    };
-    printer.print_message(&MessageRef::from(message))?;
-    Ok(printer.buf)
+    let mut buf = String::new();
+    print_to_fmt_write(message, &mut buf, print_options)?;
+    Ok(buf)
 }
 
 /// Serialize message to JSON according to protobuf specification.
 pub fn print_to_string(message: &dyn MessageDyn) -> PrintResult<String> {
     print_to_string_with_options(message, &PrintOptions::default())
 }
+
+/// Print message to given sink.
+pub(crate) fn print_to_sink(
+    message: &dyn MessageDyn,
+    sink: &mut dyn JsonSink,
+    print_options: &PrintOptions,
+) -> PrintResult<()> {
+    let mut printer = Printer {
+        sink,
+        print_options: print_options.clone(),
+    };
+    printer.print_message(&MessageRef::from(message))
+}
+
+fn print_to_fmt_write(
+    message: &dyn MessageDyn,
+    out: &mut dyn fmt::Write,
+    print_options: &PrintOptions,
+) -> PrintResult<()> {
+    let mut sink = TextSink {
+        out,
+        pretty: print_options.pretty,
+        indent: print_options.indent,
+        first: Vec::new(),
+        after_key: false,
+    };
+    print_to_sink(message, &mut sink, print_options)
+}
+
+/// Adapter which remembers the error returned by underlying `io::Write`,
+/// because `fmt::Error` carries no information.
+struct IoWriteAdapter<W> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoWriteAdapter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match self.writer.write_all(s.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                self.error = Some(e);
+                Err(fmt::Error)
+            }
+        }
+    }
+}
+
+/// Serialize message to JSON according to protobuf specification,
+/// writing it to given writer as it is printed.
+///
+/// The output is written in many small pieces,
+/// so unbuffered writer (like `File`) should be wrapped in `BufWriter`.
+///
+/// Printing stops at the first error returned by the writer,
+/// and that error is returned, so the output may be incomplete.
+pub fn print_to_writer<W: io::Write>(
+    message: &dyn MessageDyn,
+    writer: W,
+    print_options: &PrintOptions,
+) -> PrintResult<()> {
+    let mut adapter = IoWriteAdapter {
+        writer,
+        error: None,
+    };
+    match print_to_fmt_write(message, &mut adapter, print_options) {
+        Err(PrintError(PrintErrorInner::Fmt(e))) => match adapter.error {
+            Some(e) => Err(PrintError(PrintErrorInner::Io(e))),
+            None => Err(PrintError(PrintErrorInner::Fmt(e))),
+        },
+        r => r,
+    }
+}
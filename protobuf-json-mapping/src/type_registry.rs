@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::well_known_types::any::Any;
+use protobuf::well_known_types::duration::Duration;
+use protobuf::well_known_types::field_mask::FieldMask;
+use protobuf::well_known_types::struct_::ListValue;
+use protobuf::well_known_types::struct_::Struct;
+use protobuf::well_known_types::struct_::Value;
+use protobuf::well_known_types::timestamp::Timestamp;
+use protobuf::well_known_types::wrappers::BoolValue;
+use protobuf::well_known_types::wrappers::BytesValue;
+use protobuf::well_known_types::wrappers::DoubleValue;
+use protobuf::well_known_types::wrappers::FloatValue;
+use protobuf::well_known_types::wrappers::Int32Value;
+use protobuf::well_known_types::wrappers::Int64Value;
+use protobuf::well_known_types::wrappers::StringValue;
+use protobuf::well_known_types::wrappers::UInt32Value;
+use protobuf::well_known_types::wrappers::UInt64Value;
+use protobuf::MessageFull;
+
+/// Message types used to resolve `google.protobuf.Any` type URLs.
+///
+/// # Examples
+///
+/// ```
+/// use protobuf::MessageFull;
+/// use protobuf::well_known_types::timestamp::Timestamp;
+///
+/// let mut type_registry = protobuf_json_mapping::TypeRegistry::new();
+/// type_registry.add_message(Timestamp::descriptor());
+/// let print_options = protobuf_json_mapping::PrintOptions {
+///     type_registry,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct TypeRegistry {
+    messages: HashMap<String, MessageDescriptor>,
+}
+
+impl TypeRegistry {
+    /// Empty registry.
+    pub fn new() -> TypeRegistry {
+        TypeRegistry::default()
+    }
+
+    /// Register a message type.
+    ///
+    /// Nested messages are not registered.
+    pub fn add_message(&mut self, descriptor: MessageDescriptor) {
+        self.messages
+            .insert(descriptor.full_name().to_owned(), descriptor);
+    }
+
+    /// Register all messages of a file including nested messages.
+    pub fn add_file(&mut self, file: &FileDescriptor) {
+        for message in file.messages() {
+            self.add_message_recursively(message);
+        }
+    }
+
+    fn add_message_recursively(&mut self, descriptor: MessageDescriptor) {
+        if descriptor.is_map_entry() {
+            return;
+        }
+        for nested in descriptor.nested_messages() {
+            self.add_message_recursively(nested);
+        }
+        self.add_message(descriptor);
+    }
+
+    /// Find message by fully qualified name (without leading dot).
+    pub fn find_message(&self, full_name: &str) -> Option<&MessageDescriptor> {
+        self.messages.get(full_name)
+    }
+
+    /// Find message by `Any.type_url`.
+    pub(crate) fn find_message_by_type_url(&self, type_url: &str) -> Option<&MessageDescriptor> {
+        let full_name = match type_url.rfind('/') {
+            Some(i) => &type_url[i + 1..],
+            None => return None,
+        };
+        self.find_message(full_name)
+    }
+}
+
+/// Well-known types which are not mapped to JSON objects,
+/// so they are stored in `"value"` field of `Any` JSON object.
+pub(crate) fn is_any_value_wrapped(descriptor: &MessageDescriptor) -> bool {
+    [
+        Any::descriptor(),
+        Duration::descriptor(),
+        FieldMask::descriptor(),
+        Timestamp::descriptor(),
+        ListValue::descriptor(),
+        Struct::descriptor(),
+        Value::descriptor(),
+        BoolValue::descriptor(),
+        BytesValue::descriptor(),
+        DoubleValue::descriptor(),
+        FloatValue::descriptor(),
+        Int32Value::descriptor(),
+        Int64Value::descriptor(),
+        StringValue::descriptor(),
+        UInt32Value::descriptor(),
+        UInt64Value::descriptor(),
+    ]
+    .contains(descriptor)
+}
@@ -0,0 +1,119 @@
+//! Conversion of messages to `serde_json::Value`.
+
+use protobuf::MessageDyn;
+use serde_json::Map;
+use serde_json::Number;
+
+use crate::print;
+use crate::print::JsonSink;
+use crate::print::PrintResult;
+use crate::PrintOptions;
+
+enum Frame {
+    /// Object and the key of the member being built.
+    Object(Map<String, serde_json::Value>, Option<String>),
+    List(Vec<serde_json::Value>),
+}
+
+/// Sink which builds `serde_json::Value`.
+#[derive(Default)]
+struct ValueSink {
+    stack: Vec<Frame>,
+    result: Option<serde_json::Value>,
+}
+
+impl ValueSink {
+    fn value(&mut self, value: serde_json::Value) -> PrintResult<()> {
+        match self.stack.last_mut() {
+            None => self.result = Some(value),
+            Some(Frame::Object(object, key)) => {
+                object.insert(key.take().expect("value without key"), value);
+            }
+            Some(Frame::List(list)) => list.push(value),
+        }
+        Ok(())
+    }
+}
+
+impl JsonSink for ValueSink {
+    fn begin_object(&mut self) -> PrintResult<()> {
+        self.stack.push(Frame::Object(Map::new(), None));
+        Ok(())
+    }
+
+    fn key(&mut self, key: &str) -> PrintResult<()> {
+        match self.stack.last_mut() {
+            Some(Frame::Object(_, k)) => *k = Some(key.to_owned()),
+            _ => panic!("key outside of object"),
+        }
+        Ok(())
+    }
+
+    fn end_object(&mut self) -> PrintResult<()> {
+        match self.stack.pop() {
+            Some(Frame::Object(object, _)) => self.value(serde_json::Value::Object(object)),
+            _ => panic!("unbalanced end of object"),
+        }
+    }
+
+    fn begin_list(&mut self) -> PrintResult<()> {
+        self.stack.push(Frame::List(Vec::new()));
+        Ok(())
+    }
+
+    fn end_list(&mut self) -> PrintResult<()> {
+        match self.stack.pop() {
+            Some(Frame::List(list)) => self.value(serde_json::Value::Array(list)),
+            _ => panic!("unbalanced end of list"),
+        }
+    }
+
+    fn null(&mut self) -> PrintResult<()> {
+        self.value(serde_json::Value::Null)
+    }
+
+    fn bool(&mut self, v: bool) -> PrintResult<()> {
+        self.value(v.into())
+    }
+
+    fn u64(&mut self, v: u64) -> PrintResult<()> {
+        self.value(v.into())
+    }
+
+    fn i64(&mut self, v: i64) -> PrintResult<()> {
+        self.value(v.into())
+    }
+
+    fn f32(&mut self, v: f32) -> PrintResult<()> {
+        // Shortest `f32` representation like in printed JSON,
+        // e. g. `0.1` rather than `0.10000000149011612`.
+        self.f64(format!("{:?}", v).parse().unwrap())
+    }
+
+    fn f64(&mut self, v: f64) -> PrintResult<()> {
+        self.value(Number::from_f64(v).expect("finite float").into())
+    }
+
+    fn str(&mut self, v: &str) -> PrintResult<()> {
+        self.value(v.into())
+    }
+}
+
+/// Convert message to `serde_json::Value` according to protobuf specification.
+///
+/// The result is the same as parsed output of
+/// [`print_to_string_with_options`](crate::print_to_string_with_options),
+/// except `pretty` and `indent` options are ignored.
+pub fn to_json_value_with_options(
+    message: &dyn MessageDyn,
+    print_options: &PrintOptions,
+) -> PrintResult<serde_json::Value> {
+    let mut sink = ValueSink::default();
+    print::print_to_sink(message, &mut sink, print_options)?;
+    Ok(sink.result.expect("message is printed"))
+}
+
+/// Convert message to `serde_json::Value` according to protobuf specification.
+pub fn to_json_value(message: &dyn MessageDyn) -> PrintResult<serde_json::Value> {
+    to_json_value_with_options(message, &PrintOptions::default())
+}
@@ -16,6 +16,12 @@ mod test {
     use std::collections::HashSet;
     use std::fs;
 
+    use protobuf::descriptor::field_descriptor_proto::Label;
+    use protobuf::descriptor::field_descriptor_proto::Type;
+    use protobuf::descriptor::FileDescriptorSet;
+    use protobuf::reflect::DescriptorRegistry;
+    use protobuf::Message;
+
     use crate::Parser;
 
     #[test]
@@ -77,4 +83,562 @@ mod test {
                 .name()
         );
     }
+
+    #[test]
+    fn import_public_transitivity() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.proto"), "message Apple {}").unwrap();
+        fs::write(dir.path().join("b.proto"), "message Banana {}").unwrap();
+        fs::write(
+            dir.path().join("c.proto"),
+            "import 'a.proto'; import public 'b.proto';",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("ok.proto"),
+            "import 'c.proto'; message Ok { optional Banana b = 1; }",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("err.proto"),
+            "import 'c.proto'; message Err { optional Apple a = 1; }",
+        )
+        .unwrap();
+
+        let parse = |input: &str| {
+            Parser::new()
+                .pure()
+                .include(dir.path())
+                .input(dir.path().join(input))
+                .parse_and_typecheck()
+        };
+
+        parse("ok.proto").unwrap();
+        let err = parse("err.proto").err().expect("err");
+        assert!(
+            format!("{:#}", err).contains("not imported"),
+            "unexpected error: {:#}",
+            err
+        );
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let dir = tempfile::tempdir().unwrap();
+        let a_proto = dir.path().join("a.proto");
+        fs::write(
+            &a_proto,
+            "syntax = 'proto3';\n\nmessage Apple {\n    int32 = 1;\n}\n",
+        )
+        .unwrap();
+
+        let err = Parser::new()
+            .pure()
+            .include(dir.path())
+            .input(&a_proto)
+            .parse_and_typecheck()
+            .err()
+            .expect("err");
+        let expected = format!("{}:4:11: ", a_proto.display());
+        assert!(
+            format!("{:#}", err).contains(&expected),
+            "{:#} does not contain {}",
+            err,
+            expected
+        );
+    }
+
+    #[test]
+    fn editions() {
+        let dir = tempfile::tempdir().unwrap();
+        let a_proto = dir.path().join("a.proto");
+        fs::write(
+            &a_proto,
+            r#"edition = "2023";
+
+option features.field_presence = IMPLICIT;
+
+message Apple {
+    int32 weight = 1 [features.field_presence = LEGACY_REQUIRED];
+    repeated int32 seeds = 2;
+    repeated int32 spots = 3 [features.repeated_field_encoding = EXPANDED];
+}
+"#,
+        )
+        .unwrap();
+
+        let parsed = Parser::new()
+            .pure()
+            .include(dir.path())
+            .input(&a_proto)
+            .parse_and_typecheck()
+            .unwrap();
+        let file = &parsed.file_descriptors[0];
+        assert_eq!("editions", file.syntax());
+        assert!(file.options.is_none());
+
+        let fields = &file.message_type[0].field;
+        assert_eq!(Label::LABEL_REQUIRED, fields[0].label());
+        assert!(fields[0].options.is_none());
+        assert!(fields[1].options.packed());
+        assert!(!fields[2].options.has_packed());
+    }
+
+    #[test]
+    fn editions_field_presence() {
+        let dir = tempfile::tempdir().unwrap();
+        let a_proto = dir.path().join("a.proto");
+        fs::write(
+            &a_proto,
+            r#"edition = "2023";
+
+message Apple {
+    int32 weight = 1;
+    int32 seeds = 2 [features.field_presence = IMPLICIT];
+    Apple core = 3 [features.field_presence = IMPLICIT];
+    oneof color {
+        string red = 4;
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let parsed = Parser::new()
+            .pure()
+            .include(dir.path())
+            .input(&a_proto)
+            .parse_and_typecheck()
+            .unwrap();
+        let file = &parsed.file_descriptors[0];
+        let message = &file.message_type[0];
+        let fields = &message.field;
+        // Presence is stored in features, not as proto3 `optional`.
+        assert_eq!(Label::LABEL_OPTIONAL, fields[0].label());
+        assert!(!fields[0].has_proto3_optional());
+        assert_eq!(None, fields[0].oneof_index);
+        assert!(fields[0].options.is_none());
+        assert_eq!(Label::LABEL_OPTIONAL, fields[1].label());
+        assert!(!fields[1].has_proto3_optional());
+        assert_eq!(None, fields[1].oneof_index);
+        assert_eq!(
+            Some(&[0x08, 0x02][..]),
+            fields[1].options.unknown_fields().get_length_delimited(21)
+        );
+        assert_eq!(None, fields[2].oneof_index);
+        assert_eq!(Some(0), fields[3].oneof_index);
+        assert_eq!(1, message.oneof_decl.len());
+
+        let file = protobuf::reflect::FileDescriptor::new_dynamic(file.clone(), &[]).unwrap();
+        let apple = file.message_by_package_relative_name("Apple").unwrap();
+        let weight = apple.field_by_name("weight").unwrap();
+        let seeds = apple.field_by_name("seeds").unwrap();
+        assert!(weight.has_presence());
+        assert!(!seeds.has_presence());
+        assert!(apple.field_by_name("core").unwrap().has_presence());
+        assert!(apple.field_by_name("red").unwrap().has_presence());
+
+        let mut message = apple.new_instance();
+        weight.set_singular_field(&mut *message, 0.into());
+        seeds.set_singular_field(&mut *message, 0.into());
+        // Explicitly set zero is written only for the field with explicit presence.
+        assert_eq!(vec![0x08, 0x00], message.write_to_bytes_dyn().unwrap());
+    }
+
+    #[test]
+    fn aggregate_option() {
+        let dir = tempfile::tempdir().unwrap();
+        let a_proto = dir.path().join("a.proto");
+        fs::write(
+            &a_proto,
+            r#"syntax = "proto2";
+package pkg;
+import "google/protobuf/descriptor.proto";
+message Inner {
+    optional int32 a = 1;
+    repeated int32 b = 2;
+    extensions 100 to 200;
+}
+message Outer {
+    optional string name = 1;
+    repeated Inner inner = 2;
+}
+extend Inner {
+    optional bool flag = 100;
+}
+extend google.protobuf.MessageOptions {
+    optional Outer outer = 50000;
+}
+message Apple {
+    option (outer) = {
+        name: "x"
+        inner { a: 1, b: [2, 3] [pkg.flag]: true }
+        inner < a: 4 >
+    };
+}
+"#,
+        )
+        .unwrap();
+
+        let parse = |parser: &mut Parser| {
+            parser
+                .include(dir.path())
+                .input(&a_proto)
+                .parse_and_typecheck()
+                .unwrap()
+                .file_descriptors
+                .into_iter()
+                .find(|f| f.name() == "a.proto")
+                .unwrap()
+        };
+
+        let pure = parse(Parser::new().pure());
+        let protoc = parse(Parser::new().protoc());
+        assert_eq!("Apple", pure.message_type[2].name());
+        assert!(pure.message_type[2]
+            .options
+            .unknown_fields()
+            .get(50000)
+            .is_some());
+        assert_eq!(protoc.message_type[2], pure.message_type[2]);
+    }
+
+    #[test]
+    fn group() {
+        let dir = tempfile::tempdir().unwrap();
+        let a_proto = dir.path().join("a.proto");
+        fs::write(
+            &a_proto,
+            r#"syntax = "proto2";
+message Apple {
+    optional group Seed = 1 [deprecated = true] {
+        optional int32 size = 2;
+    }
+    repeated group Leaf = 3 {}
+}
+"#,
+        )
+        .unwrap();
+
+        let parse = |parser: &mut Parser| {
+            parser
+                .include(dir.path())
+                .input(&a_proto)
+                .parse_and_typecheck()
+                .unwrap()
+                .file_descriptors
+                .remove(0)
+        };
+
+        let pure = parse(Parser::new().pure());
+        let protoc = parse(Parser::new().protoc());
+        assert_eq!(protoc.message_type, pure.message_type);
+
+        let apple = &pure.message_type[0];
+        assert_eq!(
+            vec!["Seed", "Leaf"],
+            apple
+                .nested_type
+                .iter()
+                .map(|m| m.name())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("seed", apple.field[0].name());
+        assert_eq!(Type::TYPE_GROUP, apple.field[0].type_());
+        assert_eq!(".Apple.Seed", apple.field[0].type_name());
+    }
+
+    #[test]
+    fn reserved() {
+        let dir = tempfile::tempdir().unwrap();
+        let a_proto = dir.path().join("a.proto");
+        fs::write(
+            &a_proto,
+            r#"syntax = "proto2";
+message Apple {
+    reserved 2, 15 to 20, 100 to max;
+    reserved "foo", "bar";
+}
+enum Color {
+    reserved -1, 2 to 5, 10 to max;
+    reserved "BLUE";
+    RED = 0;
+}
+"#,
+        )
+        .unwrap();
+
+        let parse = |parser: &mut Parser| {
+            parser
+                .include(dir.path())
+                .input(&a_proto)
+                .parse_and_typecheck()
+                .unwrap()
+                .file_descriptors
+                .remove(0)
+        };
+
+        let pure = parse(Parser::new().pure());
+        let protoc = parse(Parser::new().protoc());
+        assert_eq!(protoc.message_type, pure.message_type);
+        assert_eq!(protoc.enum_type, pure.enum_type);
+        assert_eq!(3, pure.enum_type[0].reserved_range.len());
+        assert_eq!(i32::MAX, pure.enum_type[0].reserved_range[2].end());
+    }
+
+    #[test]
+    fn source_code_info_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let a_proto = dir.path().join("a.proto");
+        fs::write(
+            &a_proto,
+            r#"syntax = "proto2";
+
+// Detached
+
+// Apple comment
+message Apple { // Apple trailing
+    // Field comment
+    optional int32 size = 1;
+    oneof kind { // Oneof trailing
+        /* Block
+         * comment */
+        string name = 2;
+    }
+    // Nested comment
+    message Seed {}
+}
+
+// Color comment
+enum Color {
+    RED = 1; // Red trailing
+}
+
+// Service comment
+service Garden {
+    // Method comment
+    rpc Grow(Apple) returns (Apple);
+}
+"#,
+        )
+        .unwrap();
+
+        let comments = |parser: &mut Parser| {
+            let parsed = parser
+                .include(dir.path())
+                .input(&a_proto)
+                .include_source_info()
+                .parse_and_typecheck()
+                .unwrap();
+            let mut comments: Vec<_> = parsed.file_descriptors[0]
+                .source_code_info
+                .location
+                .iter()
+                .filter(|l| {
+                    l.leading_comments.is_some()
+                        || l.trailing_comments.is_some()
+                        || !l.leading_detached_comments.is_empty()
+                })
+                .map(|l| {
+                    (
+                        l.path.clone(),
+                        l.leading_comments.clone(),
+                        l.trailing_comments.clone(),
+                        l.leading_detached_comments.clone(),
+                    )
+                })
+                .collect();
+            comments.sort();
+            comments
+        };
+
+        let pure = comments(Parser::new().pure());
+        let protoc = comments(Parser::new().protoc());
+        assert_eq!(protoc, pure);
+        assert_eq!(9, pure.len());
+    }
+
+    #[test]
+    fn file_descriptor_set_include_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("a.proto"),
+            "syntax = 'proto3'; package a; message Apple { int32 weight = 3; }",
+        )
+        .unwrap();
+        let b_proto = dir.path().join("b.proto");
+        fs::write(
+            &b_proto,
+            "syntax = 'proto3'; package b; import 'a.proto';\n\
+             message Banana { a.Apple apple = 1; repeated string seeds = 7; }",
+        )
+        .unwrap();
+
+        for parser in [Parser::new().pure(), Parser::new().protoc()] {
+            let fds = parser
+                .include(dir.path())
+                .input(&b_proto)
+                .include_imports()
+                .include_source_info()
+                .file_descriptor_set()
+                .unwrap();
+
+            // Dependencies come first.
+            let names: Vec<_> = fds.file.iter().map(|f| f.name()).collect();
+            assert_eq!(vec!["a.proto", "b.proto"], names);
+            assert_eq!(vec!["a.proto"], fds.file[1].dependency);
+            assert!(!fds.file[1].source_code_info.location.is_empty());
+
+            let banana = &fds.file[1].message_type[0];
+            assert_eq!("Banana", banana.name());
+            let fields: Vec<_> = banana
+                .field
+                .iter()
+                .map(|f| (f.name(), f.number(), f.type_(), f.label()))
+                .collect();
+            assert_eq!(
+                vec![
+                    ("apple", 1, Type::TYPE_MESSAGE, Label::LABEL_OPTIONAL),
+                    ("seeds", 7, Type::TYPE_STRING, Label::LABEL_REPEATED),
+                ],
+                fields
+            );
+            assert_eq!(".a.Apple", banana.field[0].type_name());
+
+            // Result is self-contained.
+            let bytes = fds.write_to_bytes().unwrap();
+            let fds = FileDescriptorSet::parse_from_bytes(&bytes).unwrap();
+            let registry = DescriptorRegistry::from_file_descriptor_set(fds).unwrap();
+            assert!(registry.message_by_full_name("a.Apple").is_some());
+        }
+    }
+
+    #[test]
+    fn parse_all() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("d.proto"),
+            "syntax = 'proto2'; message D {}",
+        )
+        .unwrap();
+        for name in ["b", "c"] {
+            fs::write(
+                dir.path().join(format!("{}.proto", name)),
+                format!(
+                    "syntax = 'proto2'; import 'd.proto'; message {} {{ optional D d = 1; }}",
+                    name.to_uppercase()
+                ),
+            )
+            .unwrap();
+        }
+        let a_proto = dir.path().join("a.proto");
+        fs::write(
+            &a_proto,
+            "syntax = 'proto2'; import 'b.proto'; import 'c.proto';\n\
+             message A { optional B b = 1; optional C c = 2; }",
+        )
+        .unwrap();
+
+        for parser in [Parser::new().pure(), Parser::new().protoc()] {
+            let files = parser
+                .include(dir.path())
+                .parse_all([&a_proto, &dir.path().join("c.proto")])
+                .unwrap();
+            let mut names: Vec<_> = files.iter().map(|f| f.name()).collect();
+            assert_eq!("d.proto", names[0]);
+            assert_eq!("a.proto", names[3]);
+            names.sort();
+            assert_eq!(vec!["a.proto", "b.proto", "c.proto", "d.proto"], names);
+        }
+    }
+
+    #[test]
+    fn custom_options() {
+        let dir = tempfile::tempdir().unwrap();
+        let options_proto = dir.path().join("options.proto");
+        let a_proto = dir.path().join("a.proto");
+        fs::write(
+            &options_proto,
+            r#"syntax = "proto2";
+package opt;
+import "google/protobuf/descriptor.proto";
+enum Level {
+    LOW = 0;
+    HIGH = 1;
+}
+message Meta {
+    optional string name = 1;
+    repeated int32 tags = 2;
+}
+extend google.protobuf.FileOptions { optional string file_opt = 50000; }
+extend google.protobuf.MessageOptions {
+    optional Meta message_opt = 50000;
+    repeated int64 message_list_opt = 50001;
+}
+extend google.protobuf.FieldOptions {
+    optional Level field_opt = 50000;
+    optional sint32 field_sint_opt = 50001;
+    optional fixed64 field_fixed_opt = 50002;
+    optional double field_double_opt = 50003;
+}
+extend google.protobuf.OneofOptions { optional bool oneof_opt = 50000; }
+extend google.protobuf.EnumOptions { optional bytes enum_opt = 50000; }
+extend google.protobuf.EnumValueOptions { optional uint32 enum_value_opt = 50000; }
+extend google.protobuf.ServiceOptions { optional float service_opt = 50000; }
+extend google.protobuf.MethodOptions { optional Meta method_opt = 50000; }
+"#,
+        )
+        .unwrap();
+        fs::write(
+            &a_proto,
+            r#"syntax = "proto3";
+package pkg;
+import "options.proto";
+option (opt.file_opt) = "file";
+option java_package = "pkg.java";
+message Apple {
+    option (opt.message_opt) = { name: "apple" tags: [1, 2] };
+    option (opt.message_opt).tags = 3;
+    option (opt.message_list_opt) = -1;
+    option (opt.message_list_opt) = 2;
+    int32 size = 1 [(opt.field_opt) = HIGH, (opt.field_sint_opt) = -5, deprecated = true];
+    fixed64 weight = 2 [(opt.field_fixed_opt) = 7, (opt.field_double_opt) = -1.5];
+    oneof kind {
+        option (opt.oneof_opt) = true;
+        string name = 3;
+    }
+    optional string color = 4;
+    map<string, Apple> seeds = 5;
+}
+enum Color {
+    option (opt.enum_opt) = "\x01\x02";
+    option allow_alias = true;
+    RED = 0 [(opt.enum_value_opt) = 10];
+    CRIMSON = 0;
+}
+service Garden {
+    option (opt.service_opt) = 0.5;
+    rpc Grow(Apple) returns (Apple) {
+        option (opt.method_opt).name = "grow";
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let parse = |parser: &mut Parser| {
+            let mut files = parser
+                .include(dir.path())
+                .input(&a_proto)
+                .parse_and_typecheck()
+                .unwrap()
+                .file_descriptors;
+            files.sort_by(|a, b| a.name().cmp(b.name()));
+            files
+        };
+
+        let pure = parse(Parser::new().pure());
+        let protoc = parse(Parser::new().protoc());
+        assert_eq!(protoc, pure);
+    }
 }
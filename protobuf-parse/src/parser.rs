@@ -5,15 +5,18 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use anyhow::Context;
+use protobuf::descriptor::FileDescriptorProto;
 use protobuf::descriptor::FileDescriptorSet;
 
 use crate::protoc;
 use crate::pure;
+use crate::pure::parse_and_typecheck::CustomResolver;
+use crate::pure::ProtoPathResolver;
 use crate::which_parser::WhichParser;
 use crate::ParsedAndTypechecked;
 
 /// Configure and invoke `.proto` parser.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct Parser {
     which_parser: WhichParser,
     pub(crate) includes: Vec<PathBuf>,
@@ -21,6 +24,9 @@ pub struct Parser {
     pub(crate) protoc: Option<PathBuf>,
     pub(crate) protoc_extra_args: Vec<OsString>,
     pub(crate) capture_stderr: bool,
+    pub(crate) include_source_info: bool,
+    include_imports: bool,
+    pub(crate) resolver: Option<CustomResolver>,
 }
 
 impl Parser {
@@ -30,6 +36,13 @@ impl Parser {
     }
 
     /// Use pure rust parser.
+    ///
+    /// Pure parser does not need `protoc` binary, and produces the same descriptors
+    /// as `protoc` does, including resolved custom options, with these exceptions:
+    ///
+    /// * [source code info](Self::include_source_info) is incomplete
+    /// * extensions of messages with `message_set_wire_format` in aggregate option values
+    ///   are encoded as regular fields rather than as message set items
     pub fn pure(&mut self) -> &mut Self {
         self.which_parser = WhichParser::Pure;
         self
@@ -55,6 +68,23 @@ impl Parser {
         self
     }
 
+    /// Resolve imports with a custom resolver, for example,
+    /// from memory instead of the file system.
+    ///
+    /// The resolver is queried before include directories.
+    /// Inputs which are not inside include directories are interpreted
+    /// as protobuf paths (like `foo/bar.proto`) and are resolved like imports.
+    ///
+    /// This option applies only to pure rust parser,
+    /// `protoc` parser fails if a resolver is set.
+    pub fn resolver(
+        &mut self,
+        resolver: impl ProtoPathResolver + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.resolver = Some(CustomResolver::new(resolver));
+        self
+    }
+
     /// Append a `.proto` file path to compile
     pub fn input(&mut self, input: impl AsRef<Path>) -> &mut Self {
         self.inputs.push(input.as_ref().to_owned());
@@ -97,6 +127,26 @@ impl Parser {
         self
     }
 
+    /// Populate `source_code_info` in generated descriptors.
+    ///
+    /// Source code info contains comments attached to declarations.
+    /// Pure rust parser only records locations of messages, fields, oneofs,
+    /// enums, enum values, services and methods.
+    pub fn include_source_info(&mut self) -> &mut Self {
+        self.include_source_info = true;
+        self
+    }
+
+    /// Include dependencies of input files in [`file_descriptor_set`](Self::file_descriptor_set),
+    /// like `protoc --include_imports`.
+    ///
+    /// Dependencies precede files which import them,
+    /// so the result is self-contained and can be consumed by tools like `grpcurl` or `buf`.
+    pub fn include_imports(&mut self) -> &mut Self {
+        self.include_imports = true;
+        self
+    }
+
     /// Parse `.proto` files and typecheck them using pure Rust parser of `protoc` command.
     pub fn parse_and_typecheck(&self) -> anyhow::Result<ParsedAndTypechecked> {
         match &self.which_parser {
@@ -109,18 +159,46 @@ impl Parser {
     }
 
     /// Parse and convert result to `FileDescriptorSet`.
+    ///
+    /// Only input files are included unless [`include_imports`](Self::include_imports) is set.
     pub fn file_descriptor_set(&self) -> anyhow::Result<FileDescriptorSet> {
         let mut generated = self.parse_and_typecheck()?;
-        let relative_paths: HashSet<_> = generated
-            .relative_paths
-            .iter()
-            .map(|path| path.to_string())
-            .collect();
-        generated
-            .file_descriptors
-            .retain(|fd| relative_paths.contains(fd.name()));
+        if !self.include_imports {
+            let relative_paths: HashSet<_> = generated
+                .relative_paths
+                .iter()
+                .map(|path| path.to_string())
+                .collect();
+            generated
+                .file_descriptors
+                .retain(|fd| relative_paths.contains(fd.name()));
+        }
         let mut fds = FileDescriptorSet::new();
         fds.file = generated.file_descriptors;
         Ok(fds)
     }
+
+    /// Parse input files and `roots` with all their dependencies.
+    ///
+    /// Returns descriptors of all parsed files, dependencies first.
+    /// Pure Rust parser parses each file once,
+    /// see [`ParserSession`](crate::pure::ParserSession).
+    pub fn parse_all(
+        &self,
+        roots: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> anyhow::Result<Vec<FileDescriptorProto>> {
+        let roots: Vec<PathBuf> = roots.into_iter().map(|p| p.as_ref().to_owned()).collect();
+        match &self.which_parser {
+            WhichParser::Pure => {
+                pure::parse_and_typecheck::parse_all(self, &roots).context("using pure parser")
+            }
+            WhichParser::Protoc => {
+                let mut parser = self.clone();
+                parser.inputs(roots);
+                Ok(protoc::parse_and_typecheck::parse_and_typecheck(&parser)
+                    .context("using protoc parser")?
+                    .file_descriptors)
+            }
+        }
+    }
 }
@@ -4,6 +4,7 @@ use protobuf::descriptor::FileDescriptorSet;
 use protobuf::Message;
 
 use crate::protoc::command::Protoc;
+use crate::pure::parse_and_typecheck::check_no_custom_resolver;
 use crate::pure::parse_and_typecheck::path_to_proto_path;
 use crate::ParsedAndTypechecked;
 use crate::Parser;
@@ -11,6 +12,8 @@ use crate::ProtoPathBuf;
 
 /// Parse `.proto` files using `protoc` command.
 pub(crate) fn parse_and_typecheck(parser: &Parser) -> anyhow::Result<ParsedAndTypechecked> {
+    check_no_custom_resolver(parser)?;
+
     let temp_dir = tempfile::Builder::new()
         .prefix("protobuf-parse")
         .tempdir()?;
@@ -33,6 +36,7 @@ pub(crate) fn parse_and_typecheck(parser: &Parser) -> anyhow::Result<ParsedAndTy
         .includes(&parser.includes)
         .out(&temp_file)
         .include_imports(true)
+        .include_source_info(parser.include_source_info)
         .extra_args(&parser.protoc_extra_args)
         .capture_stderr(parser.capture_stderr)
         .write_descriptor_set()?;
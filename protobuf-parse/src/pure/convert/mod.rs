@@ -1,10 +1,12 @@
 //! Convert parser model to rust-protobuf model
 
 mod option_resolver;
+mod source_code_info;
 mod type_resolver;
 
 use protobuf;
 use protobuf::descriptor::descriptor_proto::ReservedRange;
+use protobuf::descriptor::enum_descriptor_proto::EnumReservedRange;
 use protobuf::descriptor::field_descriptor_proto;
 use protobuf::descriptor::field_descriptor_proto::Type;
 use protobuf::descriptor::FieldDescriptorProto;
@@ -341,6 +343,13 @@ impl<'a> Resolver<'a> {
                 .full_name
                 .to_string(),
         );
+        // Like protoc, set streaming flags only when `stream` is specified.
+        if input.client_streaming {
+            output.set_client_streaming(true);
+        }
+        if input.server_streaming {
+            output.set_server_streaming(true);
+        }
         Ok(output)
     }
 
@@ -378,6 +387,10 @@ impl<'a> Resolver<'a> {
             output.set_label(protobuf::descriptor::field_descriptor_proto::Label::LABEL_REPEATED);
         } else {
             output.set_label(label(input.t.rule));
+            if input.t.features.field_presence == model::FieldPresence::LegacyRequired {
+                output
+                    .set_label(protobuf::descriptor::field_descriptor_proto::Label::LABEL_REQUIRED);
+            }
 
             if self.is_proto3_optional(input) {
                 output.set_proto3_optional(true);
@@ -526,6 +539,15 @@ impl<'a> Resolver<'a> {
             .iter()
             .map(|v| self.enum_value(scope, &v))
             .collect::<Result<_, _>>()?;
+
+        for reserved in &input.reserved_nums {
+            let mut reserved_range = EnumReservedRange::new();
+            reserved_range.set_start(reserved.from);
+            // Contrary to messages, enum reserved range end is inclusive
+            reserved_range.set_end(reserved.to);
+            output.reserved_range.push(reserved_range);
+        }
+        output.reserved_name = input.reserved_names.clone();
         Ok(output)
     }
 
@@ -563,10 +585,12 @@ impl<'a> Resolver<'a> {
     }
 }
 
-fn syntax(input: model::Syntax) -> String {
+/// Like `protoc`, do not specify syntax of proto2 files.
+fn syntax(input: model::Syntax) -> Option<String> {
     match input {
-        model::Syntax::Proto2 => "proto2".to_owned(),
-        model::Syntax::Proto3 => "proto3".to_owned(),
+        model::Syntax::Proto2 => None,
+        model::Syntax::Proto3 => Some("proto3".to_owned()),
+        model::Syntax::Editions(..) => Some("editions".to_owned()),
     }
 }
 
@@ -605,6 +629,7 @@ pub(crate) fn file_descriptor(
     name: &ProtoPath,
     input: &model::FileDescriptor,
     deps: &[FileDescriptorPair],
+    include_source_info: bool,
 ) -> anyhow::Result<protobuf::descriptor::FileDescriptorProto> {
     let resolver = Resolver {
         current_file: &input,
@@ -616,7 +641,9 @@ pub(crate) fn file_descriptor(
 
     let mut output = protobuf::descriptor::FileDescriptorProto::new();
     output.set_name(fs_path_to_proto_path(name));
-    output.set_syntax(syntax(input.syntax));
+    if let Some(syntax) = syntax(input.syntax) {
+        output.set_syntax(syntax);
+    }
 
     if input.package != ProtobufAbsPath::root() {
         output.set_package(input.package.to_root_rel().to_string());
@@ -684,5 +711,9 @@ pub(crate) fn file_descriptor(
 
     option_resolver.file(&mut output)?;
 
+    if include_source_info {
+        output.source_code_info = Some(source_code_info::source_code_info(input, &output)).into();
+    }
+
     Ok(output)
 }
@@ -1,4 +1,5 @@
 use anyhow::Context;
+use protobuf::descriptor::field_descriptor_proto;
 use protobuf::descriptor::DescriptorProto;
 use protobuf::descriptor::EnumDescriptorProto;
 use protobuf::descriptor::EnumValueDescriptorProto;
@@ -10,6 +11,7 @@ use protobuf::descriptor::ServiceDescriptorProto;
 use protobuf::reflect::FieldDescriptor;
 use protobuf::reflect::FileDescriptor;
 use protobuf::reflect::MessageDescriptor;
+use protobuf::Message;
 use protobuf::MessageFull;
 use protobuf::UnknownFields;
 use protobuf::UnknownValue;
@@ -33,6 +35,11 @@ use crate::ProtobufIdentRef;
 use crate::ProtobufRelPath;
 use crate::ProtobufRelPathRef;
 
+/// `features` field number in `FieldOptions`.
+const FIELD_OPTIONS_FEATURES: u32 = 21;
+/// `FeatureSet` with `field_presence` (field 1) set to `IMPLICIT` (2).
+const FEATURES_FIELD_PRESENCE_IMPLICIT: [u8; 2] = [0x08, 0x02];
+
 #[derive(Debug, thiserror::Error)]
 enum OptionResolverError {
     #[error(transparent)]
@@ -202,6 +209,14 @@ impl<'a> ProtobufOptions for &'a [model::ProtobufOption] {
     }
 }
 
+/// `default` and `json_name` field options are written to `FieldDescriptorProto`,
+/// not to `FieldOptions`, and are handled outside of the option resolver.
+fn is_field_pseudo_option<M: MessageFull>(option: &model::ProtobufOption) -> bool {
+    M::descriptor().full_name() == "google.protobuf.FieldOptions"
+        && matches!(&option.name, ProtobufOptionName::Builtin(name)
+            if name.get() == "default" || name.get() == "json_name")
+}
+
 pub(crate) struct OptionResoler<'a> {
     pub(crate) resolver: &'a Resolver<'a>,
     pub(crate) descriptor_without_options: FileDescriptor,
@@ -353,36 +368,32 @@ impl<'a> OptionResoler<'a> {
         let field_type = TypeResolved::from_field(field.proto());
 
         match option_name_rem.split_first() {
-            Some((first, rem)) => {
-                match field_type {
-                    TypeResolved::Message(message_name) => {
-                        let m = self.find_message_by_abs_name(&message_name)?;
-                        let mut unknown_fields = UnknownFields::new();
-                        self.custom_option_ext_step(
-                            scope,
-                            &m,
-                            &mut unknown_fields,
-                            first,
-                            rem,
-                            option_value,
-                        )?;
-                        options.add_length_delimited(
-                            field.proto().number() as u32,
-                            unknown_fields.write_to_bytes(),
-                        );
-                        Ok(())
-                    }
-                    TypeResolved::Group(..) => {
-                        // TODO: implement
-                        Ok(())
-                    }
-                    _ => Err(OptionResolverError::ExtensionIsNotMessage(format!(
-                        "scope: {}, option name: {}",
-                        scope, option_name
-                    ))
-                    .into()),
+            Some((first, rem)) => match field_type {
+                TypeResolved::Message(ref message_name) | TypeResolved::Group(ref message_name) => {
+                    let m = self.find_message_by_abs_name(message_name)?;
+                    let mut unknown_fields = UnknownFields::new();
+                    self.custom_option_ext_step(
+                        scope,
+                        &m,
+                        &mut unknown_fields,
+                        first,
+                        rem,
+                        option_value,
+                    )?;
+                    let bytes = unknown_fields.write_to_bytes();
+                    let value = match field_type {
+                        TypeResolved::Group(..) => UnknownValue::Group(bytes),
+                        _ => UnknownValue::LengthDelimited(bytes),
+                    };
+                    options.add_value(field.proto().number() as u32, value);
+                    Ok(())
                 }
-            }
+                _ => Err(OptionResolverError::ExtensionIsNotMessage(format!(
+                    "scope: {}, option name: {}",
+                    scope, option_name
+                ))
+                .into()),
+            },
             None => {
                 let value = match self.option_value_to_unknown_value(
                     &field_type,
@@ -492,14 +503,14 @@ impl<'a> OptionResoler<'a> {
         option_name_for_diag: &str,
     ) -> anyhow::Result<UnknownValue> {
         match &field_type {
-            TypeResolved::Message(ma) => {
+            TypeResolved::Message(ma) | TypeResolved::Group(ma) => {
                 let m = self
                     .resolver
                     .find_message_by_abs_name(ma)
                     .map_err(OptionResolverError::OtherError)?
                     .t;
                 let mut unknown_fields = UnknownFields::new();
-                for (n, v) in &value.fields {
+                for (n, vs) in &value.fields {
                     match n {
                         ProtobufConstantMessageFieldName::Regular(n) => {
                             let f = match m.field_by_name(n.as_str()) {
@@ -510,28 +521,46 @@ impl<'a> OptionResoler<'a> {
                                     )
                                 }
                             };
-                            let u = self
-                                .option_value_field_to_unknown_value(
-                                    ma,
+                            for v in vs {
+                                let u = self
+                                    .option_value_field_to_unknown_value(
+                                        ma,
+                                        v,
+                                        n,
+                                        &f.typ,
+                                        option_name_for_diag,
+                                    )
+                                    .map_err(OptionResolverError::OtherError)?;
+                                unknown_fields.add_value(f.number as u32, u);
+                            }
+                        }
+                        ProtobufConstantMessageFieldName::Extension(e) => {
+                            // Extension names in constants are resolved from the root scope.
+                            let field = self.ext_resolve_field_ext(
+                                ProtobufAbsPathRef::root(),
+                                &self.find_message_by_abs_name(ma)?,
+                                e,
+                            )?;
+                            let field_type = TypeResolved::from_field(field.proto());
+                            for v in vs {
+                                let u = self.option_value_to_unknown_value(
+                                    &field_type,
                                     v,
-                                    n,
-                                    &f.typ,
                                     option_name_for_diag,
-                                )
-                                .map_err(OptionResolverError::OtherError)?;
-                            unknown_fields.add_value(f.number as u32, u);
-                        }
-                        ProtobufConstantMessageFieldName::Extension(..) => {
-                            // TODO: implement extension fields in constants
+                                )?;
+                                unknown_fields.add_value(field.proto().number() as u32, u);
+                            }
                         }
                         ProtobufConstantMessageFieldName::AnyTypeUrl(..) => {
                             // TODO: implement any type url in constants
                         }
                     }
                 }
-                Ok(UnknownValue::LengthDelimited(
-                    unknown_fields.write_to_bytes(),
-                ))
+                let bytes = unknown_fields.write_to_bytes();
+                Ok(match field_type {
+                    TypeResolved::Group(..) => UnknownValue::Group(bytes),
+                    _ => UnknownValue::LengthDelimited(bytes),
+                })
             }
             _ => Err(OptionResolverError::MessageFieldRequiresMessageConstant.into()),
         }
@@ -671,12 +700,6 @@ impl<'a> OptionResoler<'a> {
     where
         M: MessageFull,
     {
-        if M::descriptor().full_name() == "google.protobuf.FieldOptions" {
-            if option.get() == "default" || option.get() == "json_name" {
-                // some options are written to non-options message and handled outside
-                return Ok(());
-            }
-        }
         match M::descriptor().field_by_name(option.get()) {
             Some(field) => {
                 if field.is_repeated_or_map() {
@@ -728,6 +751,18 @@ impl<'a> OptionResoler<'a> {
     where
         M: MessageFull,
     {
+        // `descriptor.proto` bundled with this crate does not define `features`,
+        // features are resolved by the parser into the model instead.
+        let editions = matches!(
+            self.resolver.current_file.syntax,
+            model::Syntax::Editions(..)
+        );
+        let input: Vec<&model::ProtobufOption> = input
+            .iter()
+            .filter(|o| !(editions && o.is_feature()))
+            .filter(|o| !is_field_pseudo_option::<M>(o))
+            .collect();
+
         if input.is_empty() {
             // Empty options do not have to represented to unset message field,
             // but this is what Google's parser does.
@@ -881,6 +916,37 @@ impl<'a> OptionResoler<'a> {
         field_model: &model::Field,
     ) -> anyhow::Result<()> {
         field_proto.options = self.field_options(scope, &field_model.options)?.into();
+
+        if let model::Syntax::Editions(..) = self.resolver.current_file.syntax {
+            let packed_by_default = field_model.features.repeated_field_encoding
+                == model::RepeatedFieldEncoding::Packed
+                && field_proto.label() == field_descriptor_proto::Label::LABEL_REPEATED
+                && !matches!(
+                    field_proto.type_(),
+                    field_descriptor_proto::Type::TYPE_STRING
+                        | field_descriptor_proto::Type::TYPE_BYTES
+                        | field_descriptor_proto::Type::TYPE_MESSAGE
+                        | field_descriptor_proto::Type::TYPE_GROUP
+                );
+            if packed_by_default && !field_proto.options.has_packed() {
+                field_proto.options.mut_or_insert_default().set_packed(true);
+            }
+
+            if field_model.features.field_presence == model::FieldPresence::Implicit
+                && field_proto.label() != field_descriptor_proto::Label::LABEL_REPEATED
+            {
+                // Store `features.field_presence = IMPLICIT` like `protoc` does,
+                // as `FieldOptions.features` (field 21) in unknown fields.
+                field_proto
+                    .options
+                    .mut_or_insert_default()
+                    .mut_unknown_fields()
+                    .add_length_delimited(
+                        FIELD_OPTIONS_FEATURES,
+                        FEATURES_FIELD_PRESENCE_IMPLICIT.to_vec(),
+                    );
+            }
+        }
         Ok(())
     }
 
@@ -0,0 +1,147 @@
+//! Build `SourceCodeInfo` from comments collected by the parser.
+
+use protobuf::descriptor::source_code_info::Location;
+use protobuf::descriptor::DescriptorProto;
+use protobuf::descriptor::FileDescriptorProto;
+use protobuf::descriptor::SourceCodeInfo;
+use protobuf_support::lexer::loc::Loc;
+
+use crate::pure::model;
+
+// Field numbers from `descriptor.proto` used in location paths.
+const FILE_MESSAGE_TYPE: i32 = 4;
+const FILE_ENUM_TYPE: i32 = 5;
+const FILE_SERVICE: i32 = 6;
+const MESSAGE_FIELD: i32 = 2;
+const MESSAGE_NESTED_TYPE: i32 = 3;
+const MESSAGE_ENUM_TYPE: i32 = 4;
+const MESSAGE_ONEOF_DECL: i32 = 8;
+const ENUM_VALUE: i32 = 2;
+const SERVICE_METHOD: i32 = 2;
+
+struct Builder {
+    path: Vec<i32>,
+    locations: Vec<Location>,
+}
+
+impl Builder {
+    fn nested(&mut self, path: [i32; 2], f: impl FnOnce(&mut Builder)) {
+        let len = self.path.len();
+        self.path.extend(path);
+        f(self);
+        self.path.truncate(len);
+    }
+
+    fn location(&mut self, loc: Loc, comments: &model::Comments) {
+        let mut location = Location::new();
+        location.path = self.path.clone();
+        // Parser only remembers where declarations start,
+        // so span is empty and points to the first token of a declaration.
+        let line = (loc.line - 1) as i32;
+        let col = (loc.col - 1) as i32;
+        location.span = vec![line, col, col];
+        location.leading_comments = comments.leading.clone();
+        location.trailing_comments = comments.trailing.clone();
+        location.leading_detached_comments = comments.leading_detached.clone();
+        self.locations.push(location);
+    }
+
+    fn enumeration(&mut self, input: &model::WithLoc<model::Enumeration>) {
+        self.location(input.loc, &input.comments);
+        for (i, value) in input.values.iter().enumerate() {
+            self.nested([ENUM_VALUE, i as i32], |b| {
+                b.location(value.loc, &value.comments)
+            });
+        }
+    }
+
+    fn message(&mut self, input: &model::WithLoc<model::Message>, output: &DescriptorProto) {
+        self.location(input.loc, &input.comments);
+
+        for (i, field) in input
+            .regular_fields_including_in_oneofs()
+            .iter()
+            .enumerate()
+        {
+            self.nested([MESSAGE_FIELD, i as i32], |b| {
+                b.location(field.loc, &field.comments)
+            });
+        }
+
+        for fo in &input.fields {
+            if let model::FieldOrOneOf::OneOf(oneof) = &fo.t {
+                if let Some(i) = output
+                    .oneof_decl
+                    .iter()
+                    .position(|o| o.name() == oneof.name)
+                {
+                    self.nested([MESSAGE_ONEOF_DECL, i as i32], |b| {
+                        b.location(fo.loc, &oneof.comments)
+                    });
+                }
+            }
+        }
+
+        // Nested types also contain map entries and groups,
+        // so nested messages are matched by name.
+        for nested in &input.messages {
+            if let Some(i) = output
+                .nested_type
+                .iter()
+                .position(|m| m.name() == nested.name)
+            {
+                self.nested([MESSAGE_NESTED_TYPE, i as i32], |b| {
+                    b.message(nested, &output.nested_type[i])
+                });
+            }
+        }
+
+        for (i, e) in input.enums.iter().enumerate() {
+            self.nested([MESSAGE_ENUM_TYPE, i as i32], |b| b.enumeration(e));
+        }
+    }
+
+    fn service(&mut self, input: &model::WithLoc<model::Service>) {
+        self.location(input.loc, &input.comments);
+        for (i, method) in input.methods.iter().enumerate() {
+            self.nested([SERVICE_METHOD, i as i32], |b| {
+                b.location(method.loc, &method.comments)
+            });
+        }
+    }
+}
+
+/// Locations and comments of declarations in the file.
+pub(crate) fn source_code_info(
+    input: &model::FileDescriptor,
+    output: &FileDescriptorProto,
+) -> SourceCodeInfo {
+    let mut builder = Builder {
+        path: Vec::new(),
+        locations: Vec::new(),
+    };
+
+    for message in &input.messages {
+        if let Some(i) = output
+            .message_type
+            .iter()
+            .position(|m| m.name() == message.name)
+        {
+            builder.nested([FILE_MESSAGE_TYPE, i as i32], |b| {
+                b.message(message, &output.message_type[i])
+            });
+        }
+    }
+
+    for (i, e) in input.enums.iter().enumerate() {
+        builder.nested([FILE_ENUM_TYPE, i as i32], |b| b.enumeration(e));
+    }
+
+    for (i, service) in input.services.iter().enumerate() {
+        builder.nested([FILE_SERVICE, i as i32], |b| b.service(service));
+    }
+
+    let mut source_code_info = SourceCodeInfo::new();
+    source_code_info.location = builder.locations;
+    source_code_info
+}
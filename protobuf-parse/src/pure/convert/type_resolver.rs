@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::iter;
 
 use crate::model;
@@ -15,9 +16,11 @@ use crate::ProtobufRelPathRef;
 #[derive(thiserror::Error, Debug)]
 enum TypeResolverError {
     #[error("object is not found by path: {0}")]
-    NotFoundByAbsPath(ProtobufAbsPath),
+    AbsPathNotFound(ProtobufAbsPath),
     #[error("object is not found by path `{0}` in scope `{1}`")]
-    NotFoundByRelPath(ProtobufRelPath, ProtobufAbsPath),
+    RelPathNotFound(ProtobufRelPath, ProtobufAbsPath),
+    #[error("object `{0}` is defined in a file which is not imported directly or publicly")]
+    NotVisible(ProtobufAbsPath),
 }
 
 pub(crate) enum MessageOrEnum<'a> {
@@ -150,19 +153,71 @@ impl<'a> TypeResolver<'a> {
             .collect()
     }
 
-    pub(crate) fn find_message_or_enum_by_abs_name(
-        &self,
+    /// Files which symbols can be referenced from the current file:
+    /// the current file, imported files, and files imported by them
+    /// with `import public`, transitively.
+    pub(crate) fn visible_files(&self) -> Vec<&'a model::FileDescriptor> {
+        let mut files = vec![self.current_file];
+        let mut seen = HashSet::new();
+        let mut queue: Vec<_> = self.current_file.imports.iter().collect();
+        while let Some(import) = queue.pop() {
+            if !seen.insert(&import.path) {
+                continue;
+            }
+            let dep = self
+                .deps
+                .iter()
+                .find(|d| d.descriptor_proto.name() == import.path.to_str());
+            if let Some(dep) = dep {
+                files.push(&dep.parsed);
+                queue.extend(
+                    dep.parsed
+                        .imports
+                        .iter()
+                        .filter(|i| i.vis == model::ImportVis::Public),
+                );
+            }
+        }
+        files
+    }
+
+    fn find_message_or_enum_by_abs_name_in(
+        files: &[&'a model::FileDescriptor],
         absolute_path: &ProtobufAbsPath,
-    ) -> anyhow::Result<WithFullName<MessageOrEnum<'a>>> {
-        for file in self.all_files() {
+    ) -> Option<WithFullName<MessageOrEnum<'a>>> {
+        for file in files {
             if let Some(relative) = absolute_path.remove_prefix(&file.package) {
                 if let Some(w) = LookupScope::File(file).find_message_or_enum(&relative) {
-                    return Ok(w);
+                    return Some(w);
                 }
             }
         }
+        None
+    }
 
-        return Err(TypeResolverError::NotFoundByAbsPath(absolute_path.clone()).into());
+    pub(crate) fn find_message_or_enum_by_abs_name(
+        &self,
+        absolute_path: &ProtobufAbsPath,
+    ) -> anyhow::Result<WithFullName<MessageOrEnum<'a>>> {
+        match Self::find_message_or_enum_by_abs_name_in(&self.all_files(), absolute_path) {
+            Some(w) => Ok(w),
+            None => Err(TypeResolverError::AbsPathNotFound(absolute_path.clone()).into()),
+        }
+    }
+
+    /// Same as `find_message_or_enum_by_abs_name`, but only in visible files.
+    fn find_visible_message_or_enum_by_abs_name(
+        &self,
+        absolute_path: &ProtobufAbsPath,
+    ) -> anyhow::Result<WithFullName<MessageOrEnum<'a>>> {
+        match Self::find_message_or_enum_by_abs_name_in(&self.visible_files(), absolute_path) {
+            Some(w) => Ok(w),
+            None => {
+                // Make error message more helpful.
+                self.find_message_or_enum_by_abs_name(absolute_path)?;
+                Err(TypeResolverError::NotVisible(absolute_path.clone()).into())
+            }
+        }
     }
 
     pub(crate) fn resolve_message_or_enum(
@@ -171,18 +226,29 @@ impl<'a> TypeResolver<'a> {
         name: &ProtobufPath,
     ) -> anyhow::Result<WithFullName<MessageOrEnum>> {
         match name {
-            ProtobufPath::Abs(name) => Ok(self.find_message_or_enum_by_abs_name(&name)?),
+            ProtobufPath::Abs(name) => Ok(self.find_visible_message_or_enum_by_abs_name(name)?),
             ProtobufPath::Rel(name) => {
                 // find message or enum in current package
+                let mut not_imported = None;
                 for p in scope.self_and_parents() {
                     let mut fq = p.to_owned();
                     fq.push_relative(&name);
-                    if let Ok(me) = self.find_message_or_enum_by_abs_name(&fq) {
-                        return Ok(me);
+                    match self.find_visible_message_or_enum_by_abs_name(&fq) {
+                        Ok(me) => return Ok(me),
+                        Err(e) => {
+                            if let Some(TypeResolverError::NotVisible(..)) = e.downcast_ref() {
+                                not_imported.get_or_insert(e);
+                            }
+                        }
                     }
                 }
 
-                Err(TypeResolverError::NotFoundByRelPath(name.clone(), scope.to_owned()).into())
+                match not_imported {
+                    Some(e) => Err(e),
+                    None => Err(
+                        TypeResolverError::RelPathNotFound(name.clone(), scope.to_owned()).into(),
+                    ),
+                }
             }
         }
     }
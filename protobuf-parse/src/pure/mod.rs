@@ -7,4 +7,7 @@ pub(crate) mod parse_dependencies;
 mod parser;
 
 pub use parse_and_typecheck::parse_and_typecheck_custom;
+pub use parse_and_typecheck::ParserSession;
+pub use parse_and_typecheck::ProtoPathResolver;
+pub use parse_and_typecheck::ResolvedProtoFile;
 pub use parse_dependencies::*;
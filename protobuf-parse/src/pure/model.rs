@@ -3,6 +3,7 @@
 //! This crate can be seen as a rust transcription of the
 //! [descriptor.proto](https://github.com/google/protobuf/blob/master/src/google/protobuf/descriptor.proto) file
 
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Write;
 use std::ops::Deref;
@@ -26,6 +27,19 @@ pub use crate::pure::parser::ParserErrorWithLocation;
 enum ModelError {
     #[error("cannot convert value `{1}` to type `{0}`")]
     InconvertibleValue(RuntimeType, model::ProtobufConstant),
+    #[error("unknown value `{1}` of feature `{0}`")]
+    UnknownFeatureValue(String, model::ProtobufConstant),
+    #[error("field number {1} is used by both `{2}` and `{3}` in message `{0}`")]
+    DuplicateFieldNumber(String, i32, String, String),
+    #[error("field name `{1}` is used by both field number {2} and {3} in message `{0}`")]
+    DuplicateFieldName(String, String, i32, i32),
+    #[error("enum value name `{1}` is used by both value {2} and {3} in enum `{0}`")]
+    DuplicateEnumValueName(String, String, i32, i32),
+    #[error(
+        "enum value number {1} is used by both `{2}` and `{3}` in enum `{0}`, \
+        set `option allow_alias = true` to allow aliases"
+    )]
+    DuplicateEnumValueNumber(String, i32, String, String),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -51,6 +65,20 @@ impl<T> WithLoc<T> {
     }
 }
 
+/// Comments attached to a declaration.
+///
+/// Comments are associated with declarations using the same rules
+/// `protoc` uses for `SourceCodeInfo`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Comments {
+    /// Comment immediately preceding the declaration
+    pub leading: Option<String>,
+    /// Comment following the declaration on the same or the next line
+    pub trailing: Option<String>,
+    /// Comments before the declaration separated from it by blank lines
+    pub leading_detached: Vec<String>,
+}
+
 /// Protobuf syntax.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub(crate) enum Syntax {
@@ -58,6 +86,8 @@ pub(crate) enum Syntax {
     Proto2,
     /// Protobuf syntax [3](https://developers.google.com/protocol-buffers/docs/proto3)
     Proto3,
+    /// Protobuf [editions](https://protobuf.dev/editions/overview/)
+    Editions(Edition),
 }
 
 impl Default for Syntax {
@@ -66,6 +96,158 @@ impl Default for Syntax {
     }
 }
 
+/// Protobuf edition.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum Edition {
+    /// `edition = "2023"`
+    Edition2023,
+}
+
+impl Edition {
+    pub(crate) fn parse(edition: &str) -> Option<Edition> {
+        match edition {
+            "2023" => Some(Edition::Edition2023),
+            _ => None,
+        }
+    }
+}
+
+/// Equivalent of `FeatureSet.FieldPresence`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum FieldPresence {
+    Explicit,
+    Implicit,
+    LegacyRequired,
+}
+
+/// Equivalent of `FeatureSet.EnumType`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum EnumType {
+    Open,
+    Closed,
+}
+
+/// Equivalent of `FeatureSet.RepeatedFieldEncoding`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum RepeatedFieldEncoding {
+    Packed,
+    Expanded,
+}
+
+/// Resolved features of a declaration, equivalent of `FeatureSet`.
+///
+/// Features are inherited from the enclosing declaration
+/// and can be overridden with `option features.<name> = <VALUE>`.
+/// Only features affecting the descriptor are tracked.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) struct Features {
+    pub field_presence: FieldPresence,
+    pub enum_type: EnumType,
+    pub repeated_field_encoding: RepeatedFieldEncoding,
+}
+
+impl Features {
+    /// Features implied by file syntax or edition.
+    pub(crate) fn for_syntax(syntax: Syntax) -> Features {
+        match syntax {
+            Syntax::Proto2 => Features {
+                field_presence: FieldPresence::Explicit,
+                enum_type: EnumType::Closed,
+                repeated_field_encoding: RepeatedFieldEncoding::Expanded,
+            },
+            Syntax::Proto3 => Features {
+                field_presence: FieldPresence::Implicit,
+                enum_type: EnumType::Open,
+                repeated_field_encoding: RepeatedFieldEncoding::Packed,
+            },
+            Syntax::Editions(Edition::Edition2023) => Features {
+                field_presence: FieldPresence::Explicit,
+                enum_type: EnumType::Open,
+                repeated_field_encoding: RepeatedFieldEncoding::Packed,
+            },
+        }
+    }
+
+    /// Apply `features` options of a declaration.
+    pub(crate) fn with_options(mut self, options: &[ProtobufOption]) -> anyhow::Result<Features> {
+        for option in options {
+            let name = match option.feature_name() {
+                Some(name) => name,
+                None => continue,
+            };
+            let value = match &option.value {
+                ProtobufConstant::Ident(value) => value.to_string(),
+                _ => String::new(),
+            };
+            match (name, value.as_str()) {
+                ("field_presence", "EXPLICIT") => self.field_presence = FieldPresence::Explicit,
+                ("field_presence", "IMPLICIT") => self.field_presence = FieldPresence::Implicit,
+                ("field_presence", "LEGACY_REQUIRED") => {
+                    self.field_presence = FieldPresence::LegacyRequired
+                }
+                ("enum_type", "OPEN") => self.enum_type = EnumType::Open,
+                ("enum_type", "CLOSED") => self.enum_type = EnumType::Closed,
+                ("repeated_field_encoding", "PACKED") => {
+                    self.repeated_field_encoding = RepeatedFieldEncoding::Packed
+                }
+                ("repeated_field_encoding", "EXPANDED") => {
+                    self.repeated_field_encoding = RepeatedFieldEncoding::Expanded
+                }
+                ("field_presence" | "enum_type" | "repeated_field_encoding", _) => {
+                    return Err(ModelError::UnknownFeatureValue(
+                        name.to_owned(),
+                        option.value.clone(),
+                    )
+                    .into());
+                }
+                // Other features do not affect the descriptor.
+                _ => {}
+            }
+        }
+        Ok(self)
+    }
+
+    fn resolve_field(&self, field: &mut Field) -> anyhow::Result<()> {
+        field.features = self.with_options(&field.options)?;
+        if let FieldType::Group(group) = &mut field.typ {
+            for f in &mut group.fields {
+                field.features.resolve_field(&mut f.t)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_enum(&self, enumeration: &mut Enumeration) -> anyhow::Result<()> {
+        enumeration.features = self.with_options(&enumeration.options)?;
+        Ok(())
+    }
+
+    fn resolve_message(&self, message: &mut Message) -> anyhow::Result<()> {
+        let features = self.with_options(&message.options)?;
+        for fo in &mut message.fields {
+            match &mut fo.t {
+                FieldOrOneOf::Field(f) => features.resolve_field(&mut f.t)?,
+                FieldOrOneOf::OneOf(o) => {
+                    let oneof_features = features.with_options(&o.options)?;
+                    for f in &mut o.fields {
+                        oneof_features.resolve_field(&mut f.t)?;
+                    }
+                }
+            }
+        }
+        for m in &mut message.messages {
+            features.resolve_message(&mut m.t)?;
+        }
+        for e in &mut message.enums {
+            features.resolve_enum(&mut e.t)?;
+        }
+        for ext in &mut message.extensions {
+            features.resolve_field(&mut ext.t.field.t)?;
+        }
+        Ok(())
+    }
+}
+
 /// A field rule
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub(crate) enum Rule {
@@ -204,6 +386,10 @@ pub(crate) struct Field {
     pub number: i32,
     /// Non-builtin options
     pub options: Vec<ProtobufOption>,
+    /// Resolved features
+    pub features: Features,
+    /// Field comments
+    pub comments: Comments,
 }
 
 /// A Protobuf field of oneof group
@@ -245,6 +431,8 @@ pub(crate) struct Message {
     pub extension_ranges: Vec<FieldNumberRange>,
     /// Extensions
     pub extensions: Vec<WithLoc<Extension>>,
+    /// Message comments
+    pub comments: Comments,
 }
 
 impl Message {
@@ -276,6 +464,18 @@ impl Message {
             .collect()
     }
 
+    /// Check field numbers and names are unique,
+    /// including fields of oneofs and groups.
+    pub(crate) fn check_unique_fields(&self) -> anyhow::Result<()> {
+        check_unique_fields(
+            &self.name,
+            self.regular_fields_including_in_oneofs()
+                .into_iter()
+                .map(|f| &f.t)
+                .collect(),
+        )
+    }
+
     #[cfg(test)]
     pub fn regular_fields_for_test(&self) -> Vec<&Field> {
         self.fields
@@ -298,6 +498,35 @@ impl Message {
     }
 }
 
+fn check_unique_fields(scope: &str, fields: Vec<&Field>) -> anyhow::Result<()> {
+    let mut by_number: HashMap<i32, &str> = HashMap::new();
+    let mut by_name: HashMap<&str, i32> = HashMap::new();
+    for field in fields {
+        if let Some(first) = by_number.insert(field.number, &field.name) {
+            return Err(ModelError::DuplicateFieldNumber(
+                scope.to_owned(),
+                field.number,
+                first.to_owned(),
+                field.name.clone(),
+            )
+            .into());
+        }
+        if let Some(first) = by_name.insert(&field.name, field.number) {
+            return Err(ModelError::DuplicateFieldName(
+                scope.to_owned(),
+                field.name.clone(),
+                first,
+                field.number,
+            )
+            .into());
+        }
+        if let FieldType::Group(group) = &field.typ {
+            check_unique_fields(&group.name, group.fields.iter().map(|f| &f.t).collect())?;
+        }
+    }
+    Ok(())
+}
+
 /// A protobuf enumeration field
 #[derive(Debug, Clone)]
 pub(crate) struct EnumValue {
@@ -307,6 +536,8 @@ pub(crate) struct EnumValue {
     pub number: i32,
     /// enum value options
     pub options: Vec<ProtobufOption>,
+    /// enum value comments
+    pub comments: Comments,
 }
 
 /// A protobuf enumerator
@@ -315,9 +546,57 @@ pub(crate) struct Enumeration {
     /// enum name
     pub name: String,
     /// enum values
-    pub values: Vec<EnumValue>,
+    pub values: Vec<WithLoc<EnumValue>>,
     /// enum options
     pub options: Vec<ProtobufOption>,
+    /// Reserved enum value numbers
+    pub reserved_nums: Vec<FieldNumberRange>,
+    /// Reserved enum value names
+    pub reserved_names: Vec<String>,
+    /// Resolved features
+    pub features: Features,
+    /// enum comments
+    pub comments: Comments,
+}
+
+impl Enumeration {
+    /// Check value names are unique,
+    /// and value numbers are unique unless `allow_alias` option is set.
+    pub(crate) fn check_unique_values(&self) -> anyhow::Result<()> {
+        let allow_alias = self.options.iter().any(|o| {
+            o.name == ProtobufOptionName::simple("allow_alias")
+                && o.value == ProtobufConstant::Bool(true)
+        });
+        let mut by_number: HashMap<i32, &str> = HashMap::new();
+        let mut by_name: HashMap<&str, i32> = HashMap::new();
+        for value in &self.values {
+            if let Some(first) = by_name.insert(&value.name, value.number) {
+                return Err(ModelError::DuplicateEnumValueName(
+                    self.name.clone(),
+                    value.name.clone(),
+                    first,
+                    value.number,
+                )
+                .into());
+            }
+            match by_number.get(&value.number) {
+                Some(first) if !allow_alias => {
+                    return Err(ModelError::DuplicateEnumValueNumber(
+                        self.name.clone(),
+                        value.number,
+                        (*first).to_owned(),
+                        value.name.clone(),
+                    )
+                    .into());
+                }
+                Some(_) => {}
+                None => {
+                    by_number.insert(value.number, &value.name);
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 /// A OneOf
@@ -329,6 +608,8 @@ pub(crate) struct OneOf {
     pub fields: Vec<WithLoc<Field>>,
     /// oneof options
     pub options: Vec<ProtobufOption>,
+    /// oneof comments
+    pub comments: Comments,
 }
 
 #[derive(Debug, Clone)]
@@ -356,6 +637,8 @@ pub(crate) struct Method {
     pub server_streaming: bool,
     /// Method options
     pub options: Vec<ProtobufOption>,
+    /// Method comments
+    pub comments: Comments,
 }
 
 /// Service definition
@@ -363,8 +646,10 @@ pub(crate) struct Method {
 pub(crate) struct Service {
     /// Service name
     pub name: String,
-    pub methods: Vec<Method>,
+    pub methods: Vec<WithLoc<Method>>,
     pub options: Vec<ProtobufOption>,
+    /// Service comments
+    pub comments: Comments,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -398,7 +683,8 @@ impl fmt::Display for ProtobufConstantMessageFieldName {
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub(crate) struct ProtobufConstantMessage {
-    pub(crate) fields: IndexMap<ProtobufConstantMessageFieldName, ProtobufConstant>,
+    /// Values of repeated fields are collected in declaration order.
+    pub(crate) fields: IndexMap<ProtobufConstantMessageFieldName, Vec<ProtobufConstant>>,
 }
 
 /// constant = fullIdent | ( [ "-" | "+" ] intLit ) | ( [ "-" | "+" ] floatLit ) |
@@ -423,8 +709,7 @@ impl fmt::Display for ProtobufConstant {
             ProtobufConstant::Bool(v) => write!(f, "{}", v),
             ProtobufConstant::Ident(v) => write!(f, "{}", v),
             ProtobufConstant::String(v) => write!(f, "{}", v),
-            // TODO: text format explicitly
-            ProtobufConstant::Message(v) => write!(f, "{:?}", v),
+            ProtobufConstant::Message(v) => write!(f, "{}", v.format()),
         }
     }
 }
@@ -433,13 +718,15 @@ impl ProtobufConstantMessage {
     pub fn format(&self) -> String {
         let mut s = String::new();
         write!(s, "{{").unwrap();
-        for (n, v) in &self.fields {
-            match v {
-                ProtobufConstant::Message(m) => write!(s, "{} {}", n, m.format()).unwrap(),
-                v => write!(s, "{}: {}", n, v.format()).unwrap(),
+        for (n, vs) in &self.fields {
+            for v in vs {
+                match v {
+                    ProtobufConstant::Message(m) => write!(s, " {} {}", n, m.format()).unwrap(),
+                    v => write!(s, " {}: {}", n, v.format()).unwrap(),
+                }
             }
         }
-        write!(s, "}}").unwrap();
+        write!(s, " }}").unwrap();
         s
     }
 }
@@ -533,6 +820,32 @@ pub(crate) struct ProtobufOption {
     pub value: ProtobufConstant,
 }
 
+impl ProtobufOption {
+    /// Option is `features.<...>`.
+    pub(crate) fn is_feature(&self) -> bool {
+        match &self.name {
+            ProtobufOptionName::Ext(ProtobufOptionNameExt(parts)) => match parts.first() {
+                Some(ProtobufOptionNamePart::Direct(first)) => first.get() == "features",
+                _ => false,
+            },
+            ProtobufOptionName::Builtin(..) => false,
+        }
+    }
+
+    /// Name of the feature if option is `features.<name>`.
+    pub(crate) fn feature_name(&self) -> Option<&str> {
+        match &self.name {
+            ProtobufOptionName::Ext(ProtobufOptionNameExt(parts)) if self.is_feature() => {
+                match parts.as_slice() {
+                    [_, ProtobufOptionNamePart::Direct(name)] => Some(name.get()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
 /// Visibility of import statement
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub(crate) enum ImportVis {
@@ -576,14 +889,34 @@ pub(crate) struct FileDescriptor {
 }
 
 impl FileDescriptor {
+    /// Resolve features of fields and enums.
+    pub(crate) fn resolve_features(&mut self) -> anyhow::Result<()> {
+        let features = Features::for_syntax(self.syntax).with_options(&self.options)?;
+        for m in &mut self.messages {
+            features.resolve_message(&mut m.t)?;
+        }
+        for e in &mut self.enums {
+            features.resolve_enum(&mut e.t)?;
+        }
+        for ext in &mut self.extensions {
+            features.resolve_field(&mut ext.t.field.t)?;
+        }
+        Ok(())
+    }
+
     /// Parses a .proto file content into a `FileDescriptor`
     pub fn parse<S: AsRef<str>>(file: S) -> Result<Self, ParserErrorWithLocation> {
         let mut parser = Parser::new(file.as_ref());
         match parser.next_proto() {
             Ok(r) => Ok(r),
             Err(error) => {
-                let Loc { line, col } = parser.tokenizer.loc();
-                Err(ParserErrorWithLocation { error, line, col })
+                let loc = parser.tokenizer.loc();
+                Err(ParserErrorWithLocation {
+                    error,
+                    line: loc.line,
+                    col: loc.col,
+                    offset: loc.offset(),
+                })
             }
         }
     }
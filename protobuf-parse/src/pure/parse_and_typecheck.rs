@@ -4,6 +4,7 @@ use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::str;
+use std::sync::Arc;
 
 use indexmap::IndexMap;
 use protobuf::descriptor::FileDescriptorProto;
@@ -28,6 +29,10 @@ enum ParseAndTypeckError {
     FileMustResideInImportPath(String, String),
     #[error("could not read file `{0}`: {1}")]
     CouldNotReadFile(String, io::Error),
+    #[error("import cycle: {0}")]
+    ImportCycle(String),
+    #[error("custom resolver is supported only by pure parser")]
+    ResolverRequiresPureParser,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -38,6 +43,16 @@ struct WithFileError {
     error: anyhow::Error,
 }
 
+#[derive(Debug, thiserror::Error)]
+#[error("{file}:{line}:{col}: {error}")]
+struct ParseErrorInFile {
+    file: String,
+    line: u32,
+    col: u32,
+    // Not a `#[source]`, so the message is not repeated in the error chain.
+    error: anyhow::Error,
+}
+
 /// Resolve `.proto` files. `Display` is used for error messages.
 pub trait ProtoPathResolver: fmt::Display {
     /// Resolve a `.proto` file.
@@ -52,7 +67,10 @@ where
     R: ProtoPathResolver,
 {
     parsed_files: IndexMap<ProtoPathBuf, FileDescriptorPair>,
+    // Files which imports are being resolved, to detect import cycles.
+    import_stack: Vec<ProtoPathBuf>,
     resolver: R,
+    include_source_info: bool,
 }
 
 impl<R> Run<R>
@@ -95,25 +113,34 @@ where
         let content = str::from_utf8(&resolved.content)
             .map_err(|_| ParseAndTypeckError::FileContentIsNotUtf8(protobuf_path.to_string()))?;
 
-        let parsed = model::FileDescriptor::parse(&content).map_err(|e| WithFileError {
+        let parsed = model::FileDescriptor::parse(content).map_err(|e| ParseErrorInFile {
             file: resolved.path.clone(),
-            error: e.into(),
+            line: e.line,
+            col: e.col,
+            error: e.error,
         })?;
 
-        for import in &parsed.imports {
-            self.add_imported_file(&import.path)?;
-        }
+        self.import_stack.push(protobuf_path.to_proto_path_buf());
+        let imports = self.add_imports(&parsed);
+        // Pop on error too, session can be reused after failed parse.
+        self.import_stack.pop();
+        imports?;
 
         let mut this_file_deps = IndexMap::new();
         self.all_deps_already_parsed(&parsed, &mut this_file_deps);
 
         let this_file_deps: Vec<_> = this_file_deps.into_iter().map(|(_, v)| v).collect();
 
-        let descriptor_proto = convert::file_descriptor(protobuf_path, &parsed, &this_file_deps)
-            .map_err(|e| WithFileError {
-                file: resolved.path.clone(),
-                error: e.into(),
-            })?;
+        let descriptor_proto = convert::file_descriptor(
+            protobuf_path,
+            &parsed,
+            &this_file_deps,
+            self.include_source_info,
+        )
+        .map_err(|e| WithFileError {
+            file: resolved.path.clone(),
+            error: e.into(),
+        })?;
 
         let deps: Vec<FileDescriptor> = self
             .parsed_files
@@ -134,11 +161,46 @@ where
         Ok(())
     }
 
+    fn add_imports(&mut self, parsed: &model::FileDescriptor) -> anyhow::Result<()> {
+        for import in &parsed.imports {
+            if import.vis == model::ImportVis::Weak {
+                self.add_weak_imported_file(&import.path)?;
+            } else {
+                self.add_imported_file(&import.path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Weak imports are optional, so when the imported file is not found,
+    /// an empty placeholder file is used instead.
+    fn add_weak_imported_file(&mut self, protobuf_path: &ProtoPath) -> anyhow::Result<()> {
+        match self.add_imported_file(protobuf_path) {
+            Err(e) if is_file_not_found(&e, protobuf_path) => self.add_file_content(
+                protobuf_path,
+                &ResolvedProtoFile {
+                    path: protobuf_path.to_string(),
+                    content: Vec::new(),
+                },
+            ),
+            r => r,
+        }
+    }
+
     fn add_imported_file(&mut self, protobuf_path: &ProtoPath) -> anyhow::Result<()> {
         if let Some(_) = self.parsed_files.get(protobuf_path) {
             return Ok(());
         }
 
+        if let Some(pos) = self.import_stack.iter().position(|p| &**p == protobuf_path) {
+            let cycle: Vec<&str> = self.import_stack[pos..]
+                .iter()
+                .map(|p| p.to_str())
+                .chain([protobuf_path.to_str()])
+                .collect();
+            return Err(ParseAndTypeckError::ImportCycle(cycle.join(" -> ")).into());
+        }
+
         let resolved = self.resolver.resolve(protobuf_path)?;
         if let Some(resolved) = resolved {
             return self.add_file_content(protobuf_path, &resolved);
@@ -177,6 +239,15 @@ where
     }
 }
 
+fn is_file_not_found(error: &anyhow::Error, protobuf_path: &ProtoPath) -> bool {
+    match error.downcast_ref::<ParseAndTypeckError>() {
+        Some(ParseAndTypeckError::FileNotFoundInImportPath(path, _)) => {
+            path == protobuf_path.to_str()
+        }
+        _ => false,
+    }
+}
+
 pub(crate) fn path_to_proto_path(
     path: &Path,
     includes: &[PathBuf],
@@ -248,20 +319,101 @@ fn fs_resolver(includes: &[PathBuf]) -> impl ProtoPathResolver {
     }
 }
 
+/// Resolver set with [`Parser::resolver`].
+#[derive(Clone)]
+pub(crate) struct CustomResolver(Arc<dyn ProtoPathResolver + Send + Sync>);
+
+impl CustomResolver {
+    pub(crate) fn new(resolver: impl ProtoPathResolver + Send + Sync + 'static) -> CustomResolver {
+        CustomResolver(Arc::new(resolver))
+    }
+}
+
+impl fmt::Debug for CustomResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CustomResolver")
+            .field(&format_args!("{}", self.0))
+            .finish()
+    }
+}
+
+/// Custom resolver if set, then include directories.
+fn parser_resolver(parser: &Parser) -> impl ProtoPathResolver {
+    struct Impl<R> {
+        custom: Option<CustomResolver>,
+        fs: R,
+    }
+
+    impl<R: ProtoPathResolver> fmt::Display for Impl<R> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match &self.custom {
+                Some(custom) => write!(f, "{} and {}", custom.0, self.fs),
+                None => write!(f, "{}", self.fs),
+            }
+        }
+    }
+
+    impl<R: ProtoPathResolver> ProtoPathResolver for Impl<R> {
+        fn resolve(&self, proto_path: &ProtoPath) -> anyhow::Result<Option<ResolvedProtoFile>> {
+            if let Some(custom) = &self.custom {
+                if let Some(resolved) = custom.0.resolve(proto_path)? {
+                    return Ok(Some(resolved));
+                }
+            }
+            self.fs.resolve(proto_path)
+        }
+    }
+
+    Impl {
+        custom: parser.resolver.clone(),
+        fs: fs_resolver(&parser.includes),
+    }
+}
+
+/// Protobuf path of an input file.
+///
+/// With custom resolver inputs outside of include directories
+/// are protobuf paths themselves.
+fn input_proto_path(parser: &Parser, input: &Path) -> anyhow::Result<ProtoPathBuf> {
+    match path_to_proto_path(input, &parser.includes) {
+        Err(_) if parser.resolver.is_some() => ProtoPathBuf::from_path(input),
+        r => r,
+    }
+}
+
+/// Fail if a custom resolver is set for `protoc` parser.
+pub(crate) fn check_no_custom_resolver(parser: &Parser) -> anyhow::Result<()> {
+    match parser.resolver {
+        Some(_) => Err(ParseAndTypeckError::ResolverRequiresPureParser.into()),
+        None => Ok(()),
+    }
+}
+
 /// Parse `.proto` files using pure Rust implementation.
 pub fn parse_and_typecheck(parser: &Parser) -> anyhow::Result<ParsedAndTypechecked> {
     let mut run = Run {
         parsed_files: IndexMap::new(),
-        resolver: fs_resolver(&parser.includes),
+        import_stack: Vec::new(),
+        resolver: parser_resolver(parser),
+        include_source_info: parser.include_source_info,
     };
 
     let relative_paths = parser
         .inputs
         .iter()
-        .map(|input| Ok((path_to_proto_path(input, &parser.includes)?, input)))
+        .map(|input| Ok((input_proto_path(parser, input)?, input)))
         .collect::<anyhow::Result<Vec<_>>>()?;
 
     for (proto_path, path) in &relative_paths {
+        if run.parsed_files.contains_key(&**proto_path) {
+            // Already parsed as a dependency of previous input.
+            continue;
+        }
+        if parser.resolver.is_some() {
+            // Inputs are resolved like imports.
+            run.add_imported_file(proto_path)?;
+            continue;
+        }
         let content = fs::read_to_string(path)
             .map_err(|e| ParseAndTypeckError::CouldNotReadFile(path.display().to_string(), e))?;
         run.add_file_content(
@@ -286,65 +438,367 @@ pub fn parse_and_typecheck(parser: &Parser) -> anyhow::Result<ParsedAndTypecheck
     })
 }
 
+/// Parse `.proto` files using pure Rust implementation,
+/// caching parsed files by path.
+///
+/// Each file is parsed once per session, even if it is imported by many files
+/// or requested several times, so a session can be used to parse
+/// a large set of files sharing dependencies.
+pub struct ParserSession<R: ProtoPathResolver> {
+    run: Run<R>,
+}
+
+impl<R: ProtoPathResolver> ParserSession<R> {
+    /// New session using given resolver to find files.
+    pub fn new(resolver: R) -> ParserSession<R> {
+        ParserSession {
+            run: Run {
+                parsed_files: IndexMap::new(),
+                import_stack: Vec::new(),
+                resolver,
+                include_source_info: false,
+            },
+        }
+    }
+
+    /// Populate `source_code_info` in descriptors of files parsed after this call.
+    pub fn include_source_info(&mut self) -> &mut Self {
+        self.run.include_source_info = true;
+        self
+    }
+
+    /// Parse a file and its imports unless they are already parsed in this session.
+    pub fn parse(&mut self, proto_path: &ProtoPath) -> anyhow::Result<&FileDescriptorProto> {
+        self.run.add_imported_file(proto_path)?;
+        Ok(&self.run.parsed_files[proto_path].descriptor_proto)
+    }
+
+    /// Descriptors of all files parsed in this session, dependencies first.
+    pub fn file_descriptors(&self) -> impl Iterator<Item = &FileDescriptorProto> {
+        self.run.parsed_files.values().map(|v| &v.descriptor_proto)
+    }
+
+    /// Take descriptors of all files parsed in this session, dependencies first.
+    pub fn into_file_descriptors(self) -> Vec<FileDescriptorProto> {
+        self.run
+            .parsed_files
+            .into_iter()
+            .map(|(_, v)| v.descriptor_proto)
+            .collect()
+    }
+}
+
+/// Parse given files and all their dependencies with pure Rust parser.
+pub(crate) fn parse_all(
+    parser: &Parser,
+    roots: &[PathBuf],
+) -> anyhow::Result<Vec<FileDescriptorProto>> {
+    let mut session = ParserSession::new(parser_resolver(parser));
+    if parser.include_source_info {
+        session.include_source_info();
+    }
+    for path in parser.inputs.iter().chain(roots) {
+        session.parse(&input_proto_path(parser, path)?)?;
+    }
+    Ok(session.into_file_descriptors())
+}
+
 /// TODO: this API is to be refactored.
 pub fn parse_and_typecheck_custom(
     input: &[ProtoPathBuf],
     resolver: impl ProtoPathResolver,
 ) -> anyhow::Result<Vec<FileDescriptorProto>> {
-    let mut run = Run {
-        parsed_files: IndexMap::new(),
-        resolver,
-    };
-
+    let mut session = ParserSession::new(resolver);
     for proto_path in input {
-        run.add_imported_file(proto_path)?;
+        session.parse(proto_path)?;
     }
-
-    Ok(run
-        .parsed_files
-        .into_iter()
-        .map(|(_, v)| v.descriptor_proto)
-        .collect())
+    Ok(session.into_file_descriptors())
 }
 
 #[cfg(test)]
 mod test {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
     use std::fmt;
+    use std::rc::Rc;
+
+    use protobuf::descriptor::FileDescriptorProto;
 
     use crate::proto_path::ProtoPath;
+    use crate::pure::parse_and_typecheck::ParserSession;
     use crate::pure::parse_and_typecheck::ProtoPathResolver;
     use crate::pure::parse_and_typecheck::ResolvedProtoFile;
+    use crate::Parser;
     use crate::ProtoPathBuf;
 
-    #[test]
-    fn parse_and_typecheck_custom() {
-        struct ResolverImpl;
+    /// Resolve files from `(path, content)` pairs.
+    struct ResolverImpl(&'static [(&'static str, &'static str)]);
 
-        impl fmt::Display for ResolverImpl {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                write!(f, "ResolverImpl")
-            }
+    impl fmt::Display for ResolverImpl {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "ResolverImpl")
         }
+    }
 
-        impl ProtoPathResolver for ResolverImpl {
-            fn resolve(&self, proto_path: &ProtoPath) -> anyhow::Result<Option<ResolvedProtoFile>> {
-                if proto_path == "xx.proto" {
-                    Ok(Some(ResolvedProtoFile {
-                        path: "xx.proto".to_string(),
-                        content: "syntax = 'proto3'; message Foo {}".as_bytes().to_vec(),
-                    }))
-                } else {
-                    Ok(None)
-                }
-            }
+    impl ProtoPathResolver for ResolverImpl {
+        fn resolve(&self, proto_path: &ProtoPath) -> anyhow::Result<Option<ResolvedProtoFile>> {
+            Ok(self
+                .0
+                .iter()
+                .find(|(path, _)| proto_path == *path)
+                .map(|(path, content)| ResolvedProtoFile {
+                    path: path.to_string(),
+                    content: content.as_bytes().to_vec(),
+                }))
         }
+    }
+
+    fn parse(
+        input: &str,
+        files: &'static [(&'static str, &'static str)],
+    ) -> anyhow::Result<Vec<FileDescriptorProto>> {
+        super::parse_and_typecheck_custom(
+            &[ProtoPathBuf::new(input.to_owned()).unwrap()],
+            ResolverImpl(files),
+        )
+    }
 
-        let resolved = super::parse_and_typecheck_custom(
-            &[ProtoPathBuf::new("xx.proto".to_owned()).unwrap()],
-            ResolverImpl,
+    #[test]
+    fn parse_and_typecheck_custom() {
+        let resolved = parse(
+            "xx.proto",
+            &[("xx.proto", "syntax = 'proto3'; message Foo {}")],
         )
         .unwrap();
         assert_eq!(1, resolved.len());
         assert_eq!("Foo", resolved[0].message_type[0].name());
     }
+
+    #[test]
+    fn parser_with_custom_resolver() {
+        let files = &[
+            (
+                "app/app.proto",
+                "syntax = 'proto3'; import 'common/id.proto'; message App { common.Id id = 1; }",
+            ),
+            (
+                "common/id.proto",
+                "syntax = 'proto3'; package common; message Id { string value = 1; }",
+            ),
+        ];
+
+        let fds = Parser::new()
+            .pure()
+            .resolver(ResolverImpl(files))
+            .input("app/app.proto")
+            .file_descriptor_set()
+            .unwrap();
+        assert_eq!(1, fds.file.len());
+        assert_eq!("app/app.proto", fds.file[0].name());
+        assert_eq!(
+            ".common.Id",
+            fds.file[0].message_type[0].field[0].type_name()
+        );
+
+        let all = Parser::new()
+            .pure()
+            .resolver(ResolverImpl(files))
+            .parse_all(["app/app.proto"])
+            .unwrap();
+        let names: Vec<_> = all.iter().map(|f| f.name()).collect();
+        assert_eq!(vec!["common/id.proto", "app/app.proto"], names);
+
+        let err = Parser::new()
+            .pure()
+            .resolver(ResolverImpl(files))
+            .input("app/missing.proto")
+            .file_descriptor_set()
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("ResolverImpl"), "{:#}", err);
+
+        assert!(Parser::new()
+            .protoc()
+            .resolver(ResolverImpl(files))
+            .input("app/app.proto")
+            .file_descriptor_set()
+            .is_err());
+    }
+
+    #[test]
+    fn import_cycle() {
+        let err = parse(
+            "a.proto",
+            &[
+                ("a.proto", "syntax = 'proto3'; import 'b.proto';"),
+                ("b.proto", "syntax = 'proto3'; import 'c.proto';"),
+                ("c.proto", "syntax = 'proto3'; import 'b.proto';"),
+            ],
+        )
+        .unwrap_err();
+        assert_eq!(
+            "import cycle: b.proto -> c.proto -> b.proto",
+            err.to_string()
+        );
+
+        let err = parse("a.proto", &[("a.proto", "import 'a.proto';")]).unwrap_err();
+        assert_eq!("import cycle: a.proto -> a.proto", err.to_string());
+    }
+
+    #[test]
+    fn self_referential_message() {
+        let resolved = parse(
+            "tree.proto",
+            &[(
+                "tree.proto",
+                "syntax = 'proto3'; message Tree { repeated Tree children = 1; Tree parent = 2; }",
+            )],
+        )
+        .unwrap();
+        assert_eq!(".Tree", resolved[0].message_type[0].field[0].type_name());
+    }
+
+    #[test]
+    fn service_methods() {
+        let resolved = parse(
+            "service.proto",
+            &[(
+                "service.proto",
+                r#"
+                    syntax = "proto3";
+                    package svc;
+                    message Req {}
+                    message Resp {}
+                    service Greeter {
+                        option deprecated = true;
+                        rpc Unary(Req) returns (Resp);
+                        rpc ClientStreaming(stream Req) returns (Resp);
+                        rpc ServerStreaming(Req) returns (stream Resp) {
+                            option deprecated = true;
+                        }
+                        rpc Bidi(stream .svc.Req) returns (stream svc.Resp) {}
+                    }
+                "#,
+            )],
+        )
+        .unwrap();
+        let service = &resolved[0].service[0];
+        assert_eq!("Greeter", service.name());
+        assert!(service.options.deprecated());
+
+        let methods: Vec<_> = service
+            .method
+            .iter()
+            .map(|m| {
+                (
+                    m.name(),
+                    m.input_type(),
+                    m.output_type(),
+                    m.client_streaming(),
+                    m.server_streaming(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                ("Unary", ".svc.Req", ".svc.Resp", false, false),
+                ("ClientStreaming", ".svc.Req", ".svc.Resp", true, false),
+                ("ServerStreaming", ".svc.Req", ".svc.Resp", false, true),
+                ("Bidi", ".svc.Req", ".svc.Resp", true, true),
+            ],
+            methods
+        );
+        // Unset like in protoc output.
+        assert!(!service.method[0].has_client_streaming());
+        assert!(!service.method[0].has_server_streaming());
+
+        assert!(service.method[0].options.is_none());
+        assert!(service.method[2].options.deprecated());
+    }
+
+    /// Count resolved files.
+    struct CountingResolver {
+        files: ResolverImpl,
+        counts: Rc<RefCell<HashMap<String, u32>>>,
+    }
+
+    impl fmt::Display for CountingResolver {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "CountingResolver")
+        }
+    }
+
+    impl ProtoPathResolver for CountingResolver {
+        fn resolve(&self, proto_path: &ProtoPath) -> anyhow::Result<Option<ResolvedProtoFile>> {
+            *self
+                .counts
+                .borrow_mut()
+                .entry(proto_path.to_string())
+                .or_default() += 1;
+            self.files.resolve(proto_path)
+        }
+    }
+
+    #[test]
+    fn session_parses_each_file_once() {
+        let counts = Rc::new(RefCell::new(HashMap::new()));
+        let mut session = ParserSession::new(CountingResolver {
+            files: ResolverImpl(&[
+                ("a.proto", "import 'b.proto'; import 'c.proto'; message A { optional B b = 1; optional C c = 2; }"),
+                ("b.proto", "import 'd.proto'; message B { optional D d = 1; }"),
+                ("c.proto", "import 'd.proto'; message C { optional D d = 1; }"),
+                ("d.proto", "message D {}"),
+            ]),
+            counts: counts.clone(),
+        });
+
+        for path in ["a.proto", "d.proto", "b.proto", "a.proto"] {
+            let file = session
+                .parse(&ProtoPathBuf::new(path.to_owned()).unwrap())
+                .unwrap();
+            assert_eq!(path, file.name());
+        }
+
+        for path in ["a.proto", "b.proto", "c.proto", "d.proto"] {
+            assert_eq!(Some(&1), counts.borrow().get(path), "{}", path);
+        }
+        let names: Vec<_> = session.file_descriptors().map(|f| f.name()).collect();
+        assert_eq!(vec!["d.proto", "b.proto", "c.proto", "a.proto"], names);
+    }
+
+    #[test]
+    fn session_parse_after_error() {
+        let mut session = ParserSession::new(ResolverImpl(&[
+            (
+                "a.proto",
+                "import 'b.proto'; message A { optional B b = 1; }",
+            ),
+            ("b.proto", "import 'missing.proto'; message B {}"),
+        ]));
+        let a = ProtoPathBuf::new("a.proto".to_owned()).unwrap();
+        for _ in 0..2 {
+            let err = session.parse(&a).unwrap_err();
+            assert!(err.to_string().contains("missing.proto"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn weak_import_not_found() {
+        let resolved = parse(
+            "a.proto",
+            &[
+                ("a.proto", "import weak 'missing.proto'; import weak 'b.proto'; message A { optional B b = 1; }"),
+                ("b.proto", "message B {}"),
+            ],
+        )
+        .unwrap();
+        let names: Vec<_> = resolved.iter().map(|f| f.name()).collect();
+        assert_eq!(vec!["missing.proto", "b.proto", "a.proto"], names);
+        assert_eq!(vec!["missing.proto", "b.proto"], resolved[2].dependency);
+        assert_eq!(vec![0, 1], resolved[2].weak_dependency);
+        // Placeholder declares nothing.
+        assert!(resolved[0].message_type.is_empty());
+
+        let err = parse("a.proto", &[("a.proto", "import 'missing.proto';")]).unwrap_err();
+        assert!(err.to_string().contains("missing.proto"), "{}", err);
+    }
 }
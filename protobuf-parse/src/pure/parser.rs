@@ -1,5 +1,6 @@
 use std::str;
 
+use protobuf_support::lexer::comments::TokenComments;
 use protobuf_support::lexer::int;
 use protobuf_support::lexer::lexer_impl::LexerError;
 use protobuf_support::lexer::num_lit::NumLit;
@@ -17,9 +18,12 @@ use crate::protobuf_ident::ProtobufIdent;
 use crate::protobuf_path::ProtobufPath;
 use crate::protobuf_rel_path::ProtobufRelPath;
 use crate::pure::model;
+use crate::pure::model::Comments;
+use crate::pure::model::Edition;
 use crate::pure::model::EnumValue;
 use crate::pure::model::Enumeration;
 use crate::pure::model::Extension;
+use crate::pure::model::Features;
 use crate::pure::model::Field;
 use crate::pure::model::FieldNumberRange;
 use crate::pure::model::FieldOrOneOf;
@@ -55,6 +59,10 @@ pub(crate) enum ParserError {
     ExpectConstant,
     #[error("unknown syntax")]
     UnknownSyntax,
+    #[error("unknown edition")]
+    UnknownEdition,
+    #[error("group is not allowed in editions")]
+    GroupNotAllowedInEditions,
     #[error("integer overflow")]
     IntegerOverflow,
     #[error("label not allowed")]
@@ -110,6 +118,8 @@ pub struct ParserErrorWithLocation {
     pub line: u32,
     /// 1-based
     pub col: u32,
+    /// 0-based byte offset
+    pub offset: usize,
 }
 
 trait ToU8 {
@@ -188,9 +198,11 @@ pub(crate) struct Parser<'a> {
 enum MessageBodyParseMode {
     MessageProto2,
     MessageProto3,
+    MessageEditions,
     Oneof,
     ExtendProto2,
     ExtendProto3,
+    ExtendEditions,
 }
 
 impl MessageBodyParseMode {
@@ -199,18 +211,26 @@ impl MessageBodyParseMode {
             Rule::Repeated => match *self {
                 MessageBodyParseMode::MessageProto2
                 | MessageBodyParseMode::MessageProto3
+                | MessageBodyParseMode::MessageEditions
                 | MessageBodyParseMode::ExtendProto2
-                | MessageBodyParseMode::ExtendProto3 => true,
+                | MessageBodyParseMode::ExtendProto3
+                | MessageBodyParseMode::ExtendEditions => true,
                 MessageBodyParseMode::Oneof => false,
             },
+            // Field presence is specified with features in editions.
             Rule::Optional => match *self {
                 MessageBodyParseMode::MessageProto2 | MessageBodyParseMode::ExtendProto2 => true,
                 MessageBodyParseMode::MessageProto3 | MessageBodyParseMode::ExtendProto3 => true,
-                MessageBodyParseMode::Oneof => false,
+                MessageBodyParseMode::MessageEditions
+                | MessageBodyParseMode::ExtendEditions
+                | MessageBodyParseMode::Oneof => false,
             },
             Rule::Required => match *self {
                 MessageBodyParseMode::MessageProto2 | MessageBodyParseMode::ExtendProto2 => true,
                 MessageBodyParseMode::MessageProto3 | MessageBodyParseMode::ExtendProto3 => false,
+                MessageBodyParseMode::MessageEditions | MessageBodyParseMode::ExtendEditions => {
+                    false
+                }
                 MessageBodyParseMode::Oneof => false,
             },
         }
@@ -220,7 +240,9 @@ impl MessageBodyParseMode {
         match *self {
             MessageBodyParseMode::MessageProto2 | MessageBodyParseMode::ExtendProto2 => true,
             MessageBodyParseMode::MessageProto3
+            | MessageBodyParseMode::MessageEditions
             | MessageBodyParseMode::ExtendProto3
+            | MessageBodyParseMode::ExtendEditions
             | MessageBodyParseMode::Oneof => false,
         }
     }
@@ -229,17 +251,22 @@ impl MessageBodyParseMode {
         match *self {
             MessageBodyParseMode::MessageProto2
             | MessageBodyParseMode::MessageProto3
+            | MessageBodyParseMode::MessageEditions
             | MessageBodyParseMode::ExtendProto2
-            | MessageBodyParseMode::ExtendProto3 => true,
+            | MessageBodyParseMode::ExtendProto3
+            | MessageBodyParseMode::ExtendEditions => true,
             MessageBodyParseMode::Oneof => false,
         }
     }
 
     fn is_most_non_fields_allowed(&self) -> bool {
         match *self {
-            MessageBodyParseMode::MessageProto2 | MessageBodyParseMode::MessageProto3 => true,
+            MessageBodyParseMode::MessageProto2
+            | MessageBodyParseMode::MessageProto3
+            | MessageBodyParseMode::MessageEditions => true,
             MessageBodyParseMode::ExtendProto2
             | MessageBodyParseMode::ExtendProto3
+            | MessageBodyParseMode::ExtendEditions
             | MessageBodyParseMode::Oneof => false,
         }
     }
@@ -248,14 +275,17 @@ impl MessageBodyParseMode {
         match *self {
             MessageBodyParseMode::MessageProto2
             | MessageBodyParseMode::MessageProto3
+            | MessageBodyParseMode::MessageEditions
             | MessageBodyParseMode::Oneof => true,
-            MessageBodyParseMode::ExtendProto2 | MessageBodyParseMode::ExtendProto3 => false,
+            MessageBodyParseMode::ExtendProto2
+            | MessageBodyParseMode::ExtendProto3
+            | MessageBodyParseMode::ExtendEditions => false,
         }
     }
 
     fn is_extensions_allowed(&self) -> bool {
         match self {
-            MessageBodyParseMode::MessageProto2 => true,
+            MessageBodyParseMode::MessageProto2 | MessageBodyParseMode::MessageEditions => true,
             _ => false,
         }
     }
@@ -263,6 +293,8 @@ impl MessageBodyParseMode {
 
 #[derive(Default)]
 pub(crate) struct MessageBody {
+    /// Comment after the opening brace
+    pub trailing_comments: Option<String>,
     pub fields: Vec<WithLoc<FieldOrOneOf>>,
     pub reserved_nums: Vec<FieldNumberRange>,
     pub reserved_names: Vec<String>,
@@ -298,6 +330,27 @@ impl<'a> Parser<'a> {
         }
     }
 
+    // Comments
+
+    /// Comments attached to the declaration starting with the next token.
+    fn next_leading_comments(&mut self) -> anyhow::Result<Comments> {
+        let TokenComments {
+            detached,
+            next_leading,
+            ..
+        } = self.tokenizer.lookahead_comments()?;
+        Ok(Comments {
+            leading: next_leading,
+            trailing: None,
+            leading_detached: detached,
+        })
+    }
+
+    /// Comment attached to the declaration ending with the last consumed token.
+    fn prev_trailing_comments(&mut self) -> anyhow::Result<Option<String>> {
+        Ok(self.tokenizer.lookahead_comments()?.prev_trailing)
+    }
+
     // Protobuf grammar
 
     // fullIdent = ident { "." ident }
@@ -402,15 +455,25 @@ impl<'a> Parser<'a> {
 
     fn next_message_constant(&mut self) -> anyhow::Result<ProtobufConstantMessage> {
         let mut r = ProtobufConstantMessage::default();
-        self.tokenizer
-            .next_symbol_expect_eq('{', "message constant")?;
-        while !self.tokenizer.lookahead_is_symbol('}')? {
+        // Text format also allows angle brackets as message delimiters.
+        let close = if self.tokenizer.next_symbol_if_eq('<')? {
+            '>'
+        } else {
+            self.tokenizer
+                .next_symbol_expect_eq('{', "message constant")?;
+            '}'
+        };
+        while !self.tokenizer.lookahead_is_symbol(close)? {
             let n = self.next_message_constant_field_name()?;
-            let v = self.next_field_value()?;
-            r.fields.insert(n, v);
+            let vs = self.next_field_values()?;
+            r.fields.entry(n).or_default().extend(vs);
+            // Fields are optionally separated with comma or semicolon.
+            if !self.tokenizer.next_symbol_if_eq(',')? {
+                self.tokenizer.next_symbol_if_eq(';')?;
+            }
         }
         self.tokenizer
-            .next_symbol_expect_eq('}', "message constant")?;
+            .next_symbol_expect_eq(close, "message constant")?;
         Ok(r)
     }
 
@@ -454,9 +517,30 @@ impl<'a> Parser<'a> {
         Err(ParserError::ExpectConstant.into())
     }
 
-    fn next_field_value(&mut self) -> anyhow::Result<ProtobufConstant> {
-        if self.tokenizer.next_symbol_if_eq(':')? {
-            // Colon is optional when reading message constant.
+    // Value of a message constant field, possibly a list of values like `[1, 2]`.
+    fn next_field_values(&mut self) -> anyhow::Result<Vec<ProtobufConstant>> {
+        // Colon is optional when reading message constant.
+        let colon = self.tokenizer.next_symbol_if_eq(':')?;
+        if self.tokenizer.next_symbol_if_eq('[')? {
+            let mut values = Vec::new();
+            if !self.tokenizer.lookahead_is_symbol(']')? {
+                loop {
+                    values.push(self.next_field_value(colon)?);
+                    if !self.tokenizer.next_symbol_if_eq(',')? {
+                        break;
+                    }
+                }
+            }
+            self.tokenizer
+                .next_symbol_expect_eq(']', "message constant")?;
+            Ok(values)
+        } else {
+            Ok(vec![self.next_field_value(colon)?])
+        }
+    }
+
+    fn next_field_value(&mut self, colon: bool) -> anyhow::Result<ProtobufConstant> {
+        if colon {
             self.next_constant()
         } else {
             Ok(ProtobufConstant::Message(self.next_message_constant()?))
@@ -474,8 +558,18 @@ impl<'a> Parser<'a> {
 
     // syntax = "syntax" "=" quote "proto2" quote ";"
     // syntax = "syntax" "=" quote "proto3" quote ";"
+    // edition = "edition" "=" quote "2023" quote ";"
     fn next_syntax(&mut self) -> anyhow::Result<Option<Syntax>> {
-        if self.tokenizer.next_ident_if_eq("syntax")? {
+        if self.tokenizer.next_ident_if_eq("edition")? {
+            self.tokenizer.next_symbol_expect_eq('=', "edition")?;
+            let edition_str = self.tokenizer.next_str_lit()?.decode_utf8()?;
+            let edition = match Edition::parse(&edition_str) {
+                Some(edition) => edition,
+                None => return Err(ParserError::UnknownEdition.into()),
+            };
+            self.tokenizer.next_symbol_expect_eq(';', "edition")?;
+            Ok(Some(Syntax::Editions(edition)))
+        } else if self.tokenizer.next_ident_if_eq("syntax")? {
             self.tokenizer.next_symbol_expect_eq('=', "syntax")?;
             let syntax_str = self.tokenizer.next_str_lit()?.decode_utf8()?;
             let syntax = if syntax_str == "proto2" {
@@ -656,10 +750,22 @@ impl<'a> Parser<'a> {
         Ok(options)
     }
 
+    // [ "[" fieldOptions "]" ]
+    fn next_field_options_in_brackets_opt(&mut self) -> anyhow::Result<Vec<ProtobufOption>> {
+        if self.tokenizer.next_symbol_if_eq('[')? {
+            let options = self.next_field_options()?;
+            self.tokenizer.next_symbol_expect_eq(']', "field")?;
+            Ok(options)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
     // field = label type fieldName "=" fieldNumber [ "[" fieldOptions "]" ] ";"
-    // group = label "group" groupName "=" fieldNumber messageBody
+    // group = label "group" groupName "=" fieldNumber [ "[" fieldOptions "]" ] messageBody
     fn next_field(&mut self, mode: MessageBodyParseMode) -> anyhow::Result<WithLoc<Field>> {
         let loc = self.tokenizer.lookahead_loc();
+        let mut comments = self.next_leading_comments()?;
         let rule = if self.clone().tokenizer.next_ident_if_eq("map")? {
             if !mode.map_allowed() {
                 return Err(ParserError::MapFieldNotAllowed.into());
@@ -669,16 +775,22 @@ impl<'a> Parser<'a> {
             self.next_label(mode)?
         };
         if self.tokenizer.next_ident_if_eq("group")? {
+            if let Syntax::Editions(..) = self.syntax {
+                return Err(ParserError::GroupNotAllowedInEditions.into());
+            }
             let name = self.next_group_name()?.to_owned();
             self.tokenizer.next_symbol_expect_eq('=', "group")?;
             let number = self.next_field_number()?;
+            let options = self.next_field_options_in_brackets_opt()?;
 
-            let mode = match self.syntax {
-                Syntax::Proto2 => MessageBodyParseMode::MessageProto2,
-                Syntax::Proto3 => MessageBodyParseMode::MessageProto3,
-            };
+            let mode = self.message_body_parse_mode();
 
-            let MessageBody { fields, .. } = self.next_message_body(mode)?;
+            let MessageBody {
+                fields,
+                trailing_comments,
+                ..
+            } = self.next_message_body(mode)?;
+            comments.trailing = trailing_comments;
 
             let fields = fields
                 .into_iter()
@@ -696,7 +808,9 @@ impl<'a> Parser<'a> {
                 rule,
                 typ: FieldType::Group(Group { name, fields }),
                 number,
-                options: Vec::new(),
+                options,
+                features: Features::for_syntax(self.syntax),
+                comments,
             };
             Ok(WithLoc { t: field, loc })
         } else {
@@ -704,22 +818,17 @@ impl<'a> Parser<'a> {
             let name = self.tokenizer.next_ident()?.to_owned();
             self.tokenizer.next_symbol_expect_eq('=', "field")?;
             let number = self.next_field_number()?;
-
-            let mut options = Vec::new();
-
-            if self.tokenizer.next_symbol_if_eq('[')? {
-                for o in self.next_field_options()? {
-                    options.push(o);
-                }
-                self.tokenizer.next_symbol_expect_eq(']', "field")?;
-            }
+            let options = self.next_field_options_in_brackets_opt()?;
             self.tokenizer.next_symbol_expect_eq(';', "field")?;
+            comments.trailing = self.prev_trailing_comments()?;
             let field = Field {
                 name,
                 rule,
                 typ,
                 number,
                 options,
+                features: Features::for_syntax(self.syntax),
+                comments,
             };
             Ok(WithLoc { t: field, loc })
         }
@@ -728,11 +837,16 @@ impl<'a> Parser<'a> {
     // oneof = "oneof" oneofName "{" { oneofField | emptyStatement } "}"
     // oneofField = type fieldName "=" fieldNumber [ "[" fieldOptions "]" ] ";"
     fn next_oneof_opt(&mut self) -> anyhow::Result<Option<OneOf>> {
+        let mut comments = self.next_leading_comments()?;
         if self.tokenizer.next_ident_if_eq("oneof")? {
             let name = self.tokenizer.next_ident()?.to_owned();
             let MessageBody {
-                fields, options, ..
+                fields,
+                options,
+                trailing_comments,
+                ..
             } = self.next_message_body(MessageBodyParseMode::Oneof)?;
+            comments.trailing = trailing_comments;
             let fields = fields
                 .into_iter()
                 .map(|fo| match fo.t {
@@ -744,6 +858,7 @@ impl<'a> Parser<'a> {
                 name,
                 fields,
                 options,
+                comments,
             }))
         } else {
             Ok(None)
@@ -799,6 +914,31 @@ impl<'a> Parser<'a> {
         Ok(ranges)
     }
 
+    // enumRange = intLit [ "to" ( intLit | "max" ) ]
+    fn next_enum_range(&mut self) -> anyhow::Result<FieldNumberRange> {
+        let from = self.next_enum_value()?;
+        let to = if self.tokenizer.next_ident_if_eq("to")? {
+            if self.tokenizer.next_ident_if_eq("max")? {
+                i32::MAX
+            } else {
+                self.next_enum_value()?
+            }
+        } else {
+            from
+        };
+        Ok(FieldNumberRange { from, to })
+    }
+
+    // enumRanges = enumRange { "," enumRange }
+    fn next_enum_ranges(&mut self) -> anyhow::Result<Vec<FieldNumberRange>> {
+        let mut ranges = Vec::new();
+        ranges.push(self.next_enum_range()?);
+        while self.tokenizer.next_symbol_if_eq(',')? {
+            ranges.push(self.next_enum_range()?);
+        }
+        Ok(ranges)
+    }
+
     // extensions = "extensions" ranges ";"
     fn next_extensions_opt(&mut self) -> anyhow::Result<Option<Vec<FieldNumberRange>>> {
         if self.tokenizer.next_ident_if_eq("extensions")? {
@@ -815,6 +955,20 @@ impl<'a> Parser<'a> {
     // fieldNames = fieldName { "," fieldName }
     fn next_reserved_opt(
         &mut self,
+    ) -> anyhow::Result<Option<(Vec<FieldNumberRange>, Vec<String>)>> {
+        self.next_reserved_with_ranges_opt(Parser::next_ranges)
+    }
+
+    // enumReserved = "reserved" ( enumRanges | fieldNames ) ";"
+    fn next_enum_reserved_opt(
+        &mut self,
+    ) -> anyhow::Result<Option<(Vec<FieldNumberRange>, Vec<String>)>> {
+        self.next_reserved_with_ranges_opt(Parser::next_enum_ranges)
+    }
+
+    fn next_reserved_with_ranges_opt(
+        &mut self,
+        next_ranges: impl FnOnce(&mut Self) -> anyhow::Result<Vec<FieldNumberRange>>,
     ) -> anyhow::Result<Option<(Vec<FieldNumberRange>, Vec<String>)>> {
         if self.tokenizer.next_ident_if_eq("reserved")? {
             let (ranges, names) = if let &Token::StrLit(..) = self.tokenizer.lookahead_some()? {
@@ -825,7 +979,7 @@ impl<'a> Parser<'a> {
                 }
                 (Vec::new(), names)
             } else {
-                (self.next_ranges()?, Vec::new())
+                (next_ranges(self)?, Vec::new())
             };
 
             self.tokenizer.next_symbol_expect_eq(';', "reserved")?;
@@ -866,6 +1020,7 @@ impl<'a> Parser<'a> {
 
     // enumField = ident "=" intLit [ "[" enumValueOption { ","  enumValueOption } "]" ]";"
     fn next_enum_field(&mut self) -> anyhow::Result<EnumValue> {
+        let mut comments = self.next_leading_comments()?;
         let name = self.tokenizer.next_ident()?.to_owned();
         self.tokenizer.next_symbol_expect_eq('=', "enum field")?;
         let number = self.next_enum_value()?;
@@ -877,11 +1032,15 @@ impl<'a> Parser<'a> {
             }
             self.tokenizer.next_symbol_expect_eq(']', "enum field")?;
         }
+        if self.tokenizer.next_symbol_if_eq(';')? {
+            comments.trailing = self.prev_trailing_comments()?;
+        }
 
         Ok(EnumValue {
             name,
             number,
             options,
+            comments,
         })
     }
 
@@ -889,14 +1048,18 @@ impl<'a> Parser<'a> {
     // enumBody = "{" { option | enumField | emptyStatement } "}"
     fn next_enum_opt(&mut self) -> anyhow::Result<Option<WithLoc<Enumeration>>> {
         let loc = self.tokenizer.lookahead_loc();
+        let mut comments = self.next_leading_comments()?;
 
         if self.tokenizer.next_ident_if_eq("enum")? {
             let name = self.tokenizer.next_ident()?.to_owned();
 
             let mut values = Vec::new();
             let mut options = Vec::new();
+            let mut reserved_nums = Vec::new();
+            let mut reserved_names = Vec::new();
 
             self.tokenizer.next_symbol_expect_eq('{', "enum")?;
+            comments.trailing = self.prev_trailing_comments()?;
             while self.tokenizer.lookahead_if_symbol()? != Some('}') {
                 // emptyStatement
                 if self.tokenizer.next_symbol_if_eq(';')? {
@@ -908,14 +1071,29 @@ impl<'a> Parser<'a> {
                     continue;
                 }
 
-                values.push(self.next_enum_field()?);
+                if let Some((nums, names)) = self.next_enum_reserved_opt()? {
+                    reserved_nums.extend(nums);
+                    reserved_names.extend(names);
+                    continue;
+                }
+
+                let loc = self.tokenizer.lookahead_loc();
+                values.push(WithLoc {
+                    t: self.next_enum_field()?,
+                    loc,
+                });
             }
             self.tokenizer.next_symbol_expect_eq('}', "enum")?;
             let enumeration = Enumeration {
                 name,
                 values,
                 options,
+                reserved_nums,
+                reserved_names,
+                features: Features::for_syntax(self.syntax),
+                comments,
             };
+            enumeration.check_unique_values()?;
             Ok(Some(WithLoc {
                 loc,
                 t: enumeration,
@@ -927,12 +1105,23 @@ impl<'a> Parser<'a> {
 
     // Message definition
 
+    fn message_body_parse_mode(&self) -> MessageBodyParseMode {
+        match self.syntax {
+            Syntax::Proto2 => MessageBodyParseMode::MessageProto2,
+            Syntax::Proto3 => MessageBodyParseMode::MessageProto3,
+            Syntax::Editions(..) => MessageBodyParseMode::MessageEditions,
+        }
+    }
+
     // messageBody = "{" { field | enum | message | extend | extensions | group |
     //               option | oneof | mapField | reserved | emptyStatement } "}"
     fn next_message_body(&mut self, mode: MessageBodyParseMode) -> anyhow::Result<MessageBody> {
         self.tokenizer.next_symbol_expect_eq('{', "message body")?;
 
-        let mut r = MessageBody::default();
+        let mut r = MessageBody {
+            trailing_comments: self.prev_trailing_comments()?,
+            ..MessageBody::default()
+        };
 
         while self.tokenizer.lookahead_if_symbol()? != Some('}') {
             let loc = self.tokenizer.lookahead_loc();
@@ -1007,16 +1196,15 @@ impl<'a> Parser<'a> {
     // message = "message" messageName messageBody
     fn next_message_opt(&mut self) -> anyhow::Result<Option<WithLoc<Message>>> {
         let loc = self.tokenizer.lookahead_loc();
+        let mut comments = self.next_leading_comments()?;
 
         if self.tokenizer.next_ident_if_eq("message")? {
             let name = self.tokenizer.next_ident()?.to_owned();
 
-            let mode = match self.syntax {
-                Syntax::Proto2 => MessageBodyParseMode::MessageProto2,
-                Syntax::Proto3 => MessageBodyParseMode::MessageProto3,
-            };
+            let mode = self.message_body_parse_mode();
 
             let MessageBody {
+                trailing_comments,
                 fields,
                 reserved_nums,
                 reserved_names,
@@ -1026,6 +1214,7 @@ impl<'a> Parser<'a> {
                 extensions,
                 extension_ranges,
             } = self.next_message_body(mode)?;
+            comments.trailing = trailing_comments;
 
             let message = Message {
                 name,
@@ -1037,7 +1226,9 @@ impl<'a> Parser<'a> {
                 options,
                 extensions,
                 extension_ranges,
+                comments,
             };
+            message.check_unique_fields()?;
             Ok(Some(WithLoc { t: message, loc }))
         } else {
             Ok(None)
@@ -1060,6 +1251,7 @@ impl<'a> Parser<'a> {
             let mode = match self.syntax {
                 Syntax::Proto2 => MessageBodyParseMode::ExtendProto2,
                 Syntax::Proto3 => MessageBodyParseMode::ExtendProto3,
+                Syntax::Editions(..) => MessageBodyParseMode::ExtendEditions,
             };
 
             let MessageBody { fields, .. } = self.next_message_body(mode)?;
@@ -1091,9 +1283,12 @@ impl<'a> Parser<'a> {
 
     // Service definition
 
-    fn next_options_or_colon(&mut self) -> anyhow::Result<Vec<ProtobufOption>> {
+    /// Options and trailing comments.
+    fn next_options_or_colon(&mut self) -> anyhow::Result<(Vec<ProtobufOption>, Option<String>)> {
         let mut options = Vec::new();
+        let trailing_comments;
         if self.tokenizer.next_symbol_if_eq('{')? {
+            trailing_comments = self.prev_trailing_comments()?;
             while self.tokenizer.lookahead_if_symbol()? != Some('}') {
                 if let Some(option) = self.next_option_opt()? {
                     options.push(option);
@@ -1109,15 +1304,17 @@ impl<'a> Parser<'a> {
             self.tokenizer.next_symbol_expect_eq('}', "option")?;
         } else {
             self.tokenizer.next_symbol_expect_eq(';', "option")?;
+            trailing_comments = self.prev_trailing_comments()?;
         }
 
-        Ok(options)
+        Ok((options, trailing_comments))
     }
 
     // stream = "stream" streamName "(" messageType "," messageType ")"
     //        (( "{" { option | emptyStatement } "}") | ";" )
     fn next_stream_opt(&mut self) -> anyhow::Result<Option<Method>> {
         assert_eq!(Syntax::Proto2, self.syntax);
+        let mut comments = self.next_leading_comments()?;
         if self.tokenizer.next_ident_if_eq("stream")? {
             let name = self.tokenizer.next_ident()?;
             self.tokenizer.next_symbol_expect_eq('(', "stream")?;
@@ -1125,7 +1322,8 @@ impl<'a> Parser<'a> {
             self.tokenizer.next_symbol_expect_eq(',', "stream")?;
             let output_type = self.next_message_or_enum_type()?;
             self.tokenizer.next_symbol_expect_eq(')', "stream")?;
-            let options = self.next_options_or_colon()?;
+            let (options, trailing_comments) = self.next_options_or_colon()?;
+            comments.trailing = trailing_comments;
             Ok(Some(Method {
                 name,
                 input_type,
@@ -1133,6 +1331,7 @@ impl<'a> Parser<'a> {
                 client_streaming: true,
                 server_streaming: true,
                 options,
+                comments,
             }))
         } else {
             Ok(None)
@@ -1143,6 +1342,7 @@ impl<'a> Parser<'a> {
     //     "returns" "(" [ "stream" ] messageType ")"
     //     (( "{" { option | emptyStatement } "}" ) | ";" )
     fn next_rpc_opt(&mut self) -> anyhow::Result<Option<Method>> {
+        let mut comments = self.next_leading_comments()?;
         if self.tokenizer.next_ident_if_eq("rpc")? {
             let name = self.tokenizer.next_ident()?;
             self.tokenizer.next_symbol_expect_eq('(', "rpc")?;
@@ -1154,7 +1354,8 @@ impl<'a> Parser<'a> {
             let server_streaming = self.tokenizer.next_ident_if_eq("stream")?;
             let output_type = self.next_message_or_enum_type()?;
             self.tokenizer.next_symbol_expect_eq(')', "rpc")?;
-            let options = self.next_options_or_colon()?;
+            let (options, trailing_comments) = self.next_options_or_colon()?;
+            comments.trailing = trailing_comments;
             Ok(Some(Method {
                 name,
                 input_type,
@@ -1162,6 +1363,7 @@ impl<'a> Parser<'a> {
                 client_streaming,
                 server_streaming,
                 options,
+                comments,
             }))
         } else {
             Ok(None)
@@ -1175,21 +1377,25 @@ impl<'a> Parser<'a> {
     // service = "service" serviceName "{" { option | rpc | emptyStatement } "}"
     fn next_service_opt(&mut self) -> anyhow::Result<Option<WithLoc<Service>>> {
         let loc = self.tokenizer.lookahead_loc();
+        let mut comments = self.next_leading_comments()?;
 
         if self.tokenizer.next_ident_if_eq("service")? {
             let name = self.tokenizer.next_ident()?;
             let mut methods = Vec::new();
             let mut options = Vec::new();
             self.tokenizer.next_symbol_expect_eq('{', "service")?;
+            comments.trailing = self.prev_trailing_comments()?;
             while self.tokenizer.lookahead_if_symbol()? != Some('}') {
+                let loc = self.tokenizer.lookahead_loc();
+
                 if let Some(method) = self.next_rpc_opt()? {
-                    methods.push(method);
+                    methods.push(WithLoc { t: method, loc });
                     continue;
                 }
 
                 if self.syntax == Syntax::Proto2 {
                     if let Some(method) = self.next_stream_opt()? {
-                        methods.push(method);
+                        methods.push(WithLoc { t: method, loc });
                         continue;
                     }
                 }
@@ -1212,6 +1418,7 @@ impl<'a> Parser<'a> {
                     name,
                     methods,
                     options,
+                    comments,
                 },
             }))
         } else {
@@ -1278,7 +1485,7 @@ impl<'a> Parser<'a> {
             return Err(ParserError::IncorrectInput.into());
         }
 
-        Ok(FileDescriptor {
+        let mut file = FileDescriptor {
             imports,
             package,
             syntax,
@@ -1287,13 +1494,18 @@ impl<'a> Parser<'a> {
             extensions,
             services,
             options,
-        })
+        };
+        file.resolve_features()?;
+        Ok(file)
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::pure::model::EnumType;
+    use crate::pure::model::FieldPresence;
+    use crate::pure::model::RepeatedFieldEncoding;
 
     fn parse<P, R>(input: &str, parse_what: P) -> R
     where
@@ -1389,6 +1601,29 @@ mod test {
         assert_eq!(4, enumeration.values.len());
     }
 
+    #[test]
+    fn test_enum_alias() {
+        let msg = r#"enum E {
+            A = 0;
+            B = 0;
+        }"#;
+        let mut parser = Parser::new(msg);
+        let err = parser.next_enum_opt().unwrap_err();
+        assert_eq!(
+            "enum value number 0 is used by both `A` and `B` in enum `E`, \
+            set `option allow_alias = true` to allow aliases",
+            err.to_string()
+        );
+
+        let msg = r#"enum E {
+            option allow_alias = true;
+            A = 0;
+            B = 0;
+        }"#;
+        let enumeration = parse_opt(msg, |p| p.next_enum_opt());
+        assert_eq!(2, enumeration.values.len());
+    }
+
     #[test]
     fn test_ignore() {
         let msg = r#"option optimize_for = SPEED;"#;
@@ -1418,6 +1653,75 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_import_vis() {
+        let msg = r#"syntax = "proto3";
+
+    import "a.proto";
+    import public "b.proto";
+    import weak "c.proto";
+    "#;
+        let desc = parse(msg, |p| p.next_proto());
+
+        assert_eq!(
+            vec![
+                ("a.proto", ImportVis::Default),
+                ("b.proto", ImportVis::Public),
+                ("c.proto", ImportVis::Weak),
+            ],
+            desc.imports
+                .iter()
+                .map(|i| (i.path.to_str(), i.vis.clone()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_editions() {
+        let msg = r#"edition = "2023";
+
+    option features.enum_type = CLOSED;
+
+    message A {
+        int32 a = 1;
+        repeated int32 b = 2 [features.repeated_field_encoding = EXPANDED];
+        int32 c = 3 [features.field_presence = IMPLICIT];
+        map<string, int32> d = 4;
+    }
+
+    enum E {
+        option features.enum_type = OPEN;
+        E0 = 0;
+    }
+    "#;
+        let desc = parse(msg, |p| p.next_proto());
+
+        assert_eq!(Syntax::Editions(Edition::Edition2023), desc.syntax);
+
+        let fields = desc.messages[0].regular_fields_including_in_oneofs();
+        assert_eq!(FieldPresence::Explicit, fields[0].features.field_presence);
+        assert_eq!(EnumType::Closed, fields[0].features.enum_type);
+        assert_eq!(Some(Rule::Repeated), fields[1].rule);
+        assert_eq!(
+            RepeatedFieldEncoding::Expanded,
+            fields[1].features.repeated_field_encoding
+        );
+        assert_eq!(FieldPresence::Implicit, fields[2].features.field_presence);
+        assert_eq!(EnumType::Open, desc.enums[0].features.enum_type);
+    }
+
+    #[test]
+    fn test_editions_labels() {
+        let mut parser = Parser::new("edition = \"2023\"; message A { optional int32 a = 1; }");
+        assert!(parser.next_proto().is_err());
+
+        let mut parser = Parser::new("edition = \"2023\"; message A { group G = 1 {} }");
+        assert!(parser.next_proto().is_err());
+
+        let mut parser = Parser::new("edition = \"2024\";");
+        assert!(parser.next_proto().is_err());
+    }
+
     #[test]
     fn test_nested_message() {
         let msg = r#"message A
@@ -1495,6 +1799,81 @@ mod test {
         assert_eq!(2, mess.t.fields.len());
     }
 
+    #[test]
+    fn test_reserved_max() {
+        let msg = r#"message Sample {
+            reserved 1, 5 to max;
+        }"#;
+
+        let mess = parse_opt(msg, |p| p.next_message_opt());
+        assert_eq!(
+            vec![
+                FieldNumberRange { from: 1, to: 1 },
+                FieldNumberRange {
+                    from: 5,
+                    to: 0x1fffffff
+                },
+            ],
+            mess.t.reserved_nums
+        );
+    }
+
+    #[test]
+    fn test_enum_reserved() {
+        let msg = r#"enum Sample {
+            reserved -3, 2, 15 to 20, 40 to max;
+            reserved "FOO", "BAR";
+            ZERO = 0;
+        }"#;
+
+        let enumeration = parse_opt(msg, |p| p.next_enum_opt());
+        assert_eq!(
+            vec![
+                FieldNumberRange { from: -3, to: -3 },
+                FieldNumberRange { from: 2, to: 2 },
+                FieldNumberRange { from: 15, to: 20 },
+                FieldNumberRange {
+                    from: 40,
+                    to: i32::MAX
+                },
+            ],
+            enumeration.t.reserved_nums
+        );
+        assert_eq!(
+            vec!["FOO".to_string(), "BAR".to_string()],
+            enumeration.t.reserved_names
+        );
+        assert_eq!(1, enumeration.t.values.len());
+    }
+
+    #[test]
+    fn test_aggregate_option() {
+        let msg = r#"option (my_option) = {
+            name: "x"
+            inner { a: 1, b: [2, 3] }
+            inner < a: 4 >
+            [foo.ext]: true;
+        };"#;
+        let option = parse_opt(msg, |p| p.next_option_opt());
+
+        let m = match &option.value {
+            ProtobufConstant::Message(m) => m,
+            c => panic!("expecting message constant: {:?}", c),
+        };
+        assert_eq!(
+            vec!["name", "inner", "[foo.ext]"],
+            m.fields.keys().map(|k| k.to_string()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            2,
+            m.fields[&ProtobufConstantMessageFieldName::Regular("inner".to_owned())].len()
+        );
+        assert_eq!(
+            r#"{ name: "x" inner { a: 1 b: 2 b: 3 } inner { a: 4 } [foo.ext]: true }"#,
+            option.value.format()
+        );
+    }
+
     #[test]
     fn test_default_value_int() {
         let msg = r#"message Sample {
@@ -1568,6 +1947,27 @@ mod test {
         assert_eq!("bbb", mess.t.regular_fields_for_test()[2].name);
     }
 
+    #[test]
+    fn test_group_with_options() {
+        let msg = r#"message MessageWithGroup {
+            optional group Foo = 1 [deprecated = true] {
+                optional int32 bar = 2;
+            }
+        }"#;
+        let mess = parse_opt(msg, |p| p.next_message_opt());
+
+        let field = &mess.t.regular_fields_for_test()[0];
+        assert_eq!("foo", field.name);
+        assert_eq!(1, field.options.len());
+        assert_eq!("deprecated", field.options[0].name.to_string());
+        if let FieldType::Group(Group { name, fields }) = &field.typ {
+            assert_eq!("Foo", name);
+            assert_eq!(1, fields.len());
+        } else {
+            panic!("expecting group");
+        }
+    }
+
     #[test]
     fn test_incorrect_file_descriptor() {
         let msg = r#"
@@ -1578,5 +1978,120 @@ mod test {
 
         let err = FileDescriptor::parse(msg).err().expect("err");
         assert_eq!(4, err.line);
+        assert_eq!(13, err.col);
+        assert_eq!(msg.find("dfgdg").unwrap(), err.offset);
+    }
+
+    #[test]
+    fn test_comments() {
+        let msg = r#"// Detached
+
+        // Message comment
+        message Foo { // Message trailing
+            // Field leading
+            optional int32 aaa = 1; // Field trailing
+
+            optional int32 bbb = 2;
+            // Trailing on the next line
+
+            // Detached from ccc
+
+            /* Block
+             * comment */
+            optional int32 ccc = 3;
+        }"#;
+        let mess = parse_opt(msg, |p| p.next_message_opt());
+
+        assert_eq!(
+            Comments {
+                leading: Some(" Message comment\n".to_owned()),
+                trailing: Some(" Message trailing\n".to_owned()),
+                leading_detached: vec![" Detached\n".to_owned()],
+            },
+            mess.t.comments
+        );
+
+        let fields = mess.t.regular_fields_for_test();
+        assert_eq!(
+            Comments {
+                leading: Some(" Field leading\n".to_owned()),
+                trailing: Some(" Field trailing\n".to_owned()),
+                leading_detached: Vec::new(),
+            },
+            fields[0].comments
+        );
+        assert_eq!(
+            Comments {
+                leading: None,
+                trailing: Some(" Trailing on the next line\n".to_owned()),
+                leading_detached: Vec::new(),
+            },
+            fields[1].comments
+        );
+        assert_eq!(
+            Comments {
+                leading: Some(" Block\n comment ".to_owned()),
+                trailing: None,
+                leading_detached: vec![" Detached from ccc\n".to_owned()],
+            },
+            fields[2].comments
+        );
+    }
+
+    #[test]
+    fn test_enum_comments() {
+        let msg = r#"enum Foo {
+            // Leading
+            BAR = 1; // Trailing
+            BAZ = 2;
+        }"#;
+        let enumeration = parse_opt(msg, |p| p.next_enum_opt());
+        assert_eq!(
+            Comments {
+                leading: Some(" Leading\n".to_owned()),
+                trailing: Some(" Trailing\n".to_owned()),
+                leading_detached: Vec::new(),
+            },
+            enumeration.t.values[0].comments
+        );
+        assert_eq!(Comments::default(), enumeration.t.values[1].comments);
+    }
+
+    #[test]
+    fn test_service() {
+        let input = r#"service Foo {
+            rpc Unary(A) returns (B);
+            rpc Client(stream A) returns (B);
+            rpc Server(A) returns (stream B) { option deprecated = true; }
+            rpc Bidi(stream A) returns (stream B) {}
+            stream Old(A, B);
+        }"#;
+        let service = parse_opt(input, |p| p.next_service_opt());
+        let flags: Vec<_> = service
+            .t
+            .methods
+            .iter()
+            .map(|m| {
+                (
+                    m.t.name.as_str(),
+                    m.t.client_streaming,
+                    m.t.server_streaming,
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                ("Unary", false, false),
+                ("Client", true, false),
+                ("Server", false, true),
+                ("Bidi", true, true),
+                ("Old", true, true),
+            ],
+            flags
+        );
+        assert_eq!(
+            "deprecated",
+            service.t.methods[2].t.options[0].name.to_string()
+        );
     }
 }
@@ -8,7 +8,7 @@ pub fn json_name(input: &str) -> String {
         if c == '_' {
             capitalize_next = true;
         } else if capitalize_next {
-            result.extend(c.to_uppercase());
+            result.push(c.to_ascii_uppercase());
             capitalize_next = false;
         } else {
             result.push(c);
@@ -17,3 +17,41 @@ pub fn json_name(input: &str) -> String {
 
     result
 }
+
+#[cfg(test)]
+mod test {
+    use super::json_name;
+
+    #[test]
+    fn test_json_name() {
+        // Field names from `test_messages_proto3.proto` of conformance suite.
+        let cases = [
+            ("fieldname1", "fieldname1"),
+            ("field_name2", "fieldName2"),
+            ("_field_name3", "FieldName3"),
+            ("field__name4_", "fieldName4"),
+            ("field0name5", "field0name5"),
+            ("field_0_name6", "field0Name6"),
+            ("fieldName7", "fieldName7"),
+            ("FieldName8", "FieldName8"),
+            ("field_Name9", "fieldName9"),
+            ("Field_Name10", "FieldName10"),
+            ("FIELD_NAME11", "FIELDNAME11"),
+            ("FIELD_name12", "FIELDName12"),
+            ("__field_name13", "FieldName13"),
+            ("__Field_name14", "FieldName14"),
+            ("field__name15", "fieldName15"),
+            ("field__Name16", "fieldName16"),
+            ("field_name17__", "fieldName17"),
+            ("Field_name18__", "FieldName18"),
+            // Digits are not capitalized, but underscore is still dropped.
+            ("foo_3bar", "foo3bar"),
+            ("foo_bar_baz", "fooBarBaz"),
+            ("_", ""),
+            ("", ""),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(expected, json_name(name), "{}", name);
+        }
+    }
+}
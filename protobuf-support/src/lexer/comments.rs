@@ -0,0 +1,381 @@
+//! Association of comments with tokens.
+//!
+//! This is a transcription of `Tokenizer::NextWithComments` from `protoc`,
+//! so comments are attached to declarations exactly like in `SourceCodeInfo`
+//! generated by `protoc`.
+
+use crate::lexer::token::Token;
+
+/// Comments found between two tokens.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenComments {
+    /// Comment attached to the previous token.
+    pub prev_trailing: Option<String>,
+    /// Comments not attached to any token.
+    pub detached: Vec<String>,
+    /// Comment attached to the next token.
+    pub next_leading: Option<String>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum CommentStart {
+    Line,
+    Block,
+    None,
+}
+
+struct Cursor<'a> {
+    rem: &'a str,
+    line: u32,
+}
+
+impl<'a> Cursor<'a> {
+    fn lookahead_char(&self) -> Option<char> {
+        self.rem.chars().next()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.lookahead_char()?;
+        self.rem = &self.rem[c.len_utf8()..];
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn try_consume(&mut self, c: char) -> bool {
+        if self.lookahead_char() == Some(c) {
+            self.next_char();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn try_consume_str(&mut self, s: &str) -> bool {
+        if self.rem.starts_with(s) {
+            self.rem = &self.rem[s.len()..];
+            true
+        } else {
+            false
+        }
+    }
+
+    fn consume_whitespace_no_newline(&mut self) {
+        while let Some(c) = self.lookahead_char() {
+            if c == '\n' || !c.is_whitespace() {
+                break;
+            }
+            self.next_char();
+        }
+    }
+
+    fn try_consume_comment_start(&mut self) -> CommentStart {
+        if self.try_consume_str("//") {
+            CommentStart::Line
+        } else if self.try_consume_str("/*") {
+            CommentStart::Block
+        } else {
+            CommentStart::None
+        }
+    }
+
+    /// Consume the rest of a line comment including the newline.
+    fn consume_line_comment(&mut self, content: &mut String) {
+        while let Some(c) = self.next_char() {
+            content.push(c);
+            if c == '\n' {
+                break;
+            }
+        }
+    }
+
+    /// Consume the rest of a block comment stripping leading asterisks.
+    fn consume_block_comment(&mut self, content: &mut String) {
+        loop {
+            while let Some(c) = self.lookahead_char() {
+                if c == '*' || c == '/' || c == '\n' {
+                    break;
+                }
+                content.push(c);
+                self.next_char();
+            }
+
+            if self.try_consume('\n') {
+                content.push('\n');
+                self.consume_whitespace_no_newline();
+                if self.try_consume('*') && self.try_consume('/') {
+                    break;
+                }
+            } else if self.try_consume_str("*/") {
+                break;
+            } else if let Some(c) = self.next_char() {
+                content.push(c);
+            } else {
+                // Unterminated comment is reported by the lexer.
+                break;
+            }
+        }
+    }
+}
+
+struct CommentCollector {
+    result: TokenComments,
+    buffer: String,
+    has_comment: bool,
+    is_line_comment: bool,
+    can_attach_to_prev: bool,
+    num_comments: usize,
+}
+
+impl CommentCollector {
+    fn buffer_for_line_comment(&mut self) -> &mut String {
+        // Combine with previous line comments, but not block comments.
+        if self.has_comment && !self.is_line_comment {
+            self.flush();
+        }
+        self.has_comment = true;
+        self.is_line_comment = true;
+        &mut self.buffer
+    }
+
+    fn buffer_for_block_comment(&mut self) -> &mut String {
+        if self.has_comment {
+            self.flush();
+        }
+        self.has_comment = true;
+        self.is_line_comment = false;
+        &mut self.buffer
+    }
+
+    fn clear_buffer(&mut self) {
+        self.buffer.clear();
+        self.has_comment = false;
+    }
+
+    /// Buffer is complete and not connected to the next token.
+    fn flush(&mut self) {
+        if self.has_comment {
+            let comment = std::mem::take(&mut self.buffer);
+            if self.can_attach_to_prev {
+                self.result
+                    .prev_trailing
+                    .get_or_insert_with(String::new)
+                    .push_str(&comment);
+                self.can_attach_to_prev = false;
+            } else {
+                self.result.detached.push(comment);
+            }
+            self.clear_buffer();
+            self.num_comments += 1;
+        }
+    }
+
+    fn detach_from_prev(&mut self) {
+        self.can_attach_to_prev = false;
+    }
+
+    fn maybe_detach_comment(&mut self) {
+        let mut count = self.num_comments;
+        if self.has_comment {
+            count += 1;
+        }
+
+        // If there's one comment, make sure it is detached.
+        if count == 1 {
+            if let Some(trailing) = self.result.prev_trailing.take() {
+                self.result.detached.insert(0, trailing);
+            }
+            self.can_attach_to_prev = false;
+            self.flush();
+        }
+    }
+
+    fn finish(mut self) -> TokenComments {
+        // Whatever is in the buffer is a leading comment.
+        if self.has_comment {
+            self.result.next_leading = Some(self.buffer);
+        }
+        self.result
+    }
+}
+
+impl TokenComments {
+    /// Split comments in text between two tokens.
+    ///
+    /// `gap` must contain only whitespace and comments,
+    /// `after_token` is `false` at the beginning of the input,
+    /// `next` is `None` at the end of the input.
+    pub fn split(gap: &str, after_token: bool, next: Option<&Token>) -> TokenComments {
+        let mut collector = CommentCollector {
+            result: TokenComments::default(),
+            buffer: String::new(),
+            has_comment: false,
+            is_line_comment: false,
+            can_attach_to_prev: true,
+            num_comments: 0,
+        };
+        let mut cursor = Cursor { rem: gap, line: 0 };
+
+        let prev_line = cursor.line;
+        let mut trailing_comment_end_line = None;
+
+        if !after_token {
+            collector.detach_from_prev();
+        } else {
+            // A comment appearing on the same line must be attached to the previous declaration.
+            cursor.consume_whitespace_no_newline();
+            match cursor.try_consume_comment_start() {
+                CommentStart::Line => {
+                    trailing_comment_end_line = Some(cursor.line);
+                    cursor.consume_line_comment(collector.buffer_for_line_comment());
+                    // Don't allow comments on subsequent lines to be attached to a trailing comment.
+                    collector.flush();
+                }
+                CommentStart::Block => {
+                    cursor.consume_block_comment(collector.buffer_for_block_comment());
+                    trailing_comment_end_line = Some(cursor.line);
+                    cursor.consume_whitespace_no_newline();
+                    if !cursor.try_consume('\n') {
+                        // The next token is on the same line,
+                        // we have no idea which token the comment should be attached to.
+                        return TokenComments::default();
+                    }
+                    collector.flush();
+                }
+                CommentStart::None => {
+                    if !cursor.try_consume('\n') {
+                        // The next token is on the same line. There are no comments.
+                        return TokenComments::default();
+                    }
+                }
+            }
+        }
+
+        // Now we are on the line after the previous token.
+        loop {
+            cursor.consume_whitespace_no_newline();
+
+            match cursor.try_consume_comment_start() {
+                CommentStart::Line => {
+                    cursor.consume_line_comment(collector.buffer_for_line_comment());
+                }
+                CommentStart::Block => {
+                    cursor.consume_block_comment(collector.buffer_for_block_comment());
+                    // Consume the rest of the line so that we don't interpret it
+                    // as a blank line the next time around the loop.
+                    cursor.consume_whitespace_no_newline();
+                    cursor.try_consume('\n');
+                }
+                CommentStart::None => {
+                    if cursor.try_consume('\n') {
+                        // Completely blank line.
+                        collector.flush();
+                        collector.detach_from_prev();
+                    } else {
+                        match next {
+                            None | Some(Token::Symbol('}' | ']' | ')')) => {
+                                // At the end of a scope it makes no sense
+                                // to attach a comment to the following token.
+                                collector.flush();
+                            }
+                            _ => {}
+                        }
+                        if next.is_some()
+                            && (prev_line == cursor.line
+                                || trailing_comment_end_line == Some(cursor.line))
+                        {
+                            // When the previous token and this one are on the same line,
+                            // it's unclear to what token the comment should be attached.
+                            collector.maybe_detach_comment();
+                        }
+                        return collector.finish();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn split(gap: &str) -> TokenComments {
+        TokenComments::split(gap, true, Some(&Token::Ident("x".to_owned())))
+    }
+
+    #[test]
+    fn no_comments() {
+        assert_eq!(TokenComments::default(), split(" "));
+        assert_eq!(TokenComments::default(), split("\n\n  "));
+    }
+
+    #[test]
+    fn trailing_and_leading() {
+        assert_eq!(
+            TokenComments {
+                prev_trailing: Some(" trailing\n".to_owned()),
+                detached: vec![" detached\n".to_owned()],
+                next_leading: Some(" leading 1\n leading 2\n".to_owned()),
+            },
+            split(" // trailing\n\n// detached\n\n// leading 1\n// leading 2\n")
+        );
+    }
+
+    #[test]
+    fn trailing_on_next_line() {
+        assert_eq!(
+            TokenComments {
+                prev_trailing: Some(" trailing\n".to_owned()),
+                detached: Vec::new(),
+                next_leading: None,
+            },
+            split("\n// trailing\n\n")
+        );
+    }
+
+    #[test]
+    fn block_comment() {
+        assert_eq!(
+            TokenComments {
+                prev_trailing: None,
+                detached: Vec::new(),
+                next_leading: Some("*\n Leading\n line\n".to_owned()),
+            },
+            TokenComments::split(
+                "\n\n/**\n * Leading\n * line\n */\n",
+                true,
+                Some(&Token::Ident("x".to_owned()))
+            )
+        );
+    }
+
+    #[test]
+    fn end_of_scope() {
+        assert_eq!(
+            TokenComments {
+                prev_trailing: None,
+                detached: vec![" detached\n".to_owned()],
+                next_leading: None,
+            },
+            TokenComments::split("\n\n// detached\n", true, Some(&Token::Symbol('}')))
+        );
+    }
+
+    #[test]
+    fn start_of_input() {
+        assert_eq!(
+            TokenComments {
+                prev_trailing: None,
+                detached: vec![" license\n".to_owned()],
+                next_leading: Some(" leading\n".to_owned()),
+            },
+            TokenComments::split(
+                "// license\n\n// leading\n",
+                false,
+                Some(&Token::Ident("x".to_owned()))
+            )
+        );
+    }
+}
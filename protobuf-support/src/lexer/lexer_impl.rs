@@ -90,6 +90,11 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Whole input of this lexer
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
     /// No more chars
     pub fn eof(&self) -> bool {
         self.pos == self.input.len()
@@ -117,6 +122,7 @@ impl<'a> Lexer<'a> {
             let (_, c) = char_indices.next().unwrap();
             let c_len = char_indices.next().map(|(len, _)| len).unwrap_or(rem.len());
             self.pos += c_len;
+            self.loc.offset = self.pos;
             if c == '\n' {
                 self.loc.line += 1;
                 self.loc.col = FIRST_COL;
@@ -656,10 +662,15 @@ impl<'a> Lexer<'a> {
             None
         } else {
             let token = self.next_token_inner()?;
+            let end_loc = self.loc;
             // Skip whitespace here to update location
             // to the beginning of the next token
             self.skip_ws()?;
-            Some(TokenWithLocation { token, loc })
+            Some(TokenWithLocation {
+                token,
+                loc,
+                end_loc,
+            })
         })
     }
 }
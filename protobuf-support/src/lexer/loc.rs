@@ -10,6 +10,8 @@ pub struct Loc {
     pub line: u32,
     /// 1-based
     pub col: u32,
+    /// 0-based byte offset
+    pub(crate) offset: usize,
 }
 
 impl fmt::Display for Loc {
@@ -23,6 +25,12 @@ impl Loc {
         Loc {
             line: FIRST_LINE,
             col: FIRST_COL,
+            offset: 0,
         }
     }
+
+    /// 0-based byte offset
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
 }
@@ -1,5 +1,6 @@
 //! Implementation of lexer for both protobuf parser and for text format parser.
 
+pub mod comments;
 pub mod float;
 pub mod int;
 pub mod json_number_lit;
@@ -44,4 +44,6 @@ impl Token {
 pub struct TokenWithLocation {
     pub token: Token,
     pub loc: Loc,
+    /// Location right after the token
+    pub end_loc: Loc,
 }
@@ -1,3 +1,4 @@
+use crate::lexer::comments::TokenComments;
 use crate::lexer::lexer_impl::Lexer;
 use crate::lexer::lexer_impl::LexerError;
 use crate::lexer::loc::Loc;
@@ -45,6 +46,8 @@ pub struct Tokenizer<'a> {
     lexer: Lexer<'a>,
     next_token: Option<TokenWithLocation>,
     last_token_loc: Option<Loc>,
+    /// End of the last consumed token
+    prev_token_end_loc: Option<Loc>,
 }
 
 impl<'a> Tokenizer<'a> {
@@ -53,6 +56,7 @@ impl<'a> Tokenizer<'a> {
             lexer: Lexer::new(input, comment_style),
             next_token: None,
             last_token_loc: None,
+            prev_token_end_loc: None,
         }
     }
 
@@ -73,6 +77,44 @@ impl<'a> Tokenizer<'a> {
         self.loc()
     }
 
+    /// Location right after the last consumed token.
+    pub fn prev_token_end_loc(&self) -> Option<Loc> {
+        self.prev_token_end_loc
+    }
+
+    /// Comments between the last consumed token and the next token.
+    pub fn lookahead_comments(&mut self) -> TokenizerResult<TokenComments> {
+        self.lookahead()?;
+        let input = self.lexer.input();
+        let start = self.prev_token_end_loc.map_or(0, |loc| loc.offset);
+        let end = self
+            .next_token
+            .as_ref()
+            .map_or(input.len(), |t| t.loc.offset);
+        Ok(TokenComments::split(
+            &input[start..end],
+            self.prev_token_end_loc.is_some(),
+            self.next_token.as_ref().map(|t| &t.token),
+        ))
+    }
+
+    /// Source text of the next token.
+    pub fn lookahead_text(&mut self) -> TokenizerResult<Option<&'a str>> {
+        self.lookahead()?;
+        let input = self.lexer.input();
+        Ok(self
+            .next_token
+            .as_ref()
+            .map(|t| &input[t.loc.offset..t.end_loc.offset]))
+    }
+
+    /// Consume lookahead token
+    fn take_next_token(&mut self) -> Option<Token> {
+        let TokenWithLocation { token, end_loc, .. } = self.next_token.take()?;
+        self.prev_token_end_loc = Some(end_loc);
+        Some(token)
+    }
+
     fn lookahead(&mut self) -> TokenizerResult<Option<&Token>> {
         Ok(match self.next_token {
             Some(ref token) => Some(&token.token),
@@ -96,10 +138,7 @@ impl<'a> Tokenizer<'a> {
 
     fn next(&mut self) -> TokenizerResult<Option<Token>> {
         self.lookahead()?;
-        Ok(self
-            .next_token
-            .take()
-            .map(|TokenWithLocation { token, .. }| token))
+        Ok(self.take_next_token())
     }
 
     pub fn next_some(&mut self) -> TokenizerResult<Token> {
@@ -111,10 +150,7 @@ impl<'a> Tokenizer<'a> {
 
     /// Can be called only after lookahead, otherwise it's error
     pub fn advance(&mut self) -> TokenizerResult<Token> {
-        self.next_token
-            .take()
-            .map(|TokenWithLocation { token, .. }| token)
-            .ok_or(TokenizerError::InternalError)
+        self.take_next_token().ok_or(TokenizerError::InternalError)
     }
 
     /// No more tokens
@@ -134,7 +170,7 @@ impl<'a> Tokenizer<'a> {
             },
             _ => return Ok(None),
         };
-        self.next_token = None;
+        self.take_next_token();
         Ok(Some(v))
     }
 
@@ -148,7 +184,7 @@ impl<'a> Tokenizer<'a> {
             Some(ref token) => p(&token.token)?,
             None => return Err(TokenizerError::UnexpectedEof.into()),
         };
-        self.next_token = None;
+        self.take_next_token();
         Ok(r)
     }
 
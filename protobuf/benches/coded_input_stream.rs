@@ -133,3 +133,110 @@ fn read_varint_large(b: &mut Bencher) {
         assert_eq!(1000, count);
     })
 }
+
+#[bench]
+fn read_varint_32(b: &mut Bencher) {
+    let mut v = Vec::new();
+    {
+        let mut v = protobuf::CodedOutputStream::vec(&mut v);
+        let mut rng = 1;
+        for _ in 0..1000 {
+            // three to five byte varints
+            v.write_raw_varint32((rng as u32) | (1 << 14)).unwrap();
+            rng = xorshift(rng);
+        }
+        v.flush().expect("flush");
+    }
+    b.iter(|| {
+        let mut is = CodedInputStream::from_bytes(test::black_box(&v));
+        let mut count = 0;
+        while !is.eof().expect("eof") {
+            test::black_box(is.read_raw_varint32().expect("read"));
+            count += 1;
+        }
+        assert_eq!(1000, count);
+    })
+}
+
+#[bench]
+fn read_repeated_packed_fixed32(b: &mut Bencher) {
+    let mut v = Vec::new();
+    {
+        let mut os = protobuf::CodedOutputStream::vec(&mut v);
+        os.write_bytes_no_tag(&make_bytes(40_000)).expect("write");
+        os.flush().expect("flush");
+    }
+    let mut target = Vec::new();
+    b.iter(|| {
+        let mut is = CodedInputStream::from_bytes(test::black_box(&v));
+        target.clear();
+        is.read_repeated_packed_fixed32_into(&mut target)
+            .expect("read");
+        assert_eq!(10_000, target.len());
+    })
+}
+
+#[bench]
+fn read_repeated_packed_int32(b: &mut Bencher) {
+    let mut v = Vec::new();
+    {
+        let mut elements = Vec::new();
+        let mut os = protobuf::CodedOutputStream::vec(&mut elements);
+        let mut rng = 1;
+        for _ in 0..10_000 {
+            // one to three byte varints
+            os.write_int32_no_tag((rng % (1 << 21)) as i32).unwrap();
+            rng = xorshift(rng);
+        }
+        os.flush().expect("flush");
+        drop(os);
+        let mut os = protobuf::CodedOutputStream::vec(&mut v);
+        os.write_bytes_no_tag(&elements).expect("write");
+        os.flush().expect("flush");
+    }
+    let mut target = Vec::new();
+    b.iter(|| {
+        let mut is = CodedInputStream::from_bytes(test::black_box(&v));
+        target.clear();
+        is.read_repeated_packed_int32_into(&mut target)
+            .expect("read");
+        assert_eq!(10_000, target.len());
+    })
+}
+
+#[cfg(feature = "with-bytes")]
+fn make_length_delimited(count: usize, len: usize) -> Vec<u8> {
+    let mut v = Vec::new();
+    {
+        let mut os = protobuf::CodedOutputStream::vec(&mut v);
+        for _ in 0..count {
+            os.write_bytes_no_tag(&make_bytes(len)).expect("write");
+        }
+        os.flush().expect("flush");
+    }
+    v
+}
+
+#[cfg(feature = "with-bytes")]
+#[bench]
+fn read_bytes_into_vec(b: &mut Bencher) {
+    let v = bytes::Bytes::from(make_length_delimited(100, 1_000));
+    b.iter(|| {
+        let mut is = CodedInputStream::from_tokio_bytes(test::black_box(&v));
+        while !is.eof().expect("eof") {
+            test::black_box(is.read_bytes().expect("read"));
+        }
+    })
+}
+
+#[cfg(feature = "with-bytes")]
+#[bench]
+fn read_tokio_bytes(b: &mut Bencher) {
+    let v = bytes::Bytes::from(make_length_delimited(100, 1_000));
+    b.iter(|| {
+        let mut is = CodedInputStream::from_tokio_bytes(test::black_box(&v));
+        while !is.eof().expect("eof") {
+            test::black_box(is.read_tokio_bytes().expect("read"));
+        }
+    })
+}
@@ -0,0 +1,41 @@
+// `cargo test --benches` and `#[feature(test)]` work only in nightly
+#![cfg(rustc_nightly)]
+#![feature(test)]
+
+extern crate test;
+
+use protobuf::descriptor::field_descriptor_proto::Type;
+use protobuf::descriptor::DescriptorProto;
+use protobuf::descriptor::FieldDescriptorProto;
+use protobuf::Message;
+use test::Bencher;
+
+fn make_message() -> Vec<u8> {
+    let mut m = DescriptorProto::new();
+    m.set_name("Message".to_owned());
+    for i in 0..100 {
+        let mut f = FieldDescriptorProto::new();
+        f.set_name(format!("field_{}", i));
+        f.set_number(i + 1);
+        f.set_type(Type::TYPE_INT32);
+        m.field.push(f);
+    }
+    test::black_box(m.write_to_bytes().unwrap())
+}
+
+#[bench]
+fn parse_repeated_message(b: &mut Bencher) {
+    let bytes = make_message();
+    b.iter(|| DescriptorProto::parse_from_bytes(&bytes).unwrap());
+}
+
+#[bench]
+fn merge_repeated_message_into_reused(b: &mut Bencher) {
+    let bytes = make_message();
+    let mut m = DescriptorProto::new();
+    b.iter(|| {
+        m.clear();
+        m.merge_from_bytes(&bytes).unwrap();
+        test::black_box(&m);
+    });
+}
@@ -0,0 +1,205 @@
+use std::ops::Deref;
+use std::option;
+use std::sync::Arc;
+
+use crate::Message;
+
+/// Message field stored as `Option<Arc<T>>`.
+///
+/// Generated for recursive singular message fields with
+/// `arc_recursive_fields` option, so cloning a message is cheap
+/// and subtrees can be shared between messages.
+///
+/// Mutation is copy-on-write: [`mut_or_insert_default`](Self::mut_or_insert_default)
+/// clones the message if it is shared with other fields.
+///
+/// # Examples
+///
+/// ```
+/// # use protobuf::ArcMessageField;
+/// # use protobuf::well_known_types::duration::Duration;
+/// # use std::sync::Arc;
+/// let mut a = ArcMessageField::some(Duration::new());
+/// let b = a.clone();
+/// assert!(Arc::ptr_eq(a.arc().unwrap(), b.arc().unwrap()));
+///
+/// a.mut_or_insert_default().seconds = 10;
+/// assert_eq!(10, a.get_or_default().seconds);
+/// assert_eq!(0, b.get_or_default().seconds);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ArcMessageField<T>(pub Option<Arc<T>>);
+
+impl<T> ArcMessageField<T> {
+    /// Construct field from given message.
+    #[inline]
+    pub fn some(value: T) -> ArcMessageField<T> {
+        ArcMessageField(Some(Arc::new(value)))
+    }
+
+    /// Construct field sharing given message.
+    #[inline]
+    pub fn from_arc(value: Arc<T>) -> ArcMessageField<T> {
+        ArcMessageField(Some(value))
+    }
+
+    /// Construct an empty field.
+    #[inline]
+    pub const fn none() -> ArcMessageField<T> {
+        ArcMessageField(None)
+    }
+
+    /// Construct field from optional.
+    #[inline]
+    pub fn from_option(option: Option<T>) -> ArcMessageField<T> {
+        ArcMessageField(option.map(Arc::new))
+    }
+
+    /// True iff this field is set.
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// True iff this field is not set.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// View data as reference option.
+    #[inline]
+    pub fn as_ref(&self) -> Option<&T> {
+        self.0.as_deref()
+    }
+
+    /// Shared pointer to the message, clone it to share the message with another field.
+    #[inline]
+    pub fn arc(&self) -> Option<&Arc<T>> {
+        self.0.as_ref()
+    }
+
+    /// Set the field sharing given message.
+    #[inline]
+    pub fn set_arc(&mut self, value: Arc<T>) {
+        self.0 = Some(value);
+    }
+
+    /// Set the field to given message.
+    #[inline]
+    pub fn set(&mut self, value: T) {
+        self.0 = Some(Arc::new(value));
+    }
+
+    /// View data as iterator.
+    #[inline]
+    pub fn iter(&self) -> option::IntoIter<&T> {
+        self.as_ref().into_iter()
+    }
+
+    /// Take shared pointer to the message, leaving this field empty.
+    #[inline]
+    pub fn take_arc(&mut self) -> Option<Arc<T>> {
+        self.0.take()
+    }
+
+    /// Clear this field.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl<M: Message> ArcMessageField<M> {
+    /// Get a reference to contained value or a default instance.
+    pub fn get_or_default(&self) -> &M {
+        self.as_ref().unwrap_or_else(|| M::default_instance())
+    }
+
+    /// Get a mutable reference to contained value, initialize if not initialized yet.
+    ///
+    /// The message is cloned if it is shared.
+    pub fn mut_or_insert_default(&mut self) -> &mut M {
+        Arc::make_mut(self.0.get_or_insert_with(Default::default))
+    }
+
+    /// Take the message leaving this field empty.
+    ///
+    /// The message is cloned if it is shared.
+    pub fn take(&mut self) -> Option<M> {
+        self.0
+            .take()
+            .map(|m| Arc::try_unwrap(m).unwrap_or_else(|m| (*m).clone()))
+    }
+}
+
+/// Get a reference to contained value or a default instance if the field is not initialized.
+impl<M: Message> Deref for ArcMessageField<M> {
+    type Target = M;
+
+    fn deref(&self) -> &Self::Target {
+        self.get_or_default()
+    }
+}
+
+impl<T> Default for ArcMessageField<T> {
+    #[inline]
+    fn default() -> ArcMessageField<T> {
+        ArcMessageField::none()
+    }
+}
+
+impl<T> From<Option<T>> for ArcMessageField<T> {
+    fn from(o: Option<T>) -> Self {
+        ArcMessageField::from_option(o)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArcMessageField<T> {
+    type Item = &'a T;
+    type IntoIter = option::IntoIter<&'a T>;
+
+    fn into_iter(self) -> option::IntoIter<&'a T> {
+        self.iter()
+    }
+}
+
+/// Serialized as `Option<T>`.
+#[cfg(feature = "with-serde")]
+impl<T: serde::Serialize> serde::Serialize for ArcMessageField<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for ArcMessageField<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<T>::deserialize(deserializer).map(ArcMessageField::from_option)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::well_known_types::duration::Duration;
+    use crate::ArcMessageField;
+
+    #[test]
+    fn take() {
+        let mut d = Duration::new();
+        d.seconds = 3;
+        let mut a = ArcMessageField::some(d.clone());
+        let b = a.clone();
+        assert_eq!(Some(d.clone()), a.take());
+        assert!(a.is_none());
+        assert_eq!(Some(&d), b.as_ref());
+
+        let mut b = b;
+        let arc = b.arc().unwrap().clone();
+        b.mut_or_insert_default().nanos = 1;
+        assert!(!Arc::ptr_eq(&arc, b.arc().unwrap()));
+        assert_eq!(0, arc.nanos);
+    }
+}
@@ -0,0 +1,177 @@
+use std::io;
+
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::BufReader;
+
+use crate::error::WireError;
+use crate::varint::MAX_VARINT_ENCODED_LEN;
+use crate::Message;
+
+/// Default limit of message size, 64 MiB.
+const DEFAULT_MAX_MESSAGE_SIZE: u64 = 64 << 20;
+
+/// Read length-delimited messages from `tokio::io::AsyncRead`.
+///
+/// Each message is prefixed with its length encoded as varint,
+/// which is the framing used by Java `writeDelimitedTo`
+/// and by [`Message::write_length_delimited_to_writer`].
+///
+/// The reader is buffered. Messages larger than
+/// [`max_message_size`](Self::set_max_message_size), 64 MiB by default,
+/// are rejected before they are read.
+///
+/// # Examples
+///
+/// ```
+/// # use protobuf::AsyncCodedInputStream;
+/// # use protobuf::well_known_types::duration::Duration;
+/// # async fn read(socket: impl tokio::io::AsyncRead + Unpin) -> std::io::Result<()> {
+/// let mut is = AsyncCodedInputStream::new(socket);
+/// while !is.eof().await? {
+///     let duration: Duration = is.read_message().await?;
+///     println!("{}", duration);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncCodedInputStream<R> {
+    read: BufReader<R>,
+    max_message_size: u64,
+}
+
+impl<R: AsyncRead + Unpin> AsyncCodedInputStream<R> {
+    /// Wrap an `AsyncRead`.
+    pub fn new(read: R) -> AsyncCodedInputStream<R> {
+        AsyncCodedInputStream {
+            read: BufReader::new(read),
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+        }
+    }
+
+    /// Set the limit of length-delimited bytes and messages size, default is 64 MiB.
+    ///
+    /// Reading larger messages fails with `InvalidData` error
+    /// without reading the message.
+    pub fn set_max_message_size(&mut self, max_message_size: u64) {
+        self.max_message_size = max_message_size;
+    }
+
+    /// Unwrap the reader.
+    ///
+    /// Note buffered data is lost.
+    pub fn into_inner(self) -> R {
+        self.read.into_inner()
+    }
+
+    /// Check if the end of input is reached.
+    pub async fn eof(&mut self) -> io::Result<bool> {
+        Ok(self.read.fill_buf().await?.is_empty())
+    }
+
+    /// Read varint.
+    pub async fn read_raw_varint64(&mut self) -> io::Result<u64> {
+        let mut r: u64 = 0;
+        for i in 0..MAX_VARINT_ENCODED_LEN {
+            let b = self.read.read_u8().await?;
+            if i == MAX_VARINT_ENCODED_LEN - 1 && b > 0x01 {
+                break;
+            }
+            r |= ((b & 0x7f) as u64) << (i * 7);
+            if b < 0x80 {
+                return Ok(r);
+            }
+        }
+        Err(crate::Error::from(WireError::IncorrectVarint).into())
+    }
+
+    /// Read length-delimited bytes.
+    pub async fn read_bytes(&mut self) -> io::Result<Vec<u8>> {
+        let len = self.read_raw_varint64().await?;
+        if len > self.max_message_size {
+            return Err(crate::Error::from(WireError::MessageTooLarge(len)).into());
+        }
+        let mut r = Vec::new();
+        // Do not preallocate, because length could be malformed.
+        (&mut self.read).take(len).read_to_end(&mut r).await?;
+        if (r.len() as u64) != len {
+            return Err(crate::Error::from(WireError::UnexpectedEof).into());
+        }
+        Ok(r)
+    }
+
+    /// Read length-delimited message.
+    pub async fn read_message<M: Message>(&mut self) -> io::Result<M> {
+        let bytes = self.read_bytes().await?;
+        Ok(M::parse_from_bytes(&bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+    use crate::well_known_types::duration::Duration;
+
+    #[tokio::test]
+    async fn read_messages_from_duplex() {
+        let (mut w, r) = tokio::io::duplex(3);
+
+        let messages: Vec<Duration> = (0..5)
+            .map(|i| {
+                let mut d = Duration::new();
+                d.seconds = i * 1000;
+                d.nanos = i as i32;
+                d
+            })
+            .collect();
+
+        let write = {
+            let messages = messages.clone();
+            tokio::spawn(async move {
+                for m in &messages {
+                    let bytes = m.write_length_delimited_to_bytes().unwrap();
+                    w.write_all(&bytes).await.unwrap();
+                }
+            })
+        };
+
+        let mut is = AsyncCodedInputStream::new(r);
+        let mut read = Vec::new();
+        while !is.eof().await.unwrap() {
+            read.push(is.read_message::<Duration>().await.unwrap());
+        }
+        write.await.unwrap();
+
+        assert_eq!(messages, read);
+    }
+
+    #[tokio::test]
+    async fn read_truncated() {
+        let mut is = AsyncCodedInputStream::new(&[0x05, 0x08, 0x01][..]);
+        let err = is.read_message::<Duration>().await.unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    }
+
+    #[tokio::test]
+    async fn read_too_large() {
+        let mut is = AsyncCodedInputStream::new(&[0x80, 0x80, 0x80, 0x80, 0x01][..]);
+        let err = is.read_bytes().await.unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+
+        let mut is = AsyncCodedInputStream::new(&[0x03, 0x08, 0x01, 0x10][..]);
+        is.set_max_message_size(2);
+        let err = is.read_message::<Duration>().await.unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    }
+
+    #[tokio::test]
+    async fn read_incorrect_varint() {
+        let mut is = AsyncCodedInputStream::new(&[0xff; 11][..]);
+        let err = is.read_raw_varint64().await.unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    }
+}
@@ -8,6 +8,12 @@ use std::sync::atomic::Ordering;
 /// It is always equal to itself to simplify generated code.
 /// (Generated code can use `#[derive(Eq)]`).
 ///
+/// Size is stored in an atomic, so `compute_size` can be called
+/// on a message shared between threads.
+/// Relaxed ordering is enough: concurrent computations of the same message
+/// store the same value, and cached size is only read
+/// after it was computed by the same thread.
+///
 /// This type should rarely be used directly.
 #[derive(Debug, Default)]
 pub struct CachedSize {
@@ -54,3 +60,39 @@ impl Hash for CachedSize {
         // ignore cached size in cache computation
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::thread;
+
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::Message;
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Too slow on Miri.
+    fn compute_size_shared_between_threads() {
+        let mut message = DescriptorProto::new();
+        message.set_name("Apple".to_owned());
+        for i in 0..100 {
+            let mut field = FieldDescriptorProto::new();
+            field.set_name(format!("field_{}", i));
+            field.set_number(i + 1);
+            message.field.push(field);
+        }
+        let expected = message.write_to_bytes().unwrap();
+
+        let message = &message;
+        let expected = &expected;
+        thread::scope(|s| {
+            for _ in 0..8 {
+                s.spawn(move || {
+                    for _ in 0..100 {
+                        assert_eq!(expected.len() as u64, message.compute_size());
+                        assert_eq!(*expected, message.write_to_bytes().unwrap());
+                    }
+                });
+            }
+        });
+    }
+}
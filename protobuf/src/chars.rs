@@ -1,11 +1,13 @@
-#![cfg(feature = "bytes")]
+#![cfg(feature = "with-bytes")]
 
 use std::borrow::Borrow;
 use std::fmt;
+use std::mem;
 use std::ops::Deref;
 use std::str;
 
 use bytes::Bytes;
+use bytes::BytesMut;
 
 /// Thin wrapper around `Bytes` which guarantees that bytes are valid UTF-8 string.
 /// Should be API-compatible to `String`.
@@ -23,6 +25,25 @@ impl Chars {
         self.0.clear();
     }
 
+    /// Append a char to the end of the buffer.
+    pub fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Append a string slice to the end of the buffer.
+    ///
+    /// Reuses the underlying allocation when the buffer is not shared,
+    /// otherwise copies the content into a new buffer.
+    pub fn push_str(&mut self, s: &str) {
+        if s.is_empty() {
+            return;
+        }
+        let mut buf = BytesMut::from(mem::take(&mut self.0));
+        buf.extend_from_slice(s.as_bytes());
+        // Concatenation of two valid UTF-8 strings is a valid UTF-8 string.
+        self.0 = buf.freeze();
+    }
+
     /// Try convert from `Bytes`
     pub fn from_bytes(bytes: Bytes) -> Result<Chars, str::Utf8Error> {
         str::from_utf8(&bytes)?;
@@ -30,6 +51,26 @@ impl Chars {
         Ok(Chars(bytes))
     }
 
+    /// Convert from `Bytes` without checking that bytes are valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// Bytes must be valid UTF-8.
+    pub(crate) unsafe fn from_bytes_unchecked(bytes: Bytes) -> Chars {
+        Chars(bytes)
+    }
+
+    /// Convert from bytes replacing invalid UTF-8 sequences
+    /// with `U+FFFD REPLACEMENT CHARACTER`, like `String::from_utf8_lossy`.
+    pub fn from_utf8_lossy(bytes: &[u8]) -> Chars {
+        Chars::from(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    /// Underlying UTF-8 bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
     /// Len in bytes.
     pub fn len(&self) -> usize {
         self.0.len()
@@ -53,6 +94,30 @@ impl From<String> for Chars {
     }
 }
 
+impl FromIterator<char> for Chars {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Chars {
+        Chars::from(String::from_iter(iter))
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Chars {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Chars {
+        Chars::from(String::from_iter(iter))
+    }
+}
+
+impl Extend<char> for Chars {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        self.push_str(&String::from_iter(iter));
+    }
+}
+
+impl<'a> Extend<&'a str> for Chars {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        self.push_str(&String::from_iter(iter));
+    }
+}
+
 impl Into<String> for Chars {
     fn into(self) -> String {
         // This is safe because `Chars` is guaranteed to store a valid UTF-8 string
@@ -81,6 +146,48 @@ impl Borrow<str> for Chars {
     }
 }
 
+impl PartialEq<str> for Chars {
+    fn eq(&self, other: &str) -> bool {
+        &**self == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Chars {
+    fn eq(&self, other: &&'a str) -> bool {
+        &**self == *other
+    }
+}
+
+impl PartialEq<String> for Chars {
+    fn eq(&self, other: &String) -> bool {
+        &**self == other.as_str()
+    }
+}
+
+impl PartialEq<Chars> for str {
+    fn eq(&self, other: &Chars) -> bool {
+        self == &**other
+    }
+}
+
+impl PartialEq<Chars> for &str {
+    fn eq(&self, other: &Chars) -> bool {
+        *self == &**other
+    }
+}
+
+impl PartialEq<Chars> for String {
+    fn eq(&self, other: &Chars) -> bool {
+        self.as_str() == &**other
+    }
+}
+
+impl AsRef<[u8]> for Chars {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
 impl fmt::Display for Chars {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -95,6 +202,38 @@ impl fmt::Debug for Chars {
     }
 }
 
+#[cfg(feature = "with-serde")]
+impl serde::Serialize for Chars {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de> serde::Deserialize<'de> for Chars {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Chars, D::Error> {
+        struct CharsVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for CharsVisitor {
+            type Value = Chars;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Chars, E> {
+                Ok(Chars::from(v))
+            }
+
+            fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Chars, E> {
+                Ok(Chars::from(v))
+            }
+        }
+
+        deserializer.deserialize_string(CharsVisitor)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Chars;
@@ -109,4 +248,113 @@ mod test {
         assert_eq!(format!("{}", string), format!("{}", chars));
         assert_eq!(format!("{:?}", string), format!("{:?}", chars));
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_push() {
+        let mut chars = Chars::new();
+        chars.push('a');
+        chars.push_str("bc");
+        chars.push('é');
+        chars.push_str("");
+        assert_eq!("abcé", &*chars);
+        assert_eq!(5, chars.len());
+
+        chars.clear();
+        assert!(chars.is_empty());
+
+        chars.push_str("😀");
+        chars.push('x');
+        assert_eq!("😀x", &*chars);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_push_shared() {
+        let mut chars = Chars::from("ab");
+        let copy = chars.clone();
+        chars.push_str("cd");
+        assert_eq!("abcd", &*chars);
+        assert_eq!("ab", &*copy);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_as_bytes() {
+        for s in ["", "abc", "😀 héllo"] {
+            let chars = Chars::from(s);
+            assert_eq!(s.as_bytes(), chars.as_bytes());
+            let str: &str = &chars;
+            assert_eq!(str.as_bytes(), chars.as_bytes());
+            assert_eq!(chars.as_bytes(), AsRef::<[u8]>::as_ref(&chars));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_from_iter() {
+        let chars: Chars = "héllo".chars().collect();
+        assert_eq!(Chars::from("héllo"), chars);
+
+        let chars: Chars = ["hé", "", "llo"].into_iter().collect();
+        assert_eq!(Chars::from("héllo"), chars);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_extend() {
+        let mut chars = Chars::from("hé");
+        chars.extend("llo".chars());
+        chars.extend([", ", "wörld"]);
+        assert_eq!(Chars::from("héllo, wörld"), chars);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_eq_str_and_string() {
+        let chars = Chars::from("héllo");
+        let string = String::from("héllo");
+        let str: &str = "héllo";
+
+        assert!(chars == *str);
+        assert!(chars == str);
+        assert!(chars == string);
+        assert!(*str == chars);
+        assert!(str == chars);
+        assert!(string == chars);
+
+        let other = String::from("hello");
+        assert!(chars != *other.as_str());
+        assert!(chars != other.as_str());
+        assert!(chars != other);
+        assert!(*other.as_str() != chars);
+        assert!(other.as_str() != chars);
+        assert!(other != chars);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_from_utf8_lossy() {
+        assert_eq!("héllo", Chars::from_utf8_lossy("héllo".as_bytes()));
+
+        let chars = Chars::from_utf8_lossy(b"ab\xc3\x28cd");
+        assert_eq!("ab\u{FFFD}(cd", chars);
+        assert!(chars.contains('\u{FFFD}'));
+    }
+
+    #[test]
+    #[cfg(feature = "with-serde")]
+    #[cfg_attr(miri, ignore)]
+    fn test_serde_json() {
+        let chars = Chars::from("héllo 😀");
+        let json = serde_json::to_string(&chars).unwrap();
+        assert_eq!("\"héllo 😀\"", json);
+
+        let back: Chars = serde_json::from_str(&json).unwrap();
+        assert_eq!(chars, back);
+        assert_eq!("héllo 😀", &*back);
+
+        let escaped: Chars = serde_json::from_str("\"\\u00e9\\ud83d\\ude00\"").unwrap();
+        assert_eq!("é😀", &*escaped);
+    }
 }
@@ -5,13 +5,15 @@ use std::io::Read;
 use std::mem;
 use std::mem::MaybeUninit;
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use bytes::buf::UninitSlice;
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
+use bytes::Buf;
+#[cfg(feature = "with-bytes")]
 use bytes::BufMut;
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use bytes::Bytes;
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use bytes::BytesMut;
 
 use crate::coded_input_stream::buf_read_or_reader::BufReadOrReader;
@@ -89,7 +91,7 @@ impl<'a> BufReadIter<'a> {
         }
     }
 
-    #[cfg(feature = "bytes")]
+    #[cfg(feature = "with-bytes")]
     pub(crate) fn from_bytes(bytes: &'a Bytes) -> BufReadIter<'a> {
         BufReadIter {
             input_source: InputSource::Bytes(bytes),
@@ -99,6 +101,16 @@ impl<'a> BufReadIter<'a> {
         }
     }
 
+    #[cfg(feature = "with-bytes")]
+    pub(crate) fn from_buf<B: Buf + 'a>(buf: B) -> BufReadIter<'a> {
+        BufReadIter {
+            input_source: InputSource::Read(BufReadOrReader::Buf(Box::new(buf.reader()))),
+            buf: InputBuf::empty(),
+            pos_of_buf_start: 0,
+            limit: NO_LIMIT,
+        }
+    }
+
     #[inline]
     fn assertions(&self) {
         debug_assert!(self.pos() <= self.limit);
@@ -196,7 +208,7 @@ impl<'a> BufReadIter<'a> {
         self.read_byte_slow()
     }
 
-    #[cfg(feature = "bytes")]
+    #[cfg(feature = "with-bytes")]
     pub(crate) fn read_exact_bytes(&mut self, len: usize) -> crate::Result<Bytes> {
         if let InputSource::Bytes(bytes) = self.input_source {
             if len > self.remaining_in_buf_len() {
@@ -226,7 +238,7 @@ impl<'a> BufReadIter<'a> {
         }
     }
 
-    #[cfg(feature = "bytes")]
+    #[cfg(feature = "with-bytes")]
     unsafe fn uninit_slice_as_mut_slice(slice: &mut UninitSlice) -> &mut [MaybeUninit<u8>] {
         use std::slice;
         slice::from_raw_parts_mut(slice.as_mut_ptr() as *mut MaybeUninit<u8>, slice.len())
@@ -406,7 +418,7 @@ impl<'a> BufReadIter<'a> {
     }
 }
 
-#[cfg(all(test, feature = "bytes"))]
+#[cfg(all(test, feature = "with-bytes"))]
 mod test_bytes {
     use std::io::Write;
 
@@ -14,6 +14,9 @@ use crate::misc::maybe_uninit_write_slice;
 pub(crate) enum BufReadOrReader<'a> {
     BufReader(BufReader<&'a mut dyn Read>),
     BufRead(&'a mut dyn BufRead),
+    /// Reader over owned `bytes::Buf`.
+    #[cfg(feature = "with-bytes")]
+    Buf(Box<dyn BufRead + 'a>),
 }
 
 impl<'a> fmt::Debug for BufReadOrReader<'a> {
@@ -21,6 +24,8 @@ impl<'a> fmt::Debug for BufReadOrReader<'a> {
         match self {
             BufReadOrReader::BufReader(..) => write!(f, "BufReader(...)"),
             BufReadOrReader::BufRead(..) => write!(f, "BufRead(...)"),
+            #[cfg(feature = "with-bytes")]
+            BufReadOrReader::Buf(..) => write!(f, "Buf(...)"),
         }
     }
 }
@@ -30,6 +35,8 @@ impl<'a> Read for BufReadOrReader<'a> {
         match self {
             BufReadOrReader::BufReader(r) => r.read(buf),
             BufReadOrReader::BufRead(r) => r.read(buf),
+            #[cfg(feature = "with-bytes")]
+            BufReadOrReader::Buf(r) => r.read(buf),
         }
     }
 
@@ -37,6 +44,8 @@ impl<'a> Read for BufReadOrReader<'a> {
         match self {
             BufReadOrReader::BufReader(r) => r.read_to_end(buf),
             BufReadOrReader::BufRead(r) => r.read_to_end(buf),
+            #[cfg(feature = "with-bytes")]
+            BufReadOrReader::Buf(r) => r.read_to_end(buf),
         }
     }
 
@@ -44,6 +53,8 @@ impl<'a> Read for BufReadOrReader<'a> {
         match self {
             BufReadOrReader::BufReader(r) => r.read_exact(buf),
             BufReadOrReader::BufRead(r) => r.read_exact(buf),
+            #[cfg(feature = "with-bytes")]
+            BufReadOrReader::Buf(r) => r.read_exact(buf),
         }
     }
 }
@@ -59,6 +70,8 @@ impl<'a> BufReadOrReader<'a> {
             let fill_buf = match self {
                 BufReadOrReader::BufReader(r) => r.fill_buf()?,
                 BufReadOrReader::BufRead(r) => r.fill_buf()?,
+                #[cfg(feature = "with-bytes")]
+                BufReadOrReader::Buf(r) => r.fill_buf()?,
             };
             if fill_buf.is_empty() {
                 return Err(io::Error::new(
@@ -71,6 +84,8 @@ impl<'a> BufReadOrReader<'a> {
             match self {
                 BufReadOrReader::BufReader(r) => r.consume(consume),
                 BufReadOrReader::BufRead(r) => r.consume(consume),
+                #[cfg(feature = "with-bytes")]
+                BufReadOrReader::Buf(r) => r.consume(consume),
             }
             pos += consume;
         }
@@ -100,6 +115,8 @@ impl<'a> BufRead for BufReadOrReader<'a> {
         match self {
             BufReadOrReader::BufReader(r) => r.fill_buf(),
             BufReadOrReader::BufRead(r) => r.fill_buf(),
+            #[cfg(feature = "with-bytes")]
+            BufReadOrReader::Buf(r) => r.fill_buf(),
         }
     }
 
@@ -107,6 +124,8 @@ impl<'a> BufRead for BufReadOrReader<'a> {
         match self {
             BufReadOrReader::BufReader(r) => r.consume(amt),
             BufReadOrReader::BufRead(r) => r.consume(amt),
+            #[cfg(feature = "with-bytes")]
+            BufReadOrReader::Buf(r) => r.consume(amt),
         }
     }
 }
@@ -1,4 +1,4 @@
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use bytes::Bytes;
 
 use crate::coded_input_stream::buf_read_or_reader::BufReadOrReader;
@@ -8,6 +8,6 @@ use crate::coded_input_stream::buf_read_or_reader::BufReadOrReader;
 pub(crate) enum InputSource<'a> {
     Read(BufReadOrReader<'a>),
     Slice(&'a [u8]),
-    #[cfg(feature = "bytes")]
+    #[cfg(feature = "with-bytes")]
     Bytes(&'a Bytes),
 }
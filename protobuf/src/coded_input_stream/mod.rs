@@ -9,10 +9,10 @@ use std::io::Read;
 use std::mem;
 use std::mem::MaybeUninit;
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use ::bytes::Bytes;
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use crate::chars::Chars;
 use crate::coded_input_stream::buf_read_iter::BufReadIter;
 use crate::enums::Enum;
@@ -38,11 +38,12 @@ use crate::reflect::MessageDescriptor;
 use crate::unknown::UnknownValue;
 use crate::varint::decode::decode_varint32;
 use crate::varint::decode::decode_varint64;
+use crate::varint::decode::decode_varint_short;
+use crate::varint::MAX_VARINT32_ENCODED_LEN;
 use crate::varint::MAX_VARINT_ENCODED_LEN;
-use crate::wire_format;
+use crate::wire::WireRead;
 use crate::wire_format::WireType;
-use crate::zigzag::decode_zig_zag_32;
-use crate::zigzag::decode_zig_zag_64;
+use crate::CodedOutputStream;
 use crate::EnumOrUnknown;
 use crate::Message;
 use crate::MessageDyn;
@@ -53,12 +54,31 @@ const DEFAULT_RECURSION_LIMIT: u32 = 100;
 // Max allocated vec when reading length-delimited from unknown input stream
 pub(crate) const READ_RAW_BYTES_MAX_ALLOC: usize = 10_000_000;
 
+/// How `string` fields are checked to be valid UTF-8 when reading.
+///
+/// Used with [`CodedInputStream::set_utf8_validation`]
+/// and [`CodedInputStream::disable_utf8_validation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Utf8Validation {
+    /// Fail with [`WireError::Utf8Error`](crate::error::WireError::Utf8Error)
+    /// on invalid UTF-8. This is the default.
+    Strict,
+    /// Replace invalid UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    Lenient,
+    /// Do not validate strings.
+    ///
+    /// Reading invalid UTF-8 in this mode is undefined behavior,
+    /// use it only for trusted input known to be valid.
+    None,
+}
+
 /// Buffered read with handy utilities.
 #[derive(Debug)]
 pub struct CodedInputStream<'a> {
     source: BufReadIter<'a>,
     recursion_level: u32,
     recursion_limit: u32,
+    utf8_validation: Utf8Validation,
 }
 
 impl<'a> CodedInputStream<'a> {
@@ -89,24 +109,70 @@ impl<'a> CodedInputStream<'a> {
     /// `CodedInputStream` operations like
     /// [`read_tokio_bytes`](crate::CodedInputStream::read_tokio_bytes)
     /// will return a shared copy of this bytes object.
-    #[cfg(feature = "bytes")]
+    #[cfg(feature = "with-bytes")]
     pub fn from_tokio_bytes(bytes: &'a Bytes) -> CodedInputStream<'a> {
         CodedInputStream::from_buf_read_iter(BufReadIter::from_bytes(bytes))
     }
 
+    /// Read from `bytes::Buf`, for example, a chain of `Bytes` chunks.
+    ///
+    /// Chunks are read in place, values which span chunk boundaries
+    /// are read as from any other buffered input.
+    /// Bytes consumed by this stream are advanced in the `Buf`
+    /// when the stream is dropped, so pass `&mut buf` to continue reading after it.
+    #[cfg(feature = "with-bytes")]
+    pub fn from_buf<B: ::bytes::Buf + 'a>(buf: B) -> CodedInputStream<'a> {
+        CodedInputStream::from_buf_read_iter(BufReadIter::from_buf(buf))
+    }
+
     fn from_buf_read_iter(source: BufReadIter<'a>) -> CodedInputStream<'a> {
         CodedInputStream {
             source,
             recursion_level: 0,
             recursion_limit: DEFAULT_RECURSION_LIMIT,
+            utf8_validation: Utf8Validation::Strict,
         }
     }
 
     /// Set the recursion limit.
+    ///
+    /// The limit applies to nested messages and groups, default is 100.
+    /// When the limit is exceeded, parsing fails with
+    /// [`WireError::OverRecursionLimit`](crate::error::WireError::OverRecursionLimit).
     pub fn set_recursion_limit(&mut self, limit: u32) {
         self.recursion_limit = limit;
     }
 
+    /// Set how `string` fields are validated, default is
+    /// [`Strict`](Utf8Validation::Strict).
+    ///
+    /// # Panics
+    ///
+    /// If `validation` is [`Utf8Validation::None`],
+    /// use [`disable_utf8_validation`](Self::disable_utf8_validation) instead.
+    pub fn set_utf8_validation(&mut self, validation: Utf8Validation) {
+        assert!(
+            validation != Utf8Validation::None,
+            "use `disable_utf8_validation` to disable UTF-8 validation"
+        );
+        self.utf8_validation = validation;
+    }
+
+    /// Do not validate `string` fields, i. e. use [`Utf8Validation::None`].
+    ///
+    /// # Safety
+    ///
+    /// All strings read from this stream must be valid UTF-8,
+    /// otherwise behavior is undefined.
+    pub unsafe fn disable_utf8_validation(&mut self) {
+        self.utf8_validation = Utf8Validation::None;
+    }
+
+    /// How `string` fields are validated.
+    pub fn utf8_validation(&self) -> Utf8Validation {
+        self.utf8_validation
+    }
+
     #[inline]
     pub(crate) fn incr_recursion(&mut self) -> crate::Result<()> {
         if self.recursion_level >= self.recursion_limit {
@@ -126,11 +192,21 @@ impl<'a> CodedInputStream<'a> {
         self.source.pos()
     }
 
-    /// How many bytes until current limit
+    /// How many bytes until current limit.
+    ///
+    /// Returns `u64::MAX` if no limit is set.
     pub fn bytes_until_limit(&self) -> u64 {
         self.source.bytes_until_limit()
     }
 
+    /// Input ended before the length-delimited message.
+    fn check_limit_reached(&self) -> crate::Result<()> {
+        if self.bytes_until_limit() != 0 {
+            return Err(WireError::TruncatedMessage.into());
+        }
+        Ok(())
+    }
+
     /// Read bytes into given `buf`.
     #[inline]
     pub fn read_exact(&mut self, buf: &mut [MaybeUninit<u8>]) -> crate::Result<()> {
@@ -141,7 +217,7 @@ impl<'a> CodedInputStream<'a> {
     ///
     /// This operation returns a shared view if `CodedInputStream` is
     /// constructed with `Bytes` parameter.
-    #[cfg(feature = "bytes")]
+    #[cfg(feature = "with-bytes")]
     fn read_raw_tokio_bytes(&mut self, count: usize) -> crate::Result<Bytes> {
         self.source.read_exact_bytes(count)
     }
@@ -153,11 +229,20 @@ impl<'a> CodedInputStream<'a> {
     }
 
     /// Push new limit, return previous limit.
+    ///
+    /// `limit` is the number of bytes from the current position,
+    /// for example, the length of a length-delimited field.
+    /// Until the limit is popped, the stream behaves as if input ended at the limit,
+    /// so [`eof`](Self::eof) returns `true` there.
+    ///
+    /// Limits nest: the new limit must not exceed the current one,
+    /// and each pushed limit must be restored with [`pop_limit`](Self::pop_limit)
+    /// passing the returned value.
     pub fn push_limit(&mut self, limit: u64) -> crate::Result<u64> {
         self.source.push_limit(limit)
     }
 
-    /// Restore previous limit.
+    /// Restore previous limit returned by [`push_limit`](Self::push_limit).
     pub fn pop_limit(&mut self, old_limit: u64) {
         self.source.pop_limit(old_limit);
     }
@@ -179,31 +264,34 @@ impl<'a> CodedInputStream<'a> {
         Ok(())
     }
 
-    fn read_raw_varint64_slow(&mut self) -> crate::Result<u64> {
+    /// Read varint byte by byte, when the buffer does not contain complete varint.
+    ///
+    /// Must reject the same inputs as `decode_varint64` and `decode_varint32`:
+    /// varint longer than `max_len` bytes is an error, even if value fits,
+    /// and truncated varint is an EOF error.
+    fn read_raw_varint_slow(&mut self, max_len: usize, last_byte_max: u8) -> crate::Result<u64> {
         let mut r: u64 = 0;
-        let mut i = 0;
-        loop {
-            if i == MAX_VARINT_ENCODED_LEN {
-                return Err(ProtobufError::WireError(WireError::IncorrectVarint).into());
-            }
+        for i in 0..max_len - 1 {
             let b = self.read_raw_byte()?;
-            if i == 9 && (b & 0x7f) > 1 {
-                return Err(ProtobufError::WireError(WireError::IncorrectVarint).into());
-            }
             r = r | (((b & 0x7f) as u64) << (i * 7));
-            i += 1;
             if b < 0x80 {
                 return Ok(r);
             }
         }
+        let b = self.read_raw_byte()?;
+        if b > last_byte_max {
+            return Err(ProtobufError::WireError(WireError::IncorrectVarint).into());
+        }
+        Ok(r | ((b as u64) << ((max_len - 1) * 7)))
+    }
+
+    fn read_raw_varint64_slow(&mut self) -> crate::Result<u64> {
+        self.read_raw_varint_slow(MAX_VARINT_ENCODED_LEN, 0x01)
     }
 
     fn read_raw_varint32_slow(&mut self) -> crate::Result<u32> {
-        let v = self.read_raw_varint64_slow()?;
-        if v > u32::MAX as u64 {
-            return Err(ProtobufError::WireError(WireError::U32Overflow(v)).into());
-        }
-        Ok(v as u32)
+        self.read_raw_varint_slow(MAX_VARINT32_ENCODED_LEN, 0x0f)
+            .map(|v| v as u32)
     }
 
     /// Read varint
@@ -211,6 +299,12 @@ impl<'a> CodedInputStream<'a> {
     pub fn read_raw_varint64(&mut self) -> crate::Result<u64> {
         let rem = self.source.remaining_in_buf();
 
+        // Fast path without error handling for short varints, e.g. tags.
+        if let Some((r, c)) = decode_varint_short(rem) {
+            self.source.consume(c);
+            return Ok(r);
+        }
+
         match decode_varint64(rem)? {
             Some((r, c)) => {
                 self.source.consume(c);
@@ -225,6 +319,12 @@ impl<'a> CodedInputStream<'a> {
     pub fn read_raw_varint32(&mut self) -> crate::Result<u32> {
         let rem = self.source.remaining_in_buf();
 
+        // Fast path without error handling for short varints, e.g. tags.
+        if let Some((r, c)) = decode_varint_short(rem) {
+            self.source.consume(c);
+            return Ok(r as u32);
+        }
+
         match decode_varint32(rem)? {
             Some((r, c)) => {
                 self.source.consume(c);
@@ -278,85 +378,88 @@ impl<'a> CodedInputStream<'a> {
         self.read_raw_varint32_or_eof()
     }
 
-    /// Read tag
-    #[inline]
-    pub(crate) fn read_tag(&mut self) -> crate::Result<wire_format::Tag> {
-        let v = self.read_raw_varint32()?;
-        wire_format::Tag::new(v)
-    }
-
     /// Read tag, return it is pair (field number, wire type)
     #[inline]
     pub(crate) fn read_tag_unpack(&mut self) -> crate::Result<(u32, WireType)> {
-        self.read_tag().map(|t| t.unpack())
+        WireRead::read_tag(self)
     }
 
     /// Read `double`
+    #[inline]
     pub fn read_double(&mut self) -> crate::Result<f64> {
-        let bits = self.read_raw_little_endian64()?;
-        Ok(f64::from_bits(bits))
+        WireRead::read_double(self)
     }
 
     /// Read `float`
+    #[inline]
     pub fn read_float(&mut self) -> crate::Result<f32> {
-        let bits = self.read_raw_little_endian32()?;
-        Ok(f32::from_bits(bits))
+        WireRead::read_float(self)
     }
 
     /// Read `int64`
+    #[inline]
     pub fn read_int64(&mut self) -> crate::Result<i64> {
-        self.read_raw_varint64().map(|v| v as i64)
+        WireRead::read_int64(self)
     }
 
     /// Read `int32`
+    #[inline]
     pub fn read_int32(&mut self) -> crate::Result<i32> {
-        let v = self.read_int64()?;
-        i32::try_from(v).map_err(|_| WireError::I32Overflow(v).into())
+        WireRead::read_int32(self)
     }
 
     /// Read `uint64`
+    #[inline]
     pub fn read_uint64(&mut self) -> crate::Result<u64> {
-        self.read_raw_varint64()
+        WireRead::read_uint64(self)
     }
 
     /// Read `uint32`
+    #[inline]
     pub fn read_uint32(&mut self) -> crate::Result<u32> {
-        self.read_raw_varint32()
+        WireRead::read_uint32(self)
     }
 
     /// Read `sint64`
+    #[inline]
     pub fn read_sint64(&mut self) -> crate::Result<i64> {
-        self.read_uint64().map(decode_zig_zag_64)
+        WireRead::read_sint64(self)
     }
 
     /// Read `sint32`
+    #[inline]
     pub fn read_sint32(&mut self) -> crate::Result<i32> {
-        self.read_uint32().map(decode_zig_zag_32)
+        WireRead::read_sint32(self)
     }
 
     /// Read `fixed64`
+    #[inline]
     pub fn read_fixed64(&mut self) -> crate::Result<u64> {
-        self.read_raw_little_endian64()
+        WireRead::read_fixed64(self)
     }
 
     /// Read `fixed32`
+    #[inline]
     pub fn read_fixed32(&mut self) -> crate::Result<u32> {
-        self.read_raw_little_endian32()
+        WireRead::read_fixed32(self)
     }
 
     /// Read `sfixed64`
+    #[inline]
     pub fn read_sfixed64(&mut self) -> crate::Result<i64> {
-        self.read_raw_little_endian64().map(|v| v as i64)
+        WireRead::read_sfixed64(self)
     }
 
     /// Read `sfixed32`
+    #[inline]
     pub fn read_sfixed32(&mut self) -> crate::Result<i32> {
-        self.read_raw_little_endian32().map(|v| v as i32)
+        WireRead::read_sfixed32(self)
     }
 
     /// Read `bool`
+    #[inline]
     pub fn read_bool(&mut self) -> crate::Result<bool> {
-        self.read_raw_varint64().map(|v| v != 0)
+        WireRead::read_bool(self)
     }
 
     pub(crate) fn read_enum_value(&mut self) -> crate::Result<i32> {
@@ -393,8 +496,20 @@ impl<'a> CodedInputStream<'a> {
 
         target.reserve(reserve);
 
+        let size = T::ENCODED_SIZE as usize;
         let old_limit = self.push_limit(len_bytes)?;
-        while !self.eof()? {
+        loop {
+            // Decode all elements available in the buffer at once,
+            // buffer is already truncated to the end of the packed field.
+            let buf = self.source.remaining_in_buf();
+            let whole = buf.len() - buf.len() % size;
+            target.extend(buf[..whole].chunks_exact(size).map(T::from_le_bytes));
+            self.source.consume(whole);
+
+            if self.eof()? {
+                break;
+            }
+            // Element crosses buffer boundary, or packed field is truncated.
             target.push(T::read(self)?);
         }
         self.pop_limit(old_limit);
@@ -510,19 +625,71 @@ impl<'a> CodedInputStream<'a> {
         self.read_repeated_packed_into::<ProtobufTypeInt32>(target)
     }
 
-    fn skip_group(&mut self) -> crate::Result<()> {
-        while !self.eof()? {
-            let wire_type = self.read_tag_unpack()?.1;
+    fn skip_group(&mut self, number: Option<u32>) -> crate::Result<()> {
+        self.incr_recursion()?;
+        let r = self.skip_group_fields(number);
+        self.decr_recursion();
+        r
+    }
+
+    fn skip_group_fields(&mut self, number: Option<u32>) -> crate::Result<()> {
+        loop {
+            let (end_number, wire_type) = self.read_tag_unpack()?;
             if wire_type == WireType::EndGroup {
+                return check_end_group(number, end_number);
+            }
+            self.skip_field_with_number(end_number, wire_type)?;
+        }
+    }
+
+    /// Read fields until `EndGroup` tag, return them encoded without the end tag.
+    fn read_group(&mut self, number: Option<u32>) -> crate::Result<Vec<u8>> {
+        self.incr_recursion()?;
+        let r = self.read_group_fields(number);
+        self.decr_recursion();
+        r
+    }
+
+    fn read_group_fields(&mut self, number: Option<u32>) -> crate::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        let mut os = CodedOutputStream::vec(&mut bytes);
+        loop {
+            let (field_number, wire_type) = self.read_tag_unpack()?;
+            if wire_type == WireType::EndGroup {
+                check_end_group(number, field_number)?;
                 break;
             }
-            self.skip_field(wire_type)?;
+            // Nested groups are read recursively.
+            let value = self.read_unknown_with_number(field_number, wire_type)?;
+            os.write_unknown(field_number, value.get_ref())?;
         }
-        Ok(())
+        os.flush()?;
+        drop(os);
+        Ok(bytes)
     }
 
     /// Read `UnknownValue`
+    ///
+    /// Group field number is not known here, so the end tag
+    /// of the group is not checked to match the start tag.
     pub fn read_unknown(&mut self, wire_type: WireType) -> crate::Result<UnknownValue> {
+        self.read_unknown_impl(None, wire_type)
+    }
+
+    /// Read `UnknownValue` of the field with given number.
+    pub(crate) fn read_unknown_with_number(
+        &mut self,
+        number: u32,
+        wire_type: WireType,
+    ) -> crate::Result<UnknownValue> {
+        self.read_unknown_impl(Some(number), wire_type)
+    }
+
+    fn read_unknown_impl(
+        &mut self,
+        number: Option<u32>,
+        wire_type: WireType,
+    ) -> crate::Result<UnknownValue> {
         match wire_type {
             WireType::Varint => self.read_raw_varint64().map(|v| UnknownValue::Varint(v)),
             WireType::Fixed64 => self.read_fixed64().map(|v| UnknownValue::Fixed64(v)),
@@ -532,11 +699,7 @@ impl<'a> CodedInputStream<'a> {
                 self.read_raw_bytes(len)
                     .map(|v| UnknownValue::LengthDelimited(v))
             }
-            WireType::StartGroup => {
-                self.skip_group()?;
-                // We do not support groups, so just return something.
-                Ok(UnknownValue::LengthDelimited(Vec::new()))
-            }
+            WireType::StartGroup => self.read_group(number).map(UnknownValue::Group),
             WireType::EndGroup => {
                 Err(ProtobufError::WireError(WireError::UnexpectedWireType(wire_type)).into())
             }
@@ -544,7 +707,23 @@ impl<'a> CodedInputStream<'a> {
     }
 
     /// Skip field.
+    ///
+    /// Group field number is not known here, so the end tag
+    /// of the group is not checked to match the start tag.
     pub fn skip_field(&mut self, wire_type: WireType) -> crate::Result<()> {
+        self.skip_field_impl(None, wire_type)
+    }
+
+    /// Skip field with given number.
+    pub(crate) fn skip_field_with_number(
+        &mut self,
+        number: u32,
+        wire_type: WireType,
+    ) -> crate::Result<()> {
+        self.skip_field_impl(Some(number), wire_type)
+    }
+
+    fn skip_field_impl(&mut self, number: Option<u32>, wire_type: WireType) -> crate::Result<()> {
         match wire_type {
             WireType::Varint => self.read_raw_varint64().map(|_| ()),
             WireType::Fixed64 => self.read_fixed64().map(|_| ()),
@@ -553,7 +732,7 @@ impl<'a> CodedInputStream<'a> {
                 let len = self.read_raw_varint32()?;
                 self.skip_raw_bytes(len)
             }
-            WireType::StartGroup => self.skip_group(),
+            WireType::StartGroup => self.skip_group(number),
             WireType::EndGroup => {
                 Err(ProtobufError::WireError(WireError::UnexpectedWireType(wire_type)).into())
             }
@@ -586,17 +765,29 @@ impl<'a> CodedInputStream<'a> {
     }
 
     /// Read `bytes` field, length delimited
-    #[cfg(feature = "bytes")]
+    ///
+    /// When the stream is constructed with
+    /// [`from_tokio_bytes`](crate::CodedInputStream::from_tokio_bytes),
+    /// the result is a slice of the input sharing its allocation.
+    #[cfg(feature = "with-bytes")]
     pub fn read_tokio_bytes(&mut self) -> crate::Result<Bytes> {
         let len = self.read_raw_varint32()?;
         self.read_raw_tokio_bytes(len as usize)
     }
 
     /// Read `string` field, length delimited
-    #[cfg(feature = "bytes")]
+    #[cfg(feature = "with-bytes")]
     pub fn read_tokio_chars(&mut self) -> crate::Result<Chars> {
         let bytes = self.read_tokio_bytes()?;
-        Ok(Chars::from_bytes(bytes).map_err(ProtobufError::Utf8)?)
+        match self.utf8_validation {
+            Utf8Validation::Strict => Ok(Chars::from_bytes(bytes).map_err(ProtobufError::Utf8)?),
+            Utf8Validation::Lenient => match Chars::from_bytes(bytes.clone()) {
+                Ok(chars) => Ok(chars),
+                Err(_) => Ok(Chars::from_utf8_lossy(&bytes)),
+            },
+            // SAFETY: `disable_utf8_validation` caller guarantees input is valid UTF-8.
+            Utf8Validation::None => Ok(unsafe { Chars::from_bytes_unchecked(bytes) }),
+        }
     }
 
     /// Read `bytes` field, length delimited
@@ -620,9 +811,17 @@ impl<'a> CodedInputStream<'a> {
         let mut vec = mem::replace(target, String::new()).into_bytes();
         self.read_bytes_into(&mut vec)?;
 
-        let s = match String::from_utf8(vec) {
-            Ok(t) => t,
-            Err(_) => return Err(ProtobufError::WireError(WireError::Utf8Error).into()),
+        let s = match self.utf8_validation {
+            Utf8Validation::Strict => match String::from_utf8(vec) {
+                Ok(t) => t,
+                Err(_) => return Err(ProtobufError::WireError(WireError::Utf8Error).into()),
+            },
+            Utf8Validation::Lenient => match String::from_utf8(vec) {
+                Ok(t) => t,
+                Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+            },
+            // SAFETY: `disable_utf8_validation` caller guarantees input is valid UTF-8.
+            Utf8Validation::None => unsafe { String::from_utf8_unchecked(vec) },
         };
         *target = s;
         Ok(())
@@ -643,20 +842,38 @@ impl<'a> CodedInputStream<'a> {
         let len = decr.0.read_raw_varint64()?;
         let old_limit = decr.0.push_limit(len)?;
         message.merge_from(&mut decr.0)?;
+        decr.0.check_limit_reached()?;
         decr.0.pop_limit(old_limit);
         Ok(())
     }
 
     /// Like `merge_message`, but for dynamic messages.
     pub fn merge_message_dyn(&mut self, message: &mut dyn MessageDyn) -> crate::Result<()> {
+        self.incr_recursion()?;
+        let r = self.merge_message_dyn_no_recursion_check(message);
+        self.decr_recursion();
+        r
+    }
+
+    fn merge_message_dyn_no_recursion_check(
+        &mut self,
+        message: &mut dyn MessageDyn,
+    ) -> crate::Result<()> {
         let len = self.read_raw_varint64()?;
         let old_limit = self.push_limit(len)?;
         message.merge_from_dyn(self)?;
+        self.check_limit_reached()?;
         self.pop_limit(old_limit);
         Ok(())
     }
 
-    /// Read message
+    /// Read message prefixed with its length encoded as varint.
+    ///
+    /// This is compatible with Java `parseDelimitedFrom`/`writeDelimitedTo`,
+    /// so a stream of delimited messages can be read by calling this function
+    /// until [`eof`](Self::eof) returns `true`.
+    /// See [`CodedOutputStream::write_message_no_tag`](crate::CodedOutputStream::write_message_no_tag)
+    /// for the reverse operation.
     pub fn read_message<M: Message>(&mut self) -> crate::Result<M> {
         let mut r: M = Message::new();
         self.merge_message(&mut r)?;
@@ -676,6 +893,40 @@ impl<'a> CodedInputStream<'a> {
     }
 }
 
+/// End tag of a group must have the same field number as the start tag.
+fn check_end_group(start: Option<u32>, end: u32) -> crate::Result<()> {
+    match start {
+        Some(start) if start != end => {
+            Err(ProtobufError::WireError(WireError::MismatchedEndGroup(start, end)).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+impl<'a> WireRead for CodedInputStream<'a> {
+    type Error = crate::Error;
+
+    #[inline]
+    fn read_raw_varint64(&mut self) -> crate::Result<u64> {
+        CodedInputStream::read_raw_varint64(self)
+    }
+
+    #[inline]
+    fn read_raw_varint32(&mut self) -> crate::Result<u32> {
+        CodedInputStream::read_raw_varint32(self)
+    }
+
+    #[inline]
+    fn read_raw_little_endian32(&mut self) -> crate::Result<u32> {
+        CodedInputStream::read_raw_little_endian32(self)
+    }
+
+    #[inline]
+    fn read_raw_little_endian64(&mut self) -> crate::Result<u64> {
+        CodedInputStream::read_raw_little_endian64(self)
+    }
+}
+
 impl<'a> Read for CodedInputStream<'a> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.source.read(buf).map_err(Into::into)
@@ -701,12 +952,17 @@ mod test {
     use std::io::Read;
 
     use super::CodedInputStream;
+    use super::Utf8Validation;
     use super::READ_RAW_BYTES_MAX_ALLOC;
     use crate::error::ProtobufError;
+    use crate::error::WireError;
     use crate::hex::decode_hex;
-    use crate::wire_format::Tag;
+    use crate::hex::encode_hex;
+    use crate::well_known_types::duration::Duration;
+    use crate::well_known_types::wrappers::StringValue;
     use crate::wire_format::WireType;
     use crate::CodedOutputStream;
+    use crate::Message;
 
     fn test_read_partial<F>(hex: &str, mut callback: F)
     where
@@ -728,6 +984,19 @@ mod test {
         }
     }
 
+    /// Like `test_read_partial`, but also test with reader which
+    /// returns one byte at a time, so values are never complete in the buffer.
+    fn test_read_partial_byte_by_byte<F>(hex: &str, mut callback: F)
+    where
+        F: FnMut(&mut CodedInputStream),
+    {
+        test_read_partial(hex, &mut callback);
+        let d = decode_hex(hex);
+        let mut reader = io::BufReader::with_capacity(1, io::Cursor::new(&d));
+        let mut is = CodedInputStream::from_buf_read(&mut reader as &mut dyn BufRead);
+        callback(&mut is);
+    }
+
     fn test_read<F>(hex: &str, mut callback: F)
     where
         F: FnMut(&mut CodedInputStream),
@@ -789,33 +1058,56 @@ mod test {
         });
     }
 
+    fn assert_wire_error(expected: WireError, r: crate::Result<impl Debug>) {
+        match *r.unwrap_err().0 {
+            ProtobufError::WireError(e) => {
+                assert_eq!(format!("{:?}", expected), format!("{:?}", e))
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+
     #[test]
     fn test_input_stream_read_raw_varint_too_long() {
         // varint cannot have length > 10
-        test_read_partial("ff ff ff ff ff ff ff ff ff ff 01", |reader| {
-            let error = reader.read_raw_varint64().unwrap_err().0;
-            match *error {
-                ProtobufError::WireError(..) => (),
-                _ => panic!(),
-            }
-        });
-        test_read_partial("ff ff ff ff ff ff ff ff ff ff 01", |reader| {
-            let error = reader.read_raw_varint32().unwrap_err().0;
-            match *error {
-                ProtobufError::WireError(..) => (),
-                _ => panic!(),
-            }
+        for hex in [
+            "ff ff ff ff ff ff ff ff ff ff 01",
+            "80 80 80 80 80 80 80 80 80 80 00",
+            "ff ff ff ff ff ff ff ff ff 81 01",
+        ] {
+            test_read_partial_byte_by_byte(hex, |reader| {
+                assert_wire_error(WireError::IncorrectVarint, reader.read_raw_varint64());
+            });
+            test_read_partial_byte_by_byte(hex, |reader| {
+                assert_wire_error(WireError::IncorrectVarint, reader.read_raw_varint32());
+            });
+        }
+        // varint32 cannot have length > 5
+        test_read_partial_byte_by_byte("ff ff ff ff 8f 00", |reader| {
+            assert_wire_error(WireError::IncorrectVarint, reader.read_raw_varint32());
         });
     }
 
     #[test]
     fn test_input_stream_read_raw_varint_unexpected_eof() {
-        test_read_partial("96 97", |reader| {
-            let error = reader.read_raw_varint32().unwrap_err().0;
-            match *error {
-                ProtobufError::WireError(..) => (),
-                _ => panic!(),
-            }
+        for hex in ["", "80", "96 97", "ff ff ff ff"] {
+            test_read_partial_byte_by_byte(hex, |reader| {
+                assert_wire_error(WireError::UnexpectedEof, reader.read_raw_varint32());
+            });
+        }
+        for hex in ["", "80", "96 97", "ff ff ff ff ff ff ff ff ff"] {
+            test_read_partial_byte_by_byte(hex, |reader| {
+                assert_wire_error(WireError::UnexpectedEof, reader.read_raw_varint64());
+            });
+        }
+    }
+
+    #[test]
+    fn test_input_stream_read_raw_varint_truncated_by_limit() {
+        test_read_partial_byte_by_byte("96 97 01", |reader| {
+            let old_limit = reader.push_limit(2).unwrap();
+            assert_wire_error(WireError::UnexpectedEof, reader.read_raw_varint64());
+            reader.pop_limit(old_limit);
         });
     }
 
@@ -967,19 +1259,336 @@ mod test {
         let mut input = CodedInputStream::from_bytes(&vec);
         // Now act like a generated client
         assert_eq!(
-            Tag::make(1, WireType::LengthDelimited),
-            input.read_tag().unwrap()
+            (1, WireType::LengthDelimited),
+            input.read_tag_unpack().unwrap()
         );
         assert_eq!("field 1", &input.read_string().unwrap());
+        assert_eq!((2, WireType::StartGroup), input.read_tag_unpack().unwrap());
+        input.skip_field(WireType::StartGroup).unwrap();
         assert_eq!(
-            Tag::make(2, WireType::StartGroup),
-            input.read_tag().unwrap()
+            (3, WireType::LengthDelimited),
+            input.read_tag_unpack().unwrap()
         );
+        assert_eq!("field 3", input.read_string().unwrap());
+    }
+
+    #[test]
+    fn test_skip_group_recursion_limit() {
+        let mut vec = Vec::new();
+        let mut os = CodedOutputStream::new(&mut vec);
+        for _ in 0..200 {
+            os.write_tag(1, WireType::StartGroup).unwrap();
+        }
+        for _ in 0..200 {
+            os.write_tag(1, WireType::EndGroup).unwrap();
+        }
+        os.flush().unwrap();
+        drop(os);
+
+        let mut input = CodedInputStream::from_bytes(&vec);
+        input.read_tag_unpack().unwrap();
+        let err = input.skip_field(WireType::StartGroup).unwrap_err();
+        assert!(
+            matches!(
+                err.0.as_ref(),
+                ProtobufError::WireError(WireError::OverRecursionLimit)
+            ),
+            "{:?}",
+            err
+        );
+
+        let mut input = CodedInputStream::from_bytes(&vec);
+        input.set_recursion_limit(200);
+        input.read_tag_unpack().unwrap();
         input.skip_field(WireType::StartGroup).unwrap();
+        assert!(input.eof().unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "with-bytes")]
+    #[cfg_attr(miri, ignore)] // bytes violates SB, see https://github.com/tokio-rs/bytes/issues/522
+    fn read_tokio_bytes_shares_allocation() {
+        let bytes = bytes::Bytes::from(decode_hex("03 61 62 63 02 64 65"));
+        let mut is = CodedInputStream::from_tokio_bytes(&bytes);
+        let abc = is.read_tokio_bytes().unwrap();
+        let de = is.read_tokio_bytes().unwrap();
+        assert!(is.eof().unwrap());
+        assert_eq!(b"abc", &abc[..]);
+        assert_eq!(b"de", &de[..]);
+        assert_eq!(bytes[1..].as_ptr(), abc.as_ptr());
+        assert_eq!(bytes[5..].as_ptr(), de.as_ptr());
+    }
+
+    #[test]
+    #[cfg(feature = "with-bytes")]
+    fn from_buf_chain() {
+        use ::bytes::Buf;
+
+        use crate::descriptor::DescriptorProto;
+        use crate::descriptor::FileDescriptorProto;
+
+        let mut message = FileDescriptorProto::new();
+        message.set_name("some/file.proto".to_owned());
+        let mut nested = DescriptorProto::new();
+        nested.set_name("Nested".to_owned());
+        message.message_type.push(nested);
+        message.weak_dependency.push(300_000);
+        let encoded = ::bytes::Bytes::from(message.write_to_bytes().unwrap());
+
+        // Split at every position, including inside varints and strings.
+        for split in 0..=encoded.len() {
+            let mut buf = encoded.slice(..split).chain(encoded.slice(split..));
+            {
+                let mut is = CodedInputStream::from_buf(&mut buf);
+                let parsed = FileDescriptorProto::parse_from(&mut is).unwrap();
+                assert_eq!(message, parsed, "split at {}", split);
+            }
+            assert!(!buf.has_remaining());
+        }
+    }
+
+    #[test]
+    fn read_delimited_messages() {
+        let messages: Vec<Duration> = (1..=3)
+            .map(|i| {
+                let mut d = Duration::new();
+                d.seconds = i;
+                d
+            })
+            .collect();
+
+        let mut vec = Vec::new();
+        let mut os = CodedOutputStream::new(&mut vec);
+        for m in &messages {
+            os.write_message_no_tag(m).unwrap();
+        }
+        os.flush().unwrap();
+        drop(os);
+
+        // Same framing as `Message::write_length_delimited_to_bytes`.
+        let mut expected = Vec::new();
+        for m in &messages {
+            expected.extend(m.write_length_delimited_to_bytes().unwrap());
+        }
+        assert_eq!(expected, vec);
+
+        let mut is = CodedInputStream::from_bytes(&vec);
+        let mut read = Vec::new();
+        while !is.eof().unwrap() {
+            read.push(is.read_message::<Duration>().unwrap());
+        }
+        assert_eq!(messages, read);
+    }
+
+    #[test]
+    fn read_delimited_message_eof() {
+        let mut is = CodedInputStream::from_bytes(&[]);
+        assert!(is.eof().unwrap());
+        assert!(is.read_message::<Duration>().is_err());
+
+        // Length prefix says 5 bytes, but only 2 bytes available.
+        let mut is = CodedInputStream::from_bytes(&[0x05, 0x08, 0x01]);
+        assert!(is.read_message::<Duration>().is_err());
+    }
+
+    fn parse_string_value(bytes: &[u8], validation: Utf8Validation) -> crate::Result<StringValue> {
+        let mut is = CodedInputStream::from_bytes(bytes);
+        assert_eq!(Utf8Validation::Strict, is.utf8_validation());
+        match validation {
+            // SAFETY: tests use `Utf8Validation::None` only with valid UTF-8.
+            Utf8Validation::None => unsafe { is.disable_utf8_validation() },
+            validation => is.set_utf8_validation(validation),
+        }
+        let mut message = StringValue::new();
+        message.merge_from(&mut is)?;
+        Ok(message)
+    }
+
+    // `value: "a\xffb"`
+    const INVALID_UTF8: &[u8] = &[0x0a, 0x03, b'a', 0xff, b'b'];
+
+    #[test]
+    fn utf8_validation_strict() {
+        assert_wire_error(
+            WireError::Utf8Error,
+            parse_string_value(INVALID_UTF8, Utf8Validation::Strict),
+        );
+    }
+
+    #[test]
+    fn utf8_validation_lenient() {
+        let message = parse_string_value(INVALID_UTF8, Utf8Validation::Lenient).unwrap();
+        assert_eq!("a\u{fffd}b", message.value);
+    }
+
+    #[test]
+    fn utf8_validation_none() {
+        let valid = [0x0a, 0x03, b'a', 0xc3, 0xa9];
+        let message = parse_string_value(&valid, Utf8Validation::None).unwrap();
+        assert_eq!("a\u{e9}", message.value);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_utf8_validation_none() {
+        CodedInputStream::from_bytes(&[]).set_utf8_validation(Utf8Validation::None);
+    }
+
+    #[test]
+    fn nested_limits() {
+        // Duration { seconds: 3 } wrapped in field 1 of an outer message,
+        // followed by field 2 of the outer message.
+        let bytes = [0x0a, 0x02, 0x08, 0x03, 0x10, 0x07];
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        assert_eq!(u64::MAX, is.bytes_until_limit());
+
+        let outer = is.push_limit(bytes.len() as u64).unwrap();
+        assert_eq!(6, is.bytes_until_limit());
+
         assert_eq!(
-            Tag::make(3, WireType::LengthDelimited),
-            input.read_tag().unwrap()
+            (1, WireType::LengthDelimited),
+            is.read_tag_unpack().unwrap()
+        );
+        let len = is.read_raw_varint64().unwrap();
+        let inner = is.push_limit(len).unwrap();
+        assert_eq!(2, is.bytes_until_limit());
+        // Inner limit must not exceed outer limit.
+        assert_wire_error(WireError::LimitIncrease, is.push_limit(3));
+
+        let mut seconds = 0;
+        while !is.eof().unwrap() {
+            match is.read_raw_tag_or_eof().unwrap() {
+                Some(0x08) => seconds = is.read_int64().unwrap(),
+                t => panic!("{:?}", t),
+            }
+        }
+        assert_eq!(3, seconds);
+        assert_eq!(0, is.bytes_until_limit());
+        is.pop_limit(inner);
+
+        assert_eq!(2, is.bytes_until_limit());
+        assert_eq!(Some(0x10), is.read_raw_tag_or_eof().unwrap());
+        assert_eq!(7, is.read_int32().unwrap());
+        assert!(is.eof().unwrap());
+        is.pop_limit(outer);
+        assert_eq!(u64::MAX, is.bytes_until_limit());
+    }
+
+    /// Check that packed field is decoded the same way as a sequence of scalars,
+    /// including when the packed field crosses buffer boundaries.
+    fn check_packed_like_scalar<V, P, S>(elements: &[u8], read_packed: P, read_scalar: S)
+    where
+        V: PartialEq + Debug,
+        P: Fn(&mut CodedInputStream, &mut Vec<V>) -> crate::Result<()>,
+        S: Fn(&mut CodedInputStream) -> crate::Result<V>,
+    {
+        let mut expected = Vec::new();
+        let mut is = CodedInputStream::from_bytes(elements);
+        while !is.eof().unwrap() {
+            expected.push(read_scalar(&mut is).unwrap());
+        }
+
+        fn packed_hex(elements: &[u8]) -> String {
+            let mut packed = Vec::new();
+            let mut os = CodedOutputStream::vec(&mut packed);
+            os.write_bytes_no_tag(elements).unwrap();
+            os.flush().unwrap();
+            drop(os);
+            encode_hex(&packed)
+        }
+
+        test_read_partial_byte_by_byte(&packed_hex(elements), |is| {
+            let mut values = Vec::new();
+            read_packed(is, &mut values).unwrap();
+            assert_eq!(expected, values);
+            assert!(is.eof().unwrap());
+        });
+
+        // Last element is truncated.
+        let truncated = packed_hex(&elements[..elements.len() - 1]);
+        test_read_partial_byte_by_byte(&truncated, |is| {
+            assert!(read_packed(is, &mut Vec::new()).is_err());
+        });
+    }
+
+    #[test]
+    fn read_repeated_packed_varint() {
+        let mut elements = Vec::new();
+        let mut os = CodedOutputStream::vec(&mut elements);
+        for i in -300..300 {
+            os.write_int64_no_tag(i * 1_000_003).unwrap();
+        }
+        os.flush().unwrap();
+        drop(os);
+
+        check_packed_like_scalar(
+            &elements,
+            |is, v| is.read_repeated_packed_int32_into(v),
+            |is| is.read_int32(),
+        );
+        check_packed_like_scalar(
+            &elements,
+            |is, v| is.read_repeated_packed_int64_into(v),
+            |is| is.read_int64(),
+        );
+        check_packed_like_scalar(
+            &elements,
+            |is, v| is.read_repeated_packed_sint64_into(v),
+            |is| is.read_sint64(),
+        );
+    }
+
+    #[test]
+    fn read_repeated_packed_fixed() {
+        let elements: Vec<u8> = (0..1000).map(|i| (i * 7 % 256) as u8).collect();
+
+        check_packed_like_scalar(
+            &elements,
+            |is, v| is.read_repeated_packed_fixed32_into(v),
+            |is| is.read_fixed32(),
+        );
+        check_packed_like_scalar(
+            &elements,
+            |is, v| is.read_repeated_packed_fixed64_into(v),
+            |is| is.read_fixed64(),
+        );
+        check_packed_like_scalar(
+            &elements,
+            |is, v| is.read_repeated_packed_sfixed32_into(v),
+            |is| is.read_sfixed32(),
+        );
+        check_packed_like_scalar(
+            &elements,
+            |is, v| is.read_repeated_packed_sfixed64_into(v),
+            |is| is.read_sfixed64(),
+        );
+
+        let mut elements = Vec::new();
+        let mut os = CodedOutputStream::vec(&mut elements);
+        for i in 0..100 {
+            os.write_double_no_tag(i as f64 * -0.3).unwrap();
+        }
+        os.flush().unwrap();
+        drop(os);
+
+        check_packed_like_scalar(
+            &elements,
+            |is, v| is.read_repeated_packed_double_into(v),
+            |is| is.read_double(),
+        );
+
+        let mut elements = Vec::new();
+        let mut os = CodedOutputStream::vec(&mut elements);
+        for i in 0..100 {
+            os.write_float_no_tag(i as f32 * -0.3).unwrap();
+        }
+        os.flush().unwrap();
+        drop(os);
+
+        check_packed_like_scalar(
+            &elements,
+            |is, v| is.read_repeated_packed_float_into(v),
+            |is| is.read_float(),
         );
-        assert_eq!("field 3", input.read_string().unwrap());
     }
 }
@@ -20,12 +20,10 @@ use crate::varint::encode::encode_varint32;
 use crate::varint::encode::encode_varint64;
 use crate::varint::encode::encoded_varint64_len;
 use crate::varint::MAX_VARINT_ENCODED_LEN;
-use crate::wire_format;
+use crate::wire::WireWrite;
 use crate::wire_format::check_message_size;
 use crate::wire_format::WireType;
 use crate::wire_format::MAX_MESSAGE_SIZE;
-use crate::zigzag::encode_zig_zag_32;
-use crate::zigzag::encode_zig_zag_64;
 use crate::Enum;
 use crate::EnumOrUnknown;
 use crate::Message;
@@ -45,6 +43,8 @@ pub struct CodedOutputStream<'a> {
     buffer: OutputBuffer,
     /// Absolute position of the buffer start.
     pos_of_buffer_start: u64,
+    deterministic: bool,
+    flush_on_drop: bool,
 }
 
 impl<'a> CodedOutputStream<'a> {
@@ -65,6 +65,8 @@ impl<'a> CodedOutputStream<'a> {
             target: OutputTarget::Write(writer, buffer_storage),
             buffer,
             pos_of_buffer_start: 0,
+            deterministic: false,
+            flush_on_drop: true,
         }
     }
 
@@ -80,6 +82,8 @@ impl<'a> CodedOutputStream<'a> {
             target: OutputTarget::Bytes,
             buffer,
             pos_of_buffer_start: 0,
+            deterministic: false,
+            flush_on_drop: true,
         }
     }
 
@@ -90,6 +94,8 @@ impl<'a> CodedOutputStream<'a> {
             target: OutputTarget::Vec(vec),
             buffer,
             pos_of_buffer_start: 0,
+            deterministic: false,
+            flush_on_drop: true,
         }
     }
 
@@ -134,6 +140,36 @@ impl<'a> CodedOutputStream<'a> {
         self.reserve_additional(reserve, message)
     }
 
+    /// Write map entries sorted by key.
+    ///
+    /// By default map entries are written in hash map iteration order,
+    /// so the same message may be serialized differently each time.
+    /// With this option serialization is deterministic within
+    /// a given version of rust-protobuf, but it is not canonical:
+    /// other implementations may produce different bytes from the same message.
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+    }
+
+    /// Whether map entries are written sorted by key.
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Flush the buffer when the stream is dropped (default).
+    ///
+    /// Flush error in destructor results in panic,
+    /// use [`finish`](Self::finish) to handle it.
+    /// When disabled, bytes which were not explicitly flushed are discarded.
+    pub fn set_flush_on_drop(&mut self, flush_on_drop: bool) {
+        self.flush_on_drop = flush_on_drop;
+    }
+
+    /// Whether the buffer is flushed when the stream is dropped.
+    pub fn is_flush_on_drop(&self) -> bool {
+        self.flush_on_drop
+    }
+
     /// Total number of bytes written to this stream.
     ///
     /// This number may be larger than the actual number of bytes written to the underlying stream,
@@ -190,10 +226,11 @@ impl<'a> CodedOutputStream<'a> {
     }
 
     /// Flush to buffer to the underlying buffer.
-    /// Note that `CodedOutputStream` does `flush` in the destructor,
+    /// Note that `CodedOutputStream` does `flush` in the destructor
+    /// (unless disabled with [`set_flush_on_drop`](Self::set_flush_on_drop)),
     /// however, if `flush` in destructor fails, then destructor panics
     /// and program terminates. So it's advisable to explicitly call flush
-    /// before destructor.
+    /// or [`finish`](Self::finish) before destructor.
     pub fn flush(&mut self) -> crate::Result<()> {
         match &mut self.target {
             OutputTarget::Bytes => Ok(()),
@@ -211,6 +248,16 @@ impl<'a> CodedOutputStream<'a> {
         }
     }
 
+    /// Flush the buffer and consume the stream.
+    ///
+    /// Unlike flush in destructor, flush error is returned, not panicked.
+    pub fn finish(mut self) -> io::Result<()> {
+        // Do not retry failed flush in destructor.
+        self.flush_on_drop = false;
+        self.flush()?;
+        Ok(())
+    }
+
     /// Write a byte
     pub fn write_raw_byte(&mut self, byte: u8) -> crate::Result<()> {
         if self.buffer.unfilled_len() == 0 {
@@ -256,8 +303,9 @@ impl<'a> CodedOutputStream<'a> {
     }
 
     /// Write a tag
+    #[inline]
     pub fn write_tag(&mut self, field_number: u32, wire_type: WireType) -> crate::Result<()> {
-        self.write_raw_varint32(wire_format::Tag::make(field_number, wire_type).value())
+        WireWrite::write_tag(self, field_number, wire_type)
     }
 
     /// Write varint
@@ -299,92 +347,105 @@ impl<'a> CodedOutputStream<'a> {
     }
 
     /// Write 32-bit integer little endian
+    #[inline]
     pub fn write_raw_little_endian32(&mut self, value: u32) -> crate::Result<()> {
-        self.write_raw_bytes(&value.to_le_bytes())
+        WireWrite::write_raw_little_endian32(self, value)
     }
 
     /// Write 64-bit integer little endian
+    #[inline]
     pub fn write_raw_little_endian64(&mut self, value: u64) -> crate::Result<()> {
-        self.write_raw_bytes(&value.to_le_bytes())
+        WireWrite::write_raw_little_endian64(self, value)
     }
 
     /// Write `float`
+    #[inline]
     pub fn write_float_no_tag(&mut self, value: f32) -> crate::Result<()> {
-        self.write_raw_little_endian32(value.to_bits())
+        WireWrite::write_float_no_tag(self, value)
     }
 
     /// Write `double`
+    #[inline]
     pub fn write_double_no_tag(&mut self, value: f64) -> crate::Result<()> {
-        self.write_raw_little_endian64(value.to_bits())
+        WireWrite::write_double_no_tag(self, value)
     }
 
     /// Write `float` field
+    #[inline]
     pub fn write_float(&mut self, field_number: u32, value: f32) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Fixed32)?;
-        self.write_float_no_tag(value)?;
-        Ok(())
+        WireWrite::write_float(self, field_number, value)
     }
 
     /// Write `double` field
+    #[inline]
     pub fn write_double(&mut self, field_number: u32, value: f64) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Fixed64)?;
-        self.write_double_no_tag(value)?;
-        Ok(())
+        WireWrite::write_double(self, field_number, value)
     }
 
     /// Write varint
+    #[inline]
     pub fn write_uint64_no_tag(&mut self, value: u64) -> crate::Result<()> {
-        self.write_raw_varint64(value)
+        WireWrite::write_uint64_no_tag(self, value)
     }
 
     /// Write varint
+    #[inline]
     pub fn write_uint32_no_tag(&mut self, value: u32) -> crate::Result<()> {
-        self.write_raw_varint32(value)
+        WireWrite::write_uint32_no_tag(self, value)
     }
 
     /// Write varint
+    #[inline]
     pub fn write_int64_no_tag(&mut self, value: i64) -> crate::Result<()> {
-        self.write_raw_varint64(value as u64)
+        WireWrite::write_int64_no_tag(self, value)
     }
 
     /// Write varint
+    #[inline]
     pub fn write_int32_no_tag(&mut self, value: i32) -> crate::Result<()> {
-        self.write_raw_varint64(value as u64)
+        WireWrite::write_int32_no_tag(self, value)
     }
 
     /// Write zigzag varint
+    #[inline]
     pub fn write_sint64_no_tag(&mut self, value: i64) -> crate::Result<()> {
-        self.write_uint64_no_tag(encode_zig_zag_64(value))
+        WireWrite::write_sint64_no_tag(self, value)
     }
 
     /// Write zigzag varint
+    #[inline]
     pub fn write_sint32_no_tag(&mut self, value: i32) -> crate::Result<()> {
-        self.write_uint32_no_tag(encode_zig_zag_32(value))
+        WireWrite::write_sint32_no_tag(self, value)
     }
 
     /// Write `fixed64`
+    #[inline]
     pub fn write_fixed64_no_tag(&mut self, value: u64) -> crate::Result<()> {
-        self.write_raw_little_endian64(value)
+        WireWrite::write_fixed64_no_tag(self, value)
     }
 
     /// Write `fixed32`
+    #[inline]
     pub fn write_fixed32_no_tag(&mut self, value: u32) -> crate::Result<()> {
-        self.write_raw_little_endian32(value)
+        WireWrite::write_fixed32_no_tag(self, value)
     }
 
     /// Write `sfixed64`
+    #[inline]
     pub fn write_sfixed64_no_tag(&mut self, value: i64) -> crate::Result<()> {
-        self.write_raw_little_endian64(value as u64)
+        WireWrite::write_sfixed64_no_tag(self, value)
     }
 
     /// Write `sfixed32`
+    #[inline]
     pub fn write_sfixed32_no_tag(&mut self, value: i32) -> crate::Result<()> {
-        self.write_raw_little_endian32(value as u32)
+        WireWrite::write_sfixed32_no_tag(self, value)
     }
 
     /// Write `bool`
+    #[inline]
     pub fn write_bool_no_tag(&mut self, value: bool) -> crate::Result<()> {
-        self.write_raw_varint32(if value { 1 } else { 0 })
+        WireWrite::write_bool_no_tag(self, value)
     }
 
     /// Write `enum`
@@ -409,90 +470,83 @@ impl<'a> CodedOutputStream<'a> {
     }
 
     /// Write unknown value
+    ///
+    /// For groups only encoded group fields are written,
+    /// end group tag must be written by the caller.
     pub fn write_unknown_no_tag(&mut self, unknown: UnknownValueRef) -> crate::Result<()> {
         match unknown {
             UnknownValueRef::Fixed64(fixed64) => self.write_raw_little_endian64(fixed64),
             UnknownValueRef::Fixed32(fixed32) => self.write_raw_little_endian32(fixed32),
             UnknownValueRef::Varint(varint) => self.write_raw_varint64(varint),
             UnknownValueRef::LengthDelimited(bytes) => self.write_bytes_no_tag(bytes),
+            UnknownValueRef::Group(bytes) => self.write_raw_bytes(bytes),
         }
     }
 
     /// Write `uint64` field
+    #[inline]
     pub fn write_uint64(&mut self, field_number: u32, value: u64) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Varint)?;
-        self.write_uint64_no_tag(value)?;
-        Ok(())
+        WireWrite::write_uint64(self, field_number, value)
     }
 
     /// Write `uint32` field
+    #[inline]
     pub fn write_uint32(&mut self, field_number: u32, value: u32) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Varint)?;
-        self.write_uint32_no_tag(value)?;
-        Ok(())
+        WireWrite::write_uint32(self, field_number, value)
     }
 
     /// Write `int64` field
+    #[inline]
     pub fn write_int64(&mut self, field_number: u32, value: i64) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Varint)?;
-        self.write_int64_no_tag(value)?;
-        Ok(())
+        WireWrite::write_int64(self, field_number, value)
     }
 
     /// Write `int32` field
+    #[inline]
     pub fn write_int32(&mut self, field_number: u32, value: i32) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Varint)?;
-        self.write_int32_no_tag(value)?;
-        Ok(())
+        WireWrite::write_int32(self, field_number, value)
     }
 
     /// Write `sint64` field
+    #[inline]
     pub fn write_sint64(&mut self, field_number: u32, value: i64) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Varint)?;
-        self.write_sint64_no_tag(value)?;
-        Ok(())
+        WireWrite::write_sint64(self, field_number, value)
     }
 
     /// Write `sint32` field
+    #[inline]
     pub fn write_sint32(&mut self, field_number: u32, value: i32) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Varint)?;
-        self.write_sint32_no_tag(value)?;
-        Ok(())
+        WireWrite::write_sint32(self, field_number, value)
     }
 
     /// Write `fixed64` field
+    #[inline]
     pub fn write_fixed64(&mut self, field_number: u32, value: u64) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Fixed64)?;
-        self.write_fixed64_no_tag(value)?;
-        Ok(())
+        WireWrite::write_fixed64(self, field_number, value)
     }
 
     /// Write `fixed32` field
+    #[inline]
     pub fn write_fixed32(&mut self, field_number: u32, value: u32) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Fixed32)?;
-        self.write_fixed32_no_tag(value)?;
-        Ok(())
+        WireWrite::write_fixed32(self, field_number, value)
     }
 
     /// Write `sfixed64` field
+    #[inline]
     pub fn write_sfixed64(&mut self, field_number: u32, value: i64) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Fixed64)?;
-        self.write_sfixed64_no_tag(value)?;
-        Ok(())
+        WireWrite::write_sfixed64(self, field_number, value)
     }
 
     /// Write `sfixed32` field
+    #[inline]
     pub fn write_sfixed32(&mut self, field_number: u32, value: i32) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Fixed32)?;
-        self.write_sfixed32_no_tag(value)?;
-        Ok(())
+        WireWrite::write_sfixed32(self, field_number, value)
     }
 
     /// Write `bool` field
+    #[inline]
     pub fn write_bool(&mut self, field_number: u32, value: bool) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::Varint)?;
-        self.write_bool_no_tag(value)?;
-        Ok(())
+        WireWrite::write_bool(self, field_number, value)
     }
 
     /// Write `enum` field
@@ -528,8 +582,12 @@ impl<'a> CodedOutputStream<'a> {
         field_number: u32,
         value: UnknownValueRef,
     ) -> crate::Result<()> {
+        let is_group = matches!(value, UnknownValueRef::Group(..));
         self.write_tag(field_number, value.wire_type())?;
         self.write_unknown_no_tag(value)?;
+        if is_group {
+            self.write_tag(field_number, WireType::EndGroup)?;
+        }
         Ok(())
     }
 
@@ -556,18 +614,22 @@ impl<'a> CodedOutputStream<'a> {
     }
 
     /// Write bytes
+    #[inline]
     pub fn write_bytes_no_tag(&mut self, bytes: &[u8]) -> crate::Result<()> {
-        self.write_raw_varint32(bytes.len() as u32)?;
-        self.write_raw_bytes(bytes)?;
-        Ok(())
+        WireWrite::write_bytes_no_tag(self, bytes)
     }
 
     /// Write string
+    #[inline]
     pub fn write_string_no_tag(&mut self, s: &str) -> crate::Result<()> {
-        self.write_bytes_no_tag(s.as_bytes())
+        WireWrite::write_string_no_tag(self, s)
     }
 
-    /// Write message
+    /// Write message prefixed with its length encoded as varint.
+    ///
+    /// This is compatible with Java `writeDelimitedTo`,
+    /// use [`CodedInputStream::read_message`](crate::CodedInputStream::read_message)
+    /// to read the message back.
     pub fn write_message_no_tag<M: Message>(&mut self, msg: &M) -> crate::Result<()> {
         msg.write_length_delimited_to(self)
     }
@@ -582,17 +644,15 @@ impl<'a> CodedOutputStream<'a> {
     }
 
     /// Write `bytes` field
+    #[inline]
     pub fn write_bytes(&mut self, field_number: u32, bytes: &[u8]) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::LengthDelimited)?;
-        self.write_bytes_no_tag(bytes)?;
-        Ok(())
+        WireWrite::write_bytes(self, field_number, bytes)
     }
 
     /// Write `string` field
+    #[inline]
     pub fn write_string(&mut self, field_number: u32, s: &str) -> crate::Result<()> {
-        self.write_tag(field_number, WireType::LengthDelimited)?;
-        self.write_string_no_tag(s)?;
-        Ok(())
+        WireWrite::write_string(self, field_number, s)
     }
 
     /// Write repeated packed float values.
@@ -997,6 +1057,25 @@ impl<'a> CodedOutputStream<'a> {
     }
 }
 
+impl<'a> WireWrite for CodedOutputStream<'a> {
+    type Error = crate::Error;
+
+    #[inline]
+    fn write_raw_bytes(&mut self, bytes: &[u8]) -> crate::Result<()> {
+        CodedOutputStream::write_raw_bytes(self, bytes)
+    }
+
+    #[inline]
+    fn write_raw_varint32(&mut self, value: u32) -> crate::Result<()> {
+        CodedOutputStream::write_raw_varint32(self, value)
+    }
+
+    #[inline]
+    fn write_raw_varint64(&mut self, value: u64) -> crate::Result<()> {
+        CodedOutputStream::write_raw_varint64(self, value)
+    }
+}
+
 impl<'a> Write for CodedOutputStream<'a> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.write_raw_bytes(buf)?;
@@ -1010,8 +1089,10 @@ impl<'a> Write for CodedOutputStream<'a> {
 
 impl<'a> Drop for CodedOutputStream<'a> {
     fn drop(&mut self) {
-        // This may panic
-        CodedOutputStream::flush(self).expect("failed to flush");
+        if self.flush_on_drop {
+            // This may panic
+            CodedOutputStream::flush(self).expect("failed to flush");
+        }
     }
 }
 
@@ -1202,6 +1283,58 @@ mod test {
         assert_eq!(10, v.capacity());
     }
 
+    #[test]
+    fn flush_on_drop() {
+        let mut v = Vec::new();
+        {
+            let mut os = CodedOutputStream::new(&mut v as &mut dyn Write);
+            os.write_raw_bytes(&[1, 2, 3]).unwrap();
+        }
+        assert_eq!(&[1u8, 2, 3][..], &v[..]);
+
+        let mut v = Vec::new();
+        {
+            let mut os = CodedOutputStream::vec(&mut v);
+            os.write_raw_bytes(&[4, 5]).unwrap();
+        }
+        assert_eq!(&[4u8, 5][..], &v[..]);
+
+        let mut v = Vec::new();
+        {
+            let mut os = CodedOutputStream::new(&mut v as &mut dyn Write);
+            os.set_flush_on_drop(false);
+            os.write_raw_bytes(&[6]).unwrap();
+        }
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn finish() {
+        let mut v = Vec::new();
+        let mut os = CodedOutputStream::new(&mut v as &mut dyn Write);
+        os.set_flush_on_drop(false);
+        os.write_raw_bytes(&[1, 2, 3]).unwrap();
+        os.finish().unwrap();
+        assert_eq!(&[1u8, 2, 3][..], &v[..]);
+
+        struct FailingWrite;
+
+        impl Write for FailingWrite {
+            fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+                Err(io::Error::other("failing write"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut w = FailingWrite;
+        let mut os = CodedOutputStream::new(&mut w);
+        os.write_raw_byte(1).unwrap();
+        assert!(os.finish().is_err());
+    }
+
     #[test]
     fn total_bytes_written_to_bytes() {
         let mut buf = vec![0; 10];
@@ -73,11 +73,20 @@ impl crate::Message for FileDescriptorSet {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.file.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("file[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
                 10 => {
-                    self.file.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.file)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -423,6 +432,40 @@ impl crate::Message for FileDescriptorProto {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.message_type.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("message_type[{}].{}", i, path));
+            }
+        };
+        for (i, v) in self.enum_type.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("enum_type[{}].{}", i, path));
+            }
+        };
+        for (i, v) in self.service.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("service[{}].{}", i, path));
+            }
+        };
+        for (i, v) in self.extension.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("extension[{}].{}", i, path));
+            }
+        };
+        for v in &self.options {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("options.{}", path));
+            }
+        };
+        for v in &self.source_code_info {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("source_code_info.{}", path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -448,16 +491,16 @@ impl crate::Message for FileDescriptorProto {
                     self.weak_dependency.push(is.read_int32()?);
                 },
                 34 => {
-                    self.message_type.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.message_type)?;
                 },
                 42 => {
-                    self.enum_type.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.enum_type)?;
                 },
                 50 => {
-                    self.service.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.service)?;
                 },
                 58 => {
-                    self.extension.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.extension)?;
                 },
                 66 => {
                     crate::rt::read_singular_message_into_field(is, &mut self.options)?;
@@ -820,6 +863,50 @@ impl crate::Message for DescriptorProto {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.field.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("field[{}].{}", i, path));
+            }
+        };
+        for (i, v) in self.extension.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("extension[{}].{}", i, path));
+            }
+        };
+        for (i, v) in self.nested_type.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("nested_type[{}].{}", i, path));
+            }
+        };
+        for (i, v) in self.enum_type.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("enum_type[{}].{}", i, path));
+            }
+        };
+        for (i, v) in self.extension_range.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("extension_range[{}].{}", i, path));
+            }
+        };
+        for (i, v) in self.oneof_decl.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("oneof_decl[{}].{}", i, path));
+            }
+        };
+        for v in &self.options {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("options.{}", path));
+            }
+        };
+        for (i, v) in self.reserved_range.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("reserved_range[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -827,28 +914,28 @@ impl crate::Message for DescriptorProto {
                     self.name = ::std::option::Option::Some(is.read_string()?);
                 },
                 18 => {
-                    self.field.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.field)?;
                 },
                 50 => {
-                    self.extension.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.extension)?;
                 },
                 26 => {
-                    self.nested_type.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.nested_type)?;
                 },
                 34 => {
-                    self.enum_type.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.enum_type)?;
                 },
                 42 => {
-                    self.extension_range.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.extension_range)?;
                 },
                 66 => {
-                    self.oneof_decl.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.oneof_decl)?;
                 },
                 58 => {
                     crate::rt::read_singular_message_into_field(is, &mut self.options)?;
                 },
                 74 => {
-                    self.reserved_range.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.reserved_range)?;
                 },
                 82 => {
                     self.reserved_name.push(is.read_string()?);
@@ -1108,6 +1195,15 @@ pub mod descriptor_proto {
             true
         }
 
+        fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+            for v in &self.options {
+                if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                    return ::std::option::Option::Some(format!("options.{}", path));
+                }
+            };
+            ::std::option::Option::None
+        }
+
         fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
             while let Some(tag) = is.read_raw_tag_or_eof()? {
                 match tag {
@@ -1441,11 +1537,20 @@ impl crate::Message for ExtensionRangeOptions {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.uninterpreted_option.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("uninterpreted_option[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
                 7994 => {
-                    self.uninterpreted_option.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.uninterpreted_option)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -1962,6 +2067,15 @@ impl crate::Message for FieldDescriptorProto {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for v in &self.options {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("options.{}", path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -2155,14 +2269,20 @@ pub mod field_descriptor_proto {
     #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
     // @@protoc_insertion_point(enum:google.protobuf.FieldDescriptorProto.Type)
     pub enum Type {
+        ///  0 is reserved for errors.
+        ///  Order is weird for historical reasons.
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Type.TYPE_DOUBLE)
         TYPE_DOUBLE = 1,
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Type.TYPE_FLOAT)
         TYPE_FLOAT = 2,
+        ///  Not ZigZag encoded.  Negative numbers take 10 bytes.  Use TYPE_SINT64 if
+        ///  negative values are likely.
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Type.TYPE_INT64)
         TYPE_INT64 = 3,
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Type.TYPE_UINT64)
         TYPE_UINT64 = 4,
+        ///  Not ZigZag encoded.  Negative numbers take 10 bytes.  Use TYPE_SINT32 if
+        ///  negative values are likely.
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Type.TYPE_INT32)
         TYPE_INT32 = 5,
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Type.TYPE_FIXED64)
@@ -2173,10 +2293,15 @@ pub mod field_descriptor_proto {
         TYPE_BOOL = 8,
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Type.TYPE_STRING)
         TYPE_STRING = 9,
+        ///  Tag-delimited aggregate.
+        ///  Group type is deprecated and not supported in proto3. However, Proto3
+        ///  implementations should still be able to parse the group wire format and
+        ///  treat group fields as unknown fields.
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Type.TYPE_GROUP)
         TYPE_GROUP = 10,
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Type.TYPE_MESSAGE)
         TYPE_MESSAGE = 11,
+        ///  New in version 2.
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Type.TYPE_BYTES)
         TYPE_BYTES = 12,
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Type.TYPE_UINT32)
@@ -2200,6 +2325,29 @@ pub mod field_descriptor_proto {
             *self as i32
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Type::TYPE_DOUBLE => "TYPE_DOUBLE",
+                Type::TYPE_FLOAT => "TYPE_FLOAT",
+                Type::TYPE_INT64 => "TYPE_INT64",
+                Type::TYPE_UINT64 => "TYPE_UINT64",
+                Type::TYPE_INT32 => "TYPE_INT32",
+                Type::TYPE_FIXED64 => "TYPE_FIXED64",
+                Type::TYPE_FIXED32 => "TYPE_FIXED32",
+                Type::TYPE_BOOL => "TYPE_BOOL",
+                Type::TYPE_STRING => "TYPE_STRING",
+                Type::TYPE_GROUP => "TYPE_GROUP",
+                Type::TYPE_MESSAGE => "TYPE_MESSAGE",
+                Type::TYPE_BYTES => "TYPE_BYTES",
+                Type::TYPE_UINT32 => "TYPE_UINT32",
+                Type::TYPE_ENUM => "TYPE_ENUM",
+                Type::TYPE_SFIXED32 => "TYPE_SFIXED32",
+                Type::TYPE_SFIXED64 => "TYPE_SFIXED64",
+                Type::TYPE_SINT32 => "TYPE_SINT32",
+                Type::TYPE_SINT64 => "TYPE_SINT64",
+            }
+        }
+
         fn from_i32(value: i32) -> ::std::option::Option<Type> {
             match value {
                 1 => ::std::option::Option::Some(Type::TYPE_DOUBLE),
@@ -2270,6 +2418,14 @@ pub mod field_descriptor_proto {
         ];
     }
 
+    impl ::std::convert::TryFrom<i32> for Type {
+        type Error = crate::UnknownEnumValue;
+
+        fn try_from(value: i32) -> ::std::result::Result<Self, Self::Error> {
+            <Self as crate::Enum>::from_i32(value).ok_or(crate::UnknownEnumValue(value))
+        }
+    }
+
     impl crate::EnumFull for Type {
         fn enum_descriptor() -> crate::reflect::EnumDescriptor {
             static descriptor: crate::rt::Lazy<crate::reflect::EnumDescriptor> = crate::rt::Lazy::new();
@@ -2317,6 +2473,7 @@ pub mod field_descriptor_proto {
     #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
     // @@protoc_insertion_point(enum:google.protobuf.FieldDescriptorProto.Label)
     pub enum Label {
+        ///  0 is reserved for errors
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Label.LABEL_OPTIONAL)
         LABEL_OPTIONAL = 1,
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldDescriptorProto.Label.LABEL_REQUIRED)
@@ -2332,6 +2489,14 @@ pub mod field_descriptor_proto {
             *self as i32
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Label::LABEL_OPTIONAL => "LABEL_OPTIONAL",
+                Label::LABEL_REQUIRED => "LABEL_REQUIRED",
+                Label::LABEL_REPEATED => "LABEL_REPEATED",
+            }
+        }
+
         fn from_i32(value: i32) -> ::std::option::Option<Label> {
             match value {
                 1 => ::std::option::Option::Some(Label::LABEL_OPTIONAL),
@@ -2357,6 +2522,14 @@ pub mod field_descriptor_proto {
         ];
     }
 
+    impl ::std::convert::TryFrom<i32> for Label {
+        type Error = crate::UnknownEnumValue;
+
+        fn try_from(value: i32) -> ::std::result::Result<Self, Self::Error> {
+            <Self as crate::Enum>::from_i32(value).ok_or(crate::UnknownEnumValue(value))
+        }
+    }
+
     impl crate::EnumFull for Label {
         fn enum_descriptor() -> crate::reflect::EnumDescriptor {
             static descriptor: crate::rt::Lazy<crate::reflect::EnumDescriptor> = crate::rt::Lazy::new();
@@ -2481,6 +2654,15 @@ impl crate::Message for OneofDescriptorProto {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for v in &self.options {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("options.{}", path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -2700,6 +2882,25 @@ impl crate::Message for EnumDescriptorProto {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.value.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("value[{}].{}", i, path));
+            }
+        };
+        for v in &self.options {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("options.{}", path));
+            }
+        };
+        for (i, v) in self.reserved_range.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("reserved_range[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -2707,13 +2908,13 @@ impl crate::Message for EnumDescriptorProto {
                     self.name = ::std::option::Option::Some(is.read_string()?);
                 },
                 18 => {
-                    self.value.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.value)?;
                 },
                 26 => {
                     crate::rt::read_singular_message_into_field(is, &mut self.options)?;
                 },
                 34 => {
-                    self.reserved_range.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.reserved_range)?;
                 },
                 42 => {
                     self.reserved_name.push(is.read_string()?);
@@ -3131,6 +3332,15 @@ impl crate::Message for EnumValueDescriptorProto {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for v in &self.options {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("options.{}", path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -3337,6 +3547,20 @@ impl crate::Message for ServiceDescriptorProto {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.method.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("method[{}].{}", i, path));
+            }
+        };
+        for v in &self.options {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("options.{}", path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -3344,7 +3568,7 @@ impl crate::Message for ServiceDescriptorProto {
                     self.name = ::std::option::Option::Some(is.read_string()?);
                 },
                 18 => {
-                    self.method.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.method)?;
                 },
                 26 => {
                     crate::rt::read_singular_message_into_field(is, &mut self.options)?;
@@ -3674,6 +3898,15 @@ impl crate::Message for MethodDescriptorProto {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for v in &self.options {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("options.{}", path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -4613,6 +4846,15 @@ impl crate::Message for FileOptions {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.uninterpreted_option.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("uninterpreted_option[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -4677,7 +4919,7 @@ impl crate::Message for FileOptions {
                     self.ruby_package = ::std::option::Option::Some(is.read_string()?);
                 },
                 7994 => {
-                    self.uninterpreted_option.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.uninterpreted_option)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -4919,6 +5161,7 @@ pub mod file_options {
     pub enum OptimizeMode {
         // @@protoc_insertion_point(enum_value:google.protobuf.FileOptions.OptimizeMode.SPEED)
         SPEED = 1,
+        ///  etc.
         // @@protoc_insertion_point(enum_value:google.protobuf.FileOptions.OptimizeMode.CODE_SIZE)
         CODE_SIZE = 2,
         // @@protoc_insertion_point(enum_value:google.protobuf.FileOptions.OptimizeMode.LITE_RUNTIME)
@@ -4932,6 +5175,14 @@ pub mod file_options {
             *self as i32
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                OptimizeMode::SPEED => "SPEED",
+                OptimizeMode::CODE_SIZE => "CODE_SIZE",
+                OptimizeMode::LITE_RUNTIME => "LITE_RUNTIME",
+            }
+        }
+
         fn from_i32(value: i32) -> ::std::option::Option<OptimizeMode> {
             match value {
                 1 => ::std::option::Option::Some(OptimizeMode::SPEED),
@@ -4957,6 +5208,14 @@ pub mod file_options {
         ];
     }
 
+    impl ::std::convert::TryFrom<i32> for OptimizeMode {
+        type Error = crate::UnknownEnumValue;
+
+        fn try_from(value: i32) -> ::std::result::Result<Self, Self::Error> {
+            <Self as crate::Enum>::from_i32(value).ok_or(crate::UnknownEnumValue(value))
+        }
+    }
+
     impl crate::EnumFull for OptimizeMode {
         fn enum_descriptor() -> crate::reflect::EnumDescriptor {
             static descriptor: crate::rt::Lazy<crate::reflect::EnumDescriptor> = crate::rt::Lazy::new();
@@ -5149,6 +5408,15 @@ impl crate::Message for MessageOptions {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.uninterpreted_option.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("uninterpreted_option[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -5165,7 +5433,7 @@ impl crate::Message for MessageOptions {
                     self.map_entry = ::std::option::Option::Some(is.read_bool()?);
                 },
                 7994 => {
-                    self.uninterpreted_option.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.uninterpreted_option)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -5537,6 +5805,15 @@ impl crate::Message for FieldOptions {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.uninterpreted_option.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("uninterpreted_option[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -5559,7 +5836,7 @@ impl crate::Message for FieldOptions {
                     self.weak = ::std::option::Option::Some(is.read_bool()?);
                 },
                 7994 => {
-                    self.uninterpreted_option.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.uninterpreted_option)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -5686,6 +5963,7 @@ pub mod field_options {
     #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
     // @@protoc_insertion_point(enum:google.protobuf.FieldOptions.CType)
     pub enum CType {
+        ///  Default mode.
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldOptions.CType.STRING)
         STRING = 0,
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldOptions.CType.CORD)
@@ -5701,6 +5979,14 @@ pub mod field_options {
             *self as i32
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                CType::STRING => "STRING",
+                CType::CORD => "CORD",
+                CType::STRING_PIECE => "STRING_PIECE",
+            }
+        }
+
         fn from_i32(value: i32) -> ::std::option::Option<CType> {
             match value {
                 0 => ::std::option::Option::Some(CType::STRING),
@@ -5726,6 +6012,14 @@ pub mod field_options {
         ];
     }
 
+    impl ::std::convert::TryFrom<i32> for CType {
+        type Error = crate::UnknownEnumValue;
+
+        fn try_from(value: i32) -> ::std::result::Result<Self, Self::Error> {
+            <Self as crate::Enum>::from_i32(value).ok_or(crate::UnknownEnumValue(value))
+        }
+    }
+
     impl crate::EnumFull for CType {
         fn enum_descriptor() -> crate::reflect::EnumDescriptor {
             static descriptor: crate::rt::Lazy<crate::reflect::EnumDescriptor> = crate::rt::Lazy::new();
@@ -5753,10 +6047,13 @@ pub mod field_options {
     #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
     // @@protoc_insertion_point(enum:google.protobuf.FieldOptions.JSType)
     pub enum JSType {
+        ///  Use the default type.
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldOptions.JSType.JS_NORMAL)
         JS_NORMAL = 0,
+        ///  Use JavaScript strings.
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldOptions.JSType.JS_STRING)
         JS_STRING = 1,
+        ///  Use JavaScript numbers.
         // @@protoc_insertion_point(enum_value:google.protobuf.FieldOptions.JSType.JS_NUMBER)
         JS_NUMBER = 2,
     }
@@ -5768,6 +6065,14 @@ pub mod field_options {
             *self as i32
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                JSType::JS_NORMAL => "JS_NORMAL",
+                JSType::JS_STRING => "JS_STRING",
+                JSType::JS_NUMBER => "JS_NUMBER",
+            }
+        }
+
         fn from_i32(value: i32) -> ::std::option::Option<JSType> {
             match value {
                 0 => ::std::option::Option::Some(JSType::JS_NORMAL),
@@ -5793,6 +6098,14 @@ pub mod field_options {
         ];
     }
 
+    impl ::std::convert::TryFrom<i32> for JSType {
+        type Error = crate::UnknownEnumValue;
+
+        fn try_from(value: i32) -> ::std::result::Result<Self, Self::Error> {
+            <Self as crate::Enum>::from_i32(value).ok_or(crate::UnknownEnumValue(value))
+        }
+    }
+
     impl crate::EnumFull for JSType {
         fn enum_descriptor() -> crate::reflect::EnumDescriptor {
             static descriptor: crate::rt::Lazy<crate::reflect::EnumDescriptor> = crate::rt::Lazy::new();
@@ -5869,11 +6182,20 @@ impl crate::Message for OneofOptions {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.uninterpreted_option.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("uninterpreted_option[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
                 7994 => {
-                    self.uninterpreted_option.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.uninterpreted_option)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -6056,6 +6378,15 @@ impl crate::Message for EnumOptions {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.uninterpreted_option.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("uninterpreted_option[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -6066,7 +6397,7 @@ impl crate::Message for EnumOptions {
                     self.deprecated = ::std::option::Option::Some(is.read_bool()?);
                 },
                 7994 => {
-                    self.uninterpreted_option.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.uninterpreted_option)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -6237,6 +6568,15 @@ impl crate::Message for EnumValueOptions {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.uninterpreted_option.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("uninterpreted_option[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -6244,7 +6584,7 @@ impl crate::Message for EnumValueOptions {
                     self.deprecated = ::std::option::Option::Some(is.read_bool()?);
                 },
                 7994 => {
-                    self.uninterpreted_option.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.uninterpreted_option)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -6407,6 +6747,15 @@ impl crate::Message for ServiceOptions {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.uninterpreted_option.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("uninterpreted_option[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -6414,7 +6763,7 @@ impl crate::Message for ServiceOptions {
                     self.deprecated = ::std::option::Option::Some(is.read_bool()?);
                 },
                 7994 => {
-                    self.uninterpreted_option.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.uninterpreted_option)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -6606,6 +6955,15 @@ impl crate::Message for MethodOptions {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.uninterpreted_option.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("uninterpreted_option[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -6616,7 +6974,7 @@ impl crate::Message for MethodOptions {
                     self.idempotency_level = ::std::option::Option::Some(is.read_enum_or_unknown()?);
                 },
                 7994 => {
-                    self.uninterpreted_option.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.uninterpreted_option)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -6729,6 +7087,14 @@ pub mod method_options {
             *self as i32
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                IdempotencyLevel::IDEMPOTENCY_UNKNOWN => "IDEMPOTENCY_UNKNOWN",
+                IdempotencyLevel::NO_SIDE_EFFECTS => "NO_SIDE_EFFECTS",
+                IdempotencyLevel::IDEMPOTENT => "IDEMPOTENT",
+            }
+        }
+
         fn from_i32(value: i32) -> ::std::option::Option<IdempotencyLevel> {
             match value {
                 0 => ::std::option::Option::Some(IdempotencyLevel::IDEMPOTENCY_UNKNOWN),
@@ -6754,6 +7120,14 @@ pub mod method_options {
         ];
     }
 
+    impl ::std::convert::TryFrom<i32> for IdempotencyLevel {
+        type Error = crate::UnknownEnumValue;
+
+        fn try_from(value: i32) -> ::std::result::Result<Self, Self::Error> {
+            <Self as crate::Enum>::from_i32(value).ok_or(crate::UnknownEnumValue(value))
+        }
+    }
+
     impl crate::EnumFull for IdempotencyLevel {
         fn enum_descriptor() -> crate::reflect::EnumDescriptor {
             static descriptor: crate::rt::Lazy<crate::reflect::EnumDescriptor> = crate::rt::Lazy::new();
@@ -7044,11 +7418,20 @@ impl crate::Message for UninterpretedOption {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.name.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("name[{}].{}", i, path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
                 18 => {
-                    self.name.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.name)?;
                 },
                 26 => {
                     self.identifier_value = ::std::option::Option::Some(is.read_string()?);
@@ -7308,6 +7691,16 @@ pub mod uninterpreted_option {
             true
         }
 
+        fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+            if self.name_part.is_none() {
+                return ::std::option::Option::Some("name_part".to_owned());
+            }
+            if self.is_extension.is_none() {
+                return ::std::option::Option::Some("is_extension".to_owned());
+            }
+            ::std::option::Option::None
+        }
+
         fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
             while let Some(tag) = is.read_raw_tag_or_eof()? {
                 match tag {
@@ -7448,7 +7841,7 @@ impl crate::Message for SourceCodeInfo {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
                 10 => {
-                    self.location.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.location)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -7848,7 +8241,7 @@ impl crate::Message for GeneratedCodeInfo {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
                 10 => {
-                    self.annotation.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.annotation)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -9,7 +9,7 @@ use crate::Enum;
 use crate::EnumFull;
 
 /// Protobuf enums with possibly unknown values are preserved in this struct.
-#[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash)]
 #[repr(transparent)]
 // This should be <E: ProtobufEnum> when it no longer prevents using const fns.
 pub struct EnumOrUnknown<E> {
@@ -102,3 +102,51 @@ impl<E: Enum + fmt::Debug> fmt::Debug for EnumOrUnknown<E> {
 impl<E: EnumFull> ProtobufValue for EnumOrUnknown<E> {
     type RuntimeType = RuntimeTypeEnumOrUnknown<E>;
 }
+
+/// Known values are serialized as `E`, unknown values are serialized as `i32`.
+#[cfg(feature = "with-serde")]
+impl<E: Enum + serde::Serialize> serde::Serialize for EnumOrUnknown<E> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.enum_value() {
+            Ok(e) => e.serialize(serializer),
+            Err(v) => serializer.serialize_i32(v),
+        }
+    }
+}
+
+/// Accepts `E` serialized as string or any `i32` value.
+#[cfg(feature = "with-serde")]
+impl<'de, E: Enum + serde::Deserialize<'de>> serde::Deserialize<'de> for EnumOrUnknown<E> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EnumOrUnknownVisitor<E>(PhantomData<E>);
+
+        impl<'de, E: Enum + serde::Deserialize<'de>> serde::de::Visitor<'de> for EnumOrUnknownVisitor<E> {
+            type Value = EnumOrUnknown<E>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an enum value name or an integer")
+            }
+
+            fn visit_str<R: serde::de::Error>(self, v: &str) -> Result<Self::Value, R> {
+                use serde::de::IntoDeserializer;
+                E::deserialize(v.into_deserializer()).map(EnumOrUnknown::new)
+            }
+
+            fn visit_i64<R: serde::de::Error>(self, v: i64) -> Result<Self::Value, R> {
+                match i32::try_from(v) {
+                    Ok(v) => Ok(EnumOrUnknown::from_i32(v)),
+                    Err(_) => Err(R::invalid_value(serde::de::Unexpected::Signed(v), &self)),
+                }
+            }
+
+            fn visit_u64<R: serde::de::Error>(self, v: u64) -> Result<Self::Value, R> {
+                match i32::try_from(v) {
+                    Ok(v) => Ok(EnumOrUnknown::from_i32(v)),
+                    Err(_) => Err(R::invalid_value(serde::de::Unexpected::Unsigned(v), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(EnumOrUnknownVisitor(PhantomData))
+    }
+}
@@ -15,6 +15,9 @@ pub trait Enum: Eq + Sized + Copy + fmt::Debug + Default + Send + Sync + 'static
     /// Get enum `i32` value.
     fn value(&self) -> i32;
 
+    /// Get enum value name as specified in `.proto` file.
+    fn name(&self) -> &'static str;
+
     /// Try to create an enum from `i32` value.
     /// Return `None` if value is unknown.
     fn from_i32(v: i32) -> Option<Self>;
@@ -26,3 +29,18 @@ pub trait Enum: Eq + Sized + Copy + fmt::Debug + Default + Send + Sync + 'static
     /// All enum values for enum type.
     const VALUES: &'static [Self] = &[];
 }
+
+/// Error returned by `TryFrom<i32>` implementation of generated enums
+/// when the value is not known to the enum.
+///
+/// # Examples
+///
+/// ```
+/// # use protobuf::descriptor::field_descriptor_proto::Type;
+/// # use protobuf::UnknownEnumValue;
+/// assert_eq!(Ok(Type::TYPE_INT32), Type::try_from(5));
+/// assert_eq!(Err(UnknownEnumValue(99)), Type::try_from(99));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown enum value: {0}")]
+pub struct UnknownEnumValue(pub i32);
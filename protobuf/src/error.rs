@@ -1,7 +1,9 @@
+use std::fmt;
 use std::io;
 use std::str;
 
 use crate::reflect::error::ReflectError;
+use crate::wire;
 use crate::wire_format::WireType;
 
 /// [`Result`] alias for [`Error`].
@@ -25,6 +27,8 @@ pub(crate) enum WireError {
     InvalidEnumValue(&'static str, i32),
     #[error("Over recursion limit")]
     OverRecursionLimit,
+    #[error("End group tag number {1} does not match start group tag number {0}")]
+    MismatchedEndGroup(u32, u32),
     #[error("Truncated message")]
     TruncatedMessage,
     // not really possible
@@ -34,10 +38,24 @@ pub(crate) enum WireError {
     LimitIncrease,
     #[error("Encoded message size {0} is too large")]
     MessageTooLarge(u64),
-    #[error("Value too large for u32: {}", .0)]
-    U32Overflow(u64),
     #[error("Value too large for i32: {}", .0)]
     I32Overflow(i64),
+    #[error("Output buffer is too small")]
+    BufferTooSmall,
+}
+
+impl From<wire::Error> for WireError {
+    fn from(e: wire::Error) -> Self {
+        match e {
+            wire::Error::UnexpectedEof => WireError::UnexpectedEof,
+            wire::Error::UnexpectedWireType(wire_type) => WireError::UnexpectedWireType(wire_type),
+            wire::Error::IncorrectTag(tag) => WireError::IncorrectTag(tag),
+            wire::Error::IncorrectVarint => WireError::IncorrectVarint,
+            wire::Error::Utf8Error => WireError::Utf8Error,
+            wire::Error::I32Overflow(v) => WireError::I32Overflow(v),
+            wire::Error::BufferTooSmall => WireError::BufferTooSmall,
+        }
+    }
 }
 
 /// Generic protobuf error
@@ -59,14 +77,28 @@ pub(crate) enum ProtobufError {
         str::Utf8Error,
     ),
     /// Not all required fields of message set.
-    #[error("Message `{}` is missing required fields", .0)]
-    MessageNotInitialized(String),
+    #[error(transparent)]
+    MessageNotInitialized(UninitializedError),
     /// Message is too large.
     #[error("Provided buffer has not enough capacity to write message `{0}`")]
     BufferHasNotEnoughCapacity(String),
     /// Protobuf type and runtime types mismatch.
     #[error("Protobuf type and runtime types are not compatible")]
     IncompatibleProtobufTypeAndRuntimeType,
+    /// `Timestamp` is malformed or outside of supported range.
+    #[error("Timestamp is malformed or out of range: {0}s {1}ns")]
+    TimestampOutOfRange(i64, i32),
+    /// `Duration` is malformed or outside of supported range.
+    #[error("Duration is malformed or out of range: {0}s {1}ns")]
+    DurationOutOfRange(i64, i32),
+    /// `Value` contains NaN or infinity.
+    #[cfg(feature = "with-serde")]
+    #[error("Number `{0}` cannot be represented in JSON")]
+    NonFiniteJsonNumber(f64),
+    /// JSON number is not representable as finite `f64`.
+    #[cfg(feature = "with-serde")]
+    #[error("JSON number `{0}` cannot be represented as `f64`")]
+    JsonNumberOutOfRange(String),
     /// Group field type not implemented.
     #[error("Group field is not supported")]
     GroupIsNotImplemented,
@@ -91,6 +123,65 @@ impl From<WireError> for Error {
     }
 }
 
+impl From<wire::Error> for Error {
+    #[cold]
+    fn from(e: wire::Error) -> Self {
+        WireError::from(e).into()
+    }
+}
+
+/// Error returned when some required fields of a message are not set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UninitializedError {
+    message: String,
+    path: String,
+}
+
+impl UninitializedError {
+    pub(crate) fn new(message: &str, path: Option<String>) -> UninitializedError {
+        UninitializedError {
+            message: message.to_owned(),
+            path: path.unwrap_or_default(),
+        }
+    }
+
+    /// Name of the message which is checked.
+    pub fn message_name(&self) -> &str {
+        &self.message
+    }
+
+    /// Path to the first required field which is not set,
+    /// for example `child.items[1].name`.
+    ///
+    /// Empty if the message does not provide the path.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for UninitializedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "Message `{}` is missing required fields", self.message)
+        } else {
+            write!(
+                f,
+                "Message `{}` is missing required field `{}`",
+                self.message, self.path
+            )
+        }
+    }
+}
+
+impl std::error::Error for UninitializedError {}
+
+impl From<UninitializedError> for Error {
+    #[cold]
+    fn from(e: UninitializedError) -> Self {
+        Self(Box::new(ProtobufError::MessageNotInitialized(e)))
+    }
+}
+
 impl From<ReflectError> for Error {
     #[cold]
     fn from(e: ReflectError) -> Self {
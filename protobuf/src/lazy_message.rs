@@ -0,0 +1,357 @@
+use std::fmt;
+
+use once_cell::sync::OnceCell;
+
+use crate::wire_format::WireType;
+use crate::CodedOutputStream;
+use crate::Message;
+
+/// Message field which is kept encoded after parsing and decoded on first access.
+///
+/// Generated for singular message fields with `[lazy = true]` option
+/// when `lazy_message_fields` codegen option is enabled.
+///
+/// Encoded bytes are kept until the message is modified,
+/// so a field which was never accessed mutably is serialized back
+/// exactly as it was parsed, without decoding.
+///
+/// Errors in encoded message are reported when the field is accessed,
+/// not when the outer message is parsed.
+/// Required fields of the nested message are also checked only
+/// when the field is decoded.
+///
+/// # Examples
+///
+/// ```
+/// # use protobuf::LazyMessage;
+/// # use protobuf::Message;
+/// # use protobuf::well_known_types::duration::Duration;
+/// let mut duration = Duration::new();
+/// duration.seconds = 10;
+///
+/// let field = LazyMessage::<Duration>::from_bytes(duration.write_to_bytes().unwrap());
+/// assert!(!field.is_parsed());
+/// assert_eq!(10, field.get().unwrap().unwrap().seconds);
+/// assert!(field.is_parsed());
+/// ```
+#[derive(Clone)]
+pub struct LazyMessage<M> {
+    state: State<M>,
+}
+
+#[derive(Clone)]
+enum State<M> {
+    None,
+    Bytes {
+        bytes: Vec<u8>,
+        message: OnceCell<Box<M>>,
+    },
+    Message(Box<M>),
+}
+
+impl<M> LazyMessage<M> {
+    /// Construct an empty field.
+    #[inline]
+    pub const fn none() -> LazyMessage<M> {
+        LazyMessage { state: State::None }
+    }
+
+    /// Construct field from decoded message.
+    #[inline]
+    pub fn some(message: M) -> LazyMessage<M> {
+        LazyMessage {
+            state: State::Message(Box::new(message)),
+        }
+    }
+
+    /// Construct field from encoded message.
+    ///
+    /// Bytes are not validated.
+    #[inline]
+    pub fn from_bytes(bytes: Vec<u8>) -> LazyMessage<M> {
+        LazyMessage {
+            state: State::Bytes {
+                bytes,
+                message: OnceCell::new(),
+            },
+        }
+    }
+
+    /// True iff this field is set.
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        !self.is_none()
+    }
+
+    /// True iff this field is not set.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        matches!(self.state, State::None)
+    }
+
+    /// True iff this field is set and the message is decoded.
+    pub fn is_parsed(&self) -> bool {
+        match &self.state {
+            State::None => false,
+            State::Bytes { message, .. } => message.get().is_some(),
+            State::Message(..) => true,
+        }
+    }
+
+    /// Encoded message if it was not modified after parsing.
+    pub fn encoded_bytes(&self) -> Option<&[u8]> {
+        match &self.state {
+            State::Bytes { bytes, .. } => Some(bytes),
+            State::None | State::Message(..) => None,
+        }
+    }
+
+    /// Set the field to decoded message.
+    #[inline]
+    pub fn set(&mut self, message: M) {
+        self.state = State::Message(Box::new(message));
+    }
+
+    /// Clear the field.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.state = State::None;
+    }
+}
+
+impl<M: Message> LazyMessage<M> {
+    /// Get the message, decoding it if necessary.
+    ///
+    /// Decoded message is cached, encoded bytes are kept.
+    pub fn get(&self) -> crate::Result<Option<&M>> {
+        match &self.state {
+            State::None => Ok(None),
+            State::Bytes { bytes, message } => message
+                .get_or_try_init(|| M::parse_from_bytes(bytes).map(Box::new))
+                .map(|m| Some(&**m)),
+            State::Message(m) => Ok(Some(m)),
+        }
+    }
+
+    /// Get the message or default instance if the field is not set.
+    pub fn get_or_default(&self) -> crate::Result<&M> {
+        Ok(self.get()?.unwrap_or_else(|| M::default_instance()))
+    }
+
+    /// Get mutable reference to the message, decoding it if necessary,
+    /// or initializing the field with default message if it is not set.
+    ///
+    /// Encoded bytes are dropped.
+    pub fn mut_or_insert_default(&mut self) -> crate::Result<&mut M> {
+        let message = match std::mem::replace(&mut self.state, State::None) {
+            State::None => Box::new(M::new()),
+            State::Bytes { bytes, mut message } => match message.take() {
+                Some(m) => m,
+                None => match M::parse_from_bytes(&bytes) {
+                    Ok(m) => Box::new(m),
+                    Err(e) => {
+                        self.state = State::Bytes {
+                            bytes,
+                            message: OnceCell::new(),
+                        };
+                        return Err(e);
+                    }
+                },
+            },
+            State::Message(m) => m,
+        };
+        self.state = State::Message(message);
+        match &mut self.state {
+            State::Message(m) => Ok(m),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Take the message leaving the field empty.
+    pub fn take(&mut self) -> crate::Result<Option<M>> {
+        if self.is_none() {
+            return Ok(None);
+        }
+        let message = std::mem::take(self.mut_or_insert_default()?);
+        self.clear();
+        Ok(Some(message))
+    }
+
+    /// Check required fields if the message is decoded.
+    pub fn is_initialized(&self) -> bool {
+        match &self.state {
+            State::None => true,
+            State::Bytes { message, .. } => match message.get() {
+                Some(m) => m.is_initialized(),
+                None => true,
+            },
+            State::Message(m) => m.is_initialized(),
+        }
+    }
+
+    /// Merge encoded message into this field.
+    pub(crate) fn merge_from_bytes(&mut self, encoded: Vec<u8>) -> crate::Result<()> {
+        match &mut self.state {
+            State::None => *self = LazyMessage::from_bytes(encoded),
+            // Concatenation of encoded messages is a merge of these messages.
+            State::Bytes { bytes, message } => {
+                bytes.extend_from_slice(&encoded);
+                *message = OnceCell::new();
+            }
+            State::Message(m) => m.merge_from_bytes(&encoded)?,
+        }
+        Ok(())
+    }
+
+    /// Size of encoded message, also caching the sizes of decoded message.
+    pub(crate) fn compute_size(&self) -> Option<u64> {
+        match &self.state {
+            State::None => None,
+            State::Bytes { bytes, .. } => Some(bytes.len() as u64),
+            State::Message(m) => Some(m.compute_size()),
+        }
+    }
+
+    /// Write the field with tag, sizes must be cached.
+    pub(crate) fn write_with_cached_size(
+        &self,
+        field_number: u32,
+        os: &mut CodedOutputStream,
+    ) -> crate::Result<()> {
+        match &self.state {
+            State::None => Ok(()),
+            State::Bytes { bytes, .. } => os.write_bytes(field_number, bytes),
+            State::Message(m) => {
+                os.write_tag(field_number, WireType::LengthDelimited)?;
+                os.write_raw_varint32(m.cached_size())?;
+                m.write_to_with_cached_sizes(os)
+            }
+        }
+    }
+}
+
+impl<M> Default for LazyMessage<M> {
+    #[inline]
+    fn default() -> LazyMessage<M> {
+        LazyMessage::none()
+    }
+}
+
+impl<M> From<Option<M>> for LazyMessage<M> {
+    fn from(option: Option<M>) -> Self {
+        match option {
+            Some(m) => LazyMessage::some(m),
+            None => LazyMessage::none(),
+        }
+    }
+}
+
+/// Fields are equal if encoded bytes are equal or decoded messages are equal.
+///
+/// Encoded bytes are compared first, so a field which cannot be decoded
+/// is equal to itself and to fields with the same bytes.
+impl<M: Message> PartialEq for LazyMessage<M> {
+    fn eq(&self, other: &Self) -> bool {
+        if let (Some(a), Some(b)) = (self.encoded_bytes(), other.encoded_bytes()) {
+            if a == b {
+                return true;
+            }
+        }
+        match (self.get(), other.get()) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Formats decoded message, or encoded bytes if message cannot be decoded.
+impl<M: Message + fmt::Debug> fmt::Debug for LazyMessage<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.get() {
+            Ok(m) => fmt::Debug::fmt(&m, f),
+            Err(_) => f
+                .debug_struct("LazyMessage")
+                .field("bytes", &self.encoded_bytes())
+                .finish(),
+        }
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<M: Message + serde::Serialize> serde::Serialize for LazyMessage<M> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get()
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de, M: serde::Deserialize<'de>> serde::Deserialize<'de> for LazyMessage<M> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<M>::deserialize(deserializer).map(LazyMessage::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::well_known_types::duration::Duration;
+    use crate::LazyMessage;
+    use crate::Message;
+
+    fn duration(seconds: i64) -> Duration {
+        let mut d = Duration::new();
+        d.seconds = seconds;
+        d
+    }
+
+    #[test]
+    fn merge() {
+        let mut field = LazyMessage::<Duration>::none();
+        field
+            .merge_from_bytes(duration(1).write_to_bytes().unwrap())
+            .unwrap();
+        let mut nanos = Duration::new();
+        nanos.nanos = 2;
+        field
+            .merge_from_bytes(nanos.write_to_bytes().unwrap())
+            .unwrap();
+        assert!(!field.is_parsed());
+
+        let mut expected = duration(1);
+        expected.nanos = 2;
+        assert_eq!(Some(&expected), field.get().unwrap());
+
+        field.mut_or_insert_default().unwrap().seconds = 3;
+        field
+            .merge_from_bytes(duration(4).write_to_bytes().unwrap())
+            .unwrap();
+        expected.seconds = 4;
+        assert_eq!(Some(&expected), field.get().unwrap());
+        assert_eq!(None, field.encoded_bytes());
+    }
+
+    #[test]
+    fn malformed() {
+        let mut field = LazyMessage::<Duration>::from_bytes(vec![0x08]);
+        assert!(field.get().is_err());
+        assert!(field.mut_or_insert_default().is_err());
+        assert_eq!(Some(&[0x08][..]), field.encoded_bytes());
+    }
+
+    #[test]
+    fn eq_malformed() {
+        let field = LazyMessage::<Duration>::from_bytes(vec![0x08]);
+        assert_eq!(field, field.clone());
+        assert_ne!(field, LazyMessage::from_bytes(vec![0x10]));
+        assert_ne!(field, LazyMessage::some(Duration::new()));
+    }
+
+    #[test]
+    fn take() {
+        let mut field = LazyMessage::from_bytes(duration(5).write_to_bytes().unwrap());
+        assert_eq!(Some(duration(5)), field.take().unwrap());
+        assert!(field.is_none());
+        assert_eq!(None, field.take().unwrap());
+    }
+}
@@ -2,7 +2,19 @@
 //!
 //! ## Features
 //!
-//! This crate has one feature, which is `with-bytes`.
+//! This crate has five features, `std`, `with-bytes`, `with-serde`, `with-tokio` and `with-chrono`.
+//!
+//! `std` is enabled by default. Without it the crate is `no_std`
+//! and contains only [`wire`] module: wire format readers and writers
+//! over byte slices, which do not allocate.
+//! Generated code, reflection and text format require `std`.
+//! Other features imply `std`.
+//! Crates which depend on `protobuf` with `default-features = false`
+//! must enable `std` explicitly to keep the rest of the API:
+//!
+//! ```toml
+//! protobuf = { version = "4", default-features = false, features = ["std"] }
+//! ```
 //!
 //! `with-bytes` enables `protobuf` crate support for
 //! [`bytes` crate](https://github.com/tokio-rs/bytes):
@@ -15,6 +27,19 @@
 //!
 //! See `Customize` struct in [`protobuf-codegen` crate](https://docs.rs/protobuf-codegen).
 //!
+//! `with-serde` implements `serde` `Serialize` and `Deserialize`
+//! for `protobuf` support types like [`Chars`] (which also requires `with-bytes`),
+//! [`MessageField`] and [`EnumOrUnknown`], which is needed for code generated
+//! with `serde_derive` codegen option,
+//! and conversions between `google.protobuf.Value` and `serde_json::Value`.
+//!
+//! `with-tokio` enables `AsyncCodedInputStream`
+//! which reads length-delimited messages from `tokio::io::AsyncRead`.
+//!
+//! `with-chrono` implements conversions between
+//! [`Timestamp`](crate::well_known_types::timestamp::Timestamp)
+//! and `chrono::DateTime<Utc>`.
+//!
 //! ## Accompanying crates
 //!
 //! * [`protobuf-json-mapping`](https://docs.rs/protobuf-json-mapping)
@@ -27,56 +52,127 @@
 //!   `.proto` file parser. Rarely need to be used directly,
 //!   but can be used for mechanical processing of `.proto` files.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
 #![deny(rustdoc::broken_intra_doc_links)]
 
+#[cfg(feature = "std")]
+pub use crate::arc_message_field::ArcMessageField;
+#[cfg(feature = "with-tokio")]
+pub use crate::async_coded_input_stream::AsyncCodedInputStream;
+#[cfg(feature = "std")]
 pub use crate::coded_input_stream::CodedInputStream;
+#[cfg(feature = "std")]
+pub use crate::coded_input_stream::Utf8Validation;
+#[cfg(feature = "std")]
 pub use crate::coded_output_stream::CodedOutputStream;
+#[cfg(feature = "std")]
 pub use crate::enum_full::EnumFull;
+#[cfg(feature = "std")]
 pub use crate::enum_or_unknown::EnumOrUnknown;
+#[cfg(feature = "std")]
 pub use crate::enums::Enum;
+#[cfg(feature = "std")]
+pub use crate::enums::UnknownEnumValue;
+#[cfg(feature = "std")]
+pub use crate::lazy_message::LazyMessage;
+#[cfg(feature = "std")]
 pub use crate::message::Message;
+#[cfg(feature = "std")]
 pub use crate::message_dyn::MessageDyn;
+#[cfg(feature = "std")]
 pub use crate::message_field::MessageField;
+#[cfg(feature = "std")]
 pub use crate::message_full::MessageFull;
+#[cfg(feature = "std")]
 pub use crate::oneof::Oneof;
+#[cfg(feature = "std")]
 pub use crate::oneof_full::OneofFull;
+#[cfg(feature = "std")]
+pub use crate::prost_compat::ProstCompat;
+#[cfg(feature = "with-bytes")]
+pub use crate::serialize::serialize_to_bytes;
+#[cfg(feature = "std")]
+pub use crate::serialize::serialize_to_vec;
+#[cfg(feature = "std")]
 pub use crate::special::SpecialFields;
+#[cfg(feature = "std")]
 pub use crate::unknown::UnknownFields;
+#[cfg(feature = "std")]
 pub use crate::unknown::UnknownFieldsIter;
+#[cfg(feature = "std")]
 pub use crate::unknown::UnknownValue;
+#[cfg(feature = "std")]
 pub use crate::unknown::UnknownValueRef;
 pub(crate) mod wire_format;
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 pub use crate::chars::Chars;
+#[cfg(feature = "std")]
 pub use crate::error::Error;
+#[cfg(feature = "std")]
 pub use crate::error::Result;
+#[cfg(feature = "std")]
+pub use crate::error::UninitializedError;
 
 // generated
+#[cfg(feature = "std")]
 pub mod descriptor;
+#[cfg(feature = "std")]
 pub mod plugin;
+#[cfg(feature = "std")]
 pub mod rustproto;
 
+#[cfg(feature = "std")]
+mod arc_message_field;
+#[cfg(feature = "with-tokio")]
+mod async_coded_input_stream;
+#[cfg(feature = "std")]
 mod byteorder;
+#[cfg(feature = "std")]
 mod coded_input_stream;
+#[cfg(feature = "std")]
 mod coded_output_stream;
+#[cfg(feature = "std")]
 mod enum_full;
+#[cfg(feature = "std")]
 mod enum_or_unknown;
+#[cfg(feature = "std")]
 mod enums;
+#[cfg(feature = "std")]
 mod error;
+#[cfg(feature = "std")]
 pub mod ext;
+#[cfg(feature = "std")]
 mod lazy;
+#[cfg(feature = "std")]
+mod lazy_message;
+#[cfg(feature = "std")]
 mod message;
+#[cfg(feature = "std")]
 mod message_dyn;
+#[cfg(feature = "std")]
 mod message_field;
+#[cfg(feature = "std")]
 mod message_full;
+#[cfg(feature = "std")]
 mod oneof;
+#[cfg(feature = "std")]
 mod oneof_full;
+#[cfg(feature = "std")]
 mod owning_ref;
+#[cfg(feature = "std")]
+mod prost_compat;
+#[cfg(feature = "std")]
 pub mod reflect;
+#[cfg(feature = "std")]
 pub mod rt;
+#[cfg(feature = "std")]
+mod serialize;
+#[cfg(feature = "std")]
 pub mod text_format;
+#[cfg(feature = "std")]
 pub mod well_known_types;
+#[cfg(feature = "std")]
 mod well_known_types_util;
 
 // used by test
@@ -84,18 +180,25 @@ mod well_known_types_util;
 #[path = "../../test-crates/protobuf-test-common/src/hex.rs"]
 mod hex;
 
+#[cfg(feature = "std")]
 mod cached_size;
+#[cfg(feature = "std")]
 mod chars;
+#[cfg(feature = "std")]
 mod fixed;
+#[cfg(feature = "std")]
 mod special;
+#[cfg(feature = "std")]
 mod unknown;
 mod varint;
+pub mod wire;
 mod zigzag;
 
+#[cfg(feature = "std")]
 mod misc;
 
 // This does not work: https://github.com/rust-lang/rust/issues/67295
-#[cfg(doctest)]
+#[cfg(all(doctest, feature = "std"))]
 mod doctest_pb;
 
 /// This symbol is in generated `version.rs`, include here for IDE
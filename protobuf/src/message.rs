@@ -2,7 +2,7 @@ use std::io::Read;
 use std::io::Write;
 
 use crate::coded_output_stream::with::WithCodedOutputStream;
-use crate::error::ProtobufError;
+use crate::error::UninitializedError;
 use crate::wire_format::check_message_size;
 use crate::CodedInputStream;
 use crate::CodedOutputStream;
@@ -128,9 +128,23 @@ pub trait Message: Default + Clone + Send + Sync + Sized + PartialEq + 'static {
         Ok(r)
     }
 
+    /// Parse length-delimited message from the beginning of byte array.
+    ///
+    /// The message must be prefixed with its length encoded as varint,
+    /// like written by [`write_length_delimited_to_vec`](Message::write_length_delimited_to_vec)
+    /// or Java `writeDelimitedTo`.
+    ///
+    /// Returns the message and the number of bytes consumed including the length,
+    /// so a buffer of several messages can be parsed by repeated calls.
+    fn parse_length_delimited_from_bytes(bytes: &[u8]) -> crate::Result<(Self, usize)> {
+        let mut is = CodedInputStream::from_bytes(bytes);
+        let r = is.read_message()?;
+        Ok((r, is.pos() as usize))
+    }
+
     /// Parse message from `Bytes` object.
     /// Resulting message may share references to the passed bytes object.
-    #[cfg(feature = "bytes")]
+    #[cfg(feature = "with-bytes")]
     fn parse_from_tokio_bytes(bytes: &bytes::Bytes) -> crate::Result<Self> {
         let mut is = CodedInputStream::from_tokio_bytes(bytes);
         let r = Self::parse_from(&mut is)?;
@@ -138,10 +152,27 @@ pub trait Message: Default + Clone + Send + Sync + Sized + PartialEq + 'static {
         Ok(r)
     }
 
+    /// Path to the first required field which is not set,
+    /// including fields of nested messages and repeated elements,
+    /// for example `child.items[1].name`.
+    ///
+    /// Returns `None` if all required fields are set.
+    /// Generated for protobuf 2 messages which may have required fields unset,
+    /// default implementation returns `None`.
+    fn uninitialized_field_path(&self) -> Option<String> {
+        None
+    }
+
     /// Check if all required fields of this object are initialized.
-    fn check_initialized(&self) -> crate::Result<()> {
+    ///
+    /// The error contains [path](Message::uninitialized_field_path)
+    /// of the first field which is not set.
+    fn check_initialized(&self) -> Result<(), UninitializedError> {
         if !self.is_initialized() {
-            Err(ProtobufError::MessageNotInitialized(Self::NAME.to_owned()).into())
+            Err(UninitializedError::new(
+                Self::NAME,
+                self.uninitialized_field_path(),
+            ))
         } else {
             Ok(())
         }
@@ -211,7 +242,12 @@ pub trait Message: Default + Clone + Send + Sync + Sized + PartialEq + 'static {
     /// ```
     fn new() -> Self;
 
-    /// Reset all fields.
+    /// Reset all fields and unknown fields.
+    ///
+    /// Generated messages clear repeated fields, map fields,
+    /// proto3 `string` and `bytes` fields and unknown fields in place,
+    /// so allocated memory is retained when a message instance is reused.
+    /// Nested messages and proto2 `string` and `bytes` fields are dropped.
     fn clear(&mut self) {
         *self = Self::new();
     }
@@ -4,7 +4,7 @@ use std::fmt;
 use std::io::Write;
 
 use crate::coded_output_stream::with::WithCodedOutputStream;
-use crate::error::ProtobufError;
+use crate::error::UninitializedError;
 use crate::reflect::MessageDescriptor;
 use crate::reflect::ReflectEqMode;
 use crate::wire_format::check_message_size;
@@ -21,6 +21,11 @@ use crate::UnknownFields;
 /// [`FileDescriptor::new_dynamic`](crate::reflect::FileDescriptor::new_dynamic).
 pub trait MessageDyn: Any + fmt::Debug + fmt::Display + Send + Sync + 'static {
     /// Message descriptor for this message, used for reflection.
+    ///
+    /// This works for both generated and dynamic messages.
+    /// For generated messages it returns the same descriptor
+    /// as [`MessageFull::descriptor`], which is not available for dynamic messages,
+    /// because dynamic messages have no static type.
     fn descriptor_dyn(&self) -> MessageDescriptor;
 
     /// Update this message fields with contents of given stream.
@@ -36,6 +41,13 @@ pub trait MessageDyn: Any + fmt::Debug + fmt::Display + Send + Sync + 'static {
     /// Always returns `true` for protobuf 3.
     fn is_initialized_dyn(&self) -> bool;
 
+    /// Path to the first required field which is not set,
+    /// see [`Message::uninitialized_field_path`](crate::Message::uninitialized_field_path).
+    fn uninitialized_field_path_dyn(&self) -> Option<String>;
+
+    /// Reset all fields, see [`Message::clear`](crate::Message::clear).
+    fn clear_dyn(&mut self);
+
     /// Get a reference to special fields.
     fn special_fields_dyn(&self) -> &SpecialFields;
     /// Get a mutable reference to special fields.
@@ -63,6 +75,14 @@ impl<M: MessageFull> MessageDyn for M {
         self.is_initialized()
     }
 
+    fn uninitialized_field_path_dyn(&self) -> Option<String> {
+        self.uninitialized_field_path()
+    }
+
+    fn clear_dyn(&mut self) {
+        self.clear()
+    }
+
     fn special_fields_dyn(&self) -> &SpecialFields {
         self.special_fields()
     }
@@ -74,12 +94,12 @@ impl<M: MessageFull> MessageDyn for M {
 
 impl dyn MessageDyn {
     /// Check if all required fields of this object are initialized.
-    pub fn check_initialized_dyn(&self) -> crate::Result<()> {
+    pub fn check_initialized_dyn(&self) -> Result<(), UninitializedError> {
         if !self.is_initialized_dyn() {
-            Err(
-                ProtobufError::MessageNotInitialized(self.descriptor_dyn().name().to_owned())
-                    .into(),
-            )
+            Err(UninitializedError::new(
+                self.descriptor_dyn().name(),
+                self.uninitialized_field_path_dyn(),
+            ))
         } else {
             Ok(())
         }
@@ -266,7 +286,9 @@ impl PartialEq for Box<dyn MessageDyn> {
 #[cfg(test)]
 mod test {
     use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
     use crate::MessageDyn;
+    use crate::MessageFull;
 
     #[test]
     fn downcast_ref() {
@@ -290,6 +312,36 @@ mod test {
         );
     }
 
+    #[test]
+    fn descriptor_dyn() {
+        fn full_name(m: &dyn MessageDyn) -> String {
+            m.descriptor_dyn().full_name().to_owned()
+        }
+
+        let generated = FileDescriptorProto::new();
+        assert_eq!(
+            FileDescriptorProto::descriptor(),
+            generated.descriptor_dyn()
+        );
+
+        let file = FileDescriptor::new_dynamic(
+            FileDescriptorProto::descriptor()
+                .file_descriptor()
+                .proto()
+                .clone(),
+            &[],
+        )
+        .unwrap();
+        let descriptor = file
+            .message_by_package_relative_name("FileDescriptorProto")
+            .unwrap();
+        let dynamic = descriptor.new_instance();
+        assert_eq!(descriptor, dynamic.descriptor_dyn());
+
+        assert_eq!(full_name(&generated), full_name(&*dynamic));
+        assert_eq!("google.protobuf.FileDescriptorProto", full_name(&*dynamic));
+    }
+
     #[test]
     fn downcast_box() {
         let m = FileDescriptorProto::new();
@@ -208,3 +208,18 @@ impl<'a, T> IntoIterator for &'a MessageField<T> {
         self.iter()
     }
 }
+
+/// Serialized as `Option<T>`.
+#[cfg(feature = "with-serde")]
+impl<T: serde::Serialize> serde::Serialize for MessageField<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for MessageField<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<T>::deserialize(deserializer).map(MessageField::from_option)
+    }
+}
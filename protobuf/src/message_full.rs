@@ -34,6 +34,9 @@ pub trait MessageFull: Message + ProtobufValue + fmt::Debug + fmt::Display {
     /// assert_eq!("MyMessage", descriptor.name());
     /// # }
     /// ```
+    ///
+    /// Dynamic messages do not implement this trait,
+    /// use [`MessageDyn::descriptor_dyn`] to get descriptor of any message instance.
     fn descriptor() -> MessageDescriptor;
 
     /// Reflective equality.
@@ -425,6 +425,20 @@ impl crate::Message for CodeGeneratorRequest {
         true
     }
 
+    fn uninitialized_field_path(&self) -> ::std::option::Option<::std::string::String> {
+        for (i, v) in self.proto_file.iter().enumerate() {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("proto_file[{}].{}", i, path));
+            }
+        };
+        for v in &self.compiler_version {
+            if let ::std::option::Option::Some(path) = crate::Message::uninitialized_field_path(v) {
+                return ::std::option::Option::Some(format!("compiler_version.{}", path));
+            }
+        };
+        ::std::option::Option::None
+    }
+
     fn merge_from(&mut self, is: &mut crate::CodedInputStream<'_>) -> crate::Result<()> {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
@@ -435,7 +449,7 @@ impl crate::Message for CodeGeneratorRequest {
                     self.parameter = ::std::option::Option::Some(is.read_string()?);
                 },
                 122 => {
-                    self.proto_file.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.proto_file)?;
                 },
                 26 => {
                     crate::rt::read_singular_message_into_field(is, &mut self.compiler_version)?;
@@ -672,7 +686,7 @@ impl crate::Message for CodeGeneratorResponse {
                     self.supported_features = ::std::option::Option::Some(is.read_uint64()?);
                 },
                 122 => {
-                    self.file.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.file)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -1119,6 +1133,13 @@ pub mod code_generator_response {
             *self as i32
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Feature::FEATURE_NONE => "FEATURE_NONE",
+                Feature::FEATURE_PROTO3_OPTIONAL => "FEATURE_PROTO3_OPTIONAL",
+            }
+        }
+
         fn from_i32(value: i32) -> ::std::option::Option<Feature> {
             match value {
                 0 => ::std::option::Option::Some(Feature::FEATURE_NONE),
@@ -1141,6 +1162,14 @@ pub mod code_generator_response {
         ];
     }
 
+    impl ::std::convert::TryFrom<i32> for Feature {
+        type Error = crate::UnknownEnumValue;
+
+        fn try_from(value: i32) -> ::std::result::Result<Self, Self::Error> {
+            <Self as crate::Enum>::from_i32(value).ok_or(crate::UnknownEnumValue(value))
+        }
+    }
+
     impl crate::EnumFull for Feature {
         fn enum_descriptor() -> crate::reflect::EnumDescriptor {
             static descriptor: crate::rt::Lazy<crate::reflect::EnumDescriptor> = crate::rt::Lazy::new();
@@ -0,0 +1,86 @@
+//! Method names familiar to users of `prost`.
+
+use crate::Message;
+
+/// Aliases of [`Message`] functions named like in `prost`,
+/// to ease migration from it.
+///
+/// The trait is implemented for all messages, it only needs to be imported.
+///
+/// # Examples
+///
+/// ```
+/// use protobuf::ProstCompat;
+/// # use protobuf::well_known_types::duration::Duration;
+///
+/// let mut duration = Duration::new();
+/// duration.seconds = 10;
+/// let bytes = duration.encode_to_vec();
+/// assert_eq!(bytes.len(), duration.encoded_len());
+/// assert_eq!(duration, Duration::decode(&bytes).unwrap());
+/// ```
+pub trait ProstCompat: Message {
+    /// Size of encoded message, same as [`Message::compute_size`].
+    fn encoded_len(&self) -> usize {
+        self.compute_size() as usize
+    }
+
+    /// Append encoded message to `buf`, same as [`Message::write_to_vec`].
+    fn encode(&self, buf: &mut Vec<u8>) -> crate::Result<()> {
+        self.write_to_vec(buf)
+    }
+
+    /// Encode message to a new vec, same as [`serialize_to_vec`](crate::serialize_to_vec).
+    ///
+    /// # Panics
+    ///
+    /// If message is not initialized.
+    fn encode_to_vec(&self) -> Vec<u8> {
+        crate::serialize_to_vec(self)
+    }
+
+    /// Parse message, same as [`Message::parse_from_bytes`].
+    fn decode(buf: &[u8]) -> crate::Result<Self> {
+        Self::parse_from_bytes(buf)
+    }
+}
+
+impl<M: Message> ProstCompat for M {}
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::uninterpreted_option::NamePart;
+    use crate::descriptor::UninterpretedOption;
+    use crate::Message;
+    use crate::ProstCompat;
+
+    fn message() -> UninterpretedOption {
+        let mut m = UninterpretedOption::new();
+        m.set_identifier_value("abc".to_owned());
+        let mut name = NamePart::new();
+        name.set_name_part("x".to_owned());
+        name.set_is_extension(false);
+        m.name.push(name);
+        m
+    }
+
+    #[test]
+    fn same_as_message() {
+        let m = message();
+        assert_eq!(m.compute_size() as usize, m.encoded_len());
+        assert_eq!(m.write_to_bytes().unwrap(), m.encode_to_vec());
+
+        let mut buf = vec![1, 2];
+        m.encode(&mut buf).unwrap();
+        assert_eq!(&m.write_to_bytes().unwrap()[..], &buf[2..]);
+
+        assert_eq!(m, UninterpretedOption::decode(&buf[2..]).unwrap());
+    }
+
+    #[test]
+    fn encode_not_initialized() {
+        let mut m = message();
+        m.name[0].clear_is_extension();
+        assert!(m.encode(&mut Vec::new()).is_err());
+    }
+}
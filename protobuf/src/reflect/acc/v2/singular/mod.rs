@@ -13,8 +13,10 @@ use crate::reflect::ProtobufValue;
 use crate::reflect::ReflectOptionalRef;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
+use crate::ArcMessageField;
 use crate::EnumFull;
 use crate::EnumOrUnknown;
+use crate::LazyMessage;
 
 pub(crate) mod oneof;
 
@@ -22,6 +24,13 @@ pub(crate) mod oneof;
 pub(crate) trait SingularFieldAccessor: Send + Sync + 'static {
     fn get_field<'a>(&self, m: &'a dyn MessageDyn) -> ReflectOptionalRef<'a>;
     fn mut_field_or_default<'a>(&self, m: &'a mut dyn MessageDyn) -> ReflectValueMut<'a>;
+    /// Like `mut_field_or_default`, but fails if the field value cannot be decoded.
+    fn try_mut_field_or_default<'a>(
+        &self,
+        m: &'a mut dyn MessageDyn,
+    ) -> crate::Result<ReflectValueMut<'a>> {
+        Ok(self.mut_field_or_default(m))
+    }
     fn set_field(&self, m: &mut dyn MessageDyn, value: ReflectValueBox);
     fn clear_field(&self, m: &mut dyn MessageDyn);
 }
@@ -151,6 +160,83 @@ impl SingularFieldAccessorHolder {
         )
     }
 
+    fn new_get_mut_arc_message<M, V>(
+        get_field: for<'a> fn(&'a M) -> &'a ArcMessageField<V>,
+        mut_field: for<'a> fn(&'a mut M) -> &'a mut ArcMessageField<V>,
+    ) -> SingularFieldAccessorHolder
+    where
+        M: MessageFull,
+        V: MessageFull,
+    {
+        Self::new(
+            move |m| ReflectOptionalRef::new_from_option((get_field)(m).as_ref()),
+            move |m| V::RuntimeType::as_mut((mut_field)(m).mut_or_insert_default()),
+            move |m, value| {
+                (mut_field)(m).set(V::RuntimeType::from_value_box(value).expect("wrong type"))
+            },
+            move |m| (mut_field)(m).clear(),
+        )
+    }
+
+    fn new_get_mut_lazy_message<M, V>(
+        get_field: for<'a> fn(&'a M) -> &'a LazyMessage<V>,
+        mut_field: for<'a> fn(&'a mut M) -> &'a mut LazyMessage<V>,
+    ) -> SingularFieldAccessorHolder
+    where
+        M: MessageFull,
+        V: MessageFull,
+    {
+        struct Impl<M, V> {
+            get_field: for<'a> fn(&'a M) -> &'a LazyMessage<V>,
+            mut_field: for<'a> fn(&'a mut M) -> &'a mut LazyMessage<V>,
+        }
+
+        impl<M: MessageFull, V: MessageFull> SingularFieldAccessor for Impl<M, V> {
+            fn get_field<'a>(&self, m: &'a dyn MessageDyn) -> ReflectOptionalRef<'a> {
+                let field = (self.get_field)(m.downcast_ref::<M>().unwrap());
+                if field.is_none() {
+                    return ReflectOptionalRef::none_from::<V>();
+                }
+                // Reflection sees message which cannot be decoded as empty.
+                let v = field
+                    .get_or_default()
+                    .unwrap_or_else(|_| V::default_instance());
+                ReflectOptionalRef::some(V::RuntimeType::as_ref(v))
+            }
+
+            fn mut_field_or_default<'a>(&self, m: &'a mut dyn MessageDyn) -> ReflectValueMut<'a> {
+                self.try_mut_field_or_default(m)
+                    .unwrap_or_else(|e| panic!("lazy message field cannot be decoded: {}", e))
+            }
+
+            fn try_mut_field_or_default<'a>(
+                &self,
+                m: &'a mut dyn MessageDyn,
+            ) -> crate::Result<ReflectValueMut<'a>> {
+                // Undecodable message is not replaced with default
+                // to not lose the encoded bytes.
+                let v = (self.mut_field)(m.downcast_mut::<M>().unwrap()).mut_or_insert_default()?;
+                Ok(V::RuntimeType::as_mut(v))
+            }
+
+            fn set_field(&self, m: &mut dyn MessageDyn, value: ReflectValueBox) {
+                (self.mut_field)(m.downcast_mut::<M>().unwrap())
+                    .set(V::RuntimeType::from_value_box(value).expect("wrong type"));
+            }
+
+            fn clear_field(&self, m: &mut dyn MessageDyn) {
+                (self.mut_field)(m.downcast_mut::<M>().unwrap()).clear();
+            }
+        }
+
+        SingularFieldAccessorHolder {
+            accessor: Box::new(Impl {
+                get_field,
+                mut_field,
+            }),
+        }
+    }
+
     pub(crate) fn new_get_option_set_enum<M, E>(
         get: fn(&M) -> Option<EnumOrUnknown<E>>,
         set: fn(&mut M, EnumOrUnknown<E>),
@@ -294,6 +380,42 @@ where
     )
 }
 
+/// Make accessor for `ArcMessageField` field
+pub fn make_arc_message_field_accessor<M, V>(
+    name: &'static str,
+    get_field: for<'a> fn(&'a M) -> &'a ArcMessageField<V>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut ArcMessageField<V>,
+) -> FieldAccessor
+where
+    M: MessageFull,
+    V: MessageFull,
+{
+    FieldAccessor::new(
+        name,
+        AccessorV2::Singular(SingularFieldAccessorHolder::new_get_mut_arc_message(
+            get_field, mut_field,
+        )),
+    )
+}
+
+/// Make accessor for `LazyMessage` field
+pub fn make_lazy_message_field_accessor<M, V>(
+    name: &'static str,
+    get_field: for<'a> fn(&'a M) -> &'a LazyMessage<V>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut LazyMessage<V>,
+) -> FieldAccessor
+where
+    M: MessageFull,
+    V: MessageFull,
+{
+    FieldAccessor::new(
+        name,
+        AccessorV2::Singular(SingularFieldAccessorHolder::new_get_mut_lazy_message(
+            get_field, mut_field,
+        )),
+    )
+}
+
 /// Make accessor for `Option<C>` field
 pub fn make_option_accessor<M, V>(
     name: &'static str,
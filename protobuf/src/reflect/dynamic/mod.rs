@@ -1,8 +1,10 @@
 use std::any::Any;
 use std::any::TypeId;
+use std::cmp::Ordering;
 use std::fmt;
 
 use crate::descriptor::field_descriptor_proto::Type;
+use crate::error::ProtobufError;
 use crate::message_dyn::MessageDyn;
 use crate::reflect::dynamic::map::DynamicMap;
 use crate::reflect::dynamic::optional::DynamicOptional;
@@ -21,7 +23,7 @@ use crate::reflect::ReflectRepeatedRef;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
 use crate::reflect::RuntimeFieldType;
-use crate::reflect::Syntax;
+use crate::reflect::RuntimeType;
 use crate::rt::bytes_size;
 use crate::rt::compute_raw_varint32_size;
 use crate::rt::compute_raw_varint64_size;
@@ -222,14 +224,15 @@ impl DynamicMessage {
         &self,
         handler: &mut impl ForEachSingularFieldToWrite,
     ) -> crate::Result<()> {
-        let is_proto3 = self.descriptor.file_descriptor().syntax() == Syntax::Proto3;
         for field_desc in self.descriptor.fields() {
             let field_number = field_desc.proto().number() as u32;
             match field_desc.runtime_field_type() {
                 RuntimeFieldType::Singular(..) => {
                     if let Some(v) = field_desc.get_singular(self) {
-                        // Ignore default value for proto3.
-                        if !is_proto3 || v.is_non_zero() {
+                        // Ignore default value for fields without presence
+                        // (proto3 fields without `optional`, fields with implicit
+                        // presence in editions).
+                        if field_desc.has_presence() || v.is_non_zero() {
                             handler.field(field_desc.proto().type_(), field_number, &v)?;
                         }
                     }
@@ -252,7 +255,11 @@ impl DynamicMessage {
                 RuntimeFieldType::Map(_, _) => {
                     let map = field_desc.get_map(self);
                     let (key_type, value_type) = field_desc.map_proto_type();
-                    for (k, v) in &map {
+                    let mut entries: Vec<_> = map.into_iter().collect();
+                    if handler.sort_map_entries() {
+                        entries.sort_by(|a, b| cmp_map_keys(&a.0, &b.0));
+                    }
+                    for (k, v) in entries {
                         handler.map_field_entry(
                             field_number,
                             &k,
@@ -287,6 +294,22 @@ trait ForEachSingularFieldToWrite {
         vt: Type,
     ) -> crate::Result<()>;
     fn unknown_fields(&mut self, unknown_fields: &UnknownFields) -> crate::Result<()>;
+    /// Map entries are passed to `map_field_entry` sorted by key.
+    fn sort_map_entries(&self) -> bool {
+        false
+    }
+}
+
+fn cmp_map_keys(a: &ReflectValueRef, b: &ReflectValueRef) -> Ordering {
+    match (a, b) {
+        (ReflectValueRef::U32(a), ReflectValueRef::U32(b)) => a.cmp(b),
+        (ReflectValueRef::U64(a), ReflectValueRef::U64(b)) => a.cmp(b),
+        (ReflectValueRef::I32(a), ReflectValueRef::I32(b)) => a.cmp(b),
+        (ReflectValueRef::I64(a), ReflectValueRef::I64(b)) => a.cmp(b),
+        (ReflectValueRef::Bool(a), ReflectValueRef::Bool(b)) => a.cmp(b),
+        (ReflectValueRef::String(a), ReflectValueRef::String(b)) => a.cmp(b),
+        _ => panic!("not a map key: {:?}, {:?}", a, b),
+    }
 }
 
 impl fmt::Display for DynamicMessage {
@@ -340,6 +363,41 @@ impl MessageDyn for DynamicMessage {
         true
     }
 
+    fn uninitialized_field_path_dyn(&self) -> Option<String> {
+        if self.descriptor.is_initialized_is_always_true() {
+            return None;
+        }
+
+        for f in self.descriptor.fields() {
+            match self.get_reflect(&f) {
+                ReflectFieldRef::Optional(s) => match s.value() {
+                    None if f.is_required() => return Some(f.name().to_owned()),
+                    None => {}
+                    Some(v) => {
+                        if let Some(path) = v.uninitialized_field_path() {
+                            return Some(format!("{}.{}", f.name(), path));
+                        }
+                    }
+                },
+                ReflectFieldRef::Repeated(r) => {
+                    for (i, v) in r.into_iter().enumerate() {
+                        if let Some(path) = v.uninitialized_field_path() {
+                            return Some(format!("{}[{}].{}", f.name(), i, path));
+                        }
+                    }
+                }
+                ReflectFieldRef::Map(m) => {
+                    for (k, v) in &m {
+                        if let Some(path) = v.uninitialized_field_path() {
+                            return Some(format!("{}[{}].{}", f.name(), k, path));
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
     fn merge_from_dyn(&mut self, is: &mut CodedInputStream) -> crate::Result<()> {
         while !is.eof()? {
             let (field, wire_type) = is.read_tag_unpack()?;
@@ -356,6 +414,15 @@ impl MessageDyn for DynamicMessage {
                 }
             };
             match field_desc.runtime_field_type() {
+                RuntimeFieldType::Singular(RuntimeType::Message(_))
+                    if field_desc.proto().type_() == Type::TYPE_MESSAGE
+                        && wire_type == WireType::LengthDelimited =>
+                {
+                    // Message is merged into previously read one.
+                    match self.mut_singular_field_or_default(&field_desc) {
+                        ReflectValueMut::Message(m) => is.merge_message_dyn(m)?,
+                    }
+                }
                 RuntimeFieldType::Singular(rtb) => {
                     let pt = ProtobufType::new(rtb, field_desc.proto().type_())?;
                     let value = pt.read(is, wire_type)?;
@@ -422,7 +489,8 @@ impl MessageDyn for DynamicMessage {
                 value: &ReflectValueRef,
                 vt: Type,
             ) -> crate::Result<()> {
-                let entry_data_size = compute_map_entry_field_data_size(key, kt, value, vt);
+                let entry_data_size =
+                    compute_map_entry_field_data_size(key, kt, value, vt, MessageSize::Cached);
                 self.os.write_tag(number, WireType::LengthDelimited)?;
                 self.os.write_raw_varint32(entry_data_size as u32)?;
                 singular_write_to(kt, 1, key, self.os)?;
@@ -430,6 +498,10 @@ impl MessageDyn for DynamicMessage {
                 Ok(())
             }
 
+            fn sort_map_entries(&self) -> bool {
+                self.os.is_deterministic()
+            }
+
             fn unknown_fields(&mut self, unknown_fields: &UnknownFields) -> crate::Result<()> {
                 self.os.write_unknown_fields(unknown_fields)
             }
@@ -452,7 +524,7 @@ impl MessageDyn for DynamicMessage {
                 number: u32,
                 value: &ReflectValueRef,
             ) -> crate::Result<()> {
-                self.m_size += compute_singular_size(t, number, value);
+                self.m_size += compute_singular_size(t, number, value, MessageSize::Compute);
                 Ok(())
             }
 
@@ -474,7 +546,8 @@ impl MessageDyn for DynamicMessage {
                 value: &ReflectValueRef,
                 vt: Type,
             ) -> crate::Result<()> {
-                let entry_data_size = compute_map_entry_field_data_size(key, kt, value, vt);
+                let entry_data_size =
+                    compute_map_entry_field_data_size(key, kt, value, vt, MessageSize::Compute);
                 self.m_size += tag_size(number)
                     + compute_raw_varint32_size(entry_data_size as u32)
                     + entry_data_size;
@@ -492,6 +565,7 @@ impl MessageDyn for DynamicMessage {
         self.for_each_field_to_write(&mut handler)
             .expect("compute_size should not fail");
 
+        self.special_fields.cached_size().set(handler.m_size as u32);
         handler.m_size
     }
 
@@ -499,13 +573,20 @@ impl MessageDyn for DynamicMessage {
         &self.special_fields
     }
 
+    fn clear_dyn(&mut self) {
+        for field in &mut self.fields {
+            field.clear();
+        }
+        self.special_fields.clear();
+    }
+
     fn mut_special_fields_dyn(&mut self) -> &mut SpecialFields {
         &mut self.special_fields
     }
 }
 
 /// Write singular field to output stream
-fn singular_write_to(
+pub(crate) fn singular_write_to(
     proto_type: Type,
     field_number: u32,
     v: &ReflectValueRef,
@@ -518,11 +599,11 @@ fn singular_write_to(
         }
         Type::TYPE_MESSAGE => {
             let msg_v = v.to_message().unwrap();
-            os.write_message_dyn(field_number, &*msg_v)
-        }
-        Type::TYPE_GROUP => {
-            unimplemented!()
+            os.write_tag(field_number, WireType::LengthDelimited)?;
+            os.write_raw_varint32(msg_v.special_fields_dyn().cached_size().get())?;
+            msg_v.write_to_with_cached_sizes_dyn(os)
         }
+        Type::TYPE_GROUP => Err(ProtobufError::GroupIsNotImplemented.into()),
         Type::TYPE_UINT32 => os.write_uint32(field_number, v.to_u32().unwrap()),
         Type::TYPE_UINT64 => os.write_uint64(field_number, v.to_u64().unwrap()),
         Type::TYPE_INT32 => os.write_int32(field_number, v.to_i32().unwrap()),
@@ -541,8 +622,22 @@ fn singular_write_to(
     }
 }
 
+/// How to get the size of nested messages.
+#[derive(Copy, Clone)]
+enum MessageSize {
+    /// Compute the size, also caching it in nested messages.
+    Compute,
+    /// Use the size cached by the previous `compute_size_dyn` call.
+    Cached,
+}
+
 /// Compute singular field size
-fn compute_singular_size(proto_type: Type, field_number: u32, v: &ReflectValueRef) -> u64 {
+fn compute_singular_size(
+    proto_type: Type,
+    field_number: u32,
+    v: &ReflectValueRef,
+    message_size: MessageSize,
+) -> u64 {
     match proto_type {
         Type::TYPE_ENUM => {
             let enum_v = v.to_enum_value().unwrap();
@@ -550,7 +645,10 @@ fn compute_singular_size(proto_type: Type, field_number: u32, v: &ReflectValueRe
         }
         Type::TYPE_MESSAGE => {
             let msg_v = v.to_message().unwrap();
-            let len = msg_v.compute_size_dyn();
+            let len = match message_size {
+                MessageSize::Compute => msg_v.compute_size_dyn(),
+                MessageSize::Cached => msg_v.special_fields_dyn().cached_size().get() as u64,
+            };
             tag_size(field_number) + compute_raw_varint64_size(len) + len
         }
         Type::TYPE_GROUP => {
@@ -625,7 +723,7 @@ fn compute_repeated_packed_size(
     }
 }
 
-fn repeated_write_to(
+pub(crate) fn repeated_write_to(
     proto_type: Type,
     field_number: u32,
     v: &ReflectRepeatedRef,
@@ -658,8 +756,106 @@ fn compute_map_entry_field_data_size(
     kt: Type,
     value: &ReflectValueRef,
     vt: Type,
+    message_size: MessageSize,
 ) -> u64 {
-    let key_size = compute_singular_size(kt, 1, key);
-    let value_size = compute_singular_size(vt, 2, value);
+    let key_size = compute_singular_size(kt, 1, key, message_size);
+    let value_size = compute_singular_size(vt, 2, value, message_size);
     key_size + value_size
 }
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::MessageDescriptor;
+    use crate::reflect::ReflectValueBox;
+    use crate::text_format;
+    use crate::MessageDyn;
+
+    const FILE: &str = r#"
+        name: "node.proto"
+        syntax: "proto3"
+        message_type {
+            name: "Node"
+            field { name: "id" number: 1 label: LABEL_OPTIONAL type: TYPE_INT32 }
+            field { name: "children" number: 2 label: LABEL_REPEATED type: TYPE_MESSAGE type_name: ".Node.ChildrenEntry" }
+            field { name: "number" number: 3 label: LABEL_OPTIONAL type: TYPE_INT32 oneof_index: 0 }
+            field { name: "text" number: 4 label: LABEL_OPTIONAL type: TYPE_STRING oneof_index: 0 }
+            field { name: "next" number: 5 label: LABEL_OPTIONAL type: TYPE_MESSAGE type_name: ".Node" }
+            nested_type {
+                name: "ChildrenEntry"
+                field { name: "key" number: 1 label: LABEL_OPTIONAL type: TYPE_STRING }
+                field { name: "value" number: 2 label: LABEL_OPTIONAL type: TYPE_MESSAGE type_name: ".Node" }
+                options { map_entry: true }
+            }
+            oneof_decl { name: "kind" }
+        }
+    "#;
+
+    fn node_descriptor() -> MessageDescriptor {
+        let proto: FileDescriptorProto = text_format::parse_from_str(FILE).unwrap();
+        let file = FileDescriptor::new_dynamic(proto, &[]).unwrap();
+        file.message_by_package_relative_name("Node").unwrap()
+    }
+
+    fn node(
+        d: &MessageDescriptor,
+        id: i32,
+        next: Option<Box<dyn MessageDyn>>,
+    ) -> Box<dyn MessageDyn> {
+        let mut m = d.new_instance();
+        d.field_by_name("id")
+            .unwrap()
+            .set_singular_field(&mut *m, ReflectValueBox::I32(id));
+        if let Some(next) = next {
+            d.field_by_name("next")
+                .unwrap()
+                .set_singular_field(&mut *m, ReflectValueBox::Message(next));
+        }
+        m
+    }
+
+    #[test]
+    fn compute_size_matches_written_bytes() {
+        let d = node_descriptor();
+
+        let mut m = node(&d, 1, Some(node(&d, 2, Some(node(&d, 3, None)))));
+        // Zero value in oneof must be written in proto3.
+        d.field_by_name("number")
+            .unwrap()
+            .set_singular_field(&mut *m, ReflectValueBox::I32(0));
+        {
+            let children = d.field_by_name("children").unwrap();
+            let mut children = children.mut_map(&mut *m);
+            for i in 0..10 {
+                let child = node(&d, 1000 + i, Some(node(&d, i, None)));
+                children.insert(
+                    ReflectValueBox::String(format!("c{}", i)),
+                    ReflectValueBox::Message(child),
+                );
+            }
+        }
+        m.mut_special_fields_dyn()
+            .mut_unknown_fields()
+            .add_length_delimited(100, vec![1, 2, 3]);
+
+        let size = m.compute_size_dyn();
+        assert_eq!(size as u32, m.special_fields_dyn().cached_size().get());
+        let next = d.field_by_name("next").unwrap().get_message(&*m);
+        assert_eq!(
+            next.compute_size_dyn() as u32,
+            next.special_fields_dyn().cached_size().get()
+        );
+
+        let bytes = m.write_to_bytes_dyn().unwrap();
+        assert_eq!(size, bytes.len() as u64);
+
+        let parsed = d.parse_from_bytes(&bytes).unwrap();
+        assert!(d.field_by_name("number").unwrap().has_field(&*parsed));
+        assert_eq!(
+            10,
+            d.field_by_name("children").unwrap().get_map(&*parsed).len()
+        );
+        assert_eq!(size, parsed.compute_size_dyn());
+    }
+}
@@ -2,14 +2,34 @@
 pub(crate) enum ReflectError {
     #[error("Message `{}` not found in files: {}", .0, .1)]
     MessageNotFoundInFiles(String, String),
+    #[error("Enum `{}` not found in files: {}", .0, .1)]
+    EnumNotFoundInFiles(String, String),
+    #[error("Message `{0}` not found in registry")]
+    MessageNotFoundInRegistry(String),
     #[error("Dependency `{}` of `{}` not found; all deps: {}", .0, .1, .2)]
     DependencyNotFound(String, String, String),
     #[error("Non-unique dependencies given: {}", .0)]
     NonUniqueDependencies(String),
     #[error("Non-unique field name: `{0}`")]
     NonUniqueFieldName(String),
+    #[error("Field `{0}` number {1} is already used by field `{2}`")]
+    NonUniqueFieldNumber(String, i32, String),
+    #[error("Field `{0}` number {1} is out of range 1..={2}")]
+    FieldNumberOutOfRange(String, i32, u32),
+    #[error(
+        "Field `{0}` number {1} is in range 19000..=19999 reserved for protobuf implementation"
+    )]
+    FieldNumberReservedForImplementation(String, i32),
+    #[error("Field `{0}` number {1} is reserved in message `{2}`")]
+    FieldNumberReserved(String, i32, String),
+    #[error("Field name `{0}` is reserved in message `{1}`")]
+    FieldNameReserved(String, String),
+    #[error("JSON name `{0}` of field `{1}` conflicts with field `{2}`")]
+    JsonNameConflict(String, String, String),
     #[error("Non-unique file descriptor: `{0}`")]
     NonUniqueFileDescriptor(String),
+    #[error("Non-unique type name: `{0}`")]
+    NonUniqueTypeName(String),
     #[error("Cycle in provided file descriptors")]
     CycleInFileDescriptors,
     #[error("Map entry message name must end with `Entry`")]
@@ -21,6 +41,16 @@ pub(crate) enum ReflectError {
         numbered 1 and 2 and named `key` and `value`"
     )]
     MapEntryIncorrectFields,
+    #[error("Field mask path `{0}` is not valid for message `{1}`")]
+    IncorrectFieldMaskPath(String, String),
     #[error("Could not parse default value for field {0}")]
     CouldNotParseDefaultValueForField(String),
+    #[error("Field `{0}` not found in message `{1}`")]
+    FieldNotFound(String, String),
+    #[error("Field `{0}` is not singular")]
+    FieldIsNotSingular(String),
+    #[error("Path `{0}` goes through a field which is not a singular message")]
+    FieldIsNotSingularMessage(String),
+    #[error("Cannot parse `{0}` as {2} for field `{1}`")]
+    CannotParseFieldValue(String, String, String),
 }
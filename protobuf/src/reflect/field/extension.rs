@@ -0,0 +1,422 @@
+//! Reflective access to extension fields.
+//!
+//! Extensions are not stored in messages explicitly: when a message is parsed,
+//! values of extension fields end up in the message [`UnknownFields`].
+//! Accessors here decode values from unknown fields and encode them back
+//! according to the extension field type.
+
+use crate::descriptor::field_descriptor_proto::Type;
+use crate::error::ProtobufError;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::dynamic::repeated::DynamicRepeated;
+use crate::reflect::dynamic::repeated_write_to;
+use crate::reflect::dynamic::singular_write_to;
+use crate::reflect::field::index::FieldKind;
+use crate::reflect::field::protobuf_field_type::ProtobufFieldType;
+use crate::reflect::protobuf_type_box::ProtobufType;
+use crate::reflect::repeated::ReflectRepeated;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::ReflectRepeatedMut;
+use crate::reflect::ReflectRepeatedRef;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::RuntimeType;
+use crate::CodedInputStream;
+use crate::CodedOutputStream;
+use crate::UnknownFields;
+
+impl FieldDescriptor {
+    /// Is this field an extension field.
+    pub fn is_extension(&self) -> bool {
+        matches!(self.index().kind, FieldKind::Extension(..))
+    }
+
+    fn check_extension_of(&self, m: &dyn MessageDyn) {
+        assert!(self.is_extension(), "not an extension field: {}", self);
+        assert_eq!(
+            self.containing_message(),
+            m.descriptor_dyn(),
+            "extension {} applied to a message of different type",
+            self
+        );
+    }
+
+    /// Groups resolve to message type, but are encoded differently,
+    /// so refuse to write them rather than produce wrong wire format.
+    fn check_extension_not_group(&self) -> crate::Result<()> {
+        if self.proto().type_() == Type::TYPE_GROUP {
+            return Err(ProtobufError::GroupIsNotImplemented.into());
+        }
+        Ok(())
+    }
+
+    fn extension_number(&self) -> u32 {
+        self.number() as u32
+    }
+
+    fn singular_extension_type(&self) -> ProtobufType {
+        match self.protobuf_field_type() {
+            ProtobufFieldType::Singular(t) => t,
+            _ => panic!("not a singular extension: {}", self),
+        }
+    }
+
+    fn repeated_extension_type(&self) -> ProtobufType {
+        match self.protobuf_field_type() {
+            ProtobufFieldType::Repeated(t) => t,
+            _ => panic!("not a repeated extension: {}", self),
+        }
+    }
+
+    /// Check if extension is present in the message unknown fields.
+    ///
+    /// # Panics
+    ///
+    /// If this field is not an extension of given message type.
+    pub fn has_extension(&self, m: &dyn MessageDyn) -> bool {
+        self.check_extension_of(m);
+        m.unknown_fields_dyn()
+            .get(self.extension_number())
+            .is_some()
+    }
+
+    /// Decode singular extension value from the message unknown fields.
+    ///
+    /// If the value occurs several times, the last one is returned,
+    /// and message values are merged, like when parsing regular fields.
+    ///
+    /// # Panics
+    ///
+    /// If this field is not a singular extension of given message type.
+    pub fn get_extension(&self, m: &dyn MessageDyn) -> crate::Result<Option<ReflectValueBox>> {
+        self.check_extension_of(m);
+        let t = self.singular_extension_type();
+        let bytes = match encoded_values(m.unknown_fields_dyn(), self.extension_number()) {
+            Some(bytes) => bytes,
+            None => return Ok(None),
+        };
+
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        let mut value: Option<ReflectValueBox> = None;
+        while !is.eof()? {
+            let (_, wire_type) = is.read_tag_unpack()?;
+            match (&mut value, t.runtime()) {
+                (Some(ReflectValueBox::Message(message)), RuntimeType::Message(_)) => {
+                    let len = is.read_raw_varint64()?;
+                    let old_limit = is.push_limit(len)?;
+                    message.merge_from_dyn(&mut is)?;
+                    is.pop_limit(old_limit);
+                }
+                _ => value = Some(t.read(&mut is, wire_type)?),
+            }
+        }
+        Ok(value)
+    }
+
+    /// Decode repeated extension values from the message unknown fields.
+    ///
+    /// Both packed and non-packed encodings are accepted.
+    ///
+    /// # Panics
+    ///
+    /// If this field is not a repeated extension of given message type.
+    pub fn get_repeated_extension(
+        &self,
+        m: &dyn MessageDyn,
+    ) -> crate::Result<Vec<ReflectValueBox>> {
+        self.check_extension_of(m);
+        let t = self.repeated_extension_type();
+        let bytes = match encoded_values(m.unknown_fields_dyn(), self.extension_number()) {
+            Some(bytes) => bytes,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut repeated = DynamicRepeated::new(t.runtime().clone());
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        while !is.eof()? {
+            let (_, wire_type) = is.read_tag_unpack()?;
+            t.read_repeated_into(
+                &mut is,
+                wire_type,
+                &mut ReflectRepeatedMut::new(&mut repeated),
+            )?;
+        }
+        Ok(ReflectRepeatedRef::new(&repeated)
+            .into_iter()
+            .map(|v| v.to_box())
+            .collect())
+    }
+
+    /// Replace singular extension value in the message unknown fields.
+    ///
+    /// Returns an error if the value cannot be encoded (e. g. group extensions
+    /// are not supported); the message is not modified in that case.
+    ///
+    /// # Panics
+    ///
+    /// If this field is not a singular extension of given message type,
+    /// or value is of different type.
+    pub fn set_extension(
+        &self,
+        m: &mut dyn MessageDyn,
+        value: ReflectValueBox,
+    ) -> crate::Result<()> {
+        self.check_extension_of(m);
+        let t = self.singular_extension_type();
+        assert_eq!(t.runtime(), &value.get_type(), "wrong value type");
+        self.check_extension_not_group()?;
+
+        let value = value.as_value_ref();
+        if let Some(message) = value.to_message() {
+            // Writing nested message requires cached sizes.
+            message.compute_size_dyn();
+        }
+        let mut bytes = Vec::new();
+        let mut os = CodedOutputStream::vec(&mut bytes);
+        singular_write_to(t.t(), self.extension_number(), &value, &mut os)?;
+        os.flush()?;
+        drop(os);
+
+        self.replace_extension_values(m, &bytes);
+        Ok(())
+    }
+
+    /// Replace repeated extension values in the message unknown fields.
+    ///
+    /// Values are packed if the extension is declared with `[packed = true]`.
+    ///
+    /// Returns an error if the values cannot be encoded (e. g. group extensions
+    /// are not supported); the message is not modified in that case.
+    ///
+    /// # Panics
+    ///
+    /// If this field is not a repeated extension of given message type,
+    /// or values are of different type.
+    pub fn set_repeated_extension(
+        &self,
+        m: &mut dyn MessageDyn,
+        values: Vec<ReflectValueBox>,
+    ) -> crate::Result<()> {
+        self.check_extension_of(m);
+        let t = self.repeated_extension_type();
+        self.check_extension_not_group()?;
+
+        let mut repeated = DynamicRepeated::new(t.runtime().clone());
+        for value in values {
+            repeated.push(value);
+        }
+        let repeated = ReflectRepeatedRef::new(&repeated);
+
+        let mut bytes = Vec::new();
+        let mut os = CodedOutputStream::vec(&mut bytes);
+        if self.proto().options.get_or_default().packed() && !repeated.is_empty() {
+            repeated_write_to(t.t(), self.extension_number(), &repeated, &mut os)?;
+        } else {
+            for value in &repeated {
+                if let Some(message) = value.to_message() {
+                    message.compute_size_dyn();
+                }
+                singular_write_to(t.t(), self.extension_number(), &value, &mut os)?;
+            }
+        }
+        os.flush()?;
+        drop(os);
+
+        self.replace_extension_values(m, &bytes);
+        Ok(())
+    }
+
+    /// Remove extension values from the message unknown fields.
+    ///
+    /// # Panics
+    ///
+    /// If this field is not an extension of given message type.
+    pub fn clear_extension(&self, m: &mut dyn MessageDyn) {
+        self.check_extension_of(m);
+        m.mut_unknown_fields_dyn().remove(self.extension_number());
+    }
+
+    /// Replace unknown values of this extension with values encoded in `bytes`.
+    fn replace_extension_values(&self, m: &mut dyn MessageDyn, bytes: &[u8]) {
+        let unknown_fields = m.mut_unknown_fields_dyn();
+        unknown_fields.remove(self.extension_number());
+        let mut is = CodedInputStream::from_bytes(bytes);
+        while !is.eof().expect("read from bytes") {
+            let (number, wire_type) = is.read_tag_unpack().expect("valid tag");
+            let value = is
+                .read_unknown_with_number(number, wire_type)
+                .expect("valid value");
+            unknown_fields.add_value(number, value);
+        }
+    }
+}
+
+/// Values of given field number encoded with tags.
+fn encoded_values(unknown_fields: &UnknownFields, number: u32) -> Option<Vec<u8>> {
+    let values = unknown_fields.values(number)?;
+    let mut bytes = Vec::new();
+    let mut os = CodedOutputStream::vec(&mut bytes);
+    for value in values {
+        os.write_unknown(number, value).expect("write to vec");
+    }
+    os.flush().expect("write to vec");
+    drop(os);
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FieldDescriptor;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::MessageDescriptor;
+    use crate::reflect::ReflectValueBox;
+    use crate::text_format;
+
+    const FILE: &str = r#"
+        name: "ext.proto"
+        message_type {
+            name: "Foo"
+            field { name: "id" number: 1 label: LABEL_OPTIONAL type: TYPE_INT32 }
+            extension_range { start: 100 end: 200 }
+        }
+        extension { name: "count" number: 100 label: LABEL_OPTIONAL type: TYPE_SINT64 extendee: ".Foo" }
+        extension { name: "label" number: 101 label: LABEL_OPTIONAL type: TYPE_STRING extendee: ".Foo" }
+        extension { name: "nested" number: 102 label: LABEL_OPTIONAL type: TYPE_MESSAGE type_name: ".Foo" extendee: ".Foo" }
+        extension { name: "ints" number: 103 label: LABEL_REPEATED type: TYPE_INT32 extendee: ".Foo" }
+        extension { name: "packed" number: 104 label: LABEL_REPEATED type: TYPE_INT32 extendee: ".Foo" options { packed: true } }
+        extension { name: "group" number: 105 label: LABEL_OPTIONAL type: TYPE_GROUP type_name: ".Foo" extendee: ".Foo" }
+    "#;
+
+    fn file() -> FileDescriptor {
+        let proto: FileDescriptorProto = text_format::parse_from_str(FILE).unwrap();
+        FileDescriptor::new_dynamic(proto, &[]).unwrap()
+    }
+
+    fn extension(file: &FileDescriptor, name: &str) -> FieldDescriptor {
+        file.extensions().find(|e| e.name() == name).unwrap()
+    }
+
+    fn foo(d: &MessageDescriptor, id: i32) -> ReflectValueBox {
+        let mut m = d.new_instance();
+        d.field_by_name("id")
+            .unwrap()
+            .set_singular_field(&mut *m, ReflectValueBox::I32(id));
+        ReflectValueBox::Message(m)
+    }
+
+    #[test]
+    fn round_trip() {
+        let file = file();
+        let d = file.message_by_package_relative_name("Foo").unwrap();
+        let count = extension(&file, "count");
+        let label = extension(&file, "label");
+        let nested = extension(&file, "nested");
+        let ints = extension(&file, "ints");
+        let packed = extension(&file, "packed");
+        assert!(count.is_extension());
+        assert!(!d.field_by_name("id").unwrap().is_extension());
+
+        let mut m = d.new_instance();
+        assert!(!count.has_extension(&*m));
+        assert_eq!(None, count.get_extension(&*m).unwrap());
+        count
+            .set_extension(&mut *m, ReflectValueBox::I64(-3))
+            .unwrap();
+        count
+            .set_extension(&mut *m, ReflectValueBox::I64(-5))
+            .unwrap();
+        label
+            .set_extension(&mut *m, ReflectValueBox::String("abc".to_owned()))
+            .unwrap();
+        nested.set_extension(&mut *m, foo(&d, 7)).unwrap();
+        ints.set_repeated_extension(
+            &mut *m,
+            vec![ReflectValueBox::I32(1), ReflectValueBox::I32(2)],
+        )
+        .unwrap();
+        packed
+            .set_repeated_extension(
+                &mut *m,
+                vec![ReflectValueBox::I32(3), ReflectValueBox::I32(4)],
+            )
+            .unwrap();
+
+        let bytes = m.write_to_bytes_dyn().unwrap();
+        // Packed values are written as a single length-delimited value.
+        assert!(bytes.ends_with(&[0xc2, 0x06, 0x02, 0x03, 0x04]));
+        let m = d.parse_from_bytes(&bytes).unwrap();
+
+        assert!(count.has_extension(&*m));
+        assert_eq!(
+            Some(ReflectValueBox::I64(-5)),
+            count.get_extension(&*m).unwrap()
+        );
+        assert_eq!(
+            Some(ReflectValueBox::String("abc".to_owned())),
+            label.get_extension(&*m).unwrap()
+        );
+        assert_eq!(Some(foo(&d, 7)), nested.get_extension(&*m).unwrap());
+        assert_eq!(
+            vec![ReflectValueBox::I32(1), ReflectValueBox::I32(2)],
+            ints.get_repeated_extension(&*m).unwrap()
+        );
+        assert_eq!(
+            vec![ReflectValueBox::I32(3), ReflectValueBox::I32(4)],
+            packed.get_repeated_extension(&*m).unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_message_occurrences() {
+        let file = file();
+        let d = file.message_by_package_relative_name("Foo").unwrap();
+        let nested = extension(&file, "nested");
+
+        // nested { id: 1 } nested { nested { } }
+        let m = d
+            .parse_from_bytes(&[
+                0xb2, 0x06, 0x02, 0x08, 0x01, 0xb2, 0x06, 0x03, 0xb2, 0x06, 0x00,
+            ])
+            .unwrap();
+        let value = nested.get_extension(&*m).unwrap().unwrap();
+        let value = match value {
+            ReflectValueBox::Message(m) => m,
+            v => panic!("{:?}", v),
+        };
+        assert_eq!(
+            Some(ReflectValueBox::I32(1)),
+            d.field_by_name("id")
+                .unwrap()
+                .get_singular(&*value)
+                .map(|v| v.to_box())
+        );
+        assert!(nested.has_extension(&*value));
+    }
+
+    #[test]
+    fn clear() {
+        let file = file();
+        let d = file.message_by_package_relative_name("Foo").unwrap();
+        let ints = extension(&file, "ints");
+        let mut m = d.new_instance();
+        ints.set_repeated_extension(&mut *m, vec![ReflectValueBox::I32(1)])
+            .unwrap();
+        assert!(ints.has_extension(&*m));
+        ints.clear_extension(&mut *m);
+        assert!(!ints.has_extension(&*m));
+        assert_eq!(
+            Vec::<ReflectValueBox>::new(),
+            ints.get_repeated_extension(&*m).unwrap()
+        );
+    }
+
+    #[test]
+    fn set_group_is_error() {
+        let file = file();
+        let d = file.message_by_package_relative_name("Foo").unwrap();
+        let group = extension(&file, "group");
+        let mut m = d.new_instance();
+        assert!(group.set_extension(&mut *m, foo(&d, 1)).is_err());
+        assert!(!group.has_extension(&*m));
+    }
+}
@@ -0,0 +1,188 @@
+//! Editions features of fields.
+//!
+//! Bundled `descriptor.proto` predates editions and has no `features` fields,
+//! so features set in options are read from unknown fields.
+
+use crate::descriptor::FieldDescriptorProto;
+use crate::reflect::file::building::FileDescriptorBuilding;
+use crate::unknown::UnknownFields;
+use crate::wire::SliceInputStream;
+use crate::wire::WireRead;
+use crate::wire::WireType;
+use crate::Message;
+
+/// `features` field number in `FileOptions`.
+const FILE_OPTIONS_FEATURES: u32 = 50;
+/// `features` field number in `MessageOptions`.
+const MESSAGE_OPTIONS_FEATURES: u32 = 12;
+/// `features` field number in `FieldOptions`.
+const FIELD_OPTIONS_FEATURES: u32 = 21;
+/// `field_presence` field number in `FeatureSet`.
+const FEATURE_SET_FIELD_PRESENCE: u32 = 1;
+
+/// `FeatureSet.FieldPresence`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FieldPresence {
+    Explicit,
+    Implicit,
+    LegacyRequired,
+}
+
+impl FieldPresence {
+    fn from_u32(value: u32) -> Option<FieldPresence> {
+        match value {
+            1 => Some(FieldPresence::Explicit),
+            2 => Some(FieldPresence::Implicit),
+            3 => Some(FieldPresence::LegacyRequired),
+            _ => None,
+        }
+    }
+}
+
+/// `field_presence` from `features` stored in options unknown fields.
+fn field_presence_in_options(
+    unknown_fields: &UnknownFields,
+    features_number: u32,
+) -> Option<FieldPresence> {
+    let mut presence = None;
+    // Repeated occurrences of `features` are merged, last value wins.
+    for features in &unknown_fields.values(features_number)?.length_delimited {
+        let mut is = SliceInputStream::new(features);
+        while !is.eof() {
+            let read = match is.read_tag() {
+                Ok((FEATURE_SET_FIELD_PRESENCE, WireType::Varint)) => is
+                    .read_uint32()
+                    .map(|v| presence = FieldPresence::from_u32(v).or(presence)),
+                Ok((_, wire_type)) => is.skip_field(wire_type),
+                Err(e) => Err(e),
+            };
+            if read.is_err() {
+                break;
+            }
+        }
+    }
+    presence
+}
+
+/// `field_presence` feature resolved from field, enclosing messages and file options.
+///
+/// `containing_message` is the message where the field is declared.
+pub(crate) fn field_presence(
+    field: &FieldDescriptorProto,
+    containing_message: Option<usize>,
+    building: &FileDescriptorBuilding,
+) -> FieldPresence {
+    if let Some(presence) = field_presence_in_options(
+        field.options.get_or_default().unknown_fields(),
+        FIELD_OPTIONS_FEATURES,
+    ) {
+        return presence;
+    }
+    let mut message = containing_message;
+    while let Some(m) = message {
+        let m = &building.messages[m];
+        if let Some(presence) = field_presence_in_options(
+            m.proto.options.get_or_default().unknown_fields(),
+            MESSAGE_OPTIONS_FEATURES,
+        ) {
+            return presence;
+        }
+        message = m.enclosing_message;
+    }
+    field_presence_in_options(
+        building
+            .current_file_descriptor
+            .options
+            .get_or_default()
+            .unknown_fields(),
+        FILE_OPTIONS_FEATURES,
+    )
+    // Edition 2023 default.
+    .unwrap_or(FieldPresence::Explicit)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::field_descriptor_proto::Label;
+    use crate::descriptor::field_descriptor_proto::Type;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
+    use crate::Message;
+
+    const IMPLICIT: &[u8] = &[0x08, 0x02];
+    const EXPLICIT: &[u8] = &[0x08, 0x01];
+
+    fn field(name: &str, number: i32, features: Option<&[u8]>) -> FieldDescriptorProto {
+        let mut field = FieldDescriptorProto::new();
+        field.set_name(name.to_owned());
+        field.set_number(number);
+        field.set_label(Label::LABEL_OPTIONAL);
+        field.set_type(Type::TYPE_INT32);
+        if let Some(features) = features {
+            field
+                .options
+                .mut_or_insert_default()
+                .mut_unknown_fields()
+                .add_length_delimited(super::FIELD_OPTIONS_FEATURES, features.to_vec());
+        }
+        field
+    }
+
+    #[test]
+    fn field_presence_protoc_descriptor() {
+        // Descriptor like one produced by `protoc` for:
+        //
+        // edition = "2023";
+        // option features.field_presence = IMPLICIT;
+        // message Outer {
+        //   option features.field_presence = EXPLICIT;
+        //   int32 a = 1;
+        //   int32 b = 2 [features.field_presence = IMPLICIT];
+        //   message Inner { int32 c = 1; }
+        // }
+        // message Plain { int32 d = 1; }
+        let mut file = FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        file.set_syntax("editions".to_owned());
+        file.options
+            .mut_or_insert_default()
+            .mut_unknown_fields()
+            .add_length_delimited(super::FILE_OPTIONS_FEATURES, IMPLICIT.to_vec());
+
+        let mut inner = DescriptorProto::new();
+        inner.set_name("Inner".to_owned());
+        inner.field.push(field("c", 1, None));
+
+        let mut outer = DescriptorProto::new();
+        outer.set_name("Outer".to_owned());
+        outer
+            .options
+            .mut_or_insert_default()
+            .mut_unknown_fields()
+            .add_length_delimited(super::MESSAGE_OPTIONS_FEATURES, EXPLICIT.to_vec());
+        outer.field.push(field("a", 1, None));
+        outer.field.push(field("b", 2, Some(IMPLICIT)));
+        outer.nested_type.push(inner);
+        file.message_type.push(outer);
+
+        let mut plain = DescriptorProto::new();
+        plain.set_name("Plain".to_owned());
+        plain.field.push(field("d", 1, None));
+        file.message_type.push(plain);
+
+        let file = FileDescriptor::new_dynamic(file, &[]).unwrap();
+        let has_presence = |message: &str, field: &str| {
+            file.message_by_package_relative_name(message)
+                .unwrap()
+                .field_by_name(field)
+                .unwrap()
+                .has_presence()
+        };
+        assert!(has_presence("Outer", "a"));
+        assert!(!has_presence("Outer", "b"));
+        assert!(has_presence("Outer.Inner", "c"));
+        assert!(!has_presence("Plain", "d"));
+    }
+}
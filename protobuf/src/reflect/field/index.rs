@@ -5,6 +5,8 @@ use crate::descriptor::FieldDescriptorProto;
 use crate::descriptor::FileDescriptorProto;
 use crate::owning_ref::OwningRef;
 use crate::reflect::error::ReflectError;
+use crate::reflect::field::features;
+use crate::reflect::field::features::FieldPresence;
 use crate::reflect::field::protobuf_field_type::ProtobufFieldType;
 use crate::reflect::file::building::FileDescriptorBuilding;
 use crate::reflect::protobuf_type_box::ProtobufType;
@@ -102,6 +104,8 @@ pub(crate) struct FieldIndex {
     pub(crate) json_name: String,
     pub(crate) field_type: ForwardProtobufFieldType,
     pub(crate) default_value: Option<FieldDefaultValue>,
+    /// Resolved `features.field_presence`, only used in editions files.
+    pub(crate) field_presence: FieldPresence,
 }
 
 impl FieldIndex {
@@ -109,7 +113,7 @@ impl FieldIndex {
         field: &FieldDescriptorProto,
         building: &FileDescriptorBuilding,
     ) -> crate::Result<FieldDefaultValue> {
-        let en = building.find_enum(field.type_name());
+        let en = building.find_enum(field.type_name())?;
         let (n, _) = match en
             .value
             .iter()
@@ -176,12 +180,14 @@ impl FieldIndex {
         };
 
         let field_type = building.resolve_field_type(&field)?;
+        let field_presence = features::field_presence(&field, containing_message, building);
         Ok(FieldIndex {
             proto: field,
             kind,
             default_value,
             json_name,
             field_type,
+            field_presence,
         })
     }
 
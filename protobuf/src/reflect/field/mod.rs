@@ -10,6 +10,7 @@ use crate::reflect::acc::v2::AccessorV2;
 use crate::reflect::acc::GeneratedFieldAccessor;
 use crate::reflect::dynamic::DynamicMessage;
 use crate::reflect::field::dynamic::DynamicFieldDescriptorRef;
+use crate::reflect::field::features::FieldPresence;
 use crate::reflect::field::index::FieldIndex;
 use crate::reflect::field::index::FieldKind;
 use crate::reflect::field::protobuf_field_type::ProtobufFieldType;
@@ -31,8 +32,11 @@ use crate::reflect::ReflectOptionalRef;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
 use crate::reflect::RuntimeType;
+use crate::reflect::Syntax;
 
 pub(crate) mod dynamic;
+mod extension;
+mod features;
 pub(crate) mod index;
 pub(crate) mod protobuf_field_type;
 pub(crate) mod runtime_field_type;
@@ -260,6 +264,32 @@ impl FieldDescriptor {
         self.proto().label() == field_descriptor_proto::Label::LABEL_REQUIRED
     }
 
+    /// Can this field distinguish unset value from value set to default.
+    ///
+    /// Repeated and map fields have no presence.
+    /// Singular fields have presence in proto2, if they are message or required fields,
+    /// members of oneof (including proto3 `optional` fields), extensions,
+    /// and in editions unless `features.field_presence` is `IMPLICIT`.
+    pub fn has_presence(&self) -> bool {
+        if self.is_repeated_or_map() {
+            return false;
+        }
+        if matches!(
+            self.proto().type_(),
+            field_descriptor_proto::Type::TYPE_MESSAGE | field_descriptor_proto::Type::TYPE_GROUP
+        ) || self.is_required()
+            || self.containing_oneof_including_synthetic().is_some()
+            || matches!(self.index().kind, FieldKind::Extension(..))
+        {
+            return true;
+        }
+        match self.file_descriptor().syntax() {
+            Syntax::Proto2 => true,
+            Syntax::Proto3 => false,
+            Syntax::Editions => self.index().field_presence != FieldPresence::Implicit,
+        }
+    }
+
     /// If this field repeated or map?
     pub fn is_repeated_or_map(&self) -> bool {
         self.proto().label() == field_descriptor_proto::Label::LABEL_REPEATED
@@ -298,6 +328,9 @@ impl FieldDescriptor {
     /// For repeated field or map field return `true` if
     /// collection is not empty.
     ///
+    /// For proto3 field without `optional` label return `true`
+    /// if field value is not default.
+    ///
     /// # Panics
     ///
     /// If this field belongs to a different message type.
@@ -373,13 +406,39 @@ impl FieldDescriptor {
     /// # Panics
     ///
     /// If this field belongs to a different message type or
-    /// field type is not singular message.
+    /// field type is not singular message,
+    /// or if the field is a lazy message field which cannot be decoded.
     pub fn mut_message<'a>(&self, m: &'a mut dyn MessageDyn) -> &'a mut dyn MessageDyn {
         match self.mut_singular_field_or_default(m) {
             ReflectValueMut::Message(m) => m,
         }
     }
 
+    /// Get a mutable reference to a message field.
+    /// Initialize field with default message if unset.
+    ///
+    /// Return an error if the field is a lazy message field
+    /// which cannot be decoded, the field is unchanged in that case.
+    ///
+    /// # Panics
+    ///
+    /// If this field belongs to a different message type or
+    /// field type is not singular message.
+    pub fn try_mut_message<'a>(
+        &self,
+        m: &'a mut dyn MessageDyn,
+    ) -> crate::Result<&'a mut dyn MessageDyn> {
+        let value = match self.singular() {
+            SingularFieldAccessorRef::Generated(g) => g.accessor.try_mut_field_or_default(m)?,
+            SingularFieldAccessorRef::Dynamic(..) => {
+                DynamicMessage::downcast_mut(m).mut_singular_field_or_default(self)
+            }
+        };
+        match value {
+            ReflectValueMut::Message(m) => Ok(m),
+        }
+    }
+
     /// Default value.
     ///
     /// # Panics
@@ -389,6 +448,21 @@ impl FieldDescriptor {
         self.index().default_value(self)
     }
 
+    /// Default value of singular scalar, string, bytes or enum field.
+    ///
+    /// This is `[default = ...]` field option in proto2,
+    /// or zero value of the field type if the option is not specified
+    /// (which is always the case in proto3).
+    ///
+    /// Return `None` for message, repeated and map fields.
+    pub fn default_value(&self) -> Option<ReflectValueRef<'_>> {
+        match self.runtime_field_type() {
+            RuntimeFieldType::Singular(RuntimeType::Message(_)) => None,
+            RuntimeFieldType::Singular(_) => Some(self.singular_default_value()),
+            RuntimeFieldType::Repeated(_) | RuntimeFieldType::Map(..) => None,
+        }
+    }
+
     /// Get singular field value.
     ///
     /// Return field default value if field is unset.
@@ -439,6 +513,8 @@ impl FieldDescriptor {
     }
 
     /// Clear a field.
+    ///
+    /// Singular field becomes unset, repeated or map field becomes empty.
     pub fn clear_field(&self, m: &mut dyn MessageDyn) {
         if self.is_singular() {
             match self.singular() {
@@ -565,10 +641,16 @@ pub(crate) enum FieldDescriptorImplRef<'a> {
 mod test {
     use std::collections::HashMap;
 
+    use crate::descriptor::field_options::CType;
     use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldOptions;
+    use crate::descriptor::FileOptions;
     use crate::reflect::ReflectValueBox;
+    use crate::reflect::ReflectValueRef;
+    use crate::well_known_types::duration::Duration;
     use crate::well_known_types::struct_::Struct;
     use crate::well_known_types::struct_::Value;
+    use crate::Enum;
     use crate::MessageFull;
 
     #[test]
@@ -582,4 +664,41 @@ mod test {
             field.to_string()
         );
     }
+
+    #[test]
+    fn default_value() {
+        // proto2 explicit defaults.
+        let file_options = FileOptions::descriptor();
+        let cc_enable_arenas = file_options.field_by_name("cc_enable_arenas").unwrap();
+        assert_eq!(
+            Some(ReflectValueRef::Bool(true)),
+            cc_enable_arenas.default_value()
+        );
+        let ctype = FieldOptions::descriptor().field_by_name("ctype").unwrap();
+        assert_eq!(
+            Some(CType::STRING),
+            ctype
+                .default_value()
+                .unwrap()
+                .to_enum_value()
+                .and_then(CType::from_i32)
+        );
+        // proto2 without explicit default.
+        let java_package = file_options.field_by_name("java_package").unwrap();
+        assert_eq!(
+            Some(ReflectValueRef::String("")),
+            java_package.default_value()
+        );
+
+        // proto3 zero default.
+        let seconds = Duration::descriptor().field_by_name("seconds").unwrap();
+        assert_eq!(Some(ReflectValueRef::I64(0)), seconds.default_value());
+
+        // No default value.
+        let descriptor = DescriptorProto::descriptor();
+        for name in ["options", "field"] {
+            let field = descriptor.field_by_name(name).unwrap();
+            assert!(field.default_value().is_none(), "{}", name);
+        }
+    }
 }
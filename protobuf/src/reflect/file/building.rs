@@ -30,27 +30,26 @@ impl<'a> FileDescriptorBuilding<'a> {
             .chain(self.deps_with_public.iter().map(|d| d.proto()))
     }
 
-    pub fn find_enum(&self, full_name: &str) -> &'a EnumDescriptorProto {
+    pub fn find_enum(&self, full_name: &str) -> crate::Result<&'a EnumDescriptorProto> {
         assert!(full_name.starts_with("."));
 
         for file in self.all_descriptors() {
             if let Some(name_to_package) =
                 protobuf_name_starts_with_package(full_name, file.package())
             {
-                if let Some((_, me)) = find_message_or_enum(file, name_to_package) {
-                    match me {
-                        MessageOrEnum::Enum(e) => return e,
-                        MessageOrEnum::Message(_) => panic!("not an enum: {}", full_name),
-                    }
+                if let Some((_, MessageOrEnum::Enum(e))) =
+                    find_message_or_enum(file, name_to_package)
+                {
+                    return Ok(e);
                 }
             }
         }
 
-        panic!(
-            "enum not found: {}, in files: {}",
-            full_name,
-            self.all_files_str()
-        );
+        Err(self.enum_not_found(full_name))
+    }
+
+    fn enum_not_found(&self, full_name: &str) -> crate::Error {
+        ReflectError::EnumNotFoundInFiles(full_name.to_owned(), self.all_files_str()).into()
     }
 
     fn all_files_str(&self) -> String {
@@ -112,11 +111,7 @@ impl<'a> FileDescriptorBuilding<'a> {
                         return Ok(ForwardProtobufTypeBox::enumeration(m));
                     }
                 }
-                panic!(
-                    "enum not found: {}; files: {}",
-                    field.type_name(),
-                    self.all_files_str()
-                );
+                return Err(self.enum_not_found(field.type_name()));
             }
             t => ForwardProtobufTypeBox::from_proto_type(t),
         })
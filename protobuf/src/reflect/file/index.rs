@@ -4,6 +4,7 @@ use std::ops::Range;
 use crate::descriptor::field_descriptor_proto::Label;
 use crate::descriptor::DescriptorProto;
 use crate::descriptor::EnumDescriptorProto;
+use crate::descriptor::FieldDescriptorProto;
 use crate::descriptor::FileDescriptorProto;
 use crate::owning_ref::OwningRef;
 use crate::reflect::error::ReflectError;
@@ -14,6 +15,8 @@ use crate::reflect::message::is_initialized_is_always_true::compute_is_initializ
 use crate::reflect::name::concat_paths;
 use crate::reflect::service::index::ServiceIndex;
 use crate::reflect::FileDescriptor;
+use crate::wire_format::FIELD_NUMBER_MAX;
+use crate::wire_format::FIELD_NUMBER_RESERVED;
 
 #[derive(Debug)]
 pub(crate) struct MessageIndices {
@@ -359,6 +362,49 @@ impl FileDescriptorCommon {
         Ok(())
     }
 
+    fn check_field_number(
+        message: &DescriptorProto,
+        field: &FieldDescriptorProto,
+    ) -> crate::Result<()> {
+        let number = field.number();
+        if number < 1 || number as u32 > FIELD_NUMBER_MAX {
+            return Err(ReflectError::FieldNumberOutOfRange(
+                field.name().to_owned(),
+                number,
+                FIELD_NUMBER_MAX,
+            )
+            .into());
+        }
+        if FIELD_NUMBER_RESERVED.contains(&(number as u32)) {
+            return Err(ReflectError::FieldNumberReservedForImplementation(
+                field.name().to_owned(),
+                number,
+            )
+            .into());
+        }
+        // Reserved range end is exclusive.
+        if message
+            .reserved_range
+            .iter()
+            .any(|r| r.start() <= number && number < r.end())
+        {
+            return Err(ReflectError::FieldNumberReserved(
+                field.name().to_owned(),
+                number,
+                message.name().to_owned(),
+            )
+            .into());
+        }
+        if message.reserved_name.iter().any(|n| n == field.name()) {
+            return Err(ReflectError::FieldNameReserved(
+                field.name().to_owned(),
+                message.name().to_owned(),
+            )
+            .into());
+        }
+        Ok(())
+    }
+
     fn index_message(
         message_index: usize,
         proto: &OwningRef<FileDescriptorProto, DescriptorProto>,
@@ -380,25 +426,40 @@ impl FileDescriptorCommon {
         for (i, f) in proto.field.iter().enumerate() {
             let field_index = &fields[first_field_index + i];
 
-            if index_by_number.insert(f.number() as u32, i).is_some() {
-                return Err(ReflectError::NonUniqueFieldName(f.name().to_owned()).into());
+            Self::check_field_number(proto, f)?;
+
+            if let Some(prev) = index_by_number.insert(f.number() as u32, i) {
+                return Err(ReflectError::NonUniqueFieldNumber(
+                    f.name().to_owned(),
+                    f.number(),
+                    proto.field[prev].name().to_owned(),
+                )
+                .into());
             }
             if index_by_name.insert(f.name().to_owned(), i).is_some() {
                 return Err(ReflectError::NonUniqueFieldName(f.name().to_owned()).into());
             }
-            if index_by_name_or_json_name
-                .insert(f.name().to_owned(), i)
-                .is_some()
-            {
-                return Err(ReflectError::NonUniqueFieldName(f.name().to_owned()).into());
+            // Field names are unique, so the name can only conflict
+            // with a JSON name of a previous field.
+            if let Some(prev) = index_by_name_or_json_name.insert(f.name().to_owned(), i) {
+                return Err(ReflectError::JsonNameConflict(
+                    f.name().to_owned(),
+                    proto.field[prev].name().to_owned(),
+                    f.name().to_owned(),
+                )
+                .into());
             }
 
             if field_index.json_name != f.name() {
-                if index_by_name_or_json_name
-                    .insert(field_index.json_name.clone(), i)
-                    .is_some()
+                if let Some(prev) =
+                    index_by_name_or_json_name.insert(field_index.json_name.clone(), i)
                 {
-                    return Err(ReflectError::NonUniqueFieldName(f.name().to_owned()).into());
+                    return Err(ReflectError::JsonNameConflict(
+                        field_index.json_name.clone(),
+                        f.name().to_owned(),
+                        proto.field[prev].name().to_owned(),
+                    )
+                    .into());
                 }
             }
         }
@@ -1,12 +1,21 @@
 use crate::descriptor::FileDescriptorProto;
 
 /// `.proto` file syntax.
+///
+/// More variants may be added in the future,
+/// so `match` on this enum needs a wildcard arm.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Syntax {
     /// `syntax = "proto2"`.
     Proto2,
     /// `syntax = "proto3"`.
     Proto3,
+    /// `edition = "..."`.
+    ///
+    /// Semantics of fields (e. g. presence) is defined by features,
+    /// not by the syntax.
+    Editions,
 }
 
 impl Syntax {
@@ -14,6 +23,7 @@ impl Syntax {
         match syntax {
             "" | "proto2" => Some(Syntax::Proto2),
             "proto3" => Some(Syntax::Proto3),
+            "editions" => Some(Syntax::Editions),
             _ => None,
         }
     }
@@ -139,6 +139,15 @@ impl<'a> ReflectMapRef<'a> {
         }
     }
 
+    /// Iterate over map entries in unspecified order.
+    pub fn iter(&self) -> ReflectMapRefIter<'_> {
+        let iter = match &self.imp {
+            ReflectMapRefImpl::Generated(map) => map.reflect_iter(),
+            ReflectMapRefImpl::DynamicEmpty(map) => map.reflect_iter(),
+        };
+        ReflectMapRefIter { iter }
+    }
+
     /// Map key type
     pub fn key_type(&self) -> RuntimeType {
         match &self.imp {
@@ -226,6 +235,13 @@ impl<'a> ReflectMapMut<'a> {
         self.map.get(key)
     }
 
+    /// Iterate over map entries in unspecified order.
+    pub fn iter(&self) -> ReflectMapRefIter<'_> {
+        ReflectMapRefIter {
+            iter: self.map.reflect_iter(),
+        }
+    }
+
     /// Insert a value into the map.
     ///
     /// # Panics
@@ -279,3 +295,116 @@ impl<'a, 'b: 'a> IntoIterator for &'b ReflectMapRef<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::MessageDescriptor;
+    use crate::reflect::ReflectValueBox;
+    use crate::reflect::ReflectValueRef;
+    use crate::text_format;
+
+    const FILE: &str = r#"
+        name: "maps.proto"
+        syntax: "proto3"
+        message_type {
+            name: "Maps"
+            field { name: "id" number: 1 label: LABEL_OPTIONAL type: TYPE_INT32 }
+            field { name: "counts" number: 2 label: LABEL_REPEATED type: TYPE_MESSAGE type_name: ".Maps.CountsEntry" }
+            field { name: "children" number: 3 label: LABEL_REPEATED type: TYPE_MESSAGE type_name: ".Maps.ChildrenEntry" }
+            nested_type {
+                name: "CountsEntry"
+                field { name: "key" number: 1 label: LABEL_OPTIONAL type: TYPE_STRING }
+                field { name: "value" number: 2 label: LABEL_OPTIONAL type: TYPE_INT32 }
+                options { map_entry: true }
+            }
+            nested_type {
+                name: "ChildrenEntry"
+                field { name: "key" number: 1 label: LABEL_OPTIONAL type: TYPE_INT32 }
+                field { name: "value" number: 2 label: LABEL_OPTIONAL type: TYPE_MESSAGE type_name: ".Maps" }
+                options { map_entry: true }
+            }
+        }
+    "#;
+
+    fn maps_descriptor() -> MessageDescriptor {
+        let proto: FileDescriptorProto = text_format::parse_from_str(FILE).unwrap();
+        let file = FileDescriptor::new_dynamic(proto, &[]).unwrap();
+        file.message_by_package_relative_name("Maps").unwrap()
+    }
+
+    #[test]
+    fn iter_string_to_int32() {
+        let d = maps_descriptor();
+        let counts = d.field_by_name("counts").unwrap();
+        let mut m = d.new_instance();
+
+        let mut map = counts.mut_map(&mut *m);
+        assert_eq!(0, map.iter().count());
+        for (k, v) in [("a", 1), ("b", -2), ("c", 3)] {
+            map.insert(ReflectValueBox::from(k.to_owned()), ReflectValueBox::I32(v));
+        }
+        assert_eq!(3, map.iter().count());
+
+        let map = counts.get_map(&*m);
+        assert_eq!(3, map.len());
+        assert_eq!(
+            Some(ReflectValueRef::I32(-2)),
+            map.get(ReflectValueRef::String("b"))
+        );
+        assert_eq!(None, map.get(ReflectValueRef::String("d")));
+        let entries: BTreeMap<String, i32> = map
+            .iter()
+            .map(|(k, v)| (k.to_str().unwrap().to_owned(), v.to_i32().unwrap()))
+            .collect();
+        assert_eq!(
+            BTreeMap::from([
+                ("a".to_owned(), 1),
+                ("b".to_owned(), -2),
+                ("c".to_owned(), 3)
+            ]),
+            entries
+        );
+
+        counts.mut_map(&mut *m).clear();
+        assert!(counts.get_map(&*m).is_empty());
+    }
+
+    #[test]
+    fn iter_int32_to_message() {
+        let d = maps_descriptor();
+        let id = d.field_by_name("id").unwrap();
+        let children = d.field_by_name("children").unwrap();
+        let mut m = d.new_instance();
+
+        for key in [10, -20] {
+            let mut child = d.new_instance();
+            id.set_singular_field(&mut *child, ReflectValueBox::I32(key * 2));
+            children
+                .mut_map(&mut *m)
+                .insert(ReflectValueBox::I32(key), ReflectValueBox::Message(child));
+        }
+
+        let map = children.get_map(&*m);
+        let mut entries: Vec<(i32, i32)> = map
+            .iter()
+            .map(|(k, v)| {
+                let child = v.to_message().unwrap();
+                let child_id = id.get_singular_field_or_default(&*child).to_i32().unwrap();
+                (k.to_i32().unwrap(), child_id)
+            })
+            .collect();
+        entries.sort();
+        assert_eq!(vec![(-20, -40), (10, 20)], entries);
+
+        let child = map.get(ReflectValueRef::I32(10)).unwrap();
+        let child = child.to_message().unwrap();
+        assert_eq!(
+            ReflectValueRef::I32(20),
+            id.get_singular_field_or_default(&*child)
+        );
+    }
+}
@@ -15,6 +15,9 @@ use crate::reflect::EnumDescriptor;
 use crate::reflect::FieldDescriptor;
 use crate::reflect::FileDescriptor;
 use crate::reflect::OneofDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::Syntax;
 use crate::CodedInputStream;
 
 pub(crate) mod generated;
@@ -106,6 +109,11 @@ impl MessageDescriptor {
         &self.file_descriptor
     }
 
+    /// Syntax of the file containing this message.
+    pub fn syntax(&self) -> Syntax {
+        self.file_descriptor.syntax()
+    }
+
     /// `FileDescriptorProto` containg this message type
     pub fn file_descriptor_proto(&self) -> &FileDescriptorProto {
         self.file_descriptor().proto()
@@ -132,6 +140,9 @@ impl MessageDescriptor {
 
     /// New empty message.
     ///
+    /// For generated messages this is the message `Default`,
+    /// for messages of dynamic descriptors this is an empty dynamic message.
+    ///
     /// # Panics
     ///
     /// If this message is a map entry message.
@@ -182,6 +193,53 @@ impl MessageDescriptor {
         }
     }
 
+    /// Merge `src` message into `dst` message.
+    ///
+    /// Merge semantics is the same as for parsing serialized `src` into `dst`:
+    /// set singular fields overwrite, repeated fields are appended,
+    /// message fields are merged recursively, map entries are inserted by key,
+    /// unknown fields are appended. Setting a field in `oneof` clears other oneof fields.
+    ///
+    /// Return an error if a lazy message field of `dst` cannot be decoded,
+    /// `dst` may be partially merged in that case.
+    ///
+    /// # Panics
+    ///
+    /// Is any message has different type than this descriptor.
+    pub fn merge_from(&self, dst: &mut dyn MessageDyn, src: &dyn MessageDyn) -> crate::Result<()> {
+        assert_eq!(self, &dst.descriptor_dyn());
+        assert_eq!(self, &src.descriptor_dyn());
+
+        for field in self.fields() {
+            match field.get_reflect(src) {
+                ReflectFieldRef::Optional(v) => match v.value() {
+                    Some(ReflectValueRef::Message(m)) => {
+                        m.descriptor_dyn()
+                            .merge_from(field.try_mut_message(dst)?, &*m)?;
+                    }
+                    Some(v) => field.set_singular_field(dst, v.to_box()),
+                    None => {}
+                },
+                ReflectFieldRef::Repeated(r) => {
+                    let mut dst_repeated = field.mut_repeated(dst);
+                    for i in 0..r.len() {
+                        dst_repeated.push(r.get(i).to_box());
+                    }
+                }
+                ReflectFieldRef::Map(m) => {
+                    let mut dst_map = field.mut_map(dst);
+                    for (k, v) in &m {
+                        dst_map.insert(k.to_box(), v.to_box());
+                    }
+                }
+            }
+        }
+
+        dst.mut_unknown_fields_dyn()
+            .merge_from(src.unknown_fields_dyn());
+        Ok(())
+    }
+
     /// Similar to `eq`, but considers `NaN` values equal.
     ///
     /// # Panics
@@ -204,7 +262,7 @@ impl MessageDescriptor {
                 return false;
             }
         }
-        true
+        !mode.compare_unknown_fields || a.unknown_fields_dyn() == b.unknown_fields_dyn()
     }
 
     pub(crate) fn reflect_eq_maybe_unrelated(
@@ -347,7 +405,15 @@ mod test {
     use crate::descriptor::field_descriptor_proto::Type;
     use crate::descriptor::DescriptorProto;
     use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::descriptor::MessageOptions;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::ReflectValueBox;
+    use crate::reflect::Syntax;
+    use crate::well_known_types::struct_::Struct;
+    use crate::well_known_types::struct_::Value;
     use crate::EnumFull;
+    use crate::Message;
     use crate::MessageFull;
 
     #[test]
@@ -377,4 +443,260 @@ mod test {
         );
         assert_eq!(None, DescriptorProto::descriptor().enclosing_message());
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Too slow on Miri.
+    fn merge_from_repeated_and_nested() {
+        let mut dst = DescriptorProto::new();
+        dst.set_name("Apple".to_owned());
+        dst.field.push(FieldDescriptorProto::new());
+        dst.field[0].set_name("weight".to_owned());
+        dst.options.mut_or_insert_default().set_map_entry(false);
+
+        let mut src = DescriptorProto::new();
+        src.field.push(FieldDescriptorProto::new());
+        src.field[0].set_name("color".to_owned());
+        src.options.mut_or_insert_default().set_deprecated(true);
+        src.mut_unknown_fields().add_varint(1000, 17);
+
+        DescriptorProto::descriptor()
+            .merge_from(&mut dst, &src)
+            .unwrap();
+
+        let mut expected = DescriptorProto::new();
+        expected.set_name("Apple".to_owned());
+        expected.field.push(FieldDescriptorProto::new());
+        expected.field[0].set_name("weight".to_owned());
+        expected.field.push(FieldDescriptorProto::new());
+        expected.field[1].set_name("color".to_owned());
+        let mut options = MessageOptions::new();
+        options.set_map_entry(false);
+        options.set_deprecated(true);
+        expected.options = Some(options).into();
+        expected.mut_unknown_fields().add_varint(1000, 17);
+        assert_eq!(expected, dst);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Too slow on Miri.
+    fn merge_from_oneof_and_map() {
+        fn number(n: f64) -> Value {
+            let mut value = Value::new();
+            value.set_number_value(n);
+            value
+        }
+
+        fn string(s: &str) -> Value {
+            let mut value = Value::new();
+            value.set_string_value(s.to_owned());
+            value
+        }
+
+        let mut dst = Struct::new();
+        dst.fields.insert("apple".to_owned(), number(1.0));
+        dst.fields.insert("pear".to_owned(), string("green"));
+
+        let mut src = Struct::new();
+        src.fields.insert("pear".to_owned(), number(2.0));
+
+        Struct::descriptor().merge_from(&mut dst, &src).unwrap();
+
+        assert_eq!(2, dst.fields.len());
+        assert_eq!(number(1.0), dst.fields["apple"]);
+        assert_eq!(number(2.0), dst.fields["pear"]);
+
+        let mut dst = string("green");
+        Value::descriptor()
+            .merge_from(&mut dst, &number(2.0))
+            .unwrap();
+        assert_eq!(number(2.0), dst);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Too slow on Miri.
+    fn new_instance_generated() {
+        let descriptor = DescriptorProto::descriptor();
+        let mut message = descriptor.new_instance();
+        assert_eq!(
+            Some(&DescriptorProto::new()),
+            message.downcast_ref::<DescriptorProto>()
+        );
+
+        descriptor
+            .field_by_name("name")
+            .unwrap()
+            .set_singular_field(&mut *message, ReflectValueBox::from("Apple".to_owned()));
+
+        let mut expected = DescriptorProto::new();
+        expected.set_name("Apple".to_owned());
+        assert_eq!(
+            expected.write_to_bytes().unwrap(),
+            message.write_to_bytes_dyn().unwrap()
+        );
+    }
+
+    #[test]
+    fn syntax() {
+        assert_eq!(Syntax::Proto2, DescriptorProto::descriptor().syntax());
+        assert_eq!(Syntax::Proto3, Struct::descriptor().syntax());
+
+        for (text, expected) in [
+            ("", Syntax::Proto2),
+            ("proto2", Syntax::Proto2),
+            ("proto3", Syntax::Proto3),
+            ("editions", Syntax::Editions),
+        ] {
+            let mut message = DescriptorProto::new();
+            message.set_name("Apple".to_owned());
+            let mut file = FileDescriptorProto::new();
+            file.set_name("apple.proto".to_owned());
+            file.set_syntax(text.to_owned());
+            file.message_type.push(message);
+
+            let file = FileDescriptor::new_dynamic(file, &[]).unwrap();
+            assert_eq!(expected, file.syntax());
+            let descriptor = file.message_by_package_relative_name("Apple").unwrap();
+            assert_eq!(expected, descriptor.syntax(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Too slow on Miri.
+    fn new_instance_dynamic() {
+        let mut field = FieldDescriptorProto::new();
+        field.set_name("weight".to_owned());
+        field.set_number(1);
+        field.set_type(Type::TYPE_INT32);
+        let mut message = DescriptorProto::new();
+        message.set_name("Apple".to_owned());
+        message.field.push(field);
+        let mut file = FileDescriptorProto::new();
+        file.set_name("apple.proto".to_owned());
+        file.message_type.push(message);
+
+        let file = FileDescriptor::new_dynamic(file, &[]).unwrap();
+        let descriptor = file.message_by_package_relative_name("Apple").unwrap();
+        let mut message = descriptor.new_instance();
+        assert_eq!(descriptor, message.descriptor_dyn());
+
+        descriptor
+            .field_by_name("weight")
+            .unwrap()
+            .set_singular_field(&mut *message, ReflectValueBox::I32(150));
+        assert_eq!(
+            vec![0x08, 0x96, 0x01],
+            message.write_to_bytes_dyn().unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Too slow on Miri.
+    fn json_name() {
+        fn apple(fields: &str) -> crate::Result<FileDescriptor> {
+            let file: FileDescriptorProto = crate::text_format::parse_from_str(&format!(
+                r#"name: "apple.proto" message_type {{ name: "Apple" {} }}"#,
+                fields
+            ))
+            .unwrap();
+            FileDescriptor::new_dynamic(file, &[])
+        }
+
+        let file = apple(
+            r#"field { name: "red_apple" number: 1 type: TYPE_INT32 json_name: "rouge" }
+               field { name: "green_apple" number: 2 type: TYPE_INT32 }"#,
+        )
+        .unwrap();
+        let descriptor = file.message_by_package_relative_name("Apple").unwrap();
+        let red = descriptor.field_by_name("red_apple").unwrap();
+        assert_eq!("rouge", red.json_name());
+        assert!(descriptor.field_by_name_or_json_name("rouge") == Some(red));
+        assert!(descriptor.field_by_name_or_json_name("redApple").is_none());
+        assert_eq!(
+            "greenApple",
+            descriptor.field_by_name("green_apple").unwrap().json_name()
+        );
+
+        let err = apple(
+            r#"field { name: "a" number: 1 type: TYPE_INT32 json_name: "b" }
+               field { name: "b" number: 2 type: TYPE_INT32 }"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            "JSON name `b` of field `a` conflicts with field `b`",
+            err.to_string()
+        );
+
+        let err = apple(
+            r#"field { name: "a" number: 1 type: TYPE_INT32 json_name: "c" }
+               field { name: "b" number: 2 type: TYPE_INT32 json_name: "c" }"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            "JSON name `c` of field `b` conflicts with field `a`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Too slow on Miri.
+    fn field_numbers() {
+        fn apple(message: &str) -> crate::Result<FileDescriptor> {
+            let file: FileDescriptorProto = crate::text_format::parse_from_str(&format!(
+                r#"name: "apple.proto" message_type {{ name: "Apple" {} }}"#,
+                message
+            ))
+            .unwrap();
+            FileDescriptor::new_dynamic(file, &[])
+        }
+
+        apple(
+            r#"field { name: "a" number: 1 type: TYPE_INT32 }
+               field { name: "b" number: 536870911 type: TYPE_INT32 }
+               reserved_range { start: 2 end: 4 }
+               reserved_name: "c""#,
+        )
+        .unwrap();
+
+        let err = apple(r#"field { name: "a" number: 19500 type: TYPE_INT32 }"#).unwrap_err();
+        assert_eq!(
+            "Field `a` number 19500 is in range 19000..=19999 reserved for protobuf implementation",
+            err.to_string()
+        );
+
+        let err = apple(r#"field { name: "a" number: 0 type: TYPE_INT32 }"#).unwrap_err();
+        assert_eq!(
+            "Field `a` number 0 is out of range 1..=536870911",
+            err.to_string()
+        );
+
+        let err = apple(
+            r#"field { name: "a" number: 1 type: TYPE_INT32 }
+               field { name: "b" number: 1 type: TYPE_INT32 }"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            "Field `b` number 1 is already used by field `a`",
+            err.to_string()
+        );
+
+        let err = apple(
+            r#"field { name: "a" number: 3 type: TYPE_INT32 }
+               reserved_range { start: 2 end: 4 }"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            "Field `a` number 3 is reserved in message `Apple`",
+            err.to_string()
+        );
+
+        let err = apple(
+            r#"field { name: "c" number: 1 type: TYPE_INT32 }
+               reserved_name: "c""#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            "Field name `c` is reserved in message `Apple`",
+            err.to_string()
+        );
+    }
 }
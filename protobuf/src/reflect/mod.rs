@@ -25,9 +25,11 @@ pub(crate) mod message;
 mod oneof;
 mod optional;
 mod protobuf_type_box;
+mod registry;
 mod repeated;
 mod runtime_type_box;
 mod service;
+mod set_from_str;
 mod type_dynamic;
 pub(crate) mod value;
 
@@ -55,6 +57,7 @@ pub use self::file::syntax::Syntax;
 pub use self::file::FileDescriptor;
 pub use self::map::ReflectMapMut;
 pub use self::map::ReflectMapRef;
+pub use self::map::ReflectMapRefIter;
 #[doc(hidden)]
 pub use self::message::generated::GeneratedMessageDescriptorData;
 pub use self::message::message_ref::MessageRef;
@@ -63,13 +66,16 @@ pub use self::message::MessageDescriptor;
 pub use self::oneof::generated::GeneratedOneofDescriptorData;
 pub use self::oneof::OneofDescriptor;
 pub use self::optional::ReflectOptionalRef;
+pub use self::reflect_eq::message_equals;
 pub use self::reflect_eq::ReflectEq;
 pub use self::reflect_eq::ReflectEqMode;
+pub use self::registry::DescriptorRegistry;
 pub use self::repeated::ReflectRepeatedMut;
 pub use self::repeated::ReflectRepeatedRef;
 pub use self::runtime_type_box::RuntimeType;
 pub use self::service::MethodDescriptor;
 pub use self::service::ServiceDescriptor;
+pub use self::set_from_str::set_field_from_str;
 pub use self::value::value_box::ReflectValueBox;
 pub use self::value::value_ref::ReflectValueRef;
 pub use self::value::ProtobufValue;
@@ -1,8 +1,13 @@
+use crate::reflect::MessageDescriptor;
+use crate::MessageDyn;
+
 /// Parameter for [`ReflectEq`].
 #[derive(Debug, Default)]
 pub struct ReflectEqMode {
     /// When `true`, `NaN` values are considered equal to each other.
     pub nan_equal: bool,
+    /// When `true`, unknown fields of messages are compared too.
+    pub compare_unknown_fields: bool,
     _non_exhausitve: (),
 }
 
@@ -31,3 +36,99 @@ pub trait ReflectEq {
     /// Perform the equality comparison.
     fn reflect_eq(&self, that: &Self, mode: &ReflectEqMode) -> bool;
 }
+
+/// Compare messages field by field using reflection.
+///
+/// Maps are compared regardless of entry order,
+/// float `NaN` is not equal to anything including itself.
+/// With `ignore_unknown` unknown fields (including in nested messages) are not compared.
+///
+/// Messages of different types are never equal.
+///
+/// # Examples
+///
+/// ```
+/// # use protobuf::reflect::message_equals;
+/// # use protobuf::well_known_types::duration::Duration;
+/// # use protobuf::Message;
+/// let a = Duration::new();
+/// let mut b = Duration::new();
+/// b.mut_unknown_fields().add_varint(100, 1);
+/// assert!(!message_equals(&a, &b, false));
+/// assert!(message_equals(&a, &b, true));
+/// ```
+pub fn message_equals(a: &dyn MessageDyn, b: &dyn MessageDyn, ignore_unknown: bool) -> bool {
+    let mut mode = ReflectEqMode::default();
+    mode.compare_unknown_fields = !ignore_unknown;
+    MessageDescriptor::reflect_eq_maybe_unrelated(a, b, &mode)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::reflect::message_equals;
+    use crate::reflect::ReflectEqMode;
+    use crate::well_known_types::struct_::Struct;
+    use crate::well_known_types::struct_::Value;
+    use crate::Message;
+    use crate::MessageDyn;
+
+    fn number(n: f64) -> Value {
+        let mut v = Value::new();
+        v.set_number_value(n);
+        v
+    }
+
+    fn make_struct(fields: &[(&str, f64)]) -> Struct {
+        let mut s = Struct::new();
+        for (k, n) in fields {
+            s.fields.insert(k.to_string(), number(*n));
+        }
+        s
+    }
+
+    #[test]
+    fn map_order() {
+        let names: Vec<String> = (0..100).map(|i| format!("f{}", i)).collect();
+        let fields: Vec<(&str, f64)> = names.iter().map(|n| (&n[..], 1.0)).collect();
+        let a = make_struct(&fields);
+        let b = make_struct(&fields.iter().rev().cloned().collect::<Vec<_>>());
+        assert!(message_equals(&a, &b, false));
+        assert!(!message_equals(&a, &make_struct(&fields[1..]), false));
+        assert!(!message_equals(&a, &Struct::new(), false));
+    }
+
+    #[test]
+    fn nan() {
+        let a = make_struct(&[("x", f64::NAN)]);
+        assert!(!message_equals(&a, &a.clone(), false));
+        assert!(!message_equals(&a, &a, true));
+        assert!((&a as &dyn MessageDyn).reflect_eq_dyn(&a, &ReflectEqMode::nan_equal()));
+        assert!(!message_equals(&a, &make_struct(&[("x", 0.0)]), false));
+    }
+
+    #[test]
+    fn default_mode_ignores_unknown_fields() {
+        let a = make_struct(&[("x", 1.0)]);
+        let mut b = a.clone();
+        b.mut_unknown_fields().add_varint(100, 1);
+        assert!((&a as &dyn MessageDyn).reflect_eq_dyn(&b, &ReflectEqMode::default()));
+        assert!((&a as &dyn MessageDyn).reflect_eq_dyn(&b, &ReflectEqMode::nan_equal()));
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let a = make_struct(&[("x", 1.0)]);
+        let mut b = a.clone();
+        b.fields
+            .get_mut("x")
+            .unwrap()
+            .mut_unknown_fields()
+            .add_length_delimited(100, vec![1, 2]);
+        assert!(!message_equals(&a, &b, false));
+        assert!(message_equals(&a, &b, true));
+        assert!(message_equals(&b, &b.clone(), false));
+
+        // Messages of different types.
+        assert!(!message_equals(&a, &Value::new() as &dyn MessageDyn, true));
+    }
+}
@@ -0,0 +1,292 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::descriptor::FileDescriptorProto;
+use crate::descriptor::FileDescriptorSet;
+use crate::reflect::error::ReflectError;
+use crate::reflect::EnumDescriptor;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::FileDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::MessageDyn;
+
+/// Index of messages, enums and extensions defined in a set of files,
+/// for example, loaded at runtime from `.fds` file
+/// (produced by `protoc --descriptor_set_out`).
+///
+/// Messages found in the registry are dynamic messages,
+/// which can be used with the reflection API.
+///
+/// # Examples
+///
+/// ```
+/// # use protobuf::descriptor::FileDescriptorSet;
+/// # use protobuf::reflect::DescriptorRegistry;
+/// # fn decode(fds: FileDescriptorSet, bytes: &[u8]) -> protobuf::Result<()> {
+/// let registry = DescriptorRegistry::from_file_descriptor_set(fds)?;
+/// let message = registry.parse_message("foo.bar.Baz", bytes)?;
+/// println!("{}", message);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DescriptorRegistry {
+    files: Vec<FileDescriptor>,
+    messages: HashMap<String, MessageDescriptor>,
+    enums: HashMap<String, EnumDescriptor>,
+    // By full name of extended message.
+    extensions: HashMap<String, Vec<FieldDescriptor>>,
+}
+
+impl DescriptorRegistry {
+    /// Build the registry from files included in a descriptor set.
+    ///
+    /// All dependencies must be present in the set
+    /// (i. e. the set must be produced with `protoc --include_imports`).
+    pub fn from_file_descriptor_set(fds: FileDescriptorSet) -> crate::Result<DescriptorRegistry> {
+        DescriptorRegistry::new(fds.file, &[])
+    }
+
+    /// Build the registry from files in any order.
+    ///
+    /// Dependencies which are not in `protos` are searched in `dependencies`,
+    /// for example, descriptors of generated files like
+    /// [`well_known_types`](crate::well_known_types).
+    /// Types defined in `dependencies` are also included in the registry.
+    pub fn new(
+        protos: Vec<FileDescriptorProto>,
+        dependencies: &[FileDescriptor],
+    ) -> crate::Result<DescriptorRegistry> {
+        let mut files = dependencies.to_vec();
+        files.extend(FileDescriptor::new_dynamic_fds(protos, dependencies)?);
+
+        let mut registry = DescriptorRegistry {
+            files: Vec::new(),
+            messages: HashMap::new(),
+            enums: HashMap::new(),
+            extensions: HashMap::new(),
+        };
+        for file in &files {
+            registry.add_file(file)?;
+        }
+        registry.files = files;
+        Ok(registry)
+    }
+
+    fn add_file(&mut self, file: &FileDescriptor) -> crate::Result<()> {
+        let mut extensions: Vec<FieldDescriptor> = file.extensions().collect();
+        for i in 0..file.common().messages.len() {
+            let message = file.message_by_index(i);
+            extensions.extend(message.extensions());
+            insert_unique(&mut self.messages, message.full_name().to_owned(), message)?;
+        }
+        for i in 0..file.common().enums.len() {
+            let en = EnumDescriptor::new(file.clone(), i);
+            insert_unique(&mut self.enums, en.full_name().to_owned(), en)?;
+        }
+        for extension in extensions {
+            self.extensions
+                .entry(extension.containing_message().full_name().to_owned())
+                .or_default()
+                .push(extension);
+        }
+        Ok(())
+    }
+
+    /// All files in the registry, dependencies first.
+    pub fn files(&self) -> &[FileDescriptor] {
+        &self.files
+    }
+
+    /// Find file by name.
+    pub fn file_by_name(&self, name: &str) -> Option<&FileDescriptor> {
+        self.files.iter().find(|f| f.name() == name)
+    }
+
+    /// Find message by fully-qualified name (e. g. `foo.bar.Baz`),
+    /// leading dot is optional.
+    pub fn message_by_full_name(&self, name: &str) -> Option<MessageDescriptor> {
+        self.messages.get(strip_dot(name)).cloned()
+    }
+
+    /// Find enum by fully-qualified name, leading dot is optional.
+    pub fn enum_by_full_name(&self, name: &str) -> Option<EnumDescriptor> {
+        self.enums.get(strip_dot(name)).cloned()
+    }
+
+    /// Extensions of given message defined in any file of the registry.
+    pub fn extensions_for_message(&self, message: &MessageDescriptor) -> &[FieldDescriptor] {
+        match self.extensions.get(message.full_name()) {
+            Some(extensions) => extensions,
+            None => &[],
+        }
+    }
+
+    /// Create an empty message by fully-qualified name.
+    pub fn new_message(&self, name: &str) -> crate::Result<Box<dyn MessageDyn>> {
+        Ok(self.find_message(name)?.new_instance())
+    }
+
+    /// Parse message by fully-qualified name.
+    pub fn parse_message(&self, name: &str, bytes: &[u8]) -> crate::Result<Box<dyn MessageDyn>> {
+        self.find_message(name)?.parse_from_bytes(bytes)
+    }
+
+    fn find_message(&self, name: &str) -> crate::Result<MessageDescriptor> {
+        match self.message_by_full_name(name) {
+            Some(m) => Ok(m),
+            None => Err(ReflectError::MessageNotFoundInRegistry(name.to_owned()).into()),
+        }
+    }
+}
+
+impl fmt::Debug for DescriptorRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DescriptorRegistry")
+            .field(
+                "files",
+                &self.files.iter().map(|f| f.name()).collect::<Vec<_>>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+fn strip_dot(name: &str) -> &str {
+    name.strip_prefix('.').unwrap_or(name)
+}
+
+fn insert_unique<V>(map: &mut HashMap<String, V>, name: String, value: V) -> crate::Result<()> {
+    match map.entry(name) {
+        Entry::Occupied(e) => Err(ReflectError::NonUniqueTypeName(e.key().clone()).into()),
+        Entry::Vacant(e) => {
+            e.insert(value);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::FileDescriptorSet;
+    use crate::reflect::DescriptorRegistry;
+    use crate::reflect::ReflectValueRef;
+    use crate::text_format;
+    use crate::well_known_types::timestamp::Timestamp;
+    use crate::MessageFull;
+
+    // Files are listed in reverse dependency order.
+    const FDS: &str = r#"
+        file {
+            name: "b.proto"
+            package: "b"
+            dependency: "a.proto"
+            dependency: "google/protobuf/timestamp.proto"
+            message_type {
+                name: "Tree"
+                field { name: "label" number: 1 label: LABEL_OPTIONAL type: TYPE_ENUM type_name: ".a.Label" }
+                field { name: "children" number: 2 label: LABEL_REPEATED type: TYPE_MESSAGE type_name: ".b.Tree" }
+                field { name: "created" number: 3 label: LABEL_OPTIONAL type: TYPE_MESSAGE type_name: ".google.protobuf.Timestamp" }
+                extension_range { start: 100 end: 200 }
+            }
+            extension { name: "weight" number: 100 label: LABEL_OPTIONAL type: TYPE_INT32 extendee: ".b.Tree" }
+        }
+        file {
+            name: "a.proto"
+            package: "a"
+            enum_type {
+                name: "Label"
+                value { name: "NONE" number: 0 }
+                value { name: "LEAF" number: 1 }
+            }
+        }
+    "#;
+
+    fn fds(text: &str) -> FileDescriptorSet {
+        text_format::parse_from_str(text).unwrap()
+    }
+
+    fn registry() -> DescriptorRegistry {
+        DescriptorRegistry::new(
+            fds(FDS).file,
+            &[Timestamp::descriptor().file_descriptor().clone()],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_recursive_message() {
+        let registry = registry();
+
+        let tree = registry.message_by_full_name(".b.Tree").unwrap();
+        let label = tree.field_by_name("label").unwrap();
+        let children = tree.field_by_name("children").unwrap();
+
+        // children { label: LEAF } children { }
+        let bytes = [0x12, 0x02, 0x08, 0x01, 0x12, 0x00];
+        let message = registry.parse_message("b.Tree", &bytes).unwrap();
+        let child = children.get_repeated(&*message).get(0);
+        let child = child.to_message().unwrap();
+        match label.get_singular_field_or_default(&*child) {
+            ReflectValueRef::Enum(_, v) => assert_eq!(1, v),
+            v => panic!("{:?}", v),
+        }
+        assert_eq!(2, children.get_repeated(&*message).len());
+        assert_eq!(&bytes[..], &message.write_to_bytes_dyn().unwrap()[..]);
+    }
+
+    #[test]
+    fn lookup() {
+        let registry = registry();
+        assert_eq!(3, registry.files().len());
+        assert!(registry.file_by_name("a.proto").is_some());
+        assert!(registry.enum_by_full_name("a.Label").is_some());
+        assert!(registry
+            .message_by_full_name("google.protobuf.Timestamp")
+            .is_some());
+        assert!(registry.message_by_full_name("b.Nope").is_none());
+        assert!(registry.new_message("b.Nope").is_err());
+
+        let tree = registry.message_by_full_name("b.Tree").unwrap();
+        let extensions = registry.extensions_for_message(&tree);
+        assert_eq!(1, extensions.len());
+        assert_eq!("weight", extensions[0].name());
+    }
+
+    #[test]
+    fn missing_dependency() {
+        let err = DescriptorRegistry::from_file_descriptor_set(fds(FDS)).unwrap_err();
+        assert!(
+            err.to_string().contains("google/protobuf/timestamp.proto"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn missing_type() {
+        for type_ in ["TYPE_MESSAGE", "TYPE_ENUM"] {
+            let text = format!(
+                r#"file {{
+                    name: "c.proto"
+                    message_type {{
+                        name: "C"
+                        field {{ name: "f" number: 1 label: LABEL_OPTIONAL type: {} type_name: ".Nope" }}
+                    }}
+                }}"#,
+                type_
+            );
+            let err = DescriptorRegistry::from_file_descriptor_set(fds(&text)).unwrap_err();
+            assert!(err.to_string().contains(".Nope"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn duplicate_type() {
+        let text = r#"
+            file { name: "c.proto" message_type { name: "C" } }
+            file { name: "d.proto" message_type { name: "C" } }
+        "#;
+        assert!(DescriptorRegistry::from_file_descriptor_set(fds(text)).is_err());
+    }
+}
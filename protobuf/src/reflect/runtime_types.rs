@@ -4,10 +4,10 @@ use std::collections::HashMap;
 use std::fmt;
 use std::marker;
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use bytes::Bytes;
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use crate::chars::Chars;
 use crate::descriptor::field_descriptor_proto::Type;
 use crate::enum_or_unknown::EnumOrUnknown;
@@ -28,9 +28,9 @@ use crate::reflect::types::ProtobufTypeSfixed64;
 use crate::reflect::types::ProtobufTypeSint32;
 use crate::reflect::types::ProtobufTypeSint64;
 use crate::reflect::types::ProtobufTypeString;
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use crate::reflect::types::ProtobufTypeTokioBytes;
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use crate::reflect::types::ProtobufTypeTokioChars;
 use crate::reflect::types::ProtobufTypeTrait;
 use crate::reflect::types::ProtobufTypeUint32;
@@ -154,11 +154,11 @@ pub struct RuntimeTypeString;
 pub struct RuntimeTypeVecU8;
 
 /// Implementation for [`Bytes`].
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 #[derive(Debug, Copy, Clone)]
 pub struct RuntimeTypeTokioBytes;
 /// Implementation for [`Chars`].
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 #[derive(Debug, Copy, Clone)]
 pub struct RuntimeTypeTokioChars;
 
@@ -658,7 +658,7 @@ impl RuntimeTypeWithDeref for RuntimeTypeVecU8 {
     }
 }
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 impl RuntimeTypeTrait for RuntimeTypeTokioBytes {
     type Value = Bytes;
 
@@ -702,7 +702,7 @@ impl RuntimeTypeTrait for RuntimeTypeTokioBytes {
         ProtobufTypeTokioBytes::get_from_unknown(unknown)
     }
 }
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 impl RuntimeTypeWithDeref for RuntimeTypeTokioBytes {
     type DerefTarget = [u8];
 
@@ -711,7 +711,7 @@ impl RuntimeTypeWithDeref for RuntimeTypeTokioBytes {
     }
 }
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 impl RuntimeTypeTrait for RuntimeTypeTokioChars {
     type Value = Chars;
 
@@ -738,7 +738,7 @@ impl RuntimeTypeTrait for RuntimeTypeTokioChars {
     }
 
     fn as_ref(value: &Chars) -> ReflectValueRef {
-        ReflectValueRef::String(value.as_ref())
+        ReflectValueRef::String(value)
     }
 
     fn is_non_zero(value: &Chars) -> bool {
@@ -754,7 +754,7 @@ impl RuntimeTypeTrait for RuntimeTypeTokioChars {
         ProtobufTypeTokioChars::get_from_unknown(unknown)
     }
 }
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 impl RuntimeTypeWithDeref for RuntimeTypeTokioChars {
     type DerefTarget = str;
 
@@ -762,7 +762,7 @@ impl RuntimeTypeWithDeref for RuntimeTypeTokioChars {
         ReflectValueRef::String(value)
     }
 }
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 impl RuntimeTypeHashable for RuntimeTypeTokioChars {
     fn hash_map_get<'a, V>(map: &'a HashMap<Chars, V>, key: ReflectValueRef) -> Option<&'a V> {
         match key {
@@ -0,0 +1,164 @@
+use crate::reflect::error::ReflectError;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeType;
+use crate::MessageDyn;
+
+/// Set singular field by path like `a.b.c`, parsing the value from string.
+///
+/// Intermediate path components must be singular message fields,
+/// they are initialized if not set.
+///
+/// The value is coerced to the field type:
+/// * numbers are parsed with [`str::parse`], e. g. `42`, `-1.5` or `inf`
+/// * `bool` fields accept `true` and `false`
+/// * enum fields accept value name or number of a known value
+/// * `string` and `bytes` fields take the value as is
+///
+/// Message is not modified if the path does not resolve
+/// or the value cannot be coerced.
+///
+/// # Examples
+///
+/// ```
+/// # use protobuf::descriptor::FieldDescriptorProto;
+/// # use protobuf::descriptor::field_descriptor_proto::Label;
+/// # use protobuf::reflect::set_field_from_str;
+/// let mut field = FieldDescriptorProto::new();
+/// set_field_from_str(&mut field, "number", "42").unwrap();
+/// set_field_from_str(&mut field, "label", "LABEL_REPEATED").unwrap();
+/// set_field_from_str(&mut field, "options.packed", "true").unwrap();
+/// assert_eq!(42, field.number());
+/// assert_eq!(Label::LABEL_REPEATED, field.label());
+/// assert!(field.options.packed());
+/// ```
+pub fn set_field_from_str(
+    message: &mut dyn MessageDyn,
+    path: &str,
+    value: &str,
+) -> crate::Result<()> {
+    let fields = resolve_path(&message.descriptor_dyn(), path)?;
+    let (last, intermediate) = fields.split_last().unwrap();
+    let t = match last.runtime_field_type() {
+        RuntimeFieldType::Singular(t) => t,
+        RuntimeFieldType::Repeated(..) | RuntimeFieldType::Map(..) => {
+            return Err(ReflectError::FieldIsNotSingular(path.to_owned()).into())
+        }
+    };
+    let value = parse_value(value, &t).ok_or_else(|| {
+        ReflectError::CannotParseFieldValue(value.to_owned(), path.to_owned(), t.to_string())
+    })?;
+
+    let mut message = message;
+    for field in intermediate {
+        message = field.try_mut_message(message)?;
+    }
+    last.set_singular_field(message, value);
+    Ok(())
+}
+
+fn resolve_path(descriptor: &MessageDescriptor, path: &str) -> crate::Result<Vec<FieldDescriptor>> {
+    let mut descriptor = descriptor.clone();
+    let mut fields: Vec<FieldDescriptor> = Vec::new();
+    for name in path.split('.') {
+        if let Some(field) = fields.last() {
+            descriptor = match field.runtime_field_type() {
+                RuntimeFieldType::Singular(RuntimeType::Message(m)) => m,
+                _ => return Err(ReflectError::FieldIsNotSingularMessage(path.to_owned()).into()),
+            };
+        }
+        let field = descriptor.field_by_name(name).ok_or_else(|| {
+            ReflectError::FieldNotFound(name.to_owned(), descriptor.full_name().to_owned())
+        })?;
+        fields.push(field);
+    }
+    Ok(fields)
+}
+
+fn parse_value(value: &str, t: &RuntimeType) -> Option<ReflectValueBox> {
+    Some(match t {
+        RuntimeType::I32 => ReflectValueBox::I32(value.parse().ok()?),
+        RuntimeType::I64 => ReflectValueBox::I64(value.parse().ok()?),
+        RuntimeType::U32 => ReflectValueBox::U32(value.parse().ok()?),
+        RuntimeType::U64 => ReflectValueBox::U64(value.parse().ok()?),
+        RuntimeType::F32 => ReflectValueBox::F32(value.parse().ok()?),
+        RuntimeType::F64 => ReflectValueBox::F64(value.parse().ok()?),
+        RuntimeType::Bool => ReflectValueBox::Bool(value.parse().ok()?),
+        RuntimeType::String => ReflectValueBox::String(value.to_owned()),
+        RuntimeType::VecU8 => ReflectValueBox::Bytes(value.as_bytes().to_vec()),
+        RuntimeType::Enum(e) => {
+            let v = match e.value_by_name(value) {
+                Some(v) => v,
+                None => e.value_by_number(value.parse().ok()?)?,
+            };
+            ReflectValueBox::from(v)
+        }
+        RuntimeType::Message(_) => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::field_descriptor_proto::Label;
+    use crate::descriptor::field_options::CType;
+    use crate::descriptor::file_options::OptimizeMode;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::set_field_from_str;
+
+    #[test]
+    fn coerce() {
+        let mut field = FieldDescriptorProto::new();
+        set_field_from_str(&mut field, "number", "-7").unwrap();
+        set_field_from_str(&mut field, "name", "abc").unwrap();
+        set_field_from_str(&mut field, "label", "LABEL_REPEATED").unwrap();
+        set_field_from_str(&mut field, "options.packed", "false").unwrap();
+        set_field_from_str(&mut field, "options.ctype", "1").unwrap();
+        assert_eq!(-7, field.number());
+        assert_eq!("abc", field.name());
+        assert_eq!(Label::LABEL_REPEATED, field.label());
+        assert_eq!(Some(false), field.options.packed);
+        assert_eq!(CType::CORD, field.options.ctype());
+    }
+
+    #[test]
+    fn nested() {
+        let mut file = FileDescriptorProto::new();
+        set_field_from_str(&mut file, "options.java_multiple_files", "true").unwrap();
+        set_field_from_str(&mut file, "options.optimize_for", "LITE_RUNTIME").unwrap();
+        assert!(file.options.java_multiple_files());
+        assert_eq!(OptimizeMode::LITE_RUNTIME, file.options.optimize_for());
+    }
+
+    #[test]
+    fn errors() {
+        let mut field = FieldDescriptorProto::new();
+        let err = set_field_from_str(&mut field, "number", "4x").unwrap_err();
+        assert_eq!(
+            "Cannot parse `4x` as i32 for field `number`",
+            err.to_string()
+        );
+        for (path, value) in [
+            ("packed", "true"),
+            ("options.packed", "yes"),
+            ("label", "LABEL_NOPE"),
+            ("label", "100"),
+            ("name.x", "a"),
+            ("options", "a"),
+        ] {
+            assert!(
+                set_field_from_str(&mut field, path, value).is_err(),
+                "{}",
+                path
+            );
+        }
+        // Intermediate messages are not initialized on error.
+        assert!(field.options.is_none());
+
+        let mut file = FileDescriptorProto::new();
+        let err = set_field_from_str(&mut file, "dependency", "a.proto").unwrap_err();
+        assert_eq!("Field `dependency` is not singular", err.to_string());
+    }
+}
@@ -4,10 +4,10 @@
 
 use std::marker;
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use ::bytes::Bytes;
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use crate::chars::Chars;
 use crate::coded_input_stream::CodedInputStream;
 use crate::coded_output_stream::CodedOutputStream;
@@ -92,6 +92,11 @@ pub(crate) trait ProtobufTypeFixed: ProtobufTypeTrait {
     ///
     /// E. g. it is `4` for `fixed32`
     const ENCODED_SIZE: u32;
+
+    /// Decode value from little-endian bytes.
+    ///
+    /// Slice length must be equal to [`ENCODED_SIZE`](Self::ENCODED_SIZE).
+    fn from_le_bytes(bytes: &[u8]) -> Self::ProtobufValue;
 }
 
 /// `float`
@@ -141,11 +146,11 @@ pub struct ProtobufTypeString;
 pub struct ProtobufTypeBytes;
 
 /// `bytes` as [`Bytes`](bytes::Bytes)
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 #[derive(Copy, Clone)]
 pub struct ProtobufTypeTokioBytes;
 /// `string` as [`Chars`](crate::Chars)
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 #[derive(Copy, Clone)]
 pub struct ProtobufTypeTokioChars;
 
@@ -187,6 +192,10 @@ impl ProtobufTypeTrait for ProtobufTypeFloat {
 
 impl ProtobufTypeFixed for ProtobufTypeFloat {
     const ENCODED_SIZE: u32 = 4;
+
+    fn from_le_bytes(bytes: &[u8]) -> f32 {
+        f32::from_bits(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
 }
 
 impl ProtobufTypeTrait for ProtobufTypeDouble {
@@ -220,6 +229,10 @@ impl ProtobufTypeTrait for ProtobufTypeDouble {
 
 impl ProtobufTypeFixed for ProtobufTypeDouble {
     const ENCODED_SIZE: u32 = 8;
+
+    fn from_le_bytes(bytes: &[u8]) -> f64 {
+        f64::from_bits(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
 }
 
 impl ProtobufTypeTrait for ProtobufTypeInt32 {
@@ -425,6 +438,10 @@ impl ProtobufTypeTrait for ProtobufTypeFixed32 {
 
 impl ProtobufTypeFixed for ProtobufTypeFixed32 {
     const ENCODED_SIZE: u32 = 4;
+
+    fn from_le_bytes(bytes: &[u8]) -> u32 {
+        u32::from_le_bytes(bytes.try_into().unwrap())
+    }
 }
 
 impl ProtobufTypeTrait for ProtobufTypeFixed64 {
@@ -458,6 +475,10 @@ impl ProtobufTypeTrait for ProtobufTypeFixed64 {
 
 impl ProtobufTypeFixed for ProtobufTypeFixed64 {
     const ENCODED_SIZE: u32 = 8;
+
+    fn from_le_bytes(bytes: &[u8]) -> u64 {
+        u64::from_le_bytes(bytes.try_into().unwrap())
+    }
 }
 
 impl ProtobufTypeTrait for ProtobufTypeSfixed32 {
@@ -491,6 +512,10 @@ impl ProtobufTypeTrait for ProtobufTypeSfixed32 {
 
 impl ProtobufTypeFixed for ProtobufTypeSfixed32 {
     const ENCODED_SIZE: u32 = 4;
+
+    fn from_le_bytes(bytes: &[u8]) -> i32 {
+        i32::from_le_bytes(bytes.try_into().unwrap())
+    }
 }
 
 impl ProtobufTypeTrait for ProtobufTypeSfixed64 {
@@ -524,6 +549,10 @@ impl ProtobufTypeTrait for ProtobufTypeSfixed64 {
 
 impl ProtobufTypeFixed for ProtobufTypeSfixed64 {
     const ENCODED_SIZE: u32 = 8;
+
+    fn from_le_bytes(bytes: &[u8]) -> i64 {
+        i64::from_le_bytes(bytes.try_into().unwrap())
+    }
 }
 
 impl ProtobufTypeTrait for ProtobufTypeBool {
@@ -613,7 +642,7 @@ impl ProtobufTypeTrait for ProtobufTypeBytes {
     }
 }
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 impl ProtobufTypeTrait for ProtobufTypeTokioBytes {
     type ProtobufValue = bytes::Bytes;
 
@@ -640,7 +669,7 @@ impl ProtobufTypeTrait for ProtobufTypeTokioBytes {
     }
 }
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 impl ProtobufTypeTrait for ProtobufTypeTokioChars {
     type ProtobufValue = Chars;
 
@@ -1,9 +1,9 @@
 use std::fmt;
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use ::bytes::Bytes;
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use crate::chars::Chars;
 use crate::reflect::runtime_types::RuntimeTypeBool;
 use crate::reflect::runtime_types::RuntimeTypeF32;
@@ -11,9 +11,9 @@ use crate::reflect::runtime_types::RuntimeTypeF64;
 use crate::reflect::runtime_types::RuntimeTypeI32;
 use crate::reflect::runtime_types::RuntimeTypeI64;
 use crate::reflect::runtime_types::RuntimeTypeString;
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use crate::reflect::runtime_types::RuntimeTypeTokioBytes;
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 use crate::reflect::runtime_types::RuntimeTypeTokioChars;
 use crate::reflect::runtime_types::RuntimeTypeTrait;
 use crate::reflect::runtime_types::RuntimeTypeU32;
@@ -68,12 +68,12 @@ impl ProtobufValue for Vec<u8> {
     type RuntimeType = RuntimeTypeVecU8;
 }
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 impl ProtobufValue for Bytes {
     type RuntimeType = RuntimeTypeTokioBytes;
 }
 
-#[cfg(feature = "bytes")]
+#[cfg(feature = "with-bytes")]
 impl ProtobufValue for Chars {
     type RuntimeType = RuntimeTypeTokioChars;
 }
@@ -113,6 +113,14 @@ impl<'a> ReflectValueRef<'a> {
         }
     }
 
+    pub(crate) fn uninitialized_field_path(&self) -> Option<String> {
+        if let ReflectValueRef::Message(m) = self {
+            m.uninitialized_field_path_dyn()
+        } else {
+            None
+        }
+    }
+
     /// Take `i32` value.
     pub fn to_i32(&self) -> Option<i32> {
         match *self {
@@ -1,3 +1,7 @@
+use std::collections::hash_map;
+use std::collections::HashMap;
+use std::vec;
+
 use crate::error::WireError;
 use crate::wire_format::WireType;
 use crate::CodedInputStream;
@@ -14,7 +18,7 @@ pub(crate) fn read_map_template_new(
         match field_number {
             1 => key(wire_type, is)?,
             2 => value(wire_type, is)?,
-            _ => is.skip_field(wire_type)?,
+            _ => is.skip_field_with_number(field_number, wire_type)?,
         }
     }
     is.pop_limit(old_limit);
@@ -33,3 +37,52 @@ pub(crate) fn read_map_template(
 
     read_map_template_new(is, key, value)
 }
+
+/// Iterator over map entries, returned by [`map_entries`].
+pub struct MapEntries<'a, K, V> {
+    iter: MapEntriesIter<'a, K, V>,
+}
+
+enum MapEntriesIter<'a, K, V> {
+    Hash(hash_map::Iter<'a, K, V>),
+    Sorted(vec::IntoIter<(&'a K, &'a V)>),
+}
+
+impl<'a, K, V> Iterator for MapEntries<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        match &mut self.iter {
+            MapEntriesIter::Hash(iter) => iter.next(),
+            MapEntriesIter::Sorted(iter) => iter.next(),
+        }
+    }
+}
+
+/// Iterate map entries to write, sorted by key if `sorted` is true
+/// (see [`CodedOutputStream::set_deterministic`](crate::CodedOutputStream::set_deterministic)).
+pub fn map_entries<K: Ord, V>(map: &HashMap<K, V>, sorted: bool) -> MapEntries<'_, K, V> {
+    let iter = if sorted {
+        let mut entries: Vec<(&K, &V)> = map.iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        MapEntriesIter::Sorted(entries.into_iter())
+    } else {
+        MapEntriesIter::Hash(map.iter())
+    };
+    MapEntries { iter }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::map_entries;
+
+    #[test]
+    fn sorted() {
+        let map: HashMap<i32, ()> = [10, -1, 2, 0, -20].iter().map(|k| (*k, ())).collect();
+        let keys: Vec<i32> = map_entries(&map, true).map(|(k, _)| *k).collect();
+        assert_eq!(vec![-20, -1, 0, 2, 10], keys);
+        assert_eq!(5, map_entries(&map, false).count());
+    }
+}
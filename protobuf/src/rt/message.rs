@@ -1,10 +1,16 @@
+use crate::rt::compute_raw_varint64_size;
+use crate::rt::tag_size;
 use crate::wire_format::WireType;
+use crate::ArcMessageField;
 use crate::CodedInputStream;
 use crate::CodedOutputStream;
+use crate::LazyMessage;
 use crate::Message;
 use crate::MessageField;
 
 /// Read singular `message` field.
+///
+/// If the field is already set, read message is merged into it.
 pub fn read_singular_message_into_field<M>(
     is: &mut CodedInputStream,
     target: &mut MessageField<M>,
@@ -12,10 +18,21 @@ pub fn read_singular_message_into_field<M>(
 where
     M: Message,
 {
-    let mut m = M::new();
-    is.merge_message(&mut m)?;
-    *target = MessageField::some(m);
-    Ok(())
+    is.merge_message(target.mut_or_insert_default())
+}
+
+/// Read singular `message` field stored in `Arc`.
+///
+/// If the field is already set, read message is merged into it,
+/// cloning the message first if it is shared.
+pub fn read_arc_message_into_field<M>(
+    is: &mut CodedInputStream,
+    target: &mut ArcMessageField<M>,
+) -> crate::Result<()>
+where
+    M: Message,
+{
+    is.merge_message(target.mut_or_insert_default())
 }
 
 /// Write message with field number and length to the stream.
@@ -31,3 +48,38 @@ where
     os.write_raw_varint32(message.cached_size())?;
     message.write_to_with_cached_sizes(os)
 }
+
+/// Read singular lazy `message` field.
+pub fn read_lazy_message_into_field<M>(
+    is: &mut CodedInputStream,
+    target: &mut LazyMessage<M>,
+) -> crate::Result<()>
+where
+    M: Message,
+{
+    let bytes = is.read_bytes()?;
+    target.merge_from_bytes(bytes)
+}
+
+/// Size of lazy `message` field with tag, zero if field is not set.
+pub fn lazy_message_field_size<M>(field_number: u32, field: &LazyMessage<M>) -> u64
+where
+    M: Message,
+{
+    match field.compute_size() {
+        Some(len) => tag_size(field_number) + compute_raw_varint64_size(len) + len,
+        None => 0,
+    }
+}
+
+/// Write lazy `message` field with field number and length to the stream.
+pub fn write_lazy_message_field_with_cached_size<M>(
+    field_number: u32,
+    field: &LazyMessage<M>,
+    os: &mut CodedOutputStream,
+) -> crate::Result<()>
+where
+    M: Message,
+{
+    field.write_with_cached_size(field_number, os)
+}
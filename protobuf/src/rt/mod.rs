@@ -11,7 +11,13 @@ pub(crate) mod repeated;
 pub(crate) mod singular;
 pub(crate) mod unknown_or_group;
 
+pub use map::map_entries;
+pub use map::MapEntries;
+pub use message::lazy_message_field_size;
+pub use message::read_arc_message_into_field;
+pub use message::read_lazy_message_into_field;
 pub use message::read_singular_message_into_field;
+pub use message::write_lazy_message_field_with_cached_size;
 pub use message::write_message_field_with_cached_size;
 pub use packed::vec_packed_bool_size;
 pub use packed::vec_packed_double_size;
@@ -27,6 +33,7 @@ pub use packed::vec_packed_sint32_size;
 pub use packed::vec_packed_sint64_size;
 pub use packed::vec_packed_uint32_size;
 pub use packed::vec_packed_uint64_size;
+pub use repeated::read_repeated_message_into_vec;
 pub use repeated::read_repeated_packed_enum_or_unknown_into;
 pub use singular::bytes_size;
 pub use singular::int32_size;
@@ -41,10 +48,20 @@ pub use unknown_or_group::skip_field_for_tag;
 pub use unknown_or_group::unknown_fields_size;
 
 pub use crate::cached_size::CachedSize;
+use crate::descriptor::FileDescriptorProto;
 pub use crate::lazy::Lazy;
+use crate::reflect::FileDescriptor;
 use crate::varint::encode::encoded_varint64_len;
 pub use crate::wire_format::WireType;
 
+/// Empty file used in place of a weak dependency which declares nothing,
+/// so generated code does not need the dependency to be generated.
+pub fn weak_dependency_placeholder(name: &str) -> FileDescriptor {
+    let mut proto = FileDescriptorProto::new();
+    proto.set_name(name.to_owned());
+    FileDescriptor::new_dynamic(proto, &[]).expect("empty file")
+}
+
 /// Given `u64` value compute varint encoded length.
 pub fn compute_raw_varint64_size(value: u64) -> u64 {
     encoded_varint64_len(value) as u64
@@ -1,6 +1,30 @@
 use crate::CodedInputStream;
 use crate::Enum;
 use crate::EnumOrUnknown;
+use crate::Message;
+
+/// Read repeated `message` field element.
+///
+/// Element is decoded in place at the end of the vec,
+/// so capacity left by `clear` is reused and the message is not moved.
+/// The vec is unchanged on error.
+pub fn read_repeated_message_into_vec<M: Message>(
+    is: &mut CodedInputStream,
+    target: &mut Vec<M>,
+) -> crate::Result<()> {
+    target.push(M::new());
+    let r = is.merge_message(target.last_mut().unwrap()).and_then(|()| {
+        target
+            .last()
+            .unwrap()
+            .check_initialized()
+            .map_err(Into::into)
+    });
+    if r.is_err() {
+        target.pop();
+    }
+    r
+}
 
 /// Read repeated enum field when the wire format is length-delimited.
 pub fn read_repeated_packed_enum_or_unknown_into<E: Enum>(
@@ -15,3 +39,44 @@ pub fn read_repeated_packed_enum_or_unknown_into<E: Enum>(
     is.pop_limit(old_limit);
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FieldDescriptorProto;
+    use crate::descriptor::UninterpretedOption;
+    use crate::Message;
+
+    fn message(fields: &[&str]) -> DescriptorProto {
+        let mut m = DescriptorProto::new();
+        for name in fields {
+            let mut f = FieldDescriptorProto::new();
+            f.set_name(name.to_string());
+            m.field.push(f);
+        }
+        m
+    }
+
+    #[test]
+    fn merge_into_reused() {
+        let mut m = message(&["a", "b", "c"]);
+        m.clear();
+        let bytes = message(&["d", "e"]).write_to_bytes().unwrap();
+        m.merge_from_bytes(&bytes).unwrap();
+        assert_eq!(DescriptorProto::parse_from_bytes(&bytes).unwrap(), m);
+
+        // Merge appends to existing elements.
+        m.merge_from_bytes(&message(&["f"]).write_to_bytes().unwrap())
+            .unwrap();
+        assert_eq!(message(&["d", "e", "f"]), m);
+    }
+
+    #[test]
+    fn element_not_initialized() {
+        // `name` element without required `is_extension`.
+        let bytes = [0x12, 0x03, 0x0a, 0x01, b'a'];
+        let mut option = UninterpretedOption::new();
+        assert!(option.merge_from_bytes(&bytes).is_err());
+        assert!(option.name.is_empty());
+    }
+}
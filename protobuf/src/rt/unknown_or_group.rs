@@ -7,16 +7,6 @@ use crate::CodedInputStream;
 use crate::UnknownFields;
 use crate::UnknownValueRef;
 
-fn skip_group(is: &mut CodedInputStream) -> crate::Result<()> {
-    loop {
-        let (_, wire_type) = is.read_tag_unpack()?;
-        if wire_type == WireType::EndGroup {
-            return Ok(());
-        }
-        is.skip_field(wire_type)?;
-    }
-}
-
 /// Size of encoded unknown fields size.
 pub fn unknown_fields_size(unknown_fields: &UnknownFields) -> u64 {
     let mut r = 0;
@@ -27,31 +17,28 @@ pub fn unknown_fields_size(unknown_fields: &UnknownFields) -> u64 {
             UnknownValueRef::Fixed64(_) => 8,
             UnknownValueRef::Varint(v) => compute_raw_varint64_size(v),
             UnknownValueRef::LengthDelimited(v) => bytes_size_no_tag(v),
+            // Group contents and end tag.
+            UnknownValueRef::Group(v) => v.len() as u64 + tag_size(number),
         };
     }
     r
 }
 
 /// Handle unknown field in generated code.
-/// Either store a value in unknown, or skip a group.
+/// Store a value (including a group) in unknown fields.
 pub(crate) fn read_unknown_or_skip_group_with_tag_unpacked(
     field_number: u32,
     wire_type: WireType,
     is: &mut CodedInputStream,
     unknown_fields: &mut UnknownFields,
 ) -> crate::Result<()> {
-    match wire_type {
-        WireType::StartGroup => skip_group(is),
-        _ => {
-            let unknown = is.read_unknown(wire_type)?;
-            unknown_fields.add_value(field_number, unknown);
-            Ok(())
-        }
-    }
+    let unknown = is.read_unknown_with_number(field_number, wire_type)?;
+    unknown_fields.add_value(field_number, unknown);
+    Ok(())
 }
 
 /// Handle unknown field in generated code.
-/// Either store a value in unknown, or skip a group.
+/// Store a value (including a group) in unknown fields.
 /// Return error if tag is incorrect.
 pub fn read_unknown_or_skip_group(
     tag: u32,
@@ -64,6 +51,6 @@ pub fn read_unknown_or_skip_group(
 
 /// Skip field.
 pub fn skip_field_for_tag(tag: u32, is: &mut CodedInputStream) -> crate::Result<()> {
-    let (_field_humber, wire_type) = Tag::new(tag)?.unpack();
-    is.skip_field(wire_type)
+    let (field_humber, wire_type) = Tag::new(tag)?.unpack();
+    is.skip_field_with_number(field_humber, wire_type)
 }
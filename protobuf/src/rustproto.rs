@@ -34,6 +34,28 @@ pub mod exts {
 
     pub const lite_runtime_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, bool> = crate::ext::ExtFieldOptional::new(17035, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
 
+    pub const generate_builders_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, bool> = crate::ext::ExtFieldOptional::new(17036, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const enum_non_exhaustive_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, bool> = crate::ext::ExtFieldOptional::new(17037, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const type_name_prefix_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, ::std::string::String> = crate::ext::ExtFieldOptional::new(17038, crate::descriptor::field_descriptor_proto::Type::TYPE_STRING);
+
+    pub const type_name_suffix_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, ::std::string::String> = crate::ext::ExtFieldOptional::new(17039, crate::descriptor::field_descriptor_proto::Type::TYPE_STRING);
+
+    pub const enum_repr_i32_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, bool> = crate::ext::ExtFieldOptional::new(17040, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const derive_debug_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, bool> = crate::ext::ExtFieldOptional::new(17041, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const derive_hash_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, bool> = crate::ext::ExtFieldOptional::new(17042, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const generate_opt_getter_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, bool> = crate::ext::ExtFieldOptional::new(17043, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const generate_field_numbers_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, bool> = crate::ext::ExtFieldOptional::new(17044, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const arc_recursive_fields_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, bool> = crate::ext::ExtFieldOptional::new(17045, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const lazy_message_fields_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, bool> = crate::ext::ExtFieldOptional::new(17046, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
     pub const generate_accessors: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, bool> = crate::ext::ExtFieldOptional::new(17004, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
 
     pub const generate_getter: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, bool> = crate::ext::ExtFieldOptional::new(17005, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
@@ -42,6 +64,24 @@ pub mod exts {
 
     pub const tokio_bytes_for_string: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, bool> = crate::ext::ExtFieldOptional::new(17012, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
 
+    pub const generate_builders: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, bool> = crate::ext::ExtFieldOptional::new(17036, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const derive_debug: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, bool> = crate::ext::ExtFieldOptional::new(17041, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const derive_hash: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, bool> = crate::ext::ExtFieldOptional::new(17042, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const generate_opt_getter: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, bool> = crate::ext::ExtFieldOptional::new(17043, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const generate_field_numbers: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, bool> = crate::ext::ExtFieldOptional::new(17044, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const arc_recursive_fields: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, bool> = crate::ext::ExtFieldOptional::new(17045, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const lazy_message_fields: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, bool> = crate::ext::ExtFieldOptional::new(17046, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const enum_non_exhaustive: crate::ext::ExtFieldOptional<crate::descriptor::EnumOptions, bool> = crate::ext::ExtFieldOptional::new(17037, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const enum_repr_i32: crate::ext::ExtFieldOptional<crate::descriptor::EnumOptions, bool> = crate::ext::ExtFieldOptional::new(17040, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
     pub const generate_accessors_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, bool> = crate::ext::ExtFieldOptional::new(17004, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
 
     pub const generate_getter_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, bool> = crate::ext::ExtFieldOptional::new(17005, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
@@ -49,6 +89,10 @@ pub mod exts {
     pub const tokio_bytes_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, bool> = crate::ext::ExtFieldOptional::new(17011, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
 
     pub const tokio_bytes_for_string_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, bool> = crate::ext::ExtFieldOptional::new(17012, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const generate_opt_getter_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, bool> = crate::ext::ExtFieldOptional::new(17043, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
+
+    pub const lazy_message_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, bool> = crate::ext::ExtFieldOptional::new(17046, crate::descriptor::field_descriptor_proto::Type::TYPE_BOOL);
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
@@ -61,83 +105,219 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     tes_for_string_all\x18\xf4\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.\
     FileOptionsR\x16tokioBytesForStringAll:H\n\x10lite_runtime_all\x18\x8b\
     \x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x0eliteRuntim\
-    eAll:P\n\x12generate_accessors\x18\xec\x84\x01\x20\x01(\x08\x12\x1f.goog\
-    le.protobuf.MessageOptionsR\x11generateAccessors:J\n\x0fgenerate_getter\
-    \x18\xed\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\
-    \x0egenerateGetter:B\n\x0btokio_bytes\x18\xf3\x84\x01\x20\x01(\x08\x12\
-    \x1f.google.protobuf.MessageOptionsR\ntokioBytes:V\n\x16tokio_bytes_for_\
-    string\x18\xf4\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptio\
-    nsR\x13tokioBytesForString:Y\n\x18generate_accessors_field\x18\xec\x84\
-    \x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x16generateAcces\
-    sorsField:S\n\x15generate_getter_field\x18\xed\x84\x01\x20\x01(\x08\x12\
-    \x1d.google.protobuf.FieldOptionsR\x13generateGetterField:K\n\x11tokio_b\
-    ytes_field\x18\xf3\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOpt\
-    ionsR\x0ftokioBytesField:_\n\x1ctokio_bytes_for_string_field\x18\xf4\x84\
-    \x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x18tokioBytesFor\
-    StringFieldJ\x9d\x0f\n\x06\x12\x04\0\0.\x01\n\x08\n\x01\x0c\x12\x03\0\0\
-    \x12\n\t\n\x02\x03\0\x12\x03\x02\0*\n\xe5\x01\n\x01\x02\x12\x03\n\0\x122\
-    ^\x20see\x20https://github.com/gogo/protobuf/blob/master/gogoproto/gogo.\
-    proto\n\x20for\x20the\x20original\x20idea\n2{\x20Generated\x20files\x20c\
-    an\x20be\x20customized\x20using\x20this\x20proto\n\x20or\x20using\x20`Cu\
-    stomize`\x20struct\x20when\x20codegen\x20is\x20invoked\x20programmatical\
-    ly.\n\n\t\n\x01\x07\x12\x04\x0c\0\x18\x01\nP\n\x02\x07\0\x12\x03\x0e\x04\
-    1\x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20acce\
-    ssors\x20are\x20not\x20generated\n\n\n\n\x03\x07\0\x02\x12\x03\x0c\x07\"\
-    \n\n\n\x03\x07\0\x04\x12\x03\x0e\x04\x0c\n\n\n\x03\x07\0\x05\x12\x03\x0e\
-    \r\x11\n\n\n\x03\x07\0\x01\x12\x03\x0e\x12(\n\n\n\x03\x07\0\x03\x12\x03\
-    \x0e+0\nL\n\x02\x07\x01\x12\x03\x10\x04.\x1aA\x20When\x20false,\x20`get_\
-    `\x20is\x20not\x20generated\x20even\x20if\x20`syntax\x20=\x20\"proto2\"`\
-    \n\n\n\n\x03\x07\x01\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x01\x04\x12\x03\
-    \x10\x04\x0c\n\n\n\x03\x07\x01\x05\x12\x03\x10\r\x11\n\n\n\x03\x07\x01\
-    \x01\x12\x03\x10\x12%\n\n\n\x03\x07\x01\x03\x12\x03\x10(-\n2\n\x02\x07\
-    \x02\x12\x03\x12\x04*\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\
-    \x20fields\n\n\n\n\x03\x07\x02\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x02\
-    \x04\x12\x03\x12\x04\x0c\n\n\n\x03\x07\x02\x05\x12\x03\x12\r\x11\n\n\n\
-    \x03\x07\x02\x01\x12\x03\x12\x12!\n\n\n\x03\x07\x02\x03\x12\x03\x12$)\n3\
-    \n\x02\x07\x03\x12\x03\x14\x045\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20\
-    `string`\x20fields\n\n\n\n\x03\x07\x03\x02\x12\x03\x0c\x07\"\n\n\n\x03\
-    \x07\x03\x04\x12\x03\x14\x04\x0c\n\n\n\x03\x07\x03\x05\x12\x03\x14\r\x11\
-    \n\n\n\x03\x07\x03\x01\x12\x03\x14\x12,\n\n\n\x03\x07\x03\x03\x12\x03\
-    \x14/4\nN\n\x02\x07\x04\x12\x03\x17\x04+\x1aC\x20When\x20true,\x20will\
-    \x20only\x20generate\x20codes\x20that\x20works\x20with\x20lite\x20runtim\
-    e.\n\n\n\n\x03\x07\x04\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x04\x04\x12\
-    \x03\x17\x04\x0c\n\n\n\x03\x07\x04\x05\x12\x03\x17\r\x11\n\n\n\x03\x07\
-    \x04\x01\x12\x03\x17\x12\"\n\n\n\x03\x07\x04\x03\x12\x03\x17%*\n\t\n\x01\
-    \x07\x12\x04\x1a\0#\x01\nP\n\x02\x07\x05\x12\x03\x1c\x04-\x1aE\x20When\
-    \x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20accessors\x20are\
-    \x20not\x20generated\n\n\n\n\x03\x07\x05\x02\x12\x03\x1a\x07%\n\n\n\x03\
-    \x07\x05\x04\x12\x03\x1c\x04\x0c\n\n\n\x03\x07\x05\x05\x12\x03\x1c\r\x11\
-    \n\n\n\x03\x07\x05\x01\x12\x03\x1c\x12$\n\n\n\x03\x07\x05\x03\x12\x03\
-    \x1c',\nL\n\x02\x07\x06\x12\x03\x1e\x04*\x1aA\x20When\x20false,\x20`get_\
-    `\x20is\x20not\x20generated\x20even\x20if\x20`syntax\x20=\x20\"proto2\"`\
-    \n\n\n\n\x03\x07\x06\x02\x12\x03\x1a\x07%\n\n\n\x03\x07\x06\x04\x12\x03\
-    \x1e\x04\x0c\n\n\n\x03\x07\x06\x05\x12\x03\x1e\r\x11\n\n\n\x03\x07\x06\
-    \x01\x12\x03\x1e\x12!\n\n\n\x03\x07\x06\x03\x12\x03\x1e$)\n2\n\x02\x07\
-    \x07\x12\x03\x20\x04&\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\
-    \x20fields\n\n\n\n\x03\x07\x07\x02\x12\x03\x1a\x07%\n\n\n\x03\x07\x07\
-    \x04\x12\x03\x20\x04\x0c\n\n\n\x03\x07\x07\x05\x12\x03\x20\r\x11\n\n\n\
-    \x03\x07\x07\x01\x12\x03\x20\x12\x1d\n\n\n\x03\x07\x07\x03\x12\x03\x20\
-    \x20%\n3\n\x02\x07\x08\x12\x03\"\x041\x1a(\x20Use\x20`bytes::Bytes`\x20f\
-    or\x20`string`\x20fields\n\n\n\n\x03\x07\x08\x02\x12\x03\x1a\x07%\n\n\n\
-    \x03\x07\x08\x04\x12\x03\"\x04\x0c\n\n\n\x03\x07\x08\x05\x12\x03\"\r\x11\
-    \n\n\n\x03\x07\x08\x01\x12\x03\"\x12(\n\n\n\x03\x07\x08\x03\x12\x03\"+0\
-    \n\t\n\x01\x07\x12\x04%\0.\x01\nP\n\x02\x07\t\x12\x03'\x043\x1aE\x20When\
-    \x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20accessors\x20are\
-    \x20not\x20generated\n\n\n\n\x03\x07\t\x02\x12\x03%\x07#\n\n\n\x03\x07\t\
-    \x04\x12\x03'\x04\x0c\n\n\n\x03\x07\t\x05\x12\x03'\r\x11\n\n\n\x03\x07\t\
-    \x01\x12\x03'\x12*\n\n\n\x03\x07\t\x03\x12\x03'-2\nL\n\x02\x07\n\x12\x03\
-    )\x040\x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20generated\x20eve\
-    n\x20if\x20`syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\x07\n\x02\x12\x03%\
-    \x07#\n\n\n\x03\x07\n\x04\x12\x03)\x04\x0c\n\n\n\x03\x07\n\x05\x12\x03)\
-    \r\x11\n\n\n\x03\x07\n\x01\x12\x03)\x12'\n\n\n\x03\x07\n\x03\x12\x03)*/\
-    \n2\n\x02\x07\x0b\x12\x03+\x04,\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20\
-    `bytes`\x20fields\n\n\n\n\x03\x07\x0b\x02\x12\x03%\x07#\n\n\n\x03\x07\
-    \x0b\x04\x12\x03+\x04\x0c\n\n\n\x03\x07\x0b\x05\x12\x03+\r\x11\n\n\n\x03\
-    \x07\x0b\x01\x12\x03+\x12#\n\n\n\x03\x07\x0b\x03\x12\x03+&+\n3\n\x02\x07\
-    \x0c\x12\x03-\x047\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20f\
-    ields\n\n\n\n\x03\x07\x0c\x02\x12\x03%\x07#\n\n\n\x03\x07\x0c\x04\x12\
-    \x03-\x04\x0c\n\n\n\x03\x07\x0c\x05\x12\x03-\r\x11\n\n\n\x03\x07\x0c\x01\
-    \x12\x03-\x12.\n\n\n\x03\x07\x0c\x03\x12\x03-16\
+    eAll:R\n\x15generate_builders_all\x18\x8c\x85\x01\x20\x01(\x08\x12\x1c.g\
+    oogle.protobuf.FileOptionsR\x13generateBuildersAll:U\n\x17enum_non_exhau\
+    stive_all\x18\x8d\x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptio\
+    nsR\x14enumNonExhaustiveAll:O\n\x14type_name_prefix_all\x18\x8e\x85\x01\
+    \x20\x01(\t\x12\x1c.google.protobuf.FileOptionsR\x11typeNamePrefixAll:O\
+    \n\x14type_name_suffix_all\x18\x8f\x85\x01\x20\x01(\t\x12\x1c.google.pro\
+    tobuf.FileOptionsR\x11typeNameSuffixAll:I\n\x11enum_repr_i32_all\x18\x90\
+    \x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x0eenumReprI3\
+    2All:H\n\x10derive_debug_all\x18\x91\x85\x01\x20\x01(\x08\x12\x1c.google\
+    .protobuf.FileOptionsR\x0ederiveDebugAll:F\n\x0fderive_hash_all\x18\x92\
+    \x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\rderiveHashAl\
+    l:U\n\x17generate_opt_getter_all\x18\x93\x85\x01\x20\x01(\x08\x12\x1c.go\
+    ogle.protobuf.FileOptionsR\x14generateOptGetterAll:[\n\x1agenerate_field\
+    _numbers_all\x18\x94\x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOp\
+    tionsR\x17generateFieldNumbersAll:W\n\x18arc_recursive_fields_all\x18\
+    \x95\x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x15arcRec\
+    ursiveFieldsAll:U\n\x17lazy_message_fields_all\x18\x96\x85\x01\x20\x01(\
+    \x08\x12\x1c.google.protobuf.FileOptionsR\x14lazyMessageFieldsAll:P\n\
+    \x12generate_accessors\x18\xec\x84\x01\x20\x01(\x08\x12\x1f.google.proto\
+    buf.MessageOptionsR\x11generateAccessors:J\n\x0fgenerate_getter\x18\xed\
+    \x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x0egenerat\
+    eGetter:B\n\x0btokio_bytes\x18\xf3\x84\x01\x20\x01(\x08\x12\x1f.google.p\
+    rotobuf.MessageOptionsR\ntokioBytes:V\n\x16tokio_bytes_for_string\x18\
+    \xf4\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x13tok\
+    ioBytesForString:N\n\x11generate_builders\x18\x8c\x85\x01\x20\x01(\x08\
+    \x12\x1f.google.protobuf.MessageOptionsR\x10generateBuilders:D\n\x0cderi\
+    ve_debug\x18\x91\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOpt\
+    ionsR\x0bderiveDebug:B\n\x0bderive_hash\x18\x92\x85\x01\x20\x01(\x08\x12\
+    \x1f.google.protobuf.MessageOptionsR\nderiveHash:Q\n\x13generate_opt_get\
+    ter\x18\x93\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\
+    \x11generateOptGetter:W\n\x16generate_field_numbers\x18\x94\x85\x01\x20\
+    \x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x14generateFieldNumber\
+    s:S\n\x14arc_recursive_fields\x18\x95\x85\x01\x20\x01(\x08\x12\x1f.googl\
+    e.protobuf.MessageOptionsR\x12arcRecursiveFields:Q\n\x13lazy_message_fie\
+    lds\x18\x96\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\
+    \x11lazyMessageFields:N\n\x13enum_non_exhaustive\x18\x8d\x85\x01\x20\x01\
+    (\x08\x12\x1c.google.protobuf.EnumOptionsR\x11enumNonExhaustive:B\n\renu\
+    m_repr_i32\x18\x90\x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.EnumOpti\
+    onsR\x0benumReprI32:Y\n\x18generate_accessors_field\x18\xec\x84\x01\x20\
+    \x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x16generateAccessorsFiel\
+    d:S\n\x15generate_getter_field\x18\xed\x84\x01\x20\x01(\x08\x12\x1d.goog\
+    le.protobuf.FieldOptionsR\x13generateGetterField:K\n\x11tokio_bytes_fiel\
+    d\x18\xf3\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x0f\
+    tokioBytesField:_\n\x1ctokio_bytes_for_string_field\x18\xf4\x84\x01\x20\
+    \x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x18tokioBytesForStringFi\
+    eld:Z\n\x19generate_opt_getter_field\x18\x93\x85\x01\x20\x01(\x08\x12\
+    \x1d.google.protobuf.FieldOptionsR\x16generateOptGetterField:M\n\x12lazy\
+    _message_field\x18\x96\x85\x01\x20\x01(\x08\x12\x1d.google.protobuf.Fiel\
+    dOptionsR\x10lazyMessageFieldJ\xbe(\n\x06\x12\x04\0\0e\x01\n\x08\n\x01\
+    \x0c\x12\x03\0\0\x12\n\t\n\x02\x03\0\x12\x03\x02\0*\n\xe5\x01\n\x01\x02\
+    \x12\x03\n\0\x122^\x20see\x20https://github.com/gogo/protobuf/blob/maste\
+    r/gogoproto/gogo.proto\n\x20for\x20the\x20original\x20idea\n2{\x20Genera\
+    ted\x20files\x20can\x20be\x20customized\x20using\x20this\x20proto\n\x20o\
+    r\x20using\x20`Customize`\x20struct\x20when\x20codegen\x20is\x20invoked\
+    \x20programmatically.\n\n\t\n\x01\x07\x12\x04\x0c\01\x01\nP\n\x02\x07\0\
+    \x12\x03\x0e\x041\x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`mut_`\
+    \x20etc.\x20accessors\x20are\x20not\x20generated\n\n\n\n\x03\x07\0\x02\
+    \x12\x03\x0c\x07\"\n\n\n\x03\x07\0\x04\x12\x03\x0e\x04\x0c\n\n\n\x03\x07\
+    \0\x05\x12\x03\x0e\r\x11\n\n\n\x03\x07\0\x01\x12\x03\x0e\x12(\n\n\n\x03\
+    \x07\0\x03\x12\x03\x0e+0\nL\n\x02\x07\x01\x12\x03\x10\x04.\x1aA\x20When\
+    \x20false,\x20`get_`\x20is\x20not\x20generated\x20even\x20if\x20`syntax\
+    \x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x01\x02\x12\x03\x0c\x07\"\n\n\n\x03\
+    \x07\x01\x04\x12\x03\x10\x04\x0c\n\n\n\x03\x07\x01\x05\x12\x03\x10\r\x11\
+    \n\n\n\x03\x07\x01\x01\x12\x03\x10\x12%\n\n\n\x03\x07\x01\x03\x12\x03\
+    \x10(-\n2\n\x02\x07\x02\x12\x03\x12\x04*\x1a'\x20Use\x20`bytes::Bytes`\
+    \x20for\x20`bytes`\x20fields\n\n\n\n\x03\x07\x02\x02\x12\x03\x0c\x07\"\n\
+    \n\n\x03\x07\x02\x04\x12\x03\x12\x04\x0c\n\n\n\x03\x07\x02\x05\x12\x03\
+    \x12\r\x11\n\n\n\x03\x07\x02\x01\x12\x03\x12\x12!\n\n\n\x03\x07\x02\x03\
+    \x12\x03\x12$)\n3\n\x02\x07\x03\x12\x03\x14\x045\x1a(\x20Use\x20`bytes::\
+    Bytes`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\x03\x02\x12\x03\x0c\
+    \x07\"\n\n\n\x03\x07\x03\x04\x12\x03\x14\x04\x0c\n\n\n\x03\x07\x03\x05\
+    \x12\x03\x14\r\x11\n\n\n\x03\x07\x03\x01\x12\x03\x14\x12,\n\n\n\x03\x07\
+    \x03\x03\x12\x03\x14/4\nN\n\x02\x07\x04\x12\x03\x17\x04+\x1aC\x20When\
+    \x20true,\x20will\x20only\x20generate\x20codes\x20that\x20works\x20with\
+    \x20lite\x20runtime.\n\n\n\n\x03\x07\x04\x02\x12\x03\x0c\x07\"\n\n\n\x03\
+    \x07\x04\x04\x12\x03\x17\x04\x0c\n\n\n\x03\x07\x04\x05\x12\x03\x17\r\x11\
+    \n\n\n\x03\x07\x04\x01\x12\x03\x17\x12\"\n\n\n\x03\x07\x04\x03\x12\x03\
+    \x17%*\nH\n\x02\x07\x05\x12\x03\x1a\x040\x1a=\x20When\x20true,\x20`FooBu\
+    ilder`\x20is\x20generated\x20for\x20each\x20message\x20`Foo`\n\n\n\n\x03\
+    \x07\x05\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x05\x04\x12\x03\x1a\x04\x0c\
+    \n\n\n\x03\x07\x05\x05\x12\x03\x1a\r\x11\n\n\n\x03\x07\x05\x01\x12\x03\
+    \x1a\x12'\n\n\n\x03\x07\x05\x03\x12\x03\x1a*/\nF\n\x02\x07\x06\x12\x03\
+    \x1c\x042\x1a;\x20When\x20true,\x20generated\x20enums\x20are\x20marked\
+    \x20`#[non_exhaustive]`\n\n\n\n\x03\x07\x06\x02\x12\x03\x0c\x07\"\n\n\n\
+    \x03\x07\x06\x04\x12\x03\x1c\x04\x0c\n\n\n\x03\x07\x06\x05\x12\x03\x1c\r\
+    \x11\n\n\n\x03\x07\x06\x01\x12\x03\x1c\x12)\n\n\n\x03\x07\x06\x03\x12\
+    \x03\x1c,1\nF\n\x02\x07\x07\x12\x03\x1e\x041\x1a;\x20Prefix\x20prepended\
+    \x20to\x20names\x20of\x20generated\x20messages\x20and\x20enums\n\n\n\n\
+    \x03\x07\x07\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x07\x04\x12\x03\x1e\x04\
+    \x0c\n\n\n\x03\x07\x07\x05\x12\x03\x1e\r\x13\n\n\n\x03\x07\x07\x01\x12\
+    \x03\x1e\x14(\n\n\n\x03\x07\x07\x03\x12\x03\x1e+0\nE\n\x02\x07\x08\x12\
+    \x03\x20\x041\x1a:\x20Suffix\x20appended\x20to\x20names\x20of\x20generat\
+    ed\x20messages\x20and\x20enums\n\n\n\n\x03\x07\x08\x02\x12\x03\x0c\x07\"\
+    \n\n\n\x03\x07\x08\x04\x12\x03\x20\x04\x0c\n\n\n\x03\x07\x08\x05\x12\x03\
+    \x20\r\x13\n\n\n\x03\x07\x08\x01\x12\x03\x20\x14(\n\n\n\x03\x07\x08\x03\
+    \x12\x03\x20+0\nA\n\x02\x07\t\x12\x03\"\x04,\x1a6\x20When\x20true,\x20ge\
+    nerated\x20enums\x20are\x20marked\x20`#[repr(i32)]`\n\n\n\n\x03\x07\t\
+    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\t\x04\x12\x03\"\x04\x0c\n\n\n\x03\
+    \x07\t\x05\x12\x03\"\r\x11\n\n\n\x03\x07\t\x01\x12\x03\"\x12#\n\n\n\x03\
+    \x07\t\x03\x12\x03\"&+\nj\n\x02\x07\n\x12\x03%\x04+\x1a_\x20When\x20fals\
+    e,\x20`Debug`\x20is\x20implemented\x20manually\x20without\x20printing\
+    \x20fields\n\x20instead\x20of\x20being\x20derived\n\n\n\n\x03\x07\n\x02\
+    \x12\x03\x0c\x07\"\n\n\n\x03\x07\n\x04\x12\x03%\x04\x0c\n\n\n\x03\x07\n\
+    \x05\x12\x03%\r\x11\n\n\n\x03\x07\n\x01\x12\x03%\x12\"\n\n\n\x03\x07\n\
+    \x03\x12\x03%%*\nJ\n\x02\x07\x0b\x12\x03'\x04*\x1a?\x20When\x20true,\x20\
+    `Hash`\x20and\x20`Eq`\x20are\x20derived\x20for\x20generated\x20messages\
+    \n\n\n\n\x03\x07\x0b\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x0b\x04\x12\x03\
+    '\x04\x0c\n\n\n\x03\x07\x0b\x05\x12\x03'\r\x11\n\n\n\x03\x07\x0b\x01\x12\
+    \x03'\x12!\n\n\n\x03\x07\x0b\x03\x12\x03'$)\nq\n\x02\x07\x0c\x12\x03*\
+    \x042\x1af\x20When\x20true,\x20`foo_opt()`\x20accessors\x20returning\x20\
+    `Option`\x20are\x20generated\n\x20for\x20fields\x20with\x20explicit\x20p\
+    resence\n\n\n\n\x03\x07\x0c\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x0c\x04\
+    \x12\x03*\x04\x0c\n\n\n\x03\x07\x0c\x05\x12\x03*\r\x11\n\n\n\x03\x07\x0c\
+    \x01\x12\x03*\x12)\n\n\n\x03\x07\x0c\x03\x12\x03*,1\nU\n\x02\x07\r\x12\
+    \x03,\x045\x1aJ\x20When\x20true,\x20`FOO_FIELD_NUMBER`\x20constants\x20a\
+    re\x20generated\x20for\x20message\x20fields\n\n\n\n\x03\x07\r\x02\x12\
+    \x03\x0c\x07\"\n\n\n\x03\x07\r\x04\x12\x03,\x04\x0c\n\n\n\x03\x07\r\x05\
+    \x12\x03,\r\x11\n\n\n\x03\x07\r\x01\x12\x03,\x12,\n\n\n\x03\x07\r\x03\
+    \x12\x03,/4\nY\n\x02\x07\x0e\x12\x03.\x043\x1aN\x20When\x20true,\x20recu\
+    rsive\x20singular\x20message\x20fields\x20are\x20stored\x20as\x20`ArcMes\
+    sageField`\n\n\n\n\x03\x07\x0e\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x0e\
+    \x04\x12\x03.\x04\x0c\n\n\n\x03\x07\x0e\x05\x12\x03.\r\x11\n\n\n\x03\x07\
+    \x0e\x01\x12\x03.\x12*\n\n\n\x03\x07\x0e\x03\x12\x03.-2\n`\n\x02\x07\x0f\
+    \x12\x030\x042\x1aU\x20When\x20true,\x20singular\x20message\x20fields\
+    \x20with\x20`[lazy\x20=\x20true]`\x20are\x20stored\x20as\x20`LazyMessage\
+    `\n\n\n\n\x03\x07\x0f\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x0f\x04\x12\
+    \x030\x04\x0c\n\n\n\x03\x07\x0f\x05\x12\x030\r\x11\n\n\n\x03\x07\x0f\x01\
+    \x12\x030\x12)\n\n\n\x03\x07\x0f\x03\x12\x030,1\n\t\n\x01\x07\x12\x043\0\
+    O\x01\nP\n\x02\x07\x10\x12\x035\x04-\x1aE\x20When\x20false,\x20`get_`,\
+    \x20`set_`,\x20`mut_`\x20etc.\x20accessors\x20are\x20not\x20generated\n\
+    \n\n\n\x03\x07\x10\x02\x12\x033\x07%\n\n\n\x03\x07\x10\x04\x12\x035\x04\
+    \x0c\n\n\n\x03\x07\x10\x05\x12\x035\r\x11\n\n\n\x03\x07\x10\x01\x12\x035\
+    \x12$\n\n\n\x03\x07\x10\x03\x12\x035',\nL\n\x02\x07\x11\x12\x037\x04*\
+    \x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20generated\x20even\x20i\
+    f\x20`syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x11\x02\x12\x033\x07%\n\
+    \n\n\x03\x07\x11\x04\x12\x037\x04\x0c\n\n\n\x03\x07\x11\x05\x12\x037\r\
+    \x11\n\n\n\x03\x07\x11\x01\x12\x037\x12!\n\n\n\x03\x07\x11\x03\x12\x037$\
+    )\n2\n\x02\x07\x12\x12\x039\x04&\x1a'\x20Use\x20`bytes::Bytes`\x20for\
+    \x20`bytes`\x20fields\n\n\n\n\x03\x07\x12\x02\x12\x033\x07%\n\n\n\x03\
+    \x07\x12\x04\x12\x039\x04\x0c\n\n\n\x03\x07\x12\x05\x12\x039\r\x11\n\n\n\
+    \x03\x07\x12\x01\x12\x039\x12\x1d\n\n\n\x03\x07\x12\x03\x12\x039\x20%\n3\
+    \n\x02\x07\x13\x12\x03;\x041\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`st\
+    ring`\x20fields\n\n\n\n\x03\x07\x13\x02\x12\x033\x07%\n\n\n\x03\x07\x13\
+    \x04\x12\x03;\x04\x0c\n\n\n\x03\x07\x13\x05\x12\x03;\r\x11\n\n\n\x03\x07\
+    \x13\x01\x12\x03;\x12(\n\n\n\x03\x07\x13\x03\x12\x03;+0\nB\n\x02\x07\x14\
+    \x12\x03>\x04,\x1a7\x20When\x20true,\x20`FooBuilder`\x20is\x20generated\
+    \x20for\x20this\x20message\n\n\n\n\x03\x07\x14\x02\x12\x033\x07%\n\n\n\
+    \x03\x07\x14\x04\x12\x03>\x04\x0c\n\n\n\x03\x07\x14\x05\x12\x03>\r\x11\n\
+    \n\n\x03\x07\x14\x01\x12\x03>\x12#\n\n\n\x03\x07\x14\x03\x12\x03>&+\nj\n\
+    \x02\x07\x15\x12\x03A\x04'\x1a_\x20When\x20false,\x20`Debug`\x20is\x20im\
+    plemented\x20manually\x20without\x20printing\x20fields\n\x20instead\x20o\
+    f\x20being\x20derived\n\n\n\n\x03\x07\x15\x02\x12\x033\x07%\n\n\n\x03\
+    \x07\x15\x04\x12\x03A\x04\x0c\n\n\n\x03\x07\x15\x05\x12\x03A\r\x11\n\n\n\
+    \x03\x07\x15\x01\x12\x03A\x12\x1e\n\n\n\x03\x07\x15\x03\x12\x03A!&\nD\n\
+    \x02\x07\x16\x12\x03C\x04&\x1a9\x20When\x20true,\x20`Hash`\x20and\x20`Eq\
+    `\x20are\x20derived\x20for\x20this\x20message\n\n\n\n\x03\x07\x16\x02\
+    \x12\x033\x07%\n\n\n\x03\x07\x16\x04\x12\x03C\x04\x0c\n\n\n\x03\x07\x16\
+    \x05\x12\x03C\r\x11\n\n\n\x03\x07\x16\x01\x12\x03C\x12\x1d\n\n\n\x03\x07\
+    \x16\x03\x12\x03C\x20%\nq\n\x02\x07\x17\x12\x03F\x04.\x1af\x20When\x20tr\
+    ue,\x20`foo_opt()`\x20accessors\x20returning\x20`Option`\x20are\x20gener\
+    ated\n\x20for\x20fields\x20with\x20explicit\x20presence\n\n\n\n\x03\x07\
+    \x17\x02\x12\x033\x07%\n\n\n\x03\x07\x17\x04\x12\x03F\x04\x0c\n\n\n\x03\
+    \x07\x17\x05\x12\x03F\r\x11\n\n\n\x03\x07\x17\x01\x12\x03F\x12%\n\n\n\
+    \x03\x07\x17\x03\x12\x03F(-\nS\n\x02\x07\x18\x12\x03H\x041\x1aH\x20When\
+    \x20true,\x20`FOO_FIELD_NUMBER`\x20constants\x20are\x20generated\x20for\
+    \x20this\x20message\n\n\n\n\x03\x07\x18\x02\x12\x033\x07%\n\n\n\x03\x07\
+    \x18\x04\x12\x03H\x04\x0c\n\n\n\x03\x07\x18\x05\x12\x03H\r\x11\n\n\n\x03\
+    \x07\x18\x01\x12\x03H\x12(\n\n\n\x03\x07\x18\x03\x12\x03H+0\nj\n\x02\x07\
+    \x19\x12\x03K\x04/\x1a_\x20When\x20true,\x20recursive\x20singular\x20mes\
+    sage\x20fields\x20of\x20this\x20message\n\x20are\x20stored\x20as\x20`Arc\
+    MessageField`\n\n\n\n\x03\x07\x19\x02\x12\x033\x07%\n\n\n\x03\x07\x19\
+    \x04\x12\x03K\x04\x0c\n\n\n\x03\x07\x19\x05\x12\x03K\r\x11\n\n\n\x03\x07\
+    \x19\x01\x12\x03K\x12&\n\n\n\x03\x07\x19\x03\x12\x03K).\nq\n\x02\x07\x1a\
+    \x12\x03N\x04.\x1af\x20When\x20true,\x20singular\x20message\x20fields\
+    \x20of\x20this\x20message\x20with\x20`[lazy\x20=\x20true]`\n\x20are\x20s\
+    tored\x20as\x20`LazyMessage`\n\n\n\n\x03\x07\x1a\x02\x12\x033\x07%\n\n\n\
+    \x03\x07\x1a\x04\x12\x03N\x04\x0c\n\n\n\x03\x07\x1a\x05\x12\x03N\r\x11\n\
+    \n\n\x03\x07\x1a\x01\x12\x03N\x12%\n\n\n\x03\x07\x1a\x03\x12\x03N(-\n\t\
+    \n\x01\x07\x12\x04Q\0V\x01\nD\n\x02\x07\x1b\x12\x03S\x04.\x1a9\x20When\
+    \x20true,\x20generated\x20enum\x20is\x20marked\x20`#[non_exhaustive]`\n\
+    \n\n\n\x03\x07\x1b\x02\x12\x03Q\x07\"\n\n\n\x03\x07\x1b\x04\x12\x03S\x04\
+    \x0c\n\n\n\x03\x07\x1b\x05\x12\x03S\r\x11\n\n\n\x03\x07\x1b\x01\x12\x03S\
+    \x12%\n\n\n\x03\x07\x1b\x03\x12\x03S(-\n?\n\x02\x07\x1c\x12\x03U\x04(\
+    \x1a4\x20When\x20true,\x20generated\x20enum\x20is\x20marked\x20`#[repr(i\
+    32)]`\n\n\n\n\x03\x07\x1c\x02\x12\x03Q\x07\"\n\n\n\x03\x07\x1c\x04\x12\
+    \x03U\x04\x0c\n\n\n\x03\x07\x1c\x05\x12\x03U\r\x11\n\n\n\x03\x07\x1c\x01\
+    \x12\x03U\x12\x1f\n\n\n\x03\x07\x1c\x03\x12\x03U\"'\n\t\n\x01\x07\x12\
+    \x04X\0e\x01\nP\n\x02\x07\x1d\x12\x03Z\x043\x1aE\x20When\x20false,\x20`g\
+    et_`,\x20`set_`,\x20`mut_`\x20etc.\x20accessors\x20are\x20not\x20generat\
+    ed\n\n\n\n\x03\x07\x1d\x02\x12\x03X\x07#\n\n\n\x03\x07\x1d\x04\x12\x03Z\
+    \x04\x0c\n\n\n\x03\x07\x1d\x05\x12\x03Z\r\x11\n\n\n\x03\x07\x1d\x01\x12\
+    \x03Z\x12*\n\n\n\x03\x07\x1d\x03\x12\x03Z-2\nL\n\x02\x07\x1e\x12\x03\\\
+    \x040\x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20generated\x20even\
+    \x20if\x20`syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x1e\x02\x12\x03X\
+    \x07#\n\n\n\x03\x07\x1e\x04\x12\x03\\\x04\x0c\n\n\n\x03\x07\x1e\x05\x12\
+    \x03\\\r\x11\n\n\n\x03\x07\x1e\x01\x12\x03\\\x12'\n\n\n\x03\x07\x1e\x03\
+    \x12\x03\\*/\n2\n\x02\x07\x1f\x12\x03^\x04,\x1a'\x20Use\x20`bytes::Bytes\
+    `\x20for\x20`bytes`\x20fields\n\n\n\n\x03\x07\x1f\x02\x12\x03X\x07#\n\n\
+    \n\x03\x07\x1f\x04\x12\x03^\x04\x0c\n\n\n\x03\x07\x1f\x05\x12\x03^\r\x11\
+    \n\n\n\x03\x07\x1f\x01\x12\x03^\x12#\n\n\n\x03\x07\x1f\x03\x12\x03^&+\n3\
+    \n\x02\x07\x20\x12\x03`\x047\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`st\
+    ring`\x20fields\n\n\n\n\x03\x07\x20\x02\x12\x03X\x07#\n\n\n\x03\x07\x20\
+    \x04\x12\x03`\x04\x0c\n\n\n\x03\x07\x20\x05\x12\x03`\r\x11\n\n\n\x03\x07\
+    \x20\x01\x12\x03`\x12.\n\n\n\x03\x07\x20\x03\x12\x03`16\nL\n\x02\x07!\
+    \x12\x03b\x044\x1aA\x20When\x20true,\x20`foo_opt()`\x20accessor\x20retur\
+    ning\x20`Option`\x20is\x20generated\n\n\n\n\x03\x07!\x02\x12\x03X\x07#\n\
+    \n\n\x03\x07!\x04\x12\x03b\x04\x0c\n\n\n\x03\x07!\x05\x12\x03b\r\x11\n\n\
+    \n\x03\x07!\x01\x12\x03b\x12+\n\n\n\x03\x07!\x03\x12\x03b.3\nW\n\x02\x07\
+    \"\x12\x03d\x04-\x1aL\x20When\x20true,\x20this\x20field\x20is\x20stored\
+    \x20as\x20`LazyMessage`\x20if\x20it\x20has\x20`[lazy\x20=\x20true]`\n\n\
+    \n\n\x03\x07\"\x02\x12\x03X\x07#\n\n\n\x03\x07\"\x04\x12\x03d\x04\x0c\n\
+    \n\n\x03\x07\"\x05\x12\x03d\r\x11\n\n\n\x03\x07\"\x01\x12\x03d\x12$\n\n\
+    \n\x03\x07\"\x03\x12\x03d',\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
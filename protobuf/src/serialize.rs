@@ -0,0 +1,97 @@
+//! Shortcuts for serializing messages into memory.
+
+use crate::CodedOutputStream;
+use crate::Message;
+
+/// Serialize message to a new `Vec`.
+///
+/// This is [`Message::write_to_bytes`] which does not return `Result`.
+///
+/// Unlike [`Message::write_to_bytes`], required fields are not checked:
+/// message with unset required fields is serialized without them.
+///
+/// # Panics
+///
+/// If encoded message is larger than 2GiB.
+///
+/// # Examples
+///
+/// ```
+/// # use protobuf::well_known_types::duration::Duration;
+/// let mut duration = Duration::new();
+/// duration.seconds = 10;
+/// assert_eq!(vec![0x08, 10], protobuf::serialize_to_vec(&duration));
+/// ```
+pub fn serialize_to_vec<M: Message>(m: &M) -> Vec<u8> {
+    let size = m.compute_size() as usize;
+    let mut v = Vec::with_capacity(size);
+    let mut os = CodedOutputStream::vec(&mut v);
+    if let Err(e) = m
+        .write_to_with_cached_sizes(&mut os)
+        .and_then(|()| os.flush())
+    {
+        panic!("failed to serialize {}: {}", M::NAME, e);
+    }
+    drop(os);
+    v
+}
+
+/// Serialize message to `Bytes`.
+///
+/// # Panics
+///
+/// Same as [`serialize_to_vec`].
+#[cfg(feature = "with-bytes")]
+pub fn serialize_to_bytes<M: Message>(m: &M) -> bytes::Bytes {
+    bytes::Bytes::from(serialize_to_vec(m))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::uninterpreted_option::NamePart;
+    use crate::descriptor::UninterpretedOption;
+    use crate::CodedInputStream;
+    use crate::Message;
+
+    fn message() -> UninterpretedOption {
+        let mut m = UninterpretedOption::new();
+        m.set_identifier_value("abc".to_owned());
+        m.set_negative_int_value(-17);
+        let mut name = NamePart::new();
+        name.set_name_part("x".to_owned());
+        name.set_is_extension(false);
+        m.name.push(name);
+        m
+    }
+
+    #[test]
+    fn serialize_to_vec() {
+        let m = message();
+        assert_eq!(m.write_to_bytes().unwrap(), super::serialize_to_vec(&m));
+    }
+
+    #[test]
+    fn serialize_to_vec_not_initialized() {
+        let mut m = NamePart::new();
+        m.set_name_part("x".to_owned());
+        assert!(m.write_to_bytes().is_err());
+
+        let bytes = super::serialize_to_vec(&m);
+        // Top-level `merge_from` does not check required fields.
+        let mut parsed = NamePart::new();
+        parsed
+            .merge_from(&mut CodedInputStream::from_bytes(&bytes))
+            .unwrap();
+        assert_eq!(m, parsed);
+    }
+
+    #[test]
+    #[cfg(feature = "with-bytes")]
+    fn serialize_to_bytes() {
+        let m = message();
+        assert_eq!(
+            &m.write_to_bytes().unwrap()[..],
+            &super::serialize_to_bytes(&m)[..]
+        );
+    }
+}
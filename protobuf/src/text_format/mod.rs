@@ -19,6 +19,14 @@
 //! protobuf implementations, including `protoc` command which can decode
 //! and encode messages using text format.
 //!
+//! Unknown fields are printed with numeric tags (e. g. `5: 123`)
+//! and numeric tags are parsed back into unknown fields.
+//!
+//! `google.protobuf.Any` values can be printed and parsed in expanded form
+//! `[type.googleapis.com/foo.Bar] { ... }` when types are resolved with
+//! [`DescriptorRegistry`](crate::reflect::DescriptorRegistry),
+//! see [`print_to_string_with_registry`] and [`merge_from_str_with_registry`].
+//!
 //! # JSON
 //!
 //! rust-protobuf also supports JSON printing and parsing.
@@ -28,10 +36,17 @@
 mod parse;
 mod print;
 
+const ANY_FULL_NAME: &str = "google.protobuf.Any";
+
 pub use self::parse::merge_from_str;
+pub use self::parse::merge_from_str_with_registry;
 pub use self::parse::parse_from_str;
+pub use self::parse::parse_from_str_dyn;
+pub use self::parse::parse_from_str_with_registry;
 pub use self::parse::ParseError;
 pub use self::print::fmt;
 pub use self::print::print_to;
 pub use self::print::print_to_string;
 pub use self::print::print_to_string_pretty;
+pub use self::print::print_to_string_pretty_with_registry;
+pub use self::print::print_to_string_with_registry;
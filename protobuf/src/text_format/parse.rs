@@ -9,12 +9,16 @@ use protobuf_support::lexer::tokenizer::TokenizerError;
 
 use crate::message_dyn::MessageDyn;
 use crate::message_full::MessageFull;
+use crate::reflect::DescriptorRegistry;
 use crate::reflect::EnumDescriptor;
-use crate::reflect::EnumValueDescriptor;
 use crate::reflect::MessageDescriptor;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::RuntimeFieldType;
 use crate::reflect::RuntimeType;
+use crate::text_format::ANY_FULL_NAME;
+use crate::well_known_types::any::Any;
+use crate::wire_format::FIELD_NUMBER_MAX;
+use crate::UnknownValue;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ParseErrorWithoutLoc {
@@ -32,8 +36,14 @@ pub enum ParseErrorWithoutLoc {
     IntegerOverflow,
     #[error("Expecting bool")]
     ExpectingBool,
+    #[error("Incorrect field number: {}", .0)]
+    IncorrectFieldNumber(u64),
+    #[error("Expecting integer or string for unknown field")]
+    ExpectingUnknownFieldValue,
     #[error("Message not initialized")]
     MessageNotInitialized,
+    #[error("Cannot resolve `Any` type URL: `{}`", .0)]
+    UnknownAnyTypeUrl(String),
 }
 
 impl From<int::Overflow> for ParseErrorWithoutLoc {
@@ -56,6 +66,7 @@ pub type ParseWithLocResult<A> = Result<A, ParseError>;
 #[derive(Clone)]
 struct Parser<'a> {
     tokenizer: Tokenizer<'a>,
+    registry: Option<&'a DescriptorRegistry>,
 }
 
 impl<'a> Parser<'a> {
@@ -69,27 +80,36 @@ impl<'a> Parser<'a> {
         Ok(self.tokenizer.next_symbol_expect_eq(':', desc)?)
     }
 
-    fn read_enum<'e>(&mut self, e: &'e EnumDescriptor) -> ParseResult<EnumValueDescriptor> {
-        self.read_colon("enum")?;
+    /// Colon is optional before message value.
+    fn read_colon_before_value(&mut self, t: &RuntimeType) -> ParseResult<()> {
+        match t {
+            RuntimeType::Message(..) => {
+                self.tokenizer.next_symbol_if_eq(':')?;
+                Ok(())
+            }
+            _ => self.read_colon("value"),
+        }
+    }
+
+    /// Enum value is specified by name or by number.
+    fn read_enum(&mut self, e: &EnumDescriptor) -> ParseResult<i32> {
+        if self.tokenizer.lookahead_is_int_lit()? || self.tokenizer.lookahead_is_symbol('-')? {
+            return self.read_i32();
+        }
 
-        // TODO: read integer?
         let ident = self.tokenizer.next_ident()?;
         let value = match e.value_by_name(&ident) {
             Some(value) => value,
             None => return Err(ParseErrorWithoutLoc::UnknownEnumValue(ident)),
         };
-        Ok(value)
+        Ok(value.value())
     }
 
     fn read_u64(&mut self) -> ParseResult<u64> {
-        self.read_colon("u64")?;
-
         Ok(self.tokenizer.next_int_lit()?)
     }
 
     fn read_u32(&mut self) -> ParseResult<u32> {
-        self.read_colon("int value")?;
-
         let int_lit = self.tokenizer.next_int_lit()?;
         let value_u32 = int_lit as u32;
         if value_u32 as u64 != int_lit {
@@ -99,8 +119,6 @@ impl<'a> Parser<'a> {
     }
 
     fn read_i64(&mut self) -> ParseResult<i64> {
-        self.read_colon("int value")?;
-
         if self.tokenizer.next_symbol_if_eq('-')? {
             let int_lit = self.tokenizer.next_int_lit()?;
             Ok(int::neg(int_lit)?)
@@ -122,8 +140,6 @@ impl<'a> Parser<'a> {
     }
 
     fn read_f64(&mut self) -> ParseResult<f64> {
-        self.read_colon("float value")?;
-
         let minus = self.tokenizer.next_symbol_if_eq('-')?;
 
         let value = if let Ok(value) = self.tokenizer.next_int_lit() {
@@ -140,8 +156,6 @@ impl<'a> Parser<'a> {
     }
 
     fn read_bool(&mut self) -> ParseResult<bool> {
-        self.read_colon("bool value")?;
-
         if self.tokenizer.next_ident_if_eq("true")? {
             Ok(true)
         } else if self.tokenizer.next_ident_if_eq("false")? {
@@ -151,22 +165,21 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Adjacent string literals are concatenated.
     fn read_string(&mut self) -> ParseResult<String> {
-        self.read_colon("string value")?;
-
-        Ok(self
-            .tokenizer
-            .next_str_lit()
-            .and_then(|s| s.decode_utf8().map_err(From::from))?)
+        let mut r = self.tokenizer.next_str_lit()?.decode_utf8()?;
+        while self.tokenizer.lookahead_is_str_lit()? {
+            r.push_str(&self.tokenizer.next_str_lit()?.decode_utf8()?);
+        }
+        Ok(r)
     }
 
     fn read_bytes(&mut self) -> ParseResult<Vec<u8>> {
-        self.read_colon("bytes value")?;
-
-        Ok(self
-            .tokenizer
-            .next_str_lit()
-            .and_then(|s| s.decode_bytes().map_err(From::from))?)
+        let mut r = self.tokenizer.next_str_lit()?.decode_bytes()?;
+        while self.tokenizer.lookahead_is_str_lit()? {
+            r.extend(self.tokenizer.next_str_lit()?.decode_bytes()?);
+        }
+        Ok(r)
     }
 
     fn read_message(&mut self, descriptor: &MessageDescriptor) -> ParseResult<Box<dyn MessageDyn>> {
@@ -192,8 +205,9 @@ impl<'a> Parser<'a> {
 
         let mut key = None;
         let mut value = None;
-        self.tokenizer.next_symbol_expect_eq('{', "map entry")?;
-        while !self.tokenizer.lookahead_is_symbol('}')? {
+        let symbol = self.tokenizer.next_symbol_expect_eq_oneof(&['{', '<'])?;
+        let terminator = if symbol == '{' { '}' } else { '>' };
+        while !self.tokenizer.lookahead_is_symbol(terminator)? {
             let ident = self.next_field_name()?;
             let (field, field_type) = if ident == key_field_name {
                 (&mut key, k)
@@ -207,11 +221,13 @@ impl<'a> Parser<'a> {
                 return Err(ParseErrorWithoutLoc::MapFieldIsSpecifiedMoreThanOnce(ident));
             }
 
+            self.read_colon_before_value(field_type)?;
             let field_value = self.read_value_of_type(field_type)?;
 
             *field = Some(field_value);
         }
-        self.tokenizer.next_symbol_expect_eq('}', "map entry")?;
+        self.tokenizer
+            .next_symbol_expect_eq(terminator, "map entry")?;
         let key = match key {
             Some(key) => key,
             None => k.default_value_ref().to_box(),
@@ -226,7 +242,7 @@ impl<'a> Parser<'a> {
     fn read_value_of_type(&mut self, t: &RuntimeType) -> ParseResult<ReflectValueBox> {
         Ok(match t {
             RuntimeType::Enum(d) => {
-                let value = self.read_enum(&d)?.value();
+                let value = self.read_enum(d)?;
                 ReflectValueBox::Enum(d.clone(), value)
             }
             RuntimeType::U32 => ReflectValueBox::U32(self.read_u32()?),
@@ -242,11 +258,96 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn read_unknown_value(&mut self) -> ParseResult<UnknownValue> {
+        self.read_colon("unknown field")?;
+
+        if self.tokenizer.lookahead_is_str_lit()? {
+            let bytes = self.tokenizer.next_str_lit()?.decode_bytes()?;
+            return Ok(UnknownValue::LengthDelimited(bytes));
+        }
+
+        // Fixed-size values are printed in hex with all digits.
+        let text = self.tokenizer.lookahead_text()?.unwrap_or("");
+        let fixed_digits = match text.strip_prefix("0x").or(text.strip_prefix("0X")) {
+            Some(digits) => digits.len(),
+            None => 0,
+        };
+        if !self.tokenizer.lookahead_is_int_lit()? {
+            return Err(ParseErrorWithoutLoc::ExpectingUnknownFieldValue);
+        }
+        let value = self.tokenizer.next_int_lit()?;
+        Ok(match fixed_digits {
+            8 => UnknownValue::Fixed32(value as u32),
+            16 => UnknownValue::Fixed64(value),
+            _ => UnknownValue::Varint(value),
+        })
+    }
+
+    fn merge_unknown_field(&mut self, message: &mut dyn MessageDyn) -> ParseResult<()> {
+        let number = self.tokenizer.next_int_lit()?;
+        if number == 0 || number > FIELD_NUMBER_MAX as u64 {
+            return Err(ParseErrorWithoutLoc::IncorrectFieldNumber(number));
+        }
+        let value = self.read_unknown_value()?;
+        message
+            .mut_unknown_fields_dyn()
+            .add_value(number as u32, value);
+        Ok(())
+    }
+
+    /// `Any` value in the form `[type.googleapis.com/foo.Bar] { ... }`.
+    fn merge_expanded_any(
+        &mut self,
+        message: &mut dyn MessageDyn,
+        descriptor: &MessageDescriptor,
+    ) -> ParseResult<()> {
+        self.tokenizer.next_symbol_expect_eq('[', "type URL")?;
+        let mut type_url = String::new();
+        while !self.tokenizer.next_symbol_if_eq(']')? {
+            type_url.push_str(self.tokenizer.lookahead_text()?.unwrap_or(""));
+            self.tokenizer.next_some()?;
+        }
+
+        let value_descriptor = match (self.registry, Any::type_name_from_type_url(&type_url)) {
+            (Some(registry), Some(type_name)) => registry.message_by_full_name(type_name),
+            _ => None,
+        };
+        let value_descriptor = match value_descriptor {
+            Some(d) => d,
+            None => return Err(ParseErrorWithoutLoc::UnknownAnyTypeUrl(type_url)),
+        };
+
+        self.tokenizer.next_symbol_if_eq(':')?;
+        let value = self.read_message(&value_descriptor)?;
+        let value = match value.write_to_bytes_dyn() {
+            Ok(value) => value,
+            Err(_) => return Err(ParseErrorWithoutLoc::MessageNotInitialized),
+        };
+
+        descriptor
+            .field_by_name("type_url")
+            .unwrap()
+            .set_singular_field(message, ReflectValueBox::String(type_url));
+        descriptor
+            .field_by_name("value")
+            .unwrap()
+            .set_singular_field(message, ReflectValueBox::Bytes(value));
+        Ok(())
+    }
+
     fn merge_field(
         &mut self,
         message: &mut dyn MessageDyn,
         descriptor: &MessageDescriptor,
     ) -> ParseResult<()> {
+        if descriptor.full_name() == ANY_FULL_NAME && self.tokenizer.lookahead_is_symbol('[')? {
+            return self.merge_expanded_any(message, descriptor);
+        }
+
+        if self.tokenizer.lookahead_is_int_lit()? {
+            return self.merge_unknown_field(message);
+        }
+
         let field_name = self.next_field_name()?;
 
         let field = match descriptor.field_by_name(&field_name) {
@@ -259,14 +360,30 @@ impl<'a> Parser<'a> {
 
         match field.runtime_field_type() {
             RuntimeFieldType::Singular(t) => {
+                self.read_colon_before_value(&t)?;
                 let value = self.read_value_of_type(&t)?;
                 field.set_singular_field(message, value);
             }
             RuntimeFieldType::Repeated(t) => {
-                let value = self.read_value_of_type(&t)?;
-                field.mut_repeated(message).push(value);
+                self.read_colon_before_value(&t)?;
+                let mut repeated = field.mut_repeated(message);
+                // Repeated field values may be specified as a list: `[a, b]`.
+                if self.tokenizer.next_symbol_if_eq('[')? {
+                    if !self.tokenizer.next_symbol_if_eq(']')? {
+                        loop {
+                            repeated.push(self.read_value_of_type(&t)?);
+                            if self.tokenizer.next_symbol_if_eq(']')? {
+                                break;
+                            }
+                            self.tokenizer.next_symbol_expect_eq(',', "list")?;
+                        }
+                    }
+                } else {
+                    repeated.push(self.read_value_of_type(&t)?);
+                }
             }
             RuntimeFieldType::Map(k, v) => {
+                self.tokenizer.next_symbol_if_eq(':')?;
                 let (k, v) = self.read_map_entry(&k, &v)?;
                 field.mut_map(message).insert(k, v);
             }
@@ -297,20 +414,72 @@ impl<'a> Parser<'a> {
     }
 }
 
-/// Parse text format message.
-///
-/// This function does not check if message required fields are set.
-pub fn merge_from_str(message: &mut dyn MessageDyn, input: &str) -> ParseWithLocResult<()> {
+fn merge_from_str_internal(
+    message: &mut dyn MessageDyn,
+    input: &str,
+    registry: Option<&DescriptorRegistry>,
+) -> ParseWithLocResult<()> {
     let mut parser = Parser {
         tokenizer: Tokenizer::new(input, ParserLanguage::TextFormat),
+        registry,
     };
     parser.merge(message)
 }
 
 /// Parse text format message.
-pub fn parse_from_str<M: MessageFull>(input: &str) -> ParseWithLocResult<M> {
+///
+/// This function does not check if message required fields are set.
+pub fn merge_from_str(message: &mut dyn MessageDyn, input: &str) -> ParseWithLocResult<()> {
+    merge_from_str_internal(message, input, None)
+}
+
+/// Parse text format message,
+/// resolving types of expanded `google.protobuf.Any` values in the registry.
+///
+/// `Any` values are accepted both in expanded form
+/// `[type.googleapis.com/foo.Bar] { ... }` and as `type_url` and `value` fields.
+///
+/// This function does not check if message required fields are set.
+pub fn merge_from_str_with_registry(
+    message: &mut dyn MessageDyn,
+    input: &str,
+    registry: &DescriptorRegistry,
+) -> ParseWithLocResult<()> {
+    merge_from_str_internal(message, input, Some(registry))
+}
+
+/// Parse text format message of a type specified by the descriptor.
+///
+/// # Examples
+///
+/// ```
+/// # use protobuf::MessageFull;
+/// # use protobuf::well_known_types::duration::Duration;
+/// let descriptor = Duration::descriptor();
+/// let message = protobuf::text_format::parse_from_str_dyn(&descriptor, "seconds: 10").unwrap();
+/// assert_eq!("seconds: 10", protobuf::text_format::print_to_string(&*message));
+/// ```
+pub fn parse_from_str_dyn(
+    descriptor: &MessageDescriptor,
+    input: &str,
+) -> ParseWithLocResult<Box<dyn MessageDyn>> {
+    let mut m = descriptor.new_instance();
+    merge_from_str(&mut *m, input)?;
+    if m.check_initialized_dyn().is_err() {
+        return Err(ParseError {
+            error: ParseErrorWithoutLoc::MessageNotInitialized,
+            loc: Loc::start(),
+        });
+    }
+    Ok(m)
+}
+
+fn parse_from_str_internal<M: MessageFull>(
+    input: &str,
+    registry: Option<&DescriptorRegistry>,
+) -> ParseWithLocResult<M> {
     let mut m = M::new();
-    merge_from_str(&mut m, input)?;
+    merge_from_str_internal(&mut m, input, registry)?;
     if let Err(_) = m.check_initialized() {
         return Err(ParseError {
             error: ParseErrorWithoutLoc::MessageNotInitialized,
@@ -319,3 +488,17 @@ pub fn parse_from_str<M: MessageFull>(input: &str) -> ParseWithLocResult<M> {
     }
     Ok(m)
 }
+
+/// Parse text format message.
+pub fn parse_from_str<M: MessageFull>(input: &str) -> ParseWithLocResult<M> {
+    parse_from_str_internal(input, None)
+}
+
+/// Parse text format message,
+/// resolving types of expanded `google.protobuf.Any` values in the registry.
+pub fn parse_from_str_with_registry<M: MessageFull>(
+    input: &str,
+    registry: &DescriptorRegistry,
+) -> ParseWithLocResult<M> {
+    parse_from_str_internal(input, Some(registry))
+}
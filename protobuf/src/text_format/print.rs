@@ -4,9 +4,14 @@ use std::fmt::Write;
 use protobuf_support::text_format::quote_bytes_to;
 
 use crate::message_dyn::MessageDyn;
+use crate::reflect::DescriptorRegistry;
 use crate::reflect::MessageRef;
 use crate::reflect::ReflectFieldRef;
 use crate::reflect::ReflectValueRef;
+use crate::text_format::ANY_FULL_NAME;
+use crate::well_known_types::any::Any;
+use crate::CodedInputStream;
+use crate::UnknownFields;
 use crate::UnknownValueRef;
 
 fn print_str_to(s: &str, buf: &mut String) {
@@ -49,6 +54,7 @@ fn print_end_field(buf: &mut String, pretty: bool) {
 
 fn print_field<F: FieldName>(
     buf: &mut String,
+    registry: Option<&DescriptorRegistry>,
     pretty: bool,
     indent: usize,
     first: &mut bool,
@@ -63,7 +69,7 @@ fn print_field<F: FieldName>(
             if pretty {
                 buf.push_str("\n");
             }
-            print_to_internal(&m, buf, pretty, indent + 1);
+            print_to_internal(&m, buf, registry, pretty, indent + 1);
             do_indent(buf, pretty, indent);
             buf.push_str("}");
         }
@@ -71,7 +77,7 @@ fn print_field<F: FieldName>(
             buf.push_str(": ");
             match d.value_by_number(v) {
                 Some(e) => buf.push_str(e.name()),
-                None => write!(buf, ": {}", v).unwrap(),
+                None => write!(buf, "{}", v).unwrap(),
             }
         }
         ReflectValueRef::String(s) => {
@@ -108,9 +114,55 @@ fn print_field<F: FieldName>(
     print_end_field(buf, pretty);
 }
 
-fn print_to_internal(m: &MessageRef, buf: &mut String, pretty: bool, indent: usize) {
+/// Decode `Any` value if its type is found in the registry.
+fn unpack_any(
+    m: &MessageRef,
+    registry: &DescriptorRegistry,
+) -> Option<(String, Box<dyn MessageDyn>)> {
+    let d = m.descriptor_dyn();
+    let type_url = match d
+        .field_by_name("type_url")?
+        .get_singular_field_or_default(&**m)
+    {
+        ReflectValueRef::String(s) => s.to_owned(),
+        _ => return None,
+    };
+    let value = match d
+        .field_by_name("value")?
+        .get_singular_field_or_default(&**m)
+    {
+        ReflectValueRef::Bytes(b) => b,
+        _ => return None,
+    };
+    let descriptor = registry.message_by_full_name(Any::type_name_from_type_url(&type_url)?)?;
+    let message = descriptor.parse_from_bytes(value).ok()?;
+    Some((type_url, message))
+}
+
+fn print_to_internal(
+    m: &MessageRef,
+    buf: &mut String,
+    registry: Option<&DescriptorRegistry>,
+    pretty: bool,
+    indent: usize,
+) {
     let d = m.descriptor_dyn();
     let mut first = true;
+
+    // `Any` is printed like `[type.googleapis.com/foo.Bar] { ... }`
+    // when the type is known, and as regular message otherwise.
+    let expanded = match registry {
+        Some(registry) if d.full_name() == ANY_FULL_NAME => unpack_any(m, registry),
+        _ => None,
+    };
+    if let Some((type_url, message)) = expanded {
+        let name = format!("[{}]", type_url);
+        let value = ReflectValueRef::Message(MessageRef::from(&*message));
+        print_field(buf, registry, pretty, indent, &mut first, &*name, value);
+        print_unknown_fields(m, buf, registry, pretty, indent, &mut first);
+        return;
+    }
+
     for f in d.fields() {
         match f.get_reflect(&**m) {
             ReflectFieldRef::Map(map) => {
@@ -123,8 +175,24 @@ fn print_to_internal(m: &MessageRef, buf: &mut String, pretty: bool, indent: usi
 
                     let mut entry_first = true;
 
-                    print_field(buf, pretty, indent + 1, &mut entry_first, "key", k);
-                    print_field(buf, pretty, indent + 1, &mut entry_first, "value", v);
+                    print_field(
+                        buf,
+                        registry,
+                        pretty,
+                        indent + 1,
+                        &mut entry_first,
+                        "key",
+                        k,
+                    );
+                    print_field(
+                        buf,
+                        registry,
+                        pretty,
+                        indent + 1,
+                        &mut entry_first,
+                        "value",
+                        v,
+                    );
                     do_indent(buf, pretty, indent);
                     buf.push_str("}");
                     print_end_field(buf, pretty);
@@ -132,56 +200,170 @@ fn print_to_internal(m: &MessageRef, buf: &mut String, pretty: bool, indent: usi
             }
             ReflectFieldRef::Repeated(repeated) => {
                 for v in repeated {
-                    print_field(buf, pretty, indent, &mut first, f.name(), v);
+                    print_field(buf, registry, pretty, indent, &mut first, f.name(), v);
                 }
             }
             ReflectFieldRef::Optional(optional) => {
                 if let Some(v) = optional.value() {
-                    print_field(buf, pretty, indent, &mut first, f.name(), v);
+                    print_field(buf, registry, pretty, indent, &mut first, f.name(), v);
                 }
             }
         }
     }
 
-    let mut fields: Vec<(u32, UnknownValueRef)> = m.unknown_fields_dyn().iter().collect();
+    print_unknown_fields(m, buf, registry, pretty, indent, &mut first);
+}
+
+fn print_unknown_fields(
+    m: &MessageRef,
+    buf: &mut String,
+    registry: Option<&DescriptorRegistry>,
+    pretty: bool,
+    indent: usize,
+    first: &mut bool,
+) {
+    print_unknown_fields_of(m.unknown_fields_dyn(), buf, registry, pretty, indent, first);
+}
+
+fn print_unknown_fields_of(
+    unknown_fields: &UnknownFields,
+    buf: &mut String,
+    registry: Option<&DescriptorRegistry>,
+    pretty: bool,
+    indent: usize,
+    first: &mut bool,
+) {
+    let mut fields: Vec<(u32, UnknownValueRef)> = unknown_fields.iter().collect();
     // Sort for stable output
     fields.sort_by_key(|(field_number, _)| *field_number);
     for (field_number, value) in fields {
+        print_unknown_field(buf, registry, pretty, indent, first, field_number, value);
+    }
+}
+
+/// Unknown fields are printed with numeric tags like `protoc` does,
+/// fixed-size values are printed in hex with all digits
+/// so they can be parsed back with the same wire type,
+/// groups are printed like nested messages.
+fn print_unknown_field(
+    buf: &mut String,
+    registry: Option<&DescriptorRegistry>,
+    pretty: bool,
+    indent: usize,
+    first: &mut bool,
+    field_number: u32,
+    value: UnknownValueRef,
+) {
+    match value {
+        UnknownValueRef::Fixed32(v) => {
+            print_start_field(buf, pretty, indent, first, field_number);
+            write!(buf, ": 0x{:08x}", v).unwrap();
+            print_end_field(buf, pretty);
+        }
+        UnknownValueRef::Fixed64(v) => {
+            print_start_field(buf, pretty, indent, first, field_number);
+            write!(buf, ": 0x{:016x}", v).unwrap();
+            print_end_field(buf, pretty);
+        }
+        UnknownValueRef::Group(bytes) => match parse_group_fields(bytes) {
+            Ok(group_fields) => {
+                print_start_field(buf, pretty, indent, first, field_number);
+                buf.push_str(" {");
+                if pretty {
+                    buf.push('\n');
+                }
+                let mut group_first = true;
+                print_unknown_fields_of(
+                    &group_fields,
+                    buf,
+                    registry,
+                    pretty,
+                    indent + 1,
+                    &mut group_first,
+                );
+                do_indent(buf, pretty, indent);
+                buf.push('}');
+                print_end_field(buf, pretty);
+            }
+            // Group values can be constructed by user, print malformed group as bytes.
+            Err(_) => print_field(
+                buf,
+                registry,
+                pretty,
+                indent,
+                first,
+                field_number,
+                value.to_reflect_value_ref(),
+            ),
+        },
         // TODO: try decode nested message for length-delimited
-        print_field(
+        UnknownValueRef::Varint(..) | UnknownValueRef::LengthDelimited(..) => print_field(
             buf,
+            registry,
             pretty,
             indent,
-            &mut first,
+            first,
             field_number,
             value.to_reflect_value_ref(),
-        );
+        ),
+    }
+}
+
+fn parse_group_fields(bytes: &[u8]) -> crate::Result<UnknownFields> {
+    let mut unknown_fields = UnknownFields::new();
+    let mut is = CodedInputStream::from_bytes(bytes);
+    while !is.eof()? {
+        let (number, wire_type) = is.read_tag_unpack()?;
+        let value = is.read_unknown_with_number(number, wire_type)?;
+        unknown_fields.add_value(number, value);
     }
+    Ok(unknown_fields)
 }
 
 /// Text-format
 pub fn print_to(m: &dyn MessageDyn, buf: &mut String) {
-    print_to_internal(&MessageRef::from(m), buf, false, 0)
+    print_to_internal(&MessageRef::from(m), buf, None, false, 0)
 }
 
-fn print_to_string_internal(m: &dyn MessageDyn, pretty: bool) -> String {
+fn print_to_string_internal(
+    m: &dyn MessageDyn,
+    registry: Option<&DescriptorRegistry>,
+    pretty: bool,
+) -> String {
     let mut r = String::new();
-    print_to_internal(&MessageRef::from(m), &mut r, pretty, 0);
+    print_to_internal(&MessageRef::from(m), &mut r, registry, pretty, 0);
     r
 }
 
 /// Text-format
 pub fn print_to_string(m: &dyn MessageDyn) -> String {
-    print_to_string_internal(m, false)
+    print_to_string_internal(m, None, false)
 }
 
 /// Text-format
 pub fn print_to_string_pretty(m: &dyn MessageDyn) -> String {
-    print_to_string_internal(m, true)
+    print_to_string_internal(m, None, true)
+}
+
+/// Text-format with `google.protobuf.Any` values expanded.
+///
+/// `Any` containing a message found in the registry is printed
+/// like `protoc` does: `[type.googleapis.com/foo.Bar] { ... }`.
+/// Other `Any` values are printed with `type_url` and `value` fields.
+pub fn print_to_string_with_registry(m: &dyn MessageDyn, registry: &DescriptorRegistry) -> String {
+    print_to_string_internal(m, Some(registry), false)
+}
+
+/// Pretty text-format with `google.protobuf.Any` values expanded.
+pub fn print_to_string_pretty_with_registry(
+    m: &dyn MessageDyn,
+    registry: &DescriptorRegistry,
+) -> String {
+    print_to_string_internal(m, Some(registry), true)
 }
 
 /// Text-format to `fmt::Formatter`.
 pub fn fmt(m: &dyn MessageDyn, f: &mut fmt::Formatter) -> fmt::Result {
     let pretty = f.alternate();
-    f.write_str(&print_to_string_internal(m, pretty))
+    f.write_str(&print_to_string_internal(m, None, pretty))
 }
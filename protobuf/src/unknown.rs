@@ -1,4 +1,3 @@
-use std::collections::hash_map;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::default::Default;
@@ -18,6 +17,7 @@ use crate::CodedOutputStream;
 ///
 /// See [`UnknownFields`](crate::UnknownFields) for the explanations.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum UnknownValue {
     /// 32-bit unknown (e. g. `fixed32` or `float`)
     Fixed32(u32),
@@ -27,6 +27,8 @@ pub enum UnknownValue {
     Varint(u64),
     /// Length-delimited unknown (e. g. `message` or `string`)
     LengthDelimited(Vec<u8>),
+    /// Group unknown, encoded fields of the group without start and end tags
+    Group(Vec<u8>),
 }
 
 impl UnknownValue {
@@ -42,6 +44,7 @@ impl UnknownValue {
             UnknownValue::Fixed64(fixed64) => UnknownValueRef::Fixed64(fixed64),
             UnknownValue::Varint(varint) => UnknownValueRef::Varint(varint),
             UnknownValue::LengthDelimited(ref bytes) => UnknownValueRef::LengthDelimited(&bytes),
+            UnknownValue::Group(ref bytes) => UnknownValueRef::Group(bytes),
         }
     }
 
@@ -90,6 +93,7 @@ impl UnknownValue {
 ///
 /// See [`UnknownFields`](crate::UnknownFields) for explanations.
 #[derive(Debug, PartialEq)]
+#[non_exhaustive]
 pub enum UnknownValueRef<'o> {
     /// 32-bit unknown
     Fixed32(u32),
@@ -99,6 +103,8 @@ pub enum UnknownValueRef<'o> {
     Varint(u64),
     /// Length-delimited unknown
     LengthDelimited(&'o [u8]),
+    /// Group unknown, encoded fields of the group without start and end tags
+    Group(&'o [u8]),
 }
 
 impl<'o> UnknownValueRef<'o> {
@@ -109,6 +115,19 @@ impl<'o> UnknownValueRef<'o> {
             UnknownValueRef::Fixed64(_) => WireType::Fixed64,
             UnknownValueRef::Varint(_) => WireType::Varint,
             UnknownValueRef::LengthDelimited(_) => WireType::LengthDelimited,
+            UnknownValueRef::Group(_) => WireType::StartGroup,
+        }
+    }
+
+    pub(crate) fn to_value(&self) -> UnknownValue {
+        match *self {
+            UnknownValueRef::Fixed32(fixed32) => UnknownValue::Fixed32(fixed32),
+            UnknownValueRef::Fixed64(fixed64) => UnknownValue::Fixed64(fixed64),
+            UnknownValueRef::Varint(varint) => UnknownValue::Varint(varint),
+            UnknownValueRef::LengthDelimited(bytes) => {
+                UnknownValue::LengthDelimited(bytes.to_vec())
+            }
+            UnknownValueRef::Group(bytes) => UnknownValue::Group(bytes.to_vec()),
         }
     }
 
@@ -117,7 +136,9 @@ impl<'o> UnknownValueRef<'o> {
             UnknownValueRef::Fixed32(v) => ReflectValueRef::U32(*v),
             UnknownValueRef::Fixed64(v) => ReflectValueRef::U64(*v),
             UnknownValueRef::Varint(v) => ReflectValueRef::U64(*v),
-            UnknownValueRef::LengthDelimited(v) => ReflectValueRef::Bytes(v),
+            UnknownValueRef::LengthDelimited(v) | UnknownValueRef::Group(v) => {
+                ReflectValueRef::Bytes(v)
+            }
         }
     }
 }
@@ -135,19 +156,41 @@ pub(crate) struct UnknownValues {
     pub(crate) varint: Vec<u64>,
     /// Length-delimited unknowns
     pub(crate) length_delimited: Vec<Vec<u8>>,
+    /// Group unknowns
+    pub(crate) group: Vec<Vec<u8>>,
 }
 
 impl UnknownValues {
-    /// Add unknown value
-    pub fn add_value(&mut self, value: UnknownValue) {
+    /// Add unknown value, return its index among values of the same wire type.
+    pub fn add_value(&mut self, value: UnknownValue) -> usize {
+        fn push<T>(vec: &mut Vec<T>, value: T) -> usize {
+            vec.push(value);
+            vec.len() - 1
+        }
+
         match value {
-            UnknownValue::Fixed64(fixed64) => self.fixed64.push(fixed64),
-            UnknownValue::Fixed32(fixed32) => self.fixed32.push(fixed32),
-            UnknownValue::Varint(varint) => self.varint.push(varint),
+            UnknownValue::Fixed64(fixed64) => push(&mut self.fixed64, fixed64),
+            UnknownValue::Fixed32(fixed32) => push(&mut self.fixed32, fixed32),
+            UnknownValue::Varint(varint) => push(&mut self.varint, varint),
             UnknownValue::LengthDelimited(length_delimited) => {
-                self.length_delimited.push(length_delimited)
+                push(&mut self.length_delimited, length_delimited)
             }
-        };
+            UnknownValue::Group(group) => push(&mut self.group, group),
+        }
+    }
+
+    /// Value by wire type and index among values of that wire type.
+    fn get<'s>(&'s self, wire_type: WireType, index: usize) -> Option<UnknownValueRef<'s>> {
+        Some(match wire_type {
+            WireType::Fixed32 => UnknownValueRef::Fixed32(*self.fixed32.get(index)?),
+            WireType::Fixed64 => UnknownValueRef::Fixed64(*self.fixed64.get(index)?),
+            WireType::Varint => UnknownValueRef::Varint(*self.varint.get(index)?),
+            WireType::LengthDelimited => {
+                UnknownValueRef::LengthDelimited(self.length_delimited.get(index)?)
+            }
+            WireType::StartGroup => UnknownValueRef::Group(self.group.get(index)?),
+            WireType::EndGroup => return None,
+        })
     }
 
     /// Iterate over unknown values
@@ -157,6 +200,7 @@ impl UnknownValues {
             fixed64: self.fixed64.iter(),
             varint: self.varint.iter(),
             length_delimited: self.length_delimited.iter(),
+            group: self.group.iter(),
         }
     }
 
@@ -170,7 +214,7 @@ impl UnknownValues {
         } else if let Some(last) = self.length_delimited.last() {
             Some(UnknownValueRef::LengthDelimited(last))
         } else {
-            None
+            self.group.last().map(|last| UnknownValueRef::Group(last))
         }
     }
 }
@@ -190,6 +234,7 @@ pub(crate) struct UnknownValuesIter<'o> {
     fixed64: slice::Iter<'o, u64>,
     varint: slice::Iter<'o, u64>,
     length_delimited: slice::Iter<'o, Vec<u8>>,
+    group: slice::Iter<'o, Vec<u8>>,
 }
 
 impl<'o> Iterator for UnknownValuesIter<'o> {
@@ -208,6 +253,9 @@ impl<'o> Iterator for UnknownValuesIter<'o> {
         if let Some(length_delimited) = self.length_delimited.next() {
             return Some(UnknownValueRef::LengthDelimited(&length_delimited));
         }
+        if let Some(group) = self.group.next() {
+            return Some(UnknownValueRef::Group(group));
+        }
         None
     }
 }
@@ -220,12 +268,20 @@ impl<'o> Iterator for UnknownValuesIter<'o> {
 ///
 /// For example, in this operation: load from DB, modify, store to DB,
 /// even when working with older `.proto` file, new fields won't be lost.
+///
+/// Values are kept in the order they were added (i. e. in wire order when parsing),
+/// and are iterated and serialized in that order.
+/// Order is not significant for equality and hashing.
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
 pub struct UnknownFields {
-    /// The map.
-    //
     // `Option` is needed, because HashMap constructor performs allocation,
     // and very expensive.
+    fields: Option<Box<UnknownFieldsMap>>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct UnknownFieldsMap {
+    /// The map.
     //
     // We use "default hasher" to make iteration order deterministic.
     // Which is used to make codegen output deterministic in presence of unknown fields
@@ -234,18 +290,31 @@ pub struct UnknownFields {
     // Note, Google Protobuf C++ simply uses linear map (which can exploitable the same way),
     // and Google Protobuf Java uses tree map to store unknown fields
     // (which is more expensive than hashmap).
-    fields: Option<Box<HashMap<u32, UnknownValues, BuildHasherDefault<DefaultHasher>>>>,
+    fields: HashMap<u32, UnknownValues, BuildHasherDefault<DefaultHasher>>,
+    /// Field number, wire type and index among values of that field
+    /// and wire type, in the order values were added.
+    order: Vec<(u32, WireType, u32)>,
 }
 
+/// Order of values is not compared.
+impl PartialEq for UnknownFieldsMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.fields == other.fields
+    }
+}
+
+impl Eq for UnknownFieldsMap {}
+
 /// Very simple hash implementation of `Hash` for `UnknownFields`.
 /// Since map is unordered, we cannot put entry hashes into hasher,
 /// instead we summing hashes of entries.
 impl Hash for UnknownFields {
     fn hash<H: Hasher>(&self, state: &mut H) {
         if let Some(ref map) = self.fields {
+            let map = &map.fields;
             if !map.is_empty() {
                 let mut hash: u64 = 0;
-                for (k, v) in &**map {
+                for (k, v) in map {
                     let mut entry_hasher = DefaultHasher::new();
                     Hash::hash(&(k, v), &mut entry_hasher);
                     hash = hash.wrapping_add(entry_hasher.finish());
@@ -266,77 +335,115 @@ impl UnknownFields {
     /// Clear all unknown fields.
     pub fn clear(&mut self) {
         if let Some(ref mut fields) = self.fields {
-            fields.clear();
-        }
-    }
-
-    fn init_map(&mut self) {
-        if self.fields.is_none() {
-            self.fields = Some(Default::default());
-        }
-    }
-
-    fn find_field<'a>(&'a mut self, number: &'a u32) -> &'a mut UnknownValues {
-        self.init_map();
-
-        match self.fields.as_mut().unwrap().entry(*number) {
-            hash_map::Entry::Occupied(e) => e.into_mut(),
-            hash_map::Entry::Vacant(e) => e.insert(Default::default()),
+            fields.fields.clear();
+            fields.order.clear();
         }
     }
 
     /// Add unknown fixed 32-bit
     pub fn add_fixed32(&mut self, number: u32, fixed32: u32) {
-        self.find_field(&number).fixed32.push(fixed32);
+        self.add_value(number, UnknownValue::Fixed32(fixed32));
     }
 
     /// Add unknown fixed 64-bit
     pub fn add_fixed64(&mut self, number: u32, fixed64: u64) {
-        self.find_field(&number).fixed64.push(fixed64);
+        self.add_value(number, UnknownValue::Fixed64(fixed64));
     }
 
     /// Add unknown varint
     pub fn add_varint(&mut self, number: u32, varint: u64) {
-        self.find_field(&number).varint.push(varint);
+        self.add_value(number, UnknownValue::Varint(varint));
     }
 
     /// Add unknown length delimited
     pub fn add_length_delimited(&mut self, number: u32, length_delimited: Vec<u8>) {
-        self.find_field(&number)
-            .length_delimited
-            .push(length_delimited);
+        self.add_value(number, UnknownValue::LengthDelimited(length_delimited));
     }
 
     /// Add unknown value
     pub fn add_value(&mut self, number: u32, value: UnknownValue) {
-        self.find_field(&number).add_value(value);
+        let map = self.fields.get_or_insert_with(Default::default);
+        let wire_type = value.wire_type();
+        let index = map.fields.entry(number).or_default().add_value(value);
+        map.order.push((number, wire_type, index as u32));
+    }
+
+    /// Append all values from other unknown fields.
+    ///
+    /// Values are placed after existing values in the order of `other`.
+    pub fn merge_from(&mut self, other: &UnknownFields) {
+        for (number, value) in other {
+            self.add_value(number, value.to_value());
+        }
     }
 
     /// Remove unknown field by number
     pub fn remove(&mut self, field_number: u32) {
         if let Some(fields) = &mut self.fields {
-            fields.remove(&field_number);
+            if fields.fields.remove(&field_number).is_some() {
+                fields.order.retain(|(number, ..)| *number != field_number);
+            }
         }
     }
 
-    /// Iterate over all unknowns
+    /// Iterate over all unknowns in the order they were added
+    /// (see [`UnknownFields`] for details).
     pub fn iter<'s>(&'s self) -> UnknownFieldsIter<'s> {
         UnknownFieldsIter {
             entries: self.fields.as_ref().map(|m| UnknownFieldsNotEmptyIter {
-                fields: m.iter(),
-                current: None,
+                fields: &m.fields,
+                order: m.order.iter(),
             }),
         }
     }
 
+    /// Iterate over values grouped by field number, ordered by field number.
+    pub fn iter_by_field_number<'s>(
+        &'s self,
+    ) -> impl Iterator<Item = (u32, impl Iterator<Item = UnknownValueRef<'s>>)> {
+        let mut fields: Vec<_> = match &self.fields {
+            Some(map) => map.fields.iter().map(|(n, v)| (*n, v.iter())).collect(),
+            None => Vec::new(),
+        };
+        fields.sort_by_key(|(n, _)| *n);
+        fields.into_iter()
+    }
+
     /// Get any value for unknown fields.
     pub fn get(&self, field_number: u32) -> Option<UnknownValueRef> {
         match &self.fields {
-            Some(map) => map.get(&field_number).and_then(|v| v.any()),
+            Some(map) => map.fields.get(&field_number).and_then(|v| v.any()),
             None => None,
         }
     }
 
+    pub(crate) fn values(&self, field_number: u32) -> Option<&UnknownValues> {
+        self.fields.as_ref()?.fields.get(&field_number)
+    }
+
+    /// Get the last varint value of unknown field.
+    pub fn get_varint(&self, field_number: u32) -> Option<u64> {
+        self.values(field_number)?.varint.last().copied()
+    }
+
+    /// Get the last fixed 32-bit value of unknown field.
+    pub fn get_fixed32(&self, field_number: u32) -> Option<u32> {
+        self.values(field_number)?.fixed32.last().copied()
+    }
+
+    /// Get the last fixed 64-bit value of unknown field.
+    pub fn get_fixed64(&self, field_number: u32) -> Option<u64> {
+        self.values(field_number)?.fixed64.last().copied()
+    }
+
+    /// Get the last length-delimited value of unknown field.
+    pub fn get_length_delimited(&self, field_number: u32) -> Option<&[u8]> {
+        self.values(field_number)?
+            .length_delimited
+            .last()
+            .map(|v| &v[..])
+    }
+
     #[doc(hidden)]
     pub fn write_to_bytes(&self) -> Vec<u8> {
         let mut r = Vec::with_capacity(rt::unknown_fields_size(self) as usize);
@@ -359,8 +466,8 @@ impl<'a> IntoIterator for &'a UnknownFields {
 }
 
 struct UnknownFieldsNotEmptyIter<'s> {
-    fields: hash_map::Iter<'s, u32, UnknownValues>,
-    current: Option<(u32, UnknownValuesIter<'s>)>,
+    fields: &'s HashMap<u32, UnknownValues, BuildHasherDefault<DefaultHasher>>,
+    order: slice::Iter<'s, (u32, WireType, u32)>,
 }
 
 /// Iterator over [`UnknownFields`](crate::UnknownFields)
@@ -372,15 +479,12 @@ impl<'s> Iterator for UnknownFieldsNotEmptyIter<'s> {
     type Item = (u32, UnknownValueRef<'s>);
 
     fn next(&mut self) -> Option<(u32, UnknownValueRef<'s>)> {
-        loop {
-            if let Some((field_number, values)) = &mut self.current {
-                if let Some(value) = values.next() {
-                    return Some((*field_number, value));
-                }
-            }
-            let (field_number, values) = self.fields.next()?;
-            self.current = Some((*field_number, values.iter()));
-        }
+        let &(field_number, wire_type, index) = self.order.next()?;
+        let value = self.fields[&field_number].get(wire_type, index as usize);
+        Some((
+            field_number,
+            value.expect("order is consistent with values"),
+        ))
     }
 }
 
@@ -399,6 +503,12 @@ mod test {
     use std::hash::Hasher;
 
     use super::UnknownFields;
+    use super::UnknownValue;
+    use super::UnknownValueRef;
+    use crate::well_known_types::duration::Duration;
+    use crate::wire_format::WireType;
+    use crate::CodedInputStream;
+    use crate::Message;
 
     #[test]
     fn unknown_fields_hash() {
@@ -424,6 +534,178 @@ mod test {
         assert_eq!(hash(&unknown_fields_1), hash(&unknown_fields_2));
     }
 
+    #[test]
+    fn typed_get() {
+        let mut u = UnknownFields::new();
+        u.add_varint(1, 10);
+        u.add_varint(1, 11);
+        u.add_length_delimited(2, b"ab".to_vec());
+        u.add_fixed32(3, 12);
+        u.add_fixed64(4, 13);
+
+        assert_eq!(Some(11), u.get_varint(1));
+        assert_eq!(Some(&b"ab"[..]), u.get_length_delimited(2));
+        assert_eq!(Some(12), u.get_fixed32(3));
+        assert_eq!(Some(13), u.get_fixed64(4));
+
+        // Wrong wire type or missing field.
+        assert_eq!(None, u.get_varint(2));
+        assert_eq!(None, u.get_length_delimited(1));
+        assert_eq!(None, u.get_varint(5));
+        assert_eq!(None, UnknownFields::new().get_varint(1));
+    }
+
+    #[test]
+    fn typed_get_after_parse() {
+        // `Duration` has fields 1 and 2, fields 3 and 4 are unknown.
+        let bytes = [0x08, 0x01, 0x18, 0x96, 0x01, 0x22, 0x02, b'x', b'y'];
+        let duration = Duration::parse_from_bytes(&bytes).unwrap();
+        assert_eq!(1, duration.seconds);
+        let unknown_fields = duration.special_fields.unknown_fields();
+        assert_eq!(Some(150), unknown_fields.get_varint(3));
+        assert_eq!(Some(&b"xy"[..]), unknown_fields.get_length_delimited(4));
+    }
+
+    #[test]
+    fn nested_group_round_trip() {
+        // `Duration` field 1, then unknown group 3 containing
+        // varint field 1 and nested group 2 containing fixed32 field 1.
+        let bytes = [
+            0x08, 0x01, 0x1b, 0x08, 0x05, 0x13, 0x0d, 0x07, 0x00, 0x00, 0x00, 0x14, 0x1c,
+        ];
+        let duration = Duration::parse_from_bytes(&bytes).unwrap();
+        assert_eq!(1, duration.seconds);
+        let unknown_fields = duration.special_fields.unknown_fields();
+        assert_eq!(
+            Some(UnknownValueRef::Group(&bytes[3..12])),
+            unknown_fields.get(3)
+        );
+
+        assert_eq!(bytes.len() as u64, duration.compute_size());
+        assert_eq!(&bytes[..], &duration.write_to_bytes().unwrap()[..]);
+        assert_eq!(
+            "seconds: 1 3 {1: 5 2 {1: 0x00000007}}",
+            crate::text_format::print_to_string(&duration)
+        );
+    }
+
+    #[test]
+    fn truncated_group() {
+        // Group 3 without end tag.
+        assert!(Duration::parse_from_bytes(&[0x1b, 0x08, 0x05]).is_err());
+        let mut is = CodedInputStream::from_bytes(&[0x08, 0x05]);
+        assert!(is.skip_field(WireType::StartGroup).is_err());
+    }
+
+    #[test]
+    fn mismatched_end_group() {
+        // Group 3 closed with end tag of field 4.
+        assert!(Duration::parse_from_bytes(&[0x1b, 0x08, 0x05, 0x24]).is_err());
+        // Nested group 2 closed with end tag of field 3.
+        assert!(Duration::parse_from_bytes(&[0x1b, 0x13, 0x1c, 0x1c]).is_err());
+        let mut is = CodedInputStream::from_bytes(&[0x13, 0x1c, 0x1c]);
+        assert!(is.skip_field(WireType::StartGroup).is_err());
+    }
+
+    #[test]
+    fn print_malformed_group() {
+        let mut duration = Duration::new();
+        duration
+            .mut_unknown_fields()
+            .add_value(3, UnknownValue::Group(vec![0xff]));
+        assert_eq!(
+            "3: \"\\377\"",
+            crate::text_format::print_to_string(&duration)
+        );
+    }
+
+    #[test]
+    fn merge_from() {
+        let mut a = UnknownFields::new();
+        a.add_varint(1, 10);
+        a.add_length_delimited(2, b"a".to_vec());
+
+        let mut b = UnknownFields::new();
+        b.add_varint(1, 11);
+        b.add_length_delimited(2, b"b".to_vec());
+        b.add_fixed32(3, 7);
+
+        a.merge_from(&b);
+
+        assert_eq!(
+            vec![
+                0x08, 10, 0x08, 11, // 1: varint
+                0x12, 1, b'a', 0x12, 1, b'b', // 2: length-delimited
+                0x1d, 7, 0, 0, 0, // 3: fixed32
+            ],
+            a.write_to_bytes()
+        );
+
+        let grouped: Vec<(u32, Vec<UnknownValueRef>)> = a
+            .iter_by_field_number()
+            .map(|(n, values)| (n, values.collect()))
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    1,
+                    vec![UnknownValueRef::Varint(10), UnknownValueRef::Varint(11)]
+                ),
+                (
+                    2,
+                    vec![
+                        UnknownValueRef::LengthDelimited(b"a"),
+                        UnknownValueRef::LengthDelimited(b"b")
+                    ]
+                ),
+                (3, vec![UnknownValueRef::Fixed32(7)]),
+            ],
+            grouped
+        );
+    }
+
+    #[test]
+    fn iteration_in_wire_order() {
+        // `Duration` fields 3, 4, 3 and 5 are unknown.
+        let bytes = [
+            0x18, 0x01, 0x22, 0x00, 0x18, 0x02, 0x2d, 0x03, 0x00, 0x00, 0x00,
+        ];
+        let mut duration = Duration::parse_from_bytes(&bytes).unwrap();
+        let unknown_fields = duration.special_fields.unknown_fields();
+        let items: Vec<_> = unknown_fields.iter().collect();
+        assert_eq!(
+            vec![
+                (3, UnknownValueRef::Varint(1)),
+                (4, UnknownValueRef::LengthDelimited(b"")),
+                (3, UnknownValueRef::Varint(2)),
+                (5, UnknownValueRef::Fixed32(3)),
+            ],
+            items
+        );
+        assert_eq!(&bytes[..], &duration.write_to_bytes().unwrap()[..]);
+
+        duration.mut_unknown_fields().remove(3);
+        let items: Vec<_> = duration.special_fields.unknown_fields().iter().collect();
+        assert_eq!(
+            vec![
+                (4, UnknownValueRef::LengthDelimited(b"")),
+                (5, UnknownValueRef::Fixed32(3)),
+            ],
+            items
+        );
+    }
+
+    #[test]
+    fn order_is_not_compared() {
+        let mut a = UnknownFields::new();
+        a.add_varint(1, 10);
+        a.add_fixed32(2, 20);
+        let mut b = UnknownFields::new();
+        b.add_fixed32(2, 20);
+        b.add_varint(1, 10);
+        assert_eq!(a, b);
+    }
+
     #[test]
     fn unknown_fields_iteration_order_deterministic() {
         let mut u_1 = UnknownFields::new();
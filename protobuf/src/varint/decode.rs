@@ -1,8 +1,8 @@
 //! Varint decode utilities.
 
-use crate::error::WireError;
 use crate::varint::MAX_VARINT32_ENCODED_LEN;
 use crate::varint::MAX_VARINT_ENCODED_LEN;
+use crate::wire;
 
 trait DecodeVarint {
     const MAX_ENCODED_LEN: usize;
@@ -31,12 +31,12 @@ impl DecodeVarint for u32 {
 
 /// Decode a varint, and return decoded value and decoded byte count.
 #[inline]
-fn decode_varint_full<D: DecodeVarint>(rem: &[u8]) -> crate::Result<Option<(D, usize)>> {
+fn decode_varint_full<D: DecodeVarint>(rem: &[u8]) -> Result<Option<(D, usize)>, wire::Error> {
     let mut r: u64 = 0;
     for (i, &b) in rem.iter().enumerate() {
         if i == D::MAX_ENCODED_LEN - 1 {
             if b > D::LAST_BYTE_MAX_VALUE {
-                return Err(WireError::IncorrectVarint.into());
+                return Err(wire::Error::IncorrectVarint);
             }
             let r = r | ((b as u64) << (i as u64 * 7));
             return Ok(Some((D::from_u64(r), i + 1)));
@@ -50,34 +50,35 @@ fn decode_varint_full<D: DecodeVarint>(rem: &[u8]) -> crate::Result<Option<(D, u
     Ok(None)
 }
 
+/// Decode one or two byte varint, the most common case.
+///
+/// Return `None` if the varint is longer or the buffer is shorter than the varint.
+#[inline(always)]
+pub(crate) fn decode_varint_short(buf: &[u8]) -> Option<(u64, usize)> {
+    match *buf {
+        [b0, ..] if b0 < 0x80 => Some((b0 as u64, 1)),
+        [b0, b1, ..] if b1 < 0x80 => Some(((b0 & 0x7f) as u64 | (b1 as u64) << 7, 2)),
+        _ => None,
+    }
+}
+
 #[inline]
-fn decode_varint_impl<D: DecodeVarint>(buf: &[u8]) -> crate::Result<Option<(D, usize)>> {
-    if buf.len() >= 1 && buf[0] < 0x80 {
-        // The the most common case.
-        let ret = buf[0] as u64;
-        let consume = 1;
-        Ok(Some((D::from_u64(ret), consume)))
-    } else if buf.len() >= 2 && buf[1] < 0x80 {
-        // Handle the case of two bytes too.
-        let ret = (buf[0] & 0x7f) as u64 | (buf[1] as u64) << 7;
-        let consume = 2;
-        Ok(Some((D::from_u64(ret), consume)))
-    } else {
-        // Read from array when buf at at least 10 bytes,
-        // max len for varint.
-        decode_varint_full(buf)
+fn decode_varint_impl<D: DecodeVarint>(buf: &[u8]) -> Result<Option<(D, usize)>, wire::Error> {
+    match decode_varint_short(buf) {
+        Some((r, consumed)) => Ok(Some((D::from_u64(r), consumed))),
+        None => decode_varint_full(buf),
     }
 }
 
 /// Try decode a varint. Return `None` if the buffer does not contain complete varint.
 #[inline]
-pub(crate) fn decode_varint64(buf: &[u8]) -> crate::Result<Option<(u64, usize)>> {
+pub(crate) fn decode_varint64(buf: &[u8]) -> Result<Option<(u64, usize)>, wire::Error> {
     decode_varint_impl(buf)
 }
 
 /// Try decode a varint. Return `None` if the buffer does not contain complete varint.
 #[inline]
-pub(crate) fn decode_varint32(buf: &[u8]) -> crate::Result<Option<(u32, usize)>> {
+pub(crate) fn decode_varint32(buf: &[u8]) -> Result<Option<(u32, usize)>, wire::Error> {
     decode_varint_impl(buf)
 }
 
@@ -86,6 +87,7 @@ mod tests {
     use crate::hex::decode_hex;
     use crate::varint::decode::decode_varint32;
     use crate::varint::decode::decode_varint64;
+    use crate::varint::decode::decode_varint_short;
 
     #[test]
     fn test_decode_varint64() {
@@ -110,4 +112,54 @@ mod tests {
         );
         assert!(decode_varint32(&decode_hex("ff ff ff ff 10")).is_err());
     }
+
+    #[test]
+    fn test_decode_varint_short() {
+        assert_eq!(Some((5, 1)), decode_varint_short(&[0x05, 0xff]));
+        assert_eq!(Some((300, 2)), decode_varint_short(&[0xac, 0x02, 0xff]));
+        assert_eq!(None, decode_varint_short(&[0xac, 0x82, 0x01]));
+        // Truncated.
+        assert_eq!(None, decode_varint_short(&[]));
+        assert_eq!(None, decode_varint_short(&[0xac]));
+    }
+
+    fn encode_varint(mut value: u64) -> Vec<u8> {
+        let mut r = Vec::new();
+        while value >= 0x80 {
+            r.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        r.push(value as u8);
+        r
+    }
+
+    #[test]
+    fn test_decode_varint_all_lengths() {
+        let mut x: u64 = 1;
+        for _ in 0..1000 {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            for value in (0..64).step_by(7).map(|shift| x >> shift) {
+                let mut bytes = encode_varint(value);
+                let len = bytes.len();
+                // Following bytes must not be consumed.
+                bytes.extend_from_slice(&[0xff; 10]);
+
+                assert_eq!(Some((value, len)), decode_varint64(&bytes).unwrap());
+                if let Ok(value) = u32::try_from(value) {
+                    assert_eq!(Some((value, len)), decode_varint32(&bytes).unwrap());
+                } else {
+                    assert!(decode_varint32(&bytes).is_err());
+                }
+
+                for truncated in 0..len {
+                    assert_eq!(None, decode_varint64(&bytes[..truncated]).unwrap());
+                    if value <= u32::MAX as u64 {
+                        assert_eq!(None, decode_varint32(&bytes[..truncated]).unwrap());
+                    }
+                }
+            }
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use std::mem::MaybeUninit;
+use core::mem::MaybeUninit;
 
 use crate::varint::MAX_VARINT_ENCODED_LEN;
 
@@ -90,6 +90,7 @@ pub(crate) fn encode_varint32(mut value: u32, buf: &mut [MaybeUninit<u8>]) -> us
 }
 
 /// Encoded size of u64 value.
+#[cfg(feature = "std")]
 #[inline]
 pub(crate) fn encoded_varint64_len(value: u64) -> usize {
     if value == 0 {
@@ -100,7 +101,7 @@ pub(crate) fn encoded_varint64_len(value: u64) -> usize {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod test {
     use std::mem::MaybeUninit;
 
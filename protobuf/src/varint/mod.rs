@@ -1,5 +1,6 @@
 pub(crate) mod decode;
 pub(crate) mod encode;
+#[cfg(feature = "std")]
 pub(crate) mod generic;
 
 /// Encoded varint message is not longer than 10 bytes.
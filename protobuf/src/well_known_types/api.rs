@@ -153,10 +153,10 @@ impl crate::Message for Api {
                     self.name = is.read_string()?;
                 },
                 18 => {
-                    self.methods.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.methods)?;
                 },
                 26 => {
-                    self.options.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.options)?;
                 },
                 34 => {
                     self.version = is.read_string()?;
@@ -165,7 +165,7 @@ impl crate::Message for Api {
                     crate::rt::read_singular_message_into_field(is, &mut self.source_context)?;
                 },
                 50 => {
-                    self.mixins.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.mixins)?;
                 },
                 56 => {
                     self.syntax = is.read_enum_or_unknown()?;
@@ -407,7 +407,7 @@ impl crate::Message for Method {
                     self.response_streaming = is.read_bool()?;
                 },
                 50 => {
-                    self.options.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.options)?;
                 },
                 56 => {
                     self.syntax = is.read_enum_or_unknown()?;
@@ -118,7 +118,7 @@ impl crate::Message for Struct {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut crate::CodedOutputStream<'_>) -> crate::Result<()> {
-        for (k, v) in &self.fields {
+        for (k, v) in crate::rt::map_entries(&self.fields, os.is_deterministic()) {
             let mut entry_size = 0;
             entry_size += crate::rt::string_size(1, &k);
             let len = v.cached_size() as u64;
@@ -221,7 +221,7 @@ impl Value {
 
     // Param is passed by value, moved
     pub fn set_null_value(&mut self, v: NullValue) {
-        self.kind = ::std::option::Option::Some(value::Kind::NullValue(crate::EnumOrUnknown::new(v)))
+        self.kind = ::std::option::Option::Some(value::Kind::NullValue(crate::EnumOrUnknown::new(v)));
     }
 
     // double number_value = 2;
@@ -246,7 +246,7 @@ impl Value {
 
     // Param is passed by value, moved
     pub fn set_number_value(&mut self, v: f64) {
-        self.kind = ::std::option::Option::Some(value::Kind::NumberValue(v))
+        self.kind = ::std::option::Option::Some(value::Kind::NumberValue(v));
     }
 
     // string string_value = 3;
@@ -271,7 +271,7 @@ impl Value {
 
     // Param is passed by value, moved
     pub fn set_string_value(&mut self, v: ::std::string::String) {
-        self.kind = ::std::option::Option::Some(value::Kind::StringValue(v))
+        self.kind = ::std::option::Option::Some(value::Kind::StringValue(v));
     }
 
     // Mutable pointer to the field.
@@ -320,7 +320,7 @@ impl Value {
 
     // Param is passed by value, moved
     pub fn set_bool_value(&mut self, v: bool) {
-        self.kind = ::std::option::Option::Some(value::Kind::BoolValue(v))
+        self.kind = ::std::option::Option::Some(value::Kind::BoolValue(v));
     }
 
     // .google.protobuf.Struct struct_value = 5;
@@ -345,7 +345,7 @@ impl Value {
 
     // Param is passed by value, moved
     pub fn set_struct_value(&mut self, v: Struct) {
-        self.kind = ::std::option::Option::Some(value::Kind::StructValue(v))
+        self.kind = ::std::option::Option::Some(value::Kind::StructValue(v));
     }
 
     // Mutable pointer to the field.
@@ -394,7 +394,7 @@ impl Value {
 
     // Param is passed by value, moved
     pub fn set_list_value(&mut self, v: ListValue) {
-        self.kind = ::std::option::Option::Some(value::Kind::ListValue(v))
+        self.kind = ::std::option::Option::Some(value::Kind::ListValue(v));
     }
 
     // Mutable pointer to the field.
@@ -421,6 +421,11 @@ impl Value {
         }
     }
 
+    /// Field of oneof `kind` which is set, if any.
+    pub fn kind(&self) -> ::std::option::Option<&value::Kind> {
+        self.kind.as_ref()
+    }
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::with_capacity(6);
         let mut oneofs = ::std::vec::Vec::with_capacity(1);
@@ -476,6 +481,14 @@ impl Value {
     }
 }
 
+impl ::std::convert::From<value::Kind> for Value {
+    fn from(v: value::Kind) -> Self {
+        let mut m = Self::new();
+        m.kind = ::std::option::Option::Some(v);
+        m
+    }
+}
+
 impl crate::Message for Value {
     const NAME: &'static str = "Value";
 
@@ -499,10 +512,20 @@ impl crate::Message for Value {
                     self.kind = ::std::option::Option::Some(value::Kind::BoolValue(is.read_bool()?));
                 },
                 42 => {
-                    self.kind = ::std::option::Option::Some(value::Kind::StructValue(is.read_message()?));
+                    match self.kind {
+                        ::std::option::Option::Some(value::Kind::StructValue(ref mut v)) => is.merge_message(v)?,
+                        _ => {
+                            self.kind = ::std::option::Option::Some(value::Kind::StructValue(is.read_message()?));
+                        },
+                    };
                 },
                 50 => {
-                    self.kind = ::std::option::Option::Some(value::Kind::ListValue(is.read_message()?));
+                    match self.kind {
+                        ::std::option::Option::Some(value::Kind::ListValue(ref mut v)) => is.merge_message(v)?,
+                        _ => {
+                            self.kind = ::std::option::Option::Some(value::Kind::ListValue(is.read_message()?));
+                        },
+                    };
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -711,7 +734,7 @@ impl crate::Message for ListValue {
         while let Some(tag) = is.read_raw_tag_or_eof()? {
             match tag {
                 10 => {
-                    self.values.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.values)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -792,6 +815,7 @@ impl crate::reflect::ProtobufValue for ListValue {
 #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
 // @@protoc_insertion_point(enum:google.protobuf.NullValue)
 pub enum NullValue {
+    ///  Null value.
     // @@protoc_insertion_point(enum_value:google.protobuf.NullValue.NULL_VALUE)
     NULL_VALUE = 0,
 }
@@ -803,6 +827,12 @@ impl crate::Enum for NullValue {
         *self as i32
     }
 
+    fn name(&self) -> &'static str {
+        match *self {
+            NullValue::NULL_VALUE => "NULL_VALUE",
+        }
+    }
+
     fn from_i32(value: i32) -> ::std::option::Option<NullValue> {
         match value {
             0 => ::std::option::Option::Some(NullValue::NULL_VALUE),
@@ -822,6 +852,14 @@ impl crate::Enum for NullValue {
     ];
 }
 
+impl ::std::convert::TryFrom<i32> for NullValue {
+    type Error = crate::UnknownEnumValue;
+
+    fn try_from(value: i32) -> ::std::result::Result<Self, Self::Error> {
+        <Self as crate::Enum>::from_i32(value).ok_or(crate::UnknownEnumValue(value))
+    }
+}
+
 impl crate::EnumFull for NullValue {
     fn enum_descriptor() -> crate::reflect::EnumDescriptor {
         static descriptor: crate::rt::Lazy<crate::reflect::EnumDescriptor> = crate::rt::Lazy::new();
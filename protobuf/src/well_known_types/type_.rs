@@ -115,13 +115,13 @@ impl crate::Message for Type {
                     self.name = is.read_string()?;
                 },
                 18 => {
-                    self.fields.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.fields)?;
                 },
                 26 => {
                     self.oneofs.push(is.read_string()?);
                 },
                 34 => {
-                    self.options.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.options)?;
                 },
                 42 => {
                     crate::rt::read_singular_message_into_field(is, &mut self.source_context)?;
@@ -389,7 +389,7 @@ impl crate::Message for Field {
                     self.packed = is.read_bool()?;
                 },
                 74 => {
-                    self.options.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.options)?;
                 },
                 82 => {
                     self.json_name = is.read_string()?;
@@ -547,42 +547,61 @@ pub mod field {
     #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
     // @@protoc_insertion_point(enum:google.protobuf.Field.Kind)
     pub enum Kind {
+        ///  Field type unknown.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_UNKNOWN)
         TYPE_UNKNOWN = 0,
+        ///  Field type double.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_DOUBLE)
         TYPE_DOUBLE = 1,
+        ///  Field type float.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_FLOAT)
         TYPE_FLOAT = 2,
+        ///  Field type int64.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_INT64)
         TYPE_INT64 = 3,
+        ///  Field type uint64.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_UINT64)
         TYPE_UINT64 = 4,
+        ///  Field type int32.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_INT32)
         TYPE_INT32 = 5,
+        ///  Field type fixed64.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_FIXED64)
         TYPE_FIXED64 = 6,
+        ///  Field type fixed32.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_FIXED32)
         TYPE_FIXED32 = 7,
+        ///  Field type bool.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_BOOL)
         TYPE_BOOL = 8,
+        ///  Field type string.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_STRING)
         TYPE_STRING = 9,
+        ///  Field type group. Proto2 syntax only, and deprecated.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_GROUP)
         TYPE_GROUP = 10,
+        ///  Field type message.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_MESSAGE)
         TYPE_MESSAGE = 11,
+        ///  Field type bytes.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_BYTES)
         TYPE_BYTES = 12,
+        ///  Field type uint32.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_UINT32)
         TYPE_UINT32 = 13,
+        ///  Field type enum.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_ENUM)
         TYPE_ENUM = 14,
+        ///  Field type sfixed32.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_SFIXED32)
         TYPE_SFIXED32 = 15,
+        ///  Field type sfixed64.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_SFIXED64)
         TYPE_SFIXED64 = 16,
+        ///  Field type sint32.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_SINT32)
         TYPE_SINT32 = 17,
+        ///  Field type sint64.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Kind.TYPE_SINT64)
         TYPE_SINT64 = 18,
     }
@@ -594,6 +613,30 @@ pub mod field {
             *self as i32
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Kind::TYPE_UNKNOWN => "TYPE_UNKNOWN",
+                Kind::TYPE_DOUBLE => "TYPE_DOUBLE",
+                Kind::TYPE_FLOAT => "TYPE_FLOAT",
+                Kind::TYPE_INT64 => "TYPE_INT64",
+                Kind::TYPE_UINT64 => "TYPE_UINT64",
+                Kind::TYPE_INT32 => "TYPE_INT32",
+                Kind::TYPE_FIXED64 => "TYPE_FIXED64",
+                Kind::TYPE_FIXED32 => "TYPE_FIXED32",
+                Kind::TYPE_BOOL => "TYPE_BOOL",
+                Kind::TYPE_STRING => "TYPE_STRING",
+                Kind::TYPE_GROUP => "TYPE_GROUP",
+                Kind::TYPE_MESSAGE => "TYPE_MESSAGE",
+                Kind::TYPE_BYTES => "TYPE_BYTES",
+                Kind::TYPE_UINT32 => "TYPE_UINT32",
+                Kind::TYPE_ENUM => "TYPE_ENUM",
+                Kind::TYPE_SFIXED32 => "TYPE_SFIXED32",
+                Kind::TYPE_SFIXED64 => "TYPE_SFIXED64",
+                Kind::TYPE_SINT32 => "TYPE_SINT32",
+                Kind::TYPE_SINT64 => "TYPE_SINT64",
+            }
+        }
+
         fn from_i32(value: i32) -> ::std::option::Option<Kind> {
             match value {
                 0 => ::std::option::Option::Some(Kind::TYPE_UNKNOWN),
@@ -667,6 +710,14 @@ pub mod field {
         ];
     }
 
+    impl ::std::convert::TryFrom<i32> for Kind {
+        type Error = crate::UnknownEnumValue;
+
+        fn try_from(value: i32) -> ::std::result::Result<Self, Self::Error> {
+            <Self as crate::Enum>::from_i32(value).ok_or(crate::UnknownEnumValue(value))
+        }
+    }
+
     impl crate::EnumFull for Kind {
         fn enum_descriptor() -> crate::reflect::EnumDescriptor {
             static descriptor: crate::rt::Lazy<crate::reflect::EnumDescriptor> = crate::rt::Lazy::new();
@@ -695,12 +746,16 @@ pub mod field {
     #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
     // @@protoc_insertion_point(enum:google.protobuf.Field.Cardinality)
     pub enum Cardinality {
+        ///  For fields with unknown cardinality.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Cardinality.CARDINALITY_UNKNOWN)
         CARDINALITY_UNKNOWN = 0,
+        ///  For optional fields.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Cardinality.CARDINALITY_OPTIONAL)
         CARDINALITY_OPTIONAL = 1,
+        ///  For required fields. Proto2 syntax only.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Cardinality.CARDINALITY_REQUIRED)
         CARDINALITY_REQUIRED = 2,
+        ///  For repeated fields.
         // @@protoc_insertion_point(enum_value:google.protobuf.Field.Cardinality.CARDINALITY_REPEATED)
         CARDINALITY_REPEATED = 3,
     }
@@ -712,6 +767,15 @@ pub mod field {
             *self as i32
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Cardinality::CARDINALITY_UNKNOWN => "CARDINALITY_UNKNOWN",
+                Cardinality::CARDINALITY_OPTIONAL => "CARDINALITY_OPTIONAL",
+                Cardinality::CARDINALITY_REQUIRED => "CARDINALITY_REQUIRED",
+                Cardinality::CARDINALITY_REPEATED => "CARDINALITY_REPEATED",
+            }
+        }
+
         fn from_i32(value: i32) -> ::std::option::Option<Cardinality> {
             match value {
                 0 => ::std::option::Option::Some(Cardinality::CARDINALITY_UNKNOWN),
@@ -740,6 +804,14 @@ pub mod field {
         ];
     }
 
+    impl ::std::convert::TryFrom<i32> for Cardinality {
+        type Error = crate::UnknownEnumValue;
+
+        fn try_from(value: i32) -> ::std::result::Result<Self, Self::Error> {
+            <Self as crate::Enum>::from_i32(value).ok_or(crate::UnknownEnumValue(value))
+        }
+    }
+
     impl crate::EnumFull for Cardinality {
         fn enum_descriptor() -> crate::reflect::EnumDescriptor {
             static descriptor: crate::rt::Lazy<crate::reflect::EnumDescriptor> = crate::rt::Lazy::new();
@@ -851,10 +923,10 @@ impl crate::Message for Enum {
                     self.name = is.read_string()?;
                 },
                 18 => {
-                    self.enumvalue.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.enumvalue)?;
                 },
                 26 => {
-                    self.options.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.options)?;
                 },
                 34 => {
                     crate::rt::read_singular_message_into_field(is, &mut self.source_context)?;
@@ -1041,7 +1113,7 @@ impl crate::Message for EnumValue {
                     self.number = is.read_int32()?;
                 },
                 26 => {
-                    self.options.push(is.read_message()?);
+                    crate::rt::read_repeated_message_into_vec(is, &mut self.options)?;
                 },
                 tag => {
                     crate::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
@@ -1286,8 +1358,10 @@ impl crate::reflect::ProtobufValue for Option {
 #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
 // @@protoc_insertion_point(enum:google.protobuf.Syntax)
 pub enum Syntax {
+    ///  Syntax `proto2`.
     // @@protoc_insertion_point(enum_value:google.protobuf.Syntax.SYNTAX_PROTO2)
     SYNTAX_PROTO2 = 0,
+    ///  Syntax `proto3`.
     // @@protoc_insertion_point(enum_value:google.protobuf.Syntax.SYNTAX_PROTO3)
     SYNTAX_PROTO3 = 1,
 }
@@ -1299,6 +1373,13 @@ impl crate::Enum for Syntax {
         *self as i32
     }
 
+    fn name(&self) -> &'static str {
+        match *self {
+            Syntax::SYNTAX_PROTO2 => "SYNTAX_PROTO2",
+            Syntax::SYNTAX_PROTO3 => "SYNTAX_PROTO3",
+        }
+    }
+
     fn from_i32(value: i32) -> ::std::option::Option<Syntax> {
         match value {
             0 => ::std::option::Option::Some(Syntax::SYNTAX_PROTO2),
@@ -1321,6 +1402,14 @@ impl crate::Enum for Syntax {
     ];
 }
 
+impl ::std::convert::TryFrom<i32> for Syntax {
+    type Error = crate::UnknownEnumValue;
+
+    fn try_from(value: i32) -> ::std::result::Result<Self, Self::Error> {
+        <Self as crate::Enum>::from_i32(value).ok_or(crate::UnknownEnumValue(value))
+    }
+}
+
 impl crate::EnumFull for Syntax {
     fn enum_descriptor() -> crate::reflect::EnumDescriptor {
         static descriptor: crate::rt::Lazy<crate::reflect::EnumDescriptor> = crate::rt::Lazy::new();
@@ -8,7 +8,7 @@ impl Any {
         format!("{}/{}", type_url_prefix, descriptor.full_name())
     }
 
-    fn type_name_from_type_url(type_url: &str) -> Option<&str> {
+    pub(crate) fn type_name_from_type_url(type_url: &str) -> Option<&str> {
         match type_url.rfind('/') {
             Some(i) => Some(&type_url[i + 1..]),
             None => None,
@@ -17,6 +17,8 @@ impl Any {
 
     /// Pack any message into `well_known_types::Any` value.
     ///
+    /// Type URL is `type.googleapis.com/` followed by message full name.
+    ///
     /// # Examples
     ///
     /// ```
@@ -65,6 +67,9 @@ impl Any {
     }
 
     /// Check if `Any` contains a message of given type.
+    ///
+    /// Only the part of type URL after the last `/` is compared,
+    /// so the type URL prefix can be anything.
     pub fn is<M: MessageFull>(&self) -> bool {
         self.is_dyn(&M::descriptor())
     }
@@ -1,6 +1,11 @@
+use crate::error::ProtobufError;
 use crate::well_known_types::duration::Duration;
 use crate::SpecialFields;
 
+/// Approximately 10,000 years, maximum `Duration` value allowed by `duration.proto`.
+const MAX_SECONDS: i64 = 315_576_000_000;
+const NANOS_PER_SECOND: i32 = 1_000_000_000;
+
 impl Duration {
     /// Zero seconds zero nanoseconds.
     pub const ZERO: Duration = Duration {
@@ -8,20 +13,38 @@ impl Duration {
         nanos: 0,
         special_fields: SpecialFields::new(),
     };
+
+    /// Check the value is in range and `nanos` has the same sign as `seconds`
+    /// as required by `duration.proto`.
+    fn check_valid(&self) -> crate::Result<()> {
+        if self.seconds < -MAX_SECONDS
+            || self.seconds > MAX_SECONDS
+            || self.nanos <= -NANOS_PER_SECOND
+            || self.nanos >= NANOS_PER_SECOND
+            || (self.seconds < 0 && self.nanos > 0)
+            || (self.seconds > 0 && self.nanos < 0)
+        {
+            return Err(ProtobufError::DurationOutOfRange(self.seconds, self.nanos).into());
+        }
+        Ok(())
+    }
 }
 
 /// Convert from `std::time::Duration`.
 ///
-/// # Panics
-///
-/// If `std::time::Duration` value is outside of `Duration` supported range.
-impl From<std::time::Duration> for Duration {
-    fn from(duration: std::time::Duration) -> Self {
-        Duration {
-            seconds: duration.as_secs() as i64,
+/// Conversion fails if `std::time::Duration` value
+/// is outside of `Duration` supported range.
+impl TryFrom<std::time::Duration> for Duration {
+    type Error = crate::Error;
+
+    fn try_from(duration: std::time::Duration) -> crate::Result<Duration> {
+        let duration = Duration {
+            seconds: i64::try_from(duration.as_secs()).unwrap_or(i64::MAX),
             nanos: duration.subsec_nanos() as i32,
             ..Default::default()
-        }
+        };
+        duration.check_valid()?;
+        Ok(duration)
     }
 }
 
@@ -29,14 +52,19 @@ impl From<std::time::Duration> for Duration {
 ///
 /// This conversion might be lossy if `std::time::Duration` precision is smaller than nanoseconds.
 ///
-/// # Panics
-///
-/// If `Duration` value is outside of `std::time::Duration` supported range.
-impl Into<std::time::Duration> for Duration {
-    fn into(self) -> std::time::Duration {
-        assert!(self.seconds >= 0);
-        std::time::Duration::from_secs(self.seconds as u64)
-            + std::time::Duration::from_nanos(self.nanos as u64)
+/// Conversion fails if `Duration` is negative or malformed.
+impl TryFrom<Duration> for std::time::Duration {
+    type Error = crate::Error;
+
+    fn try_from(duration: Duration) -> crate::Result<std::time::Duration> {
+        duration.check_valid()?;
+        if duration.seconds < 0 || duration.nanos < 0 {
+            return Err(ProtobufError::DurationOutOfRange(duration.seconds, duration.nanos).into());
+        }
+        Ok(std::time::Duration::new(
+            duration.seconds as u64,
+            duration.nanos as u32,
+        ))
     }
 }
 
@@ -47,10 +75,10 @@ mod test {
     #[test]
     fn to_from_duration() {
         fn to_from(duration: Duration, std_time_duration: std::time::Duration) {
-            assert_eq!(duration, Duration::from(std_time_duration));
+            assert_eq!(duration, Duration::try_from(std_time_duration).unwrap());
             assert_eq!(
                 std_time_duration,
-                Into::<std::time::Duration>::into(duration)
+                std::time::Duration::try_from(duration).unwrap()
             );
         }
 
@@ -64,4 +92,57 @@ mod test {
             std::time::Duration::from_millis(4_123),
         );
     }
+
+    #[test]
+    fn sub_second() {
+        let duration = Duration {
+            seconds: 0,
+            nanos: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            std::time::Duration::from_nanos(1),
+            std::time::Duration::try_from(duration.clone()).unwrap()
+        );
+        assert_eq!(
+            duration,
+            Duration::try_from(std::time::Duration::from_nanos(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn negative_to_std() {
+        for (seconds, nanos) in [(-1, 0), (0, -1), (-3, -500_000_000)] {
+            let duration = Duration {
+                seconds,
+                nanos,
+                ..Default::default()
+            };
+            assert!(
+                std::time::Duration::try_from(duration).is_err(),
+                "{seconds} {nanos}"
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_to_std() {
+        for (seconds, nanos) in [(1, -1), (0, 1_000_000_000), (315_576_000_001, 0)] {
+            let duration = Duration {
+                seconds,
+                nanos,
+                ..Default::default()
+            };
+            assert!(
+                std::time::Duration::try_from(duration).is_err(),
+                "{seconds} {nanos}"
+            );
+        }
+    }
+
+    #[test]
+    fn out_of_range_from_std() {
+        assert!(Duration::try_from(std::time::Duration::from_secs(315_576_000_001)).is_err());
+        assert!(Duration::try_from(std::time::Duration::MAX).is_err());
+    }
 }
@@ -0,0 +1,225 @@
+use crate::reflect::error::ReflectError;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeType;
+use crate::well_known_types::field_mask::FieldMask;
+use crate::MessageDyn;
+
+/// `path` is `parent` or a subpath of `parent`.
+fn is_covered_by(path: &str, parent: &str) -> bool {
+    match path.strip_prefix(parent) {
+        Some(rem) => rem.is_empty() || rem.starts_with('.'),
+        None => false,
+    }
+}
+
+impl FieldMask {
+    /// Sort paths, remove duplicates and paths covered by other paths
+    /// (e. g. `a.b` is removed if `a` is present).
+    pub fn normalize(&mut self) {
+        self.paths.sort();
+        let mut normalized: Vec<String> = Vec::with_capacity(self.paths.len());
+        for path in self.paths.drain(..) {
+            match normalized.last() {
+                // Field names do not contain characters less than `.`,
+                // so all subpaths of a path follow it after sorting.
+                Some(last) if is_covered_by(&path, last) => {}
+                _ => normalized.push(path),
+            }
+        }
+        self.paths = normalized;
+    }
+
+    /// Paths covered by any of two masks, normalized.
+    pub fn union(&self, other: &FieldMask) -> FieldMask {
+        let mut r = FieldMask::new();
+        r.paths = self.paths.iter().chain(&other.paths).cloned().collect();
+        r.normalize();
+        r
+    }
+
+    /// Paths covered by both masks, normalized.
+    pub fn intersect(&self, other: &FieldMask) -> FieldMask {
+        let mut r = FieldMask::new();
+        for a in &self.paths {
+            for b in &other.paths {
+                if is_covered_by(a, b) {
+                    r.paths.push(a.clone());
+                } else if is_covered_by(b, a) {
+                    r.paths.push(b.clone());
+                }
+            }
+        }
+        r.normalize();
+        r
+    }
+
+    /// Copy fields specified by this mask from `src` to `dst`.
+    ///
+    /// Masked fields are replaced: fields unset in `src` are cleared in `dst`,
+    /// repeated and map fields are replaced entirely.
+    /// Intermediate path components must be singular message fields.
+    ///
+    /// Paths are checked before `dst` is modified,
+    /// so `dst` is unchanged if any path does not resolve against the descriptor.
+    /// Lazy message fields of `dst` on the path are decoded,
+    /// and an error is returned if one cannot be decoded.
+    ///
+    /// # Panics
+    ///
+    /// If `dst` and `src` are messages of different types.
+    pub fn apply(&self, dst: &mut dyn MessageDyn, src: &dyn MessageDyn) -> crate::Result<()> {
+        let descriptor = src.descriptor_dyn();
+        assert_eq!(descriptor, dst.descriptor_dyn());
+
+        let paths: Vec<Vec<&str>> = self.paths.iter().map(|p| p.split('.').collect()).collect();
+        for (path, names) in self.paths.iter().zip(&paths) {
+            check_path(path, names, &descriptor)?;
+        }
+        for names in &paths {
+            apply_path(names, dst, src)?;
+        }
+        Ok(())
+    }
+}
+
+fn check_path(path: &str, names: &[&str], descriptor: &MessageDescriptor) -> crate::Result<()> {
+    let incorrect_path =
+        || ReflectError::IncorrectFieldMaskPath(path.to_owned(), descriptor.full_name().to_owned());
+
+    let mut descriptor = descriptor.clone();
+    for (i, name) in names.iter().enumerate() {
+        let field = descriptor.field_by_name(name).ok_or_else(incorrect_path)?;
+        if i == names.len() - 1 {
+            break;
+        }
+        descriptor = match field.runtime_field_type() {
+            RuntimeFieldType::Singular(RuntimeType::Message(m)) => m,
+            _ => return Err(incorrect_path().into()),
+        };
+    }
+    Ok(())
+}
+
+fn apply_path(names: &[&str], dst: &mut dyn MessageDyn, src: &dyn MessageDyn) -> crate::Result<()> {
+    let field = src.descriptor_dyn().field_by_name(names[0]).unwrap();
+
+    if names.len() > 1 {
+        if field.has_field(src) || field.has_field(dst) {
+            let src = field.get_message(src);
+            apply_path(&names[1..], field.try_mut_message(dst)?, &*src)?;
+        }
+        return Ok(());
+    }
+
+    match field.get_reflect(src) {
+        ReflectFieldRef::Optional(v) => match v.value() {
+            Some(v) => field.set_singular_field(dst, v.to_box()),
+            None => field.clear_field(dst),
+        },
+        ReflectFieldRef::Repeated(repeated) => {
+            let mut dst = field.mut_repeated(dst);
+            dst.clear();
+            for v in repeated {
+                dst.push(v.to_box());
+            }
+        }
+        ReflectFieldRef::Map(map) => {
+            let mut dst = field.mut_map(dst);
+            dst.clear();
+            for (k, v) in &map {
+                dst.insert(k.to_box(), v.to_box());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::well_known_types::field_mask::FieldMask;
+
+    fn mask(paths: &[&str]) -> FieldMask {
+        let mut r = FieldMask::new();
+        r.paths = paths.iter().map(|p| p.to_string()).collect();
+        r
+    }
+
+    #[test]
+    fn normalize() {
+        let mut m = mask(&["b", "a.b.c", "c.d", "a.b", "c.d", "a_b", "c.e"]);
+        m.normalize();
+        assert_eq!(mask(&["a.b", "a_b", "b", "c.d", "c.e"]), m);
+    }
+
+    #[test]
+    fn union() {
+        assert_eq!(
+            mask(&["a", "b.c", "d"]),
+            mask(&["a.b", "b.c", "d"]).union(&mask(&["a", "b.c"]))
+        );
+    }
+
+    #[test]
+    fn intersect() {
+        assert_eq!(
+            mask(&["a.b", "c"]),
+            mask(&["a.b", "c", "e"]).intersect(&mask(&["a", "c", "d"]))
+        );
+        assert_eq!(mask(&[]), mask(&["a"]).intersect(&mask(&["ab"])));
+    }
+
+    fn file(
+        name: &str,
+        java_package: &str,
+        go_package: &str,
+        messages: &[&str],
+    ) -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.set_name(name.to_owned());
+        file.options
+            .mut_or_insert_default()
+            .set_java_package(java_package.to_owned());
+        file.options
+            .mut_or_insert_default()
+            .set_go_package(go_package.to_owned());
+        for message in messages {
+            let mut m = DescriptorProto::new();
+            m.set_name(message.to_string());
+            file.message_type.push(m);
+        }
+        file
+    }
+
+    #[test]
+    fn apply() {
+        let src = file("src", "src.java", "src.go", &["A", "B"]);
+        let mut dst = file("dst", "dst.java", "dst.go", &["C"]);
+        dst.set_package("dst".to_owned());
+
+        mask(&["package", "options.java_package", "message_type"])
+            .apply(&mut dst, &src)
+            .unwrap();
+
+        let mut expected = file("dst", "src.java", "dst.go", &["A", "B"]);
+        expected.clear_package();
+        assert_eq!(expected, dst);
+    }
+
+    #[test]
+    fn apply_invalid_path() {
+        let src = file("src", "src.java", "src.go", &[]);
+        let mut dst = file("dst", "dst.java", "dst.go", &[]);
+        for path in ["nope", "options.nope", "name.nope", "message_type.name", ""] {
+            assert!(
+                mask(&["name", path]).apply(&mut dst, &src).is_err(),
+                "{}",
+                path
+            );
+        }
+        assert_eq!(file("dst", "dst.java", "dst.go", &[]), dst);
+    }
+}
@@ -1,3 +1,6 @@
 mod any;
 mod duration;
+mod field_mask;
+#[cfg(feature = "with-serde")]
+mod struct_;
 mod timestamp;
@@ -0,0 +1,136 @@
+//! Conversions between `google.protobuf.Value` and `serde_json::Value`.
+
+use crate::error::ProtobufError;
+use crate::well_known_types::struct_::value::Kind;
+use crate::well_known_types::struct_::ListValue;
+use crate::well_known_types::struct_::NullValue;
+use crate::well_known_types::struct_::Struct;
+use crate::well_known_types::struct_::Value;
+use crate::EnumOrUnknown;
+
+/// Convert from `serde_json::Value`.
+///
+/// Numbers are converted to `f64`, so large integers may lose precision.
+///
+/// Conversion fails if a number is not representable as finite `f64`,
+/// which is possible with `serde_json` `arbitrary_precision` feature.
+impl TryFrom<serde_json::Value> for Value {
+    type Error = crate::Error;
+
+    fn try_from(json: serde_json::Value) -> crate::Result<Value> {
+        let kind = match json {
+            serde_json::Value::Null => Kind::NullValue(EnumOrUnknown::new(NullValue::NULL_VALUE)),
+            serde_json::Value::Bool(b) => Kind::BoolValue(b),
+            serde_json::Value::Number(n) => match n.as_f64() {
+                Some(f) if f.is_finite() => Kind::NumberValue(f),
+                _ => return Err(ProtobufError::JsonNumberOutOfRange(n.to_string()).into()),
+            },
+            serde_json::Value::String(s) => Kind::StringValue(s),
+            serde_json::Value::Array(values) => {
+                let mut list = ListValue::new();
+                list.values = values
+                    .into_iter()
+                    .map(Value::try_from)
+                    .collect::<crate::Result<_>>()?;
+                Kind::ListValue(list)
+            }
+            serde_json::Value::Object(fields) => {
+                let mut s = Struct::new();
+                s.fields = fields
+                    .into_iter()
+                    .map(|(k, v)| Ok((k, Value::try_from(v)?)))
+                    .collect::<crate::Result<_>>()?;
+                Kind::StructValue(s)
+            }
+        };
+        let mut value = Value::new();
+        value.kind = Some(kind);
+        Ok(value)
+    }
+}
+
+/// Convert to `serde_json::Value`.
+///
+/// Value with unset `kind` is converted to `null`,
+/// numbers are converted to JSON floats.
+///
+/// Conversion fails if value contains NaN or infinite number,
+/// which cannot be represented in JSON.
+impl TryFrom<Value> for serde_json::Value {
+    type Error = crate::Error;
+
+    fn try_from(value: Value) -> crate::Result<serde_json::Value> {
+        Ok(match value.kind {
+            None | Some(Kind::NullValue(_)) => serde_json::Value::Null,
+            Some(Kind::BoolValue(b)) => serde_json::Value::Bool(b),
+            Some(Kind::NumberValue(n)) => match serde_json::Number::from_f64(n) {
+                Some(n) => serde_json::Value::Number(n),
+                None => return Err(ProtobufError::NonFiniteJsonNumber(n).into()),
+            },
+            Some(Kind::StringValue(s)) => serde_json::Value::String(s),
+            Some(Kind::ListValue(list)) => serde_json::Value::Array(
+                list.values
+                    .into_iter()
+                    .map(serde_json::Value::try_from)
+                    .collect::<crate::Result<_>>()?,
+            ),
+            Some(Kind::StructValue(s)) => serde_json::Value::Object(
+                s.fields
+                    .into_iter()
+                    .map(|(k, v)| Ok((k, serde_json::Value::try_from(v)?)))
+                    .collect::<crate::Result<_>>()?,
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::well_known_types::struct_::value::Kind;
+    use crate::well_known_types::struct_::ListValue;
+    use crate::well_known_types::struct_::Value;
+
+    #[test]
+    fn round_trip_nested() {
+        let json = serde_json::json!({
+            "null": null,
+            "bool": true,
+            "number": -1.5,
+            "string": "abc",
+            "list": [1.0, "two", [], {}],
+            "struct": {
+                "nested": {
+                    "list": [false, null],
+                },
+            },
+        });
+
+        let value = Value::try_from(json.clone()).unwrap();
+        let fields = &value.struct_value().fields;
+        assert!(fields["null"].has_null_value());
+        assert_eq!(-1.5, fields["number"].number_value());
+        assert_eq!(4, fields["list"].list_value().values.len());
+
+        assert_eq!(json, serde_json::Value::try_from(value).unwrap());
+    }
+
+    #[test]
+    fn non_finite_number() {
+        for n in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let mut value = Value::new();
+            value.kind = Some(Kind::NumberValue(n));
+            let mut list = ListValue::new();
+            list.values.push(value);
+            let mut value = Value::new();
+            value.kind = Some(Kind::ListValue(list));
+            assert!(serde_json::Value::try_from(value).is_err());
+        }
+    }
+
+    #[test]
+    fn number_out_of_range() {
+        // Parsed with `arbitrary_precision` feature, so the number is kept as is.
+        let json: serde_json::Value = serde_json::from_str("[1e400]").unwrap();
+        assert!(Value::try_from(json).is_err());
+    }
+}
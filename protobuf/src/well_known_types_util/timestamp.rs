@@ -1,9 +1,16 @@
 use std::time::Duration;
 use std::time::SystemTime;
 
+use crate::error::ProtobufError;
 use crate::well_known_types::timestamp::Timestamp;
 use crate::SpecialFields;
 
+/// `0001-01-01T00:00:00Z`, minimum valid `Timestamp` value.
+const MIN_SECONDS: i64 = -62_135_596_800;
+/// `9999-12-31T23:59:59Z`, maximum valid `Timestamp` value.
+const MAX_SECONDS: i64 = 253_402_300_799;
+const NANOS_PER_SECOND: i32 = 1_000_000_000;
+
 impl Timestamp {
     /// Unix epoch value of timestamp.
     pub const UNIX_EPOCH: Timestamp = Timestamp {
@@ -16,13 +23,25 @@ impl Timestamp {
     pub fn now() -> Timestamp {
         Timestamp::from(SystemTime::now())
     }
+
+    /// Check `nanos` is in `0..1_000_000_000` range, and
+    /// timestamp is between `0001-01-01` and `9999-12-31` as required by `timestamp.proto`.
+    fn check_valid(&self) -> crate::Result<()> {
+        if self.seconds < MIN_SECONDS
+            || self.seconds > MAX_SECONDS
+            || self.nanos < 0
+            || self.nanos >= NANOS_PER_SECOND
+        {
+            return Err(ProtobufError::TimestampOutOfRange(self.seconds, self.nanos).into());
+        }
+        Ok(())
+    }
 }
 
-/// Convert from [`Timestamp`].
-///
-/// # Panics
+/// Convert from [`SystemTime`].
 ///
-/// This function panics if given `SystemTime` is outside of `Timestamp` range.
+/// The range is not checked, so the result is not a valid `Timestamp`
+/// if given `SystemTime` is outside of `0001-01-01` to `9999-12-31` range.
 impl From<SystemTime> for Timestamp {
     fn from(time: SystemTime) -> Self {
         match time.duration_since(SystemTime::UNIX_EPOCH) {
@@ -48,22 +67,61 @@ impl From<SystemTime> for Timestamp {
 ///
 /// The conversion could be lossy if `SystemTime` precision is smaller than nanoseconds.
 ///
-/// # Panics
-///
-/// This function panics:
-/// * if given `Timestamp` is outside of `SystemTime` range
-/// * if `Timestamp` is malformed
-impl Into<SystemTime> for Timestamp {
-    fn into(self) -> SystemTime {
-        if self.seconds >= 0 {
-            let duration =
-                Duration::from_secs(self.seconds as u64) + Duration::from_nanos(self.nanos as u64);
-            SystemTime::UNIX_EPOCH + duration
+/// Conversion fails if `Timestamp` is malformed
+/// or is outside of `Timestamp` or `SystemTime` range.
+impl TryFrom<Timestamp> for SystemTime {
+    type Error = crate::Error;
+
+    fn try_from(timestamp: Timestamp) -> crate::Result<SystemTime> {
+        timestamp.check_valid()?;
+        let time = if timestamp.seconds >= 0 {
+            let duration = Duration::from_secs(timestamp.seconds as u64)
+                + Duration::from_nanos(timestamp.nanos as u64);
+            SystemTime::UNIX_EPOCH.checked_add(duration)
         } else {
-            let duration =
-                Duration::from_secs(-self.seconds as u64) - Duration::from_nanos(self.nanos as u64);
-            SystemTime::UNIX_EPOCH - duration
-        }
+            let duration = Duration::from_secs(-timestamp.seconds as u64)
+                - Duration::from_nanos(timestamp.nanos as u64);
+            SystemTime::UNIX_EPOCH.checked_sub(duration)
+        };
+        time.ok_or_else(|| {
+            ProtobufError::TimestampOutOfRange(timestamp.seconds, timestamp.nanos).into()
+        })
+    }
+}
+
+/// Convert from `chrono::DateTime<Utc>`.
+///
+/// Leap second is converted to the next second.
+///
+/// Conversion fails if the time is outside of `Timestamp` range.
+#[cfg(feature = "with-chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for Timestamp {
+    type Error = crate::Error;
+
+    fn try_from(time: chrono::DateTime<chrono::Utc>) -> crate::Result<Timestamp> {
+        let nanos = time.timestamp_subsec_nanos() as i32;
+        let timestamp = Timestamp {
+            seconds: time.timestamp() + (nanos / NANOS_PER_SECOND) as i64,
+            nanos: nanos % NANOS_PER_SECOND,
+            ..Default::default()
+        };
+        timestamp.check_valid()?;
+        Ok(timestamp)
+    }
+}
+
+/// Convert into `chrono::DateTime<Utc>`.
+///
+/// Conversion fails if `Timestamp` is malformed or is outside of `Timestamp` range.
+#[cfg(feature = "with-chrono")]
+impl TryFrom<Timestamp> for chrono::DateTime<chrono::Utc> {
+    type Error = crate::Error;
+
+    fn try_from(timestamp: Timestamp) -> crate::Result<chrono::DateTime<chrono::Utc>> {
+        timestamp.check_valid()?;
+        chrono::DateTime::from_timestamp(timestamp.seconds, timestamp.nanos as u32).ok_or_else(
+            || ProtobufError::TimestampOutOfRange(timestamp.seconds, timestamp.nanos).into(),
+        )
     }
 }
 
@@ -78,7 +136,7 @@ mod test {
     fn to_from_system_time() {
         fn to_from(timestamp: Timestamp, system_time: SystemTime) {
             assert_eq!(timestamp, Timestamp::from(system_time));
-            assert_eq!(system_time, Into::<SystemTime>::into(timestamp));
+            assert_eq!(system_time, SystemTime::try_from(timestamp).unwrap());
         }
 
         to_from(Timestamp::UNIX_EPOCH, SystemTime::UNIX_EPOCH);
@@ -115,4 +173,80 @@ mod test {
             SystemTime::UNIX_EPOCH - Duration::from_millis(3_200),
         );
     }
+
+    #[test]
+    fn to_system_time_pre_epoch() {
+        // 1969-07-20T20:17:40.5Z
+        let timestamp = Timestamp {
+            seconds: -14_182_940,
+            nanos: 500_000_000,
+            ..Default::default()
+        };
+        let system_time = SystemTime::UNIX_EPOCH - Duration::from_millis(14_182_939_500);
+        assert_eq!(
+            system_time,
+            SystemTime::try_from(timestamp.clone()).unwrap()
+        );
+        assert_eq!(timestamp, Timestamp::from(system_time));
+    }
+
+    #[test]
+    fn to_system_time_invalid() {
+        for (seconds, nanos) in [
+            (0, -1),
+            (0, 1_000_000_000),
+            (-62_135_596_801, 0),
+            (253_402_300_800, 0),
+            (i64::MIN, 0),
+        ] {
+            let timestamp = Timestamp {
+                seconds,
+                nanos,
+                ..Default::default()
+            };
+            assert!(
+                SystemTime::try_from(timestamp).is_err(),
+                "{seconds} {nanos}"
+            );
+        }
+    }
+
+    #[cfg(feature = "with-chrono")]
+    #[test]
+    fn to_from_chrono() {
+        use chrono::DateTime;
+        use chrono::TimeZone;
+        use chrono::Utc;
+
+        fn to_from(timestamp: Timestamp, date_time: DateTime<Utc>) {
+            assert_eq!(timestamp, Timestamp::try_from(date_time).unwrap());
+            assert_eq!(date_time, DateTime::<Utc>::try_from(timestamp).unwrap());
+        }
+
+        to_from(Timestamp::UNIX_EPOCH, DateTime::UNIX_EPOCH);
+        to_from(
+            Timestamp {
+                seconds: -14_182_940,
+                nanos: 500_000_000,
+                ..Default::default()
+            },
+            Utc.with_ymd_and_hms(1969, 7, 20, 20, 17, 40).unwrap()
+                + chrono::Duration::milliseconds(500),
+        );
+        to_from(
+            Timestamp {
+                seconds: -62_135_596_800,
+                nanos: 0,
+                ..Default::default()
+            },
+            Utc.with_ymd_and_hms(1, 1, 1, 0, 0, 0).unwrap(),
+        );
+
+        assert!(DateTime::<Utc>::try_from(Timestamp {
+            seconds: 253_402_300_800,
+            ..Default::default()
+        })
+        .is_err());
+        assert!(Timestamp::try_from(Utc.with_ymd_and_hms(10000, 1, 1, 0, 0, 0).unwrap()).is_err());
+    }
 }
@@ -0,0 +1,42 @@
+use core::fmt;
+
+use crate::wire_format::WireType;
+
+/// Error of decoding or encoding wire format with
+/// [`SliceInputStream`](crate::wire::SliceInputStream) or
+/// [`SliceOutputStream`](crate::wire::SliceOutputStream).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Input ended in the middle of a value.
+    UnexpectedEof,
+    /// Unexpected wire type, for example, end group without start group.
+    UnexpectedWireType(WireType),
+    /// Tag has unknown wire type or zero field number.
+    IncorrectTag(u32),
+    /// Varint is longer than allowed for its type.
+    IncorrectVarint,
+    /// String field is not valid UTF-8.
+    Utf8Error,
+    /// `int32` value does not fit into `i32`.
+    I32Overflow(i64),
+    /// Output buffer does not have enough space.
+    BufferTooSmall,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnexpectedEof => write!(f, "Unexpected EOF"),
+            Error::UnexpectedWireType(..) => write!(f, "Unexpected wire type"),
+            Error::IncorrectTag(..) => write!(f, "Incorrect tag"),
+            Error::IncorrectVarint => write!(f, "Incorrect varint"),
+            Error::Utf8Error => write!(f, "Invalid UTF-8 sequence"),
+            Error::I32Overflow(v) => write!(f, "Value too large for i32: {}", v),
+            Error::BufferTooSmall => write!(f, "Output buffer is too small"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,165 @@
+use core::str;
+
+use crate::varint::decode::decode_varint32;
+use crate::varint::decode::decode_varint64;
+use crate::wire::Error;
+use crate::wire::WireRead;
+use crate::wire_format::WireType;
+
+/// Reader of wire format values from a byte slice.
+///
+/// Unlike [`CodedInputStream`](crate::CodedInputStream),
+/// this reader does not allocate: `bytes` and `string` values
+/// are returned as references to the input.
+///
+/// Groups are not supported.
+#[derive(Debug, Clone)]
+pub struct SliceInputStream<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+type Result<T> = core::result::Result<T, Error>;
+
+impl<'a> SliceInputStream<'a> {
+    /// Read from given bytes.
+    pub fn new(buf: &'a [u8]) -> SliceInputStream<'a> {
+        SliceInputStream { buf, pos: 0 }
+    }
+
+    /// Number of bytes read.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Bytes not read yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+
+    /// Check if all input is read.
+    pub fn eof(&self) -> bool {
+        self.pos == self.buf.len()
+    }
+
+    /// Read given number of raw bytes.
+    pub fn read_raw_bytes(&mut self, count: usize) -> Result<&'a [u8]> {
+        let rem = self.remaining();
+        if rem.len() < count {
+            return Err(Error::UnexpectedEof);
+        }
+        self.pos += count;
+        Ok(&rem[..count])
+    }
+
+    fn read_raw_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.read_raw_bytes(N)?.try_into().unwrap())
+    }
+
+    /// Read `bytes` field, length delimited.
+    pub fn read_bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.read_raw_varint32()?;
+        self.read_raw_bytes(len as usize)
+    }
+
+    /// Read `string` field, length delimited.
+    pub fn read_string(&mut self) -> Result<&'a str> {
+        str::from_utf8(self.read_bytes()?).map_err(|_| Error::Utf8Error)
+    }
+
+    /// Read length delimited nested message,
+    /// return a stream over the message bytes.
+    pub fn read_message(&mut self) -> Result<SliceInputStream<'a>> {
+        self.read_bytes().map(SliceInputStream::new)
+    }
+
+    /// Skip field value.
+    pub fn skip_field(&mut self, wire_type: WireType) -> Result<()> {
+        match wire_type {
+            WireType::Varint => self.read_raw_varint64().map(|_| ()),
+            WireType::Fixed64 => self.read_fixed64().map(|_| ()),
+            WireType::Fixed32 => self.read_fixed32().map(|_| ()),
+            WireType::LengthDelimited => self.read_bytes().map(|_| ()),
+            WireType::StartGroup | WireType::EndGroup => Err(Error::UnexpectedWireType(wire_type)),
+        }
+    }
+}
+
+impl<'a> WireRead for SliceInputStream<'a> {
+    type Error = Error;
+
+    fn read_raw_varint64(&mut self) -> Result<u64> {
+        match decode_varint64(self.remaining())? {
+            Some((r, consumed)) => {
+                self.pos += consumed;
+                Ok(r)
+            }
+            None => Err(Error::UnexpectedEof),
+        }
+    }
+
+    fn read_raw_varint32(&mut self) -> Result<u32> {
+        match decode_varint32(self.remaining())? {
+            Some((r, consumed)) => {
+                self.pos += consumed;
+                Ok(r)
+            }
+            None => Err(Error::UnexpectedEof),
+        }
+    }
+
+    fn read_raw_little_endian32(&mut self) -> Result<u32> {
+        self.read_raw_array().map(u32::from_le_bytes)
+    }
+
+    fn read_raw_little_endian64(&mut self) -> Result<u64> {
+        self.read_raw_array().map(u64::from_le_bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::wire::Error;
+    use crate::wire::SliceInputStream;
+    use crate::wire::WireRead;
+    use crate::wire::WireType;
+
+    #[test]
+    fn read_scalars() {
+        let bytes = [
+            0x08, 0x96, 0x01, // 1: 150
+            0x10, 0x03, // 2: sint32 -2
+            0x1d, 0x01, 0x02, 0x03, 0x04, // 3: fixed32
+            0x22, 0x01, 0xff, // 4: invalid string
+        ];
+        let mut is = SliceInputStream::new(&bytes);
+        assert_eq!((1, WireType::Varint), is.read_tag().unwrap());
+        assert_eq!(150, is.read_uint32().unwrap());
+        assert_eq!((2, WireType::Varint), is.read_tag().unwrap());
+        assert_eq!(-2, is.read_sint32().unwrap());
+        assert_eq!((3, WireType::Fixed32), is.read_tag().unwrap());
+        assert_eq!(0x04030201, is.read_fixed32().unwrap());
+        assert_eq!((4, WireType::LengthDelimited), is.read_tag().unwrap());
+        assert_eq!(Err(Error::Utf8Error), is.read_string());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Err(Error::UnexpectedEof),
+            SliceInputStream::new(&[0x96]).read_raw_varint64()
+        );
+        assert_eq!(
+            Err(Error::UnexpectedEof),
+            SliceInputStream::new(&[0x02, 0x00]).read_bytes()
+        );
+        assert_eq!(
+            Err(Error::IncorrectTag(0)),
+            SliceInputStream::new(&[0x00]).read_tag()
+        );
+        assert_eq!(
+            Err(Error::UnexpectedWireType(WireType::StartGroup)),
+            SliceInputStream::new(&[]).skip_field(WireType::StartGroup)
+        );
+    }
+}
@@ -0,0 +1,46 @@
+//! Wire format primitives over byte slices.
+//!
+//! This module is available without `std` feature
+//! (the crate is `no_std` when `std` feature is disabled),
+//! and does not allocate.
+//! Scalar values are encoded and decoded by [`WireRead`] and [`WireWrite`] traits,
+//! which are also implemented by `CodedInputStream` and `CodedOutputStream`.
+//! It can be used to decode and encode simple messages manually
+//! when generated code and reflection are not available.
+//!
+//! # Examples
+//!
+//! ```
+//! use protobuf::wire::SliceInputStream;
+//! use protobuf::wire::SliceOutputStream;
+//! use protobuf::wire::WireRead;
+//! use protobuf::wire::WireType;
+//! use protobuf::wire::WireWrite;
+//!
+//! let mut buf = [0; 16];
+//! let mut os = SliceOutputStream::new(&mut buf);
+//! os.write_int32(1, 150).unwrap();
+//! os.write_string(2, "ab").unwrap();
+//! let written = os.written();
+//! assert_eq!(&[0x08, 0x96, 0x01, 0x12, 0x02, b'a', b'b'], written);
+//!
+//! let mut is = SliceInputStream::new(written);
+//! assert_eq!((1, WireType::Varint), is.read_tag().unwrap());
+//! assert_eq!(150, is.read_int32().unwrap());
+//! assert_eq!((2, WireType::LengthDelimited), is.read_tag().unwrap());
+//! assert_eq!("ab", is.read_string().unwrap());
+//! assert!(is.eof());
+//! ```
+
+mod error;
+mod input;
+mod output;
+mod read;
+mod write;
+
+pub use self::error::Error;
+pub use self::input::SliceInputStream;
+pub use self::output::SliceOutputStream;
+pub use self::read::WireRead;
+pub use self::write::WireWrite;
+pub use crate::wire_format::WireType;
@@ -0,0 +1,148 @@
+use core::mem::MaybeUninit;
+
+use crate::varint::encode::encode_varint32;
+use crate::varint::encode::encode_varint64;
+use crate::varint::MAX_VARINT_ENCODED_LEN;
+use crate::wire::Error;
+use crate::wire::WireWrite;
+
+/// Writer of wire format values into a byte slice.
+///
+/// Writing fails with [`Error::BufferTooSmall`] when the slice is full,
+/// in which case partially written value is left in the buffer.
+///
+/// Length of nested messages must be computed by the caller:
+/// write tag and length with [`write_tag`](WireWrite::write_tag)
+/// and [`write_raw_varint32`](WireWrite::write_raw_varint32),
+/// then write message fields.
+#[derive(Debug)]
+pub struct SliceOutputStream<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+type Result<T> = core::result::Result<T, Error>;
+
+impl<'a> SliceOutputStream<'a> {
+    /// Write into given buffer.
+    pub fn new(buf: &'a mut [u8]) -> SliceOutputStream<'a> {
+        SliceOutputStream { buf, pos: 0 }
+    }
+
+    /// Number of bytes written.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Bytes written so far.
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.pos]
+    }
+
+    fn write_varint_with(
+        &mut self,
+        encode: impl FnOnce(&mut [MaybeUninit<u8>]) -> usize,
+    ) -> Result<()> {
+        let mut buf = [MaybeUninit::uninit(); MAX_VARINT_ENCODED_LEN];
+        let len = encode(&mut buf);
+        // SAFETY: `encode` initialized first `len` bytes.
+        let encoded = unsafe { &*(&buf[..len] as *const [MaybeUninit<u8>] as *const [u8]) };
+        self.write_raw_bytes(encoded)
+    }
+}
+
+impl<'a> WireWrite for SliceOutputStream<'a> {
+    type Error = Error;
+
+    fn write_raw_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let unfilled = &mut self.buf[self.pos..];
+        if unfilled.len() < bytes.len() {
+            return Err(Error::BufferTooSmall);
+        }
+        unfilled[..bytes.len()].copy_from_slice(bytes);
+        self.pos += bytes.len();
+        Ok(())
+    }
+
+    fn write_raw_varint32(&mut self, value: u32) -> Result<()> {
+        self.write_varint_with(|buf| encode_varint32(value, buf))
+    }
+
+    fn write_raw_varint64(&mut self, value: u64) -> Result<()> {
+        self.write_varint_with(|buf| encode_varint64(value, buf))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::wire::Error;
+    use crate::wire::SliceInputStream;
+    use crate::wire::SliceOutputStream;
+    use crate::wire::WireRead;
+    use crate::wire::WireType;
+    use crate::wire::WireWrite;
+
+    fn write_sample<W: WireWrite>(os: &mut W)
+    where
+        W::Error: core::fmt::Debug,
+    {
+        os.write_int32(1, -1).unwrap();
+        os.write_sint64(2, -3).unwrap();
+        os.write_double(3, 0.5).unwrap();
+        os.write_bool(4, true).unwrap();
+        os.write_tag(5, WireType::LengthDelimited).unwrap();
+        os.write_raw_varint32(2).unwrap();
+        os.write_uint32(1, 7).unwrap();
+        os.write_string(6, "abc").unwrap();
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut buf = [0; 64];
+        let mut os = SliceOutputStream::new(&mut buf);
+        write_sample(&mut os);
+        let written = os.written().to_vec();
+
+        let mut is = SliceInputStream::new(&written);
+        assert_eq!((1, WireType::Varint), is.read_tag().unwrap());
+        assert_eq!(-1, is.read_int32().unwrap());
+        assert_eq!((2, WireType::Varint), is.read_tag().unwrap());
+        assert_eq!(-3, is.read_sint64().unwrap());
+        assert_eq!((3, WireType::Fixed64), is.read_tag().unwrap());
+        assert_eq!(0.5, is.read_double().unwrap());
+        assert_eq!((4, WireType::Varint), is.read_tag().unwrap());
+        assert!(is.read_bool().unwrap());
+        assert_eq!((5, WireType::LengthDelimited), is.read_tag().unwrap());
+        let mut nested = is.read_message().unwrap();
+        assert_eq!((1, WireType::Varint), nested.read_tag().unwrap());
+        assert_eq!(7, nested.read_uint32().unwrap());
+        assert!(nested.eof());
+        assert_eq!((6, WireType::LengthDelimited), is.read_tag().unwrap());
+        assert_eq!("abc", is.read_string().unwrap());
+        assert!(is.eof());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn same_as_coded_output_stream() {
+        let mut buf = [0; 64];
+        let mut os = SliceOutputStream::new(&mut buf);
+        write_sample(&mut os);
+
+        let mut expected = Vec::new();
+        let mut std_os = crate::CodedOutputStream::vec(&mut expected);
+        write_sample(&mut std_os);
+        std_os.flush().unwrap();
+        drop(std_os);
+        assert_eq!(&expected[..], os.written());
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        let mut buf = [0; 3];
+        let mut os = SliceOutputStream::new(&mut buf);
+        os.write_uint64(1, 300).unwrap();
+        assert_eq!(Err(Error::BufferTooSmall), os.write_raw_varint64(1));
+        assert_eq!(3, os.pos());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::wire::Error;
+use crate::wire_format::Tag;
+use crate::wire_format::WireType;
+use crate::zigzag::decode_zig_zag_32;
+use crate::zigzag::decode_zig_zag_64;
+
+/// Decoding of wire format scalar values.
+///
+/// Implementations provide raw varint and little-endian reads,
+/// typed values are decoded by the provided methods.
+/// Implemented by both [`SliceInputStream`](crate::wire::SliceInputStream)
+/// and `CodedInputStream`, so both decode values the same way.
+pub trait WireRead {
+    /// Error returned by reads.
+    type Error: From<Error>;
+
+    /// Read varint.
+    fn read_raw_varint64(&mut self) -> Result<u64, Self::Error>;
+
+    /// Read varint, fail if it does not fit into `u32`.
+    fn read_raw_varint32(&mut self) -> Result<u32, Self::Error>;
+
+    /// Read little-endian 32-bit integer.
+    fn read_raw_little_endian32(&mut self) -> Result<u32, Self::Error>;
+
+    /// Read little-endian 64-bit integer.
+    fn read_raw_little_endian64(&mut self) -> Result<u64, Self::Error>;
+
+    /// Read tag, return it as pair (field number, wire type).
+    #[inline]
+    fn read_tag(&mut self) -> Result<(u32, WireType), Self::Error> {
+        let v = self.read_raw_varint32()?;
+        Ok(Tag::new(v)?.unpack())
+    }
+
+    /// Read `int64`
+    #[inline]
+    fn read_int64(&mut self) -> Result<i64, Self::Error> {
+        self.read_raw_varint64().map(|v| v as i64)
+    }
+
+    /// Read `int32`
+    #[inline]
+    fn read_int32(&mut self) -> Result<i32, Self::Error> {
+        let v = self.read_int64()?;
+        i32::try_from(v).map_err(|_| Error::I32Overflow(v).into())
+    }
+
+    /// Read `uint64`
+    #[inline]
+    fn read_uint64(&mut self) -> Result<u64, Self::Error> {
+        self.read_raw_varint64()
+    }
+
+    /// Read `uint32`
+    #[inline]
+    fn read_uint32(&mut self) -> Result<u32, Self::Error> {
+        self.read_raw_varint32()
+    }
+
+    /// Read `sint64`
+    #[inline]
+    fn read_sint64(&mut self) -> Result<i64, Self::Error> {
+        self.read_uint64().map(decode_zig_zag_64)
+    }
+
+    /// Read `sint32`
+    #[inline]
+    fn read_sint32(&mut self) -> Result<i32, Self::Error> {
+        self.read_uint32().map(decode_zig_zag_32)
+    }
+
+    /// Read `fixed64`
+    #[inline]
+    fn read_fixed64(&mut self) -> Result<u64, Self::Error> {
+        self.read_raw_little_endian64()
+    }
+
+    /// Read `fixed32`
+    #[inline]
+    fn read_fixed32(&mut self) -> Result<u32, Self::Error> {
+        self.read_raw_little_endian32()
+    }
+
+    /// Read `sfixed64`
+    #[inline]
+    fn read_sfixed64(&mut self) -> Result<i64, Self::Error> {
+        self.read_raw_little_endian64().map(|v| v as i64)
+    }
+
+    /// Read `sfixed32`
+    #[inline]
+    fn read_sfixed32(&mut self) -> Result<i32, Self::Error> {
+        self.read_raw_little_endian32().map(|v| v as i32)
+    }
+
+    /// Read `double`
+    #[inline]
+    fn read_double(&mut self) -> Result<f64, Self::Error> {
+        self.read_raw_little_endian64().map(f64::from_bits)
+    }
+
+    /// Read `float`
+    #[inline]
+    fn read_float(&mut self) -> Result<f32, Self::Error> {
+        self.read_raw_little_endian32().map(f32::from_bits)
+    }
+
+    /// Read `bool`
+    #[inline]
+    fn read_bool(&mut self) -> Result<bool, Self::Error> {
+        self.read_raw_varint64().map(|v| v != 0)
+    }
+}
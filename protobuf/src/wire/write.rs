@@ -0,0 +1,243 @@
+use crate::wire::Error;
+use crate::wire_format::Tag;
+use crate::wire_format::WireType;
+use crate::zigzag::encode_zig_zag_32;
+use crate::zigzag::encode_zig_zag_64;
+
+/// Encoding of wire format values.
+///
+/// Implementations provide raw bytes and varint writes,
+/// typed values are encoded by the provided methods.
+/// Implemented by both [`SliceOutputStream`](crate::wire::SliceOutputStream)
+/// and `CodedOutputStream`, so both encode values the same way.
+pub trait WireWrite {
+    /// Error returned by writes.
+    type Error: From<Error>;
+
+    /// Write raw bytes.
+    fn write_raw_bytes(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+
+    /// Write varint.
+    fn write_raw_varint32(&mut self, value: u32) -> Result<(), Self::Error>;
+
+    /// Write varint.
+    fn write_raw_varint64(&mut self, value: u64) -> Result<(), Self::Error>;
+
+    /// Write little-endian 32-bit integer.
+    #[inline]
+    fn write_raw_little_endian32(&mut self, value: u32) -> Result<(), Self::Error> {
+        self.write_raw_bytes(&value.to_le_bytes())
+    }
+
+    /// Write little-endian 64-bit integer.
+    #[inline]
+    fn write_raw_little_endian64(&mut self, value: u64) -> Result<(), Self::Error> {
+        self.write_raw_bytes(&value.to_le_bytes())
+    }
+
+    /// Write tag.
+    ///
+    /// # Panics
+    ///
+    /// If field number is outside of valid range.
+    #[inline]
+    fn write_tag(&mut self, field_number: u32, wire_type: WireType) -> Result<(), Self::Error> {
+        self.write_raw_varint32(Tag::make(field_number, wire_type).value())
+    }
+
+    /// Write `float` without tag
+    #[inline]
+    fn write_float_no_tag(&mut self, value: f32) -> Result<(), Self::Error> {
+        self.write_raw_little_endian32(value.to_bits())
+    }
+
+    /// Write `float` field
+    #[inline]
+    fn write_float(&mut self, field_number: u32, value: f32) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Fixed32)?;
+        self.write_float_no_tag(value)
+    }
+
+    /// Write `double` without tag
+    #[inline]
+    fn write_double_no_tag(&mut self, value: f64) -> Result<(), Self::Error> {
+        self.write_raw_little_endian64(value.to_bits())
+    }
+
+    /// Write `double` field
+    #[inline]
+    fn write_double(&mut self, field_number: u32, value: f64) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Fixed64)?;
+        self.write_double_no_tag(value)
+    }
+
+    /// Write `uint64` without tag
+    #[inline]
+    fn write_uint64_no_tag(&mut self, value: u64) -> Result<(), Self::Error> {
+        self.write_raw_varint64(value)
+    }
+
+    /// Write `uint64` field
+    #[inline]
+    fn write_uint64(&mut self, field_number: u32, value: u64) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Varint)?;
+        self.write_uint64_no_tag(value)
+    }
+
+    /// Write `uint32` without tag
+    #[inline]
+    fn write_uint32_no_tag(&mut self, value: u32) -> Result<(), Self::Error> {
+        self.write_raw_varint32(value)
+    }
+
+    /// Write `uint32` field
+    #[inline]
+    fn write_uint32(&mut self, field_number: u32, value: u32) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Varint)?;
+        self.write_uint32_no_tag(value)
+    }
+
+    /// Write `int64` without tag
+    #[inline]
+    fn write_int64_no_tag(&mut self, value: i64) -> Result<(), Self::Error> {
+        self.write_raw_varint64(value as u64)
+    }
+
+    /// Write `int64` field
+    #[inline]
+    fn write_int64(&mut self, field_number: u32, value: i64) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Varint)?;
+        self.write_int64_no_tag(value)
+    }
+
+    /// Write `int32` without tag
+    #[inline]
+    fn write_int32_no_tag(&mut self, value: i32) -> Result<(), Self::Error> {
+        self.write_raw_varint64(value as u64)
+    }
+
+    /// Write `int32` field
+    #[inline]
+    fn write_int32(&mut self, field_number: u32, value: i32) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Varint)?;
+        self.write_int32_no_tag(value)
+    }
+
+    /// Write `sint64` without tag
+    #[inline]
+    fn write_sint64_no_tag(&mut self, value: i64) -> Result<(), Self::Error> {
+        self.write_uint64_no_tag(encode_zig_zag_64(value))
+    }
+
+    /// Write `sint64` field
+    #[inline]
+    fn write_sint64(&mut self, field_number: u32, value: i64) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Varint)?;
+        self.write_sint64_no_tag(value)
+    }
+
+    /// Write `sint32` without tag
+    #[inline]
+    fn write_sint32_no_tag(&mut self, value: i32) -> Result<(), Self::Error> {
+        self.write_uint32_no_tag(encode_zig_zag_32(value))
+    }
+
+    /// Write `sint32` field
+    #[inline]
+    fn write_sint32(&mut self, field_number: u32, value: i32) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Varint)?;
+        self.write_sint32_no_tag(value)
+    }
+
+    /// Write `fixed64` without tag
+    #[inline]
+    fn write_fixed64_no_tag(&mut self, value: u64) -> Result<(), Self::Error> {
+        self.write_raw_little_endian64(value)
+    }
+
+    /// Write `fixed64` field
+    #[inline]
+    fn write_fixed64(&mut self, field_number: u32, value: u64) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Fixed64)?;
+        self.write_fixed64_no_tag(value)
+    }
+
+    /// Write `fixed32` without tag
+    #[inline]
+    fn write_fixed32_no_tag(&mut self, value: u32) -> Result<(), Self::Error> {
+        self.write_raw_little_endian32(value)
+    }
+
+    /// Write `fixed32` field
+    #[inline]
+    fn write_fixed32(&mut self, field_number: u32, value: u32) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Fixed32)?;
+        self.write_fixed32_no_tag(value)
+    }
+
+    /// Write `sfixed64` without tag
+    #[inline]
+    fn write_sfixed64_no_tag(&mut self, value: i64) -> Result<(), Self::Error> {
+        self.write_raw_little_endian64(value as u64)
+    }
+
+    /// Write `sfixed64` field
+    #[inline]
+    fn write_sfixed64(&mut self, field_number: u32, value: i64) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Fixed64)?;
+        self.write_sfixed64_no_tag(value)
+    }
+
+    /// Write `sfixed32` without tag
+    #[inline]
+    fn write_sfixed32_no_tag(&mut self, value: i32) -> Result<(), Self::Error> {
+        self.write_raw_little_endian32(value as u32)
+    }
+
+    /// Write `sfixed32` field
+    #[inline]
+    fn write_sfixed32(&mut self, field_number: u32, value: i32) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Fixed32)?;
+        self.write_sfixed32_no_tag(value)
+    }
+
+    /// Write `bool` without tag
+    #[inline]
+    fn write_bool_no_tag(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.write_raw_varint32(value as u32)
+    }
+
+    /// Write `bool` field
+    #[inline]
+    fn write_bool(&mut self, field_number: u32, value: bool) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::Varint)?;
+        self.write_bool_no_tag(value)
+    }
+
+    /// Write `bytes` without tag
+    #[inline]
+    fn write_bytes_no_tag(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        self.write_raw_varint32(value.len() as u32)?;
+        self.write_raw_bytes(value)
+    }
+
+    /// Write `bytes` field
+    #[inline]
+    fn write_bytes(&mut self, field_number: u32, value: &[u8]) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::LengthDelimited)?;
+        self.write_bytes_no_tag(value)
+    }
+
+    /// Write `string` without tag
+    #[inline]
+    fn write_string_no_tag(&mut self, value: &str) -> Result<(), Self::Error> {
+        self.write_bytes_no_tag(value.as_bytes())
+    }
+
+    /// Write `string` field
+    #[inline]
+    fn write_string(&mut self, field_number: u32, value: &str) -> Result<(), Self::Error> {
+        self.write_tag(field_number, WireType::LengthDelimited)?;
+        self.write_string_no_tag(value)
+    }
+}
@@ -1,7 +1,13 @@
 //! Constants used in serializations.
 
+#[cfg(feature = "std")]
+use core::ops::RangeInclusive;
+
+#[cfg(feature = "std")]
 use crate::descriptor::field_descriptor_proto;
+#[cfg(feature = "std")]
 use crate::error::WireError;
+use crate::wire;
 
 /// Tag occupies three bits.
 pub(crate) const TAG_TYPE_BITS: u32 = 3;
@@ -9,9 +15,14 @@ pub(crate) const TAG_TYPE_BITS: u32 = 3;
 pub(crate) const TAG_TYPE_MASK: u32 = (1u32 << TAG_TYPE_BITS as usize) - 1;
 /// Max possible field number
 pub(crate) const FIELD_NUMBER_MAX: u32 = 0x1fffffff;
+/// Field numbers reserved for protobuf implementation.
+#[cfg(feature = "std")]
+pub(crate) const FIELD_NUMBER_RESERVED: RangeInclusive<u32> = 19000..=19999;
 
+#[cfg(feature = "std")]
 pub(crate) const MAX_MESSAGE_SIZE: u64 = i32::MAX as u64;
 
+#[cfg(feature = "std")]
 #[inline]
 pub(crate) fn check_message_size(size: u64) -> crate::Result<u32> {
     if size <= MAX_MESSAGE_SIZE {
@@ -57,6 +68,7 @@ impl WireType {
         }
     }
 
+    #[cfg(feature = "std")]
     #[doc(hidden)]
     pub fn for_type(field_type: field_descriptor_proto::Type) -> WireType {
         use field_descriptor_proto::Type;
@@ -97,14 +109,14 @@ impl Tag {
     }
 
     /// Extract wire type and field number from integer tag
-    pub(crate) fn new(value: u32) -> crate::Result<Tag> {
+    pub(crate) fn new(value: u32) -> Result<Tag, wire::Error> {
         let wire_type = WireType::new(value & TAG_TYPE_MASK);
         if wire_type.is_none() {
-            return Err(WireError::IncorrectTag(value).into());
+            return Err(wire::Error::IncorrectTag(value));
         }
         let field_number = value >> TAG_TYPE_BITS;
         if field_number == 0 {
-            return Err(WireError::IncorrectTag(value).into());
+            return Err(wire::Error::IncorrectTag(value));
         }
         Ok(Tag {
             field_number,
@@ -1,7 +1,9 @@
 // ZigZag endoging used for efficient transfer of signed integers
 // https://developers.google.com/protocol-buffers/docs/encoding#types
 
+#[cfg(feature = "std")]
 use crate::rt::compute_raw_varint32_size;
+#[cfg(feature = "std")]
 use crate::rt::compute_raw_varint64_size;
 
 pub(crate) fn decode_zig_zag_32(n: u32) -> i32 {
@@ -21,17 +23,20 @@ pub(crate) fn encode_zig_zag_64(n: i64) -> u64 {
 }
 
 /// Helper trait implemented by integer types which could be encoded as zigzag varint.
+#[cfg(feature = "std")]
 pub(crate) trait ProtobufVarintZigzag {
     /// Size of self when encoded as zigzag varint.
     fn len_varint_zigzag(&self) -> u64;
 }
 
+#[cfg(feature = "std")]
 impl ProtobufVarintZigzag for i64 {
     fn len_varint_zigzag(&self) -> u64 {
         compute_raw_varint64_size(encode_zig_zag_64(*self))
     }
 }
 
+#[cfg(feature = "std")]
 impl ProtobufVarintZigzag for i32 {
     fn len_varint_zigzag(&self) -> u64 {
         compute_raw_varint32_size(encode_zig_zag_32(*self))
@@ -130,8 +130,10 @@ fn normalize_file_descriptor(desc: &mut FileDescriptorProto) {
         normalize_service(service);
     }
 
-    // for unittest_custom_options.proto where a custom option
-    // is an extension. Probably nobody outside of Google uses it.
+    // for unittest_custom_options.proto where an aggregate option
+    // contains an extension of a message with `message_set_wire_format`,
+    // which pure parser encodes as a regular field.
+    // Probably nobody outside of Google uses it.
     desc.options
         .mut_or_insert_default()
         .mut_unknown_fields()
@@ -163,12 +165,6 @@ fn normalize_descriptor(desc: &mut DescriptorProto) {
 
     desc.options.mut_or_insert_default();
 
-    // group are not supported
-    desc.options
-        .mut_or_insert_default()
-        .mut_unknown_fields()
-        .remove(7636463);
-
     for field in &mut desc.field {
         normalize_field(field);
     }
@@ -4,6 +4,7 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use protobuf_codegen::Codegen;
+use protobuf_codegen::Customize;
 use protobuf_test_common::build::*;
 
 fn test_protoc_bin_path() -> PathBuf {
@@ -74,11 +75,28 @@ fn generate_include_generated() {
         .run_from_script();
 }
 
+fn generate_single_file() {
+    let dir = format!("{}/single_file", env::var("OUT_DIR").unwrap());
+    if Path::new(&dir).exists() {
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    fs::create_dir(&dir).unwrap();
+    Codegen::new()
+        .protoc()
+        .out_dir(dir)
+        .input("src/single_file/single_file_a.proto")
+        .input("src/single_file/single_file_b.proto")
+        .include("src/single_file")
+        .customize(Customize::default().gen_single_file(true))
+        .run_from_script();
+}
+
 fn generate_pb_rs() {
     generate_in_common();
     generate_in_v2_v3();
     generate_interop();
     generate_include_generated();
+    generate_single_file();
 }
 
 fn main() {
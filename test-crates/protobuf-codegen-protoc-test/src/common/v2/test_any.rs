@@ -34,3 +34,26 @@ fn test_dynamic() {
     );
     assert!(any.unpack_dyn(&MessageTwo::descriptor()).unwrap().is_none());
 }
+
+#[test]
+fn test_custom_type_url_prefix() {
+    let mut m1 = MessageOne::new();
+    m1.set_i(10);
+    let mut any = Any::pack(&m1).unwrap();
+    any.type_url = "example.com/x/test_any.MessageOne".to_owned();
+    assert_eq!(Some(m1), any.unpack::<MessageOne>().unwrap());
+
+    any.type_url = "test_any.MessageOne".to_owned();
+    assert_eq!(None, any.unpack::<MessageOne>().unwrap());
+}
+
+#[test]
+fn test_unpack_malformed() {
+    let mut m1 = MessageOne::new();
+    m1.set_i(10);
+    let mut any = Any::pack(&m1).unwrap();
+    any.value = vec![0xff];
+    assert!(any.unpack::<MessageOne>().is_err());
+    assert!(any.unpack_dyn(&MessageOne::descriptor()).is_err());
+    assert_eq!(None, any.unpack::<MessageTwo>().unwrap());
+}
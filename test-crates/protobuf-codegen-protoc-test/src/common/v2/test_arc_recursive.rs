@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use protobuf::reflect::ReflectValueBox;
+use protobuf::ArcMessageField;
+use protobuf::Message;
+use protobuf::MessageField;
+use protobuf::MessageFull;
+
+use super::test_arc_recursive_pb::*;
+
+fn leaf_tree(value: i32) -> Tree {
+    let mut tree = Tree::new();
+    tree.set_value(value);
+    tree
+}
+
+fn shared_tree() -> (Tree, Arc<Tree>) {
+    let mut subtree = leaf_tree(1);
+    subtree.left = ArcMessageField::some(leaf_tree(2));
+    let subtree = Arc::new(subtree);
+
+    let mut tree = leaf_tree(0);
+    tree.left = ArcMessageField::from_arc(subtree.clone());
+    tree.right = ArcMessageField::from_arc(subtree.clone());
+    (tree, subtree)
+}
+
+#[test]
+fn test_field_types() {
+    let tree = Tree::new();
+    let _: &ArcMessageField<Tree> = &tree.left;
+    let _: &ArcMessageField<Forest> = &tree.forest;
+    let _: &MessageField<Leaf> = &tree.leaf;
+    let no_arc = NoArc::new();
+    let _: &MessageField<NoArc> = &no_arc.next;
+}
+
+#[test]
+fn test_clone_shares_subtree() {
+    let (tree, subtree) = shared_tree();
+    assert!(Arc::ptr_eq(
+        tree.left.arc().unwrap(),
+        tree.right.arc().unwrap()
+    ));
+
+    let copy = tree.clone();
+    assert!(Arc::ptr_eq(&subtree, copy.left.arc().unwrap()));
+    assert!(Arc::ptr_eq(&subtree, copy.right.arc().unwrap()));
+    assert_eq!(tree, copy);
+}
+
+#[test]
+fn test_mutation_does_not_affect_clone() {
+    let (tree, subtree) = shared_tree();
+    let mut copy = tree.clone();
+    copy.left.mut_or_insert_default().set_value(10);
+
+    assert_eq!(10, copy.left.value());
+    assert_eq!(1, copy.right.value());
+    assert_eq!(1, tree.left.value());
+    assert_eq!(1, subtree.value());
+    assert!(!Arc::ptr_eq(&subtree, copy.left.arc().unwrap()));
+    assert!(Arc::ptr_eq(&subtree, copy.right.arc().unwrap()));
+}
+
+#[test]
+fn test_serialize() {
+    let (mut tree, _) = shared_tree();
+    tree.children.push(leaf_tree(3));
+    tree.forest.mut_or_insert_default().trees.push(leaf_tree(4));
+
+    let bytes = tree.write_to_bytes().unwrap();
+    let parsed = Tree::parse_from_bytes(&bytes).unwrap();
+    assert_eq!(tree, parsed);
+    assert_eq!(2, parsed.right.left.value());
+    assert!(parsed.right.right.is_none());
+}
+
+#[test]
+fn test_merge() {
+    let mut tree = Tree::new();
+    tree.left = ArcMessageField::some(leaf_tree(1));
+    let mut other = Tree::new();
+    other.left.mut_or_insert_default().right = ArcMessageField::some(leaf_tree(2));
+
+    let mut expected = tree.clone();
+    expected.left.mut_or_insert_default().right = ArcMessageField::some(leaf_tree(2));
+
+    let mut bytes = tree.write_to_bytes().unwrap();
+    bytes.extend(other.write_to_bytes().unwrap());
+    assert_eq!(expected, Tree::parse_from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn test_reflect() {
+    let (mut tree, subtree) = shared_tree();
+    let left = Tree::descriptor().field_by_name("left").unwrap();
+    let right = Tree::descriptor().field_by_name("right").unwrap();
+
+    let value = left.get_singular(&tree).unwrap();
+    assert_eq!(
+        &*subtree,
+        value.to_message().unwrap().downcast_ref::<Tree>().unwrap()
+    );
+
+    right.set_singular_field(&mut tree, ReflectValueBox::Message(Box::new(leaf_tree(5))));
+    assert_eq!(5, tree.right.value());
+    assert_eq!(1, subtree.value());
+
+    right.clear_field(&mut tree);
+    assert!(tree.right.is_none());
+}
+
+#[test]
+fn test_accessors() {
+    let (mut tree, subtree) = shared_tree();
+    assert!(tree.has_left());
+    assert_eq!(&*subtree, tree.left());
+
+    tree.mut_left().set_value(10);
+    assert_eq!(10, tree.left().value());
+    assert_eq!(1, subtree.value());
+    assert!(Arc::ptr_eq(&subtree, tree.right.arc().unwrap()));
+
+    let right = tree.take_right();
+    assert_eq!(*subtree, right);
+    assert!(!tree.has_right());
+
+    tree.set_right(leaf_tree(5));
+    assert_eq!(5, tree.right().value());
+
+    tree.clear_left();
+    assert!(!tree.has_left());
+    assert_eq!(Tree::default_instance(), tree.left());
+}
@@ -66,6 +66,27 @@ fn test_recursion_limit() {
     }
 }
 
+#[test]
+fn test_recursion_limit_dynamic() {
+    let mut test = TestRecursion::new();
+    for _ in 0..10 {
+        let mut t = TestRecursion::new();
+        t.mut_children().push(test);
+        test = t;
+    }
+
+    let bytes = test.write_to_bytes().unwrap();
+    let descriptor = dynamic_descriptor_for_descriptor::<TestRecursion>();
+
+    for (limit, has_err) in vec![(9, true), (10, false)] {
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        is.set_recursion_limit(limit);
+        let mut t = descriptor.new_instance();
+        let res = t.merge_from_dyn(&mut is);
+        assert_eq!(res.is_err(), has_err, "limit: {:?}", limit);
+    }
+}
+
 #[test]
 fn test_end_by_negative_int() {
     // added following https://github.com/stepancheg/rust-protobuf/pull/209
@@ -84,6 +105,42 @@ fn test_read_junk() {
     assert!(Test1::parse_from_bytes(&decode_hex("00")).is_err());
 }
 
+#[test]
+fn test_length_delimited_round_trip() {
+    let mut test1 = Test1::new();
+    test1.set_a(150);
+    let bytes = test1.write_length_delimited_to_bytes().unwrap();
+    assert_eq!(decode_hex("03 08 96 01"), bytes);
+
+    let (parsed, consumed) = Test1::parse_length_delimited_from_bytes(&bytes).unwrap();
+    assert_eq!(test1, parsed);
+    assert_eq!(bytes.len(), consumed);
+}
+
+#[test]
+fn test_length_delimited_many() {
+    let mut buf = Vec::new();
+    let mut expected = Vec::new();
+    for a in [0, 1, 150, -1] {
+        let mut test1 = Test1::new();
+        test1.set_a(a);
+        test1.write_length_delimited_to_vec(&mut buf).unwrap();
+        expected.push(test1);
+    }
+
+    let mut parsed = Vec::new();
+    let mut rem = &buf[..];
+    while !rem.is_empty() {
+        let (m, consumed) = Test1::parse_length_delimited_from_bytes(rem).unwrap();
+        parsed.push(m);
+        rem = &rem[consumed..];
+    }
+    assert_eq!(expected, parsed);
+
+    // Truncated last message.
+    assert!(Test1::parse_length_delimited_from_bytes(&buf[buf.len() - 1..]).is_err());
+}
+
 #[test]
 fn test_unknown_fields_length_delimited() {
     let mut message = TestUnknownFields::new();
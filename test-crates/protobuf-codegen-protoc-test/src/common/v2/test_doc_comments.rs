@@ -0,0 +1,34 @@
+const GENERATED: &str = include_str!("test_doc_comments_pb.rs");
+
+/// Line preceding declaration, skipping attributes and regular comments.
+fn line_before(decl: &str) -> &'static str {
+    let lines: Vec<&str> = GENERATED.lines().map(|l| l.trim()).collect();
+    let pos = lines
+        .iter()
+        .position(|l| l.starts_with(decl))
+        .unwrap_or_else(|| panic!("declaration not found: {}", decl));
+    lines[..pos]
+        .iter()
+        .rev()
+        .find(|l| l.starts_with("///") || !(l.starts_with("//") || l.starts_with("#[")))
+        .unwrap()
+}
+
+#[test]
+fn test_doc_comments() {
+    // Comment text is copied as is, including the leading space.
+    assert!(GENERATED.contains("///  Message comment.\n///  Second line.\n"));
+    assert_eq!("///  Second line.", line_before("pub struct Commented "));
+    assert_eq!("///  Field comment.", line_before("pub documented:"));
+    assert!(line_before("pub undocumented:").starts_with("pub documented:"));
+    assert_eq!(
+        "///  Nested message comment.",
+        line_before("pub struct Nested ")
+    );
+    assert_eq!("///  Enum comment.", line_before("pub enum CommentedEnum "));
+    assert_eq!("///  Value comment.", line_before("COMMENTED_ENUM_FIRST"));
+    assert_eq!(
+        "COMMENTED_ENUM_FIRST = 0,",
+        line_before("COMMENTED_ENUM_SECOND")
+    );
+}
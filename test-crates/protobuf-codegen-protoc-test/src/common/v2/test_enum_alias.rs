@@ -32,3 +32,27 @@ fn descriptor() {
     assert_eq!("A", EnumWithAlias::A.descriptor().name());
     assert_eq!("A_AGAIN", EnumWithAlias::A_AGAIN.descriptor().name());
 }
+
+#[test]
+fn name() {
+    assert_eq!("A", EnumWithAlias::A.name());
+    assert_eq!("A_AGAIN", EnumWithAlias::A_AGAIN.name());
+    for &value in EnumWithAlias::VALUES {
+        assert_eq!(value.descriptor().name(), value.name());
+    }
+}
+
+#[test]
+fn alias_names_resolve_to_same_value() {
+    assert_eq!(Some(EnumWithAlias::A), EnumWithAlias::from_str("A"));
+    assert_eq!(Some(EnumWithAlias::A), EnumWithAlias::from_str("A_AGAIN"));
+    assert_eq!(
+        Some("A_AGAIN"),
+        EnumWithAlias::from_str("A_AGAIN").as_ref().map(Enum::name)
+    );
+    // First declared name is canonical.
+    assert_eq!("A", EnumWithAlias::from_i32(10).unwrap().name());
+
+    let descriptor = EnumWithAlias::enum_descriptor();
+    assert_eq!(10, descriptor.value_by_name("A_AGAIN").unwrap().value());
+}
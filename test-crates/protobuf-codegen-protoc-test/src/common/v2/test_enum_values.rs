@@ -13,3 +13,28 @@ fn test_enum_values() {
     ];
     assert_eq!(expected, TestEnumValuesEnum::VALUES);
 }
+
+#[test]
+fn test_name_from_str() {
+    for &value in TestEnumValuesEnum::VALUES {
+        assert_eq!(Some(value), TestEnumValuesEnum::from_str(value.name()));
+    }
+    assert_eq!("SUMMER", TestEnumValuesEnum::SUMMER.name());
+    assert_eq!(None, TestEnumValuesEnum::from_str("summer"));
+}
+
+#[test]
+fn test_try_from_i32() {
+    assert_eq!(
+        Ok(TestEnumValuesEnum::SUMMER),
+        TestEnumValuesEnum::try_from(33)
+    );
+    match TestEnumValuesEnum::try_from(99) {
+        Err(UnknownEnumValue(v)) => assert_eq!(99, v),
+        r => panic!("{:?}", r),
+    }
+    assert_eq!(
+        "Unknown enum value: 99",
+        TestEnumValuesEnum::try_from(99).unwrap_err().to_string()
+    );
+}
@@ -0,0 +1,33 @@
+use protobuf::MessageFull;
+
+use super::test_field_numbers_pb::*;
+
+#[test]
+fn test_field_numbers() {
+    assert_eq!(1, WithFieldNumbers::FIRST_FIELD_NUMBER);
+    assert_eq!(7, WithFieldNumbers::SOME_STRINGS_FIELD_NUMBER);
+    assert_eq!(150, WithFieldNumbers::COUNTS_FIELD_NUMBER);
+    assert_eq!(536870911, WithFieldNumbers::NESTED_FIELD_NUMBER);
+}
+
+#[test]
+fn test_field_numbers_match_descriptor() {
+    let descriptor = WithFieldNumbers::descriptor();
+    for (name, number) in [
+        ("first", WithFieldNumbers::FIRST_FIELD_NUMBER),
+        ("some_strings", WithFieldNumbers::SOME_STRINGS_FIELD_NUMBER),
+        ("counts", WithFieldNumbers::COUNTS_FIELD_NUMBER),
+        ("nested", WithFieldNumbers::NESTED_FIELD_NUMBER),
+    ] {
+        let field = descriptor.field_by_name(name).unwrap();
+        assert_eq!(field.number() as u32, number);
+    }
+}
+
+#[test]
+fn test_disabled_for_message() {
+    let generated = include_str!("test_field_numbers_pb.rs");
+    assert!(generated.contains("pub const FIRST_FIELD_NUMBER: u32 = 1;"));
+    // Only in `WithFieldNumbers`.
+    assert_eq!(1, generated.matches("FIRST_FIELD_NUMBER").count());
+}
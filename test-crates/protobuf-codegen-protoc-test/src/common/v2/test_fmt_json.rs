@@ -1,3 +1,7 @@
+use std::io;
+
+use protobuf::reflect::ReflectValueBox;
+use protobuf::EnumFull;
 use protobuf::MessageFull;
 use protobuf_test_common::*;
 
@@ -211,6 +215,37 @@ fn test_bytes() {
     test_json_print_parse_message("{\"bytesRepeated\": [\"\", \"AA==\", \"QQpC\"]}", &m);
 }
 
+#[test]
+fn test_bytes_base64() {
+    let mut m = TestTypes::new();
+    m.set_bytes_singular(vec![0xfb, 0xff]);
+    m.set_bytes_repeated(vec![b"ab".to_vec(), vec![0xfb, 0xef, 0xbe]].into());
+
+    let standard = "{\"bytesSingular\": \"+/8=\", \"bytesRepeated\": [\"YWI=\", \"++++\"]}";
+    test_json_print_parse_message(standard, &m);
+
+    let print_options = protobuf_json_mapping::PrintOptions {
+        bytes_base64: protobuf_json_mapping::BytesBase64::UrlSafeNoPad,
+        ..Default::default()
+    };
+    let json = protobuf_json_mapping::print_to_string_with_options(&m, &print_options).unwrap();
+    assert_eq!(
+        "{\"bytesSingular\": \"-_8\", \"bytesRepeated\": [\"YWI\", \"----\"]}",
+        json
+    );
+    test_json_parse_message(&json, &m);
+
+    // Padding is optional for both character sets.
+    test_json_parse_message(
+        "{\"bytesSingular\": \"+/8\", \"bytesRepeated\": [\"YWI\", \"----\"]}",
+        &m,
+    );
+    test_json_parse_message(
+        "{\"bytesSingular\": \"-_8=\", \"bytesRepeated\": [\"YWI=\", \"++++\"]}",
+        &m,
+    );
+}
+
 #[test]
 fn test_enum() {
     let mut m = TestTypes::new();
@@ -234,6 +269,65 @@ fn test_enum_int() {
     };
     let json = protobuf_json_mapping::print_to_string_with_options(&m, &print_options).unwrap();
     assert_eq!("{\"testEnumSingular\": 10}", json);
+    test_json_parse_message(&json, &m);
+
+    // Unknown enum values are preserved as numbers.
+    let mut m = TestTypes::new();
+    TestTypes::descriptor()
+        .field_by_name("test_enum_singular")
+        .unwrap()
+        .set_singular_field(
+            &mut m,
+            ReflectValueBox::Enum(TestEnum::enum_descriptor(), 17),
+        );
+    let json = protobuf_json_mapping::print_to_string_with_options(&m, &print_options).unwrap();
+    assert_eq!("{\"testEnumSingular\": 17}", json);
+    test_json_print_parse_message("{\"testEnumSingular\": 17}", &m);
+}
+
+#[test]
+fn test_int64_as_number() {
+    let print_options = protobuf_json_mapping::PrintOptions {
+        int64_as_number: true,
+        ..Default::default()
+    };
+
+    let mut m = TestTypes::new();
+    m.set_int64_singular(i64::MAX);
+    m.set_uint64_singular(u64::MAX);
+    m.int64_repeated.push(i64::MIN);
+    let json = protobuf_json_mapping::print_to_string_with_options(&m, &print_options).unwrap();
+    assert_eq!(
+        "{\"int64Singular\": 9223372036854775807, \"uint64Singular\": 18446744073709551615, \
+            \"int64Repeated\": [-9223372036854775808]}",
+        json
+    );
+    test_json_parse_message(&json, &m);
+    test_json_print_parse_message(
+        "{\"int64Singular\": \"9223372036854775807\", \"uint64Singular\": \"18446744073709551615\", \
+            \"int64Repeated\": [\"-9223372036854775808\"]}",
+        &m,
+    );
+
+    // Map keys are always strings.
+    let mut m = TestTypes::new();
+    m.fixed64_map_field.insert(10, 20);
+    let json = protobuf_json_mapping::print_to_string_with_options(&m, &print_options).unwrap();
+    assert_eq!("{\"fixed64MapField\": {\"10\": 20}}", json);
+    test_json_parse_message(&json, &m);
+
+    for json in &[
+        "{\"int64Singular\": 9223372036854775808}",
+        "{\"int64Singular\": \"9223372036854775808\"}",
+        "{\"uint64Singular\": 18446744073709551616}",
+        "{\"uint64Singular\": \"18446744073709551616\"}",
+    ] {
+        assert!(
+            protobuf_json_mapping::parse_from_str::<TestTypes>(json).is_err(),
+            "{}",
+            json
+        );
+    }
 }
 
 #[test]
@@ -250,6 +344,50 @@ fn test_map_field_int_key() {
     );
 }
 
+#[test]
+fn test_map_field_int32_key() {
+    let mut m = TestTypes::new();
+    m.int32_map_field.insert(-7, 1);
+    test_json_print_parse_message("{\"int32MapField\": {\"-7\": 1}}", &m);
+
+    let mut m = TestTypes::new();
+    m.sint64_map_field.insert(i64::MIN, 2);
+    test_json_print_parse_message(
+        "{\"sint64MapField\": {\"-9223372036854775808\": \"2\"}}",
+        &m,
+    );
+}
+
+#[test]
+fn test_map_field_bool_key() {
+    let mut m = TestTypes::new();
+    m.uint32_map_field.insert(true, 1);
+    test_json_print_parse_message("{\"uint32MapField\": {\"true\": 1}}", &m);
+
+    let mut m = TestTypes::new();
+    m.uint32_map_field.insert(false, 2);
+    test_json_print_parse_message("{\"uint32MapField\": {\"false\": 2}}", &m);
+}
+
+#[test]
+fn test_map_field_invalid_key() {
+    for json in &[
+        "{\"int32MapField\": {\"abc\": 1}}",
+        "{\"int32MapField\": {\"1.5\": 1}}",
+        "{\"int32MapField\": {\"\": 1}}",
+        "{\"int32MapField\": {\"4294967296\": 1}}",
+        "{\"fixed32MapField\": {\"-1\": 1}}",
+        "{\"uint32MapField\": {\"1\": 1}}",
+        "{\"uint32MapField\": {\"True\": 1}}",
+    ] {
+        assert!(
+            protobuf_json_mapping::parse_from_str::<TestTypes>(json).is_err(),
+            "{}",
+            json
+        );
+    }
+}
+
 #[test]
 fn test_map_field_string_key() {
     let mut m = TestTypes::new();
@@ -282,6 +420,90 @@ fn test_use_original_field_names() {
     };
     let json = protobuf_json_mapping::print_to_string_with_options(&m, &print_options).unwrap();
     assert_eq!("{\"bool_singular\": true}", json);
+
+    // Oneof fields follow the same rule, map keys are not field names.
+    let mut m = TestOriginalFieldNames::new();
+    m.set_some_field(1);
+    m.some_map.insert("map_key".to_owned(), 2);
+    m.set_oneof_field("x".to_owned());
+
+    let json = protobuf_json_mapping::print_to_string_with_options(&m, &print_options).unwrap();
+    assert_eq!(
+        "{\"some_field\": 1, \"some_map\": {\"map_key\": 2}, \"oneof_field\": \"x\"}",
+        json
+    );
+    test_json_parse_message(&json, &m);
+
+    test_json_print_parse_message(
+        "{\"someField\": 1, \"someMap\": {\"map_key\": 2}, \"oneofField\": \"x\"}",
+        &m,
+    );
+}
+
+#[test]
+fn test_pretty() {
+    let mut message = TestMessage::new();
+    message.set_value(1);
+    let mut m = TestTypes::new();
+    m.set_int32_singular(10);
+    m.set_test_message_singular(message.clone());
+    m.set_int32_repeated(vec![1, 2]);
+    m.set_test_message_repeated(vec![message.clone(), TestMessage::new()].into());
+    m.message_map_field.insert("k".to_owned(), message);
+
+    assert_eq!(
+        "{\"int32Singular\": 10, \"testMessageSingular\": {\"value\": 1}, \
+            \"int32Repeated\": [1, 2], \"testMessageRepeated\": [{\"value\": 1}, {}], \
+            \"messageMapField\": {\"k\": {\"value\": 1}}}",
+        protobuf_json_mapping::print_to_string(&m).unwrap()
+    );
+
+    let print_options = protobuf_json_mapping::PrintOptions {
+        pretty: true,
+        ..Default::default()
+    };
+    let json = protobuf_json_mapping::print_to_string_with_options(&m, &print_options).unwrap();
+    assert_eq!(
+        r#"{
+  "int32Singular": 10,
+  "testMessageSingular": {
+    "value": 1
+  },
+  "int32Repeated": [
+    1,
+    2
+  ],
+  "testMessageRepeated": [
+    {
+      "value": 1
+    },
+    {}
+  ],
+  "messageMapField": {
+    "k": {
+      "value": 1
+    }
+  }
+}"#,
+        json
+    );
+    test_json_parse_message(&json, &m);
+
+    let print_options = protobuf_json_mapping::PrintOptions {
+        pretty: true,
+        indent: 4,
+        ..Default::default()
+    };
+    let json =
+        protobuf_json_mapping::print_to_string_with_options(&TestTypes::new(), &print_options)
+            .unwrap();
+    assert_eq!("{}", json);
+    let json = protobuf_json_mapping::print_to_string_with_options(
+        m.test_message_singular(),
+        &print_options,
+    )
+    .unwrap();
+    assert_eq!("{\n    \"value\": 1\n}", json);
 }
 
 #[test]
@@ -335,6 +557,19 @@ fn test_ignore_unknown_fields() {
     assert_eq!(expected, m);
 }
 
+#[test]
+fn test_unknown_fields_error_by_default() {
+    let json = "{\"bool_singular\": true, \"fgfgfg\": 12}";
+
+    let mut m = TestTypes::new();
+    let err = protobuf_json_mapping::merge_from_str(&mut m, json).unwrap_err();
+    assert!(
+        err.to_string().contains("`fgfgfg`"),
+        "error should name the field: {}",
+        err
+    );
+}
+
 #[test]
 fn test_reflect() {
     for m in special_messages(&TestTypes::descriptor()) {
@@ -348,6 +583,12 @@ fn test_use_json_name() {
     m.set_field_with_json_name(true);
     let json = protobuf_json_mapping::print_to_string(&m).unwrap();
     assert_eq!("{\"Field With json_name\": true}", json);
+
+    let parsed: TestJsonName = protobuf_json_mapping::parse_from_str(&json).unwrap();
+    assert_eq!(m, parsed);
+    let parsed: TestJsonName =
+        protobuf_json_mapping::parse_from_str("{\"field_with_json_name\": true}").unwrap();
+    assert_eq!(m, parsed);
 }
 
 #[test]
@@ -362,3 +603,110 @@ fn test_more_than_one() {
         &m,
     );
 }
+
+#[test]
+fn test_print_to_writer() {
+    let mut m = TestTypes::new();
+    m.set_string_singular("a\"b".to_owned());
+    m.set_double_repeated(vec![1.0, f64::NAN]);
+    m.set_test_message_repeated(vec![TestMessage::new(); 3].into());
+    m.message_map_field
+        .insert("k".to_owned(), TestMessage::new());
+
+    for pretty in [false, true] {
+        let print_options = protobuf_json_mapping::PrintOptions {
+            pretty,
+            ..Default::default()
+        };
+        let mut bytes = Vec::new();
+        protobuf_json_mapping::print_to_writer(&m, &mut bytes, &print_options).unwrap();
+        assert_eq!(
+            protobuf_json_mapping::print_to_string_with_options(&m, &print_options).unwrap(),
+            String::from_utf8(bytes).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_print_to_writer_error() {
+    /// Accepts given number of bytes, then fails.
+    struct LimitedWriter {
+        rem: usize,
+    }
+
+    impl io::Write for LimitedWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.rem == 0 {
+                return Err(io::Error::new(io::ErrorKind::Other, "writer is full"));
+            }
+            let len = buf.len().min(self.rem);
+            self.rem -= len;
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut m = TestTypes::new();
+    m.set_int32_repeated((0..100).collect());
+    let len = protobuf_json_mapping::print_to_string(&m).unwrap().len();
+
+    let mut writer = LimitedWriter { rem: len / 2 };
+    let err =
+        protobuf_json_mapping::print_to_writer(&m, &mut writer, &Default::default()).unwrap_err();
+    assert_eq!("writer is full", err.to_string());
+
+    let mut writer = LimitedWriter { rem: len };
+    protobuf_json_mapping::print_to_writer(&m, &mut writer, &Default::default()).unwrap();
+    assert_eq!(0, writer.rem);
+}
+
+#[test]
+fn test_to_json_value() {
+    let mut m = TestTypes::new();
+    m.set_int64_singular(-10);
+    m.set_float_singular(0.1);
+    m.set_double_singular(f64::NEG_INFINITY);
+    m.set_test_enum_singular(TestEnum::DARK);
+    m.mut_test_message_singular().set_value(20);
+    let mut nested = TestMessage::new();
+    nested.set_value(30);
+    m.test_message_repeated.push(nested.clone());
+    m.message_map_field.insert("k".to_owned(), nested);
+    m.enum_map_field.insert(7, TestEnum::LIGHT.into());
+    m.bool_map_field.insert(1, true);
+
+    let json = protobuf_json_mapping::to_json_value(&m).unwrap();
+    assert_eq!(Some(&"-10".into()), json.pointer("/int64Singular"));
+    assert_eq!(Some(&0.1.into()), json.pointer("/floatSingular"));
+    assert_eq!(Some(&"-Infinity".into()), json.pointer("/doubleSingular"));
+    assert_eq!(Some(&"DARK".into()), json.pointer("/testEnumSingular"));
+    assert_eq!(Some(&20.into()), json.pointer("/testMessageSingular/value"));
+    assert_eq!(
+        Some(&30.into()),
+        json.pointer("/testMessageRepeated/0/value")
+    );
+    assert_eq!(Some(&30.into()), json.pointer("/messageMapField/k/value"));
+    assert_eq!(Some(&"LIGHT".into()), json.pointer("/enumMapField/7"));
+    assert_eq!(Some(&true.into()), json.pointer("/boolMapField/1"));
+    assert_eq!(None, json.pointer("/int32Singular"));
+
+    let printed = protobuf_json_mapping::print_to_string(&m).unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&printed).unwrap(),
+        json
+    );
+
+    let print_options = protobuf_json_mapping::PrintOptions {
+        enum_values_int: true,
+        proto_field_name: true,
+        int64_as_number: true,
+        ..Default::default()
+    };
+    let json = protobuf_json_mapping::to_json_value_with_options(&m, &print_options).unwrap();
+    assert_eq!(Some(&(-10).into()), json.pointer("/int64_singular"));
+    assert_eq!(Some(&10.into()), json.pointer("/test_enum_singular"));
+    assert_eq!(Some(&20.into()), json.pointer("/enum_map_field/7"));
+}
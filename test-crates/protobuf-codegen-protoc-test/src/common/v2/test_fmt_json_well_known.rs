@@ -1,3 +1,4 @@
+use protobuf::well_known_types::any::Any;
 use protobuf::well_known_types::duration::Duration;
 use protobuf::well_known_types::field_mask::FieldMask;
 use protobuf::well_known_types::struct_::ListValue;
@@ -5,6 +6,16 @@ use protobuf::well_known_types::struct_::NullValue;
 use protobuf::well_known_types::struct_::Struct;
 use protobuf::well_known_types::struct_::Value;
 use protobuf::well_known_types::timestamp::Timestamp;
+use protobuf::well_known_types::wrappers::Int32Value;
+use protobuf::well_known_types::wrappers::StringValue;
+use protobuf::MessageFull;
+use protobuf_json_mapping::parse_from_str;
+use protobuf_json_mapping::parse_from_str_with_options;
+use protobuf_json_mapping::print_to_string;
+use protobuf_json_mapping::print_to_string_with_options;
+use protobuf_json_mapping::ParseOptions;
+use protobuf_json_mapping::PrintOptions;
+use protobuf_json_mapping::TypeRegistry;
 use protobuf_test_common::*;
 
 use super::test_fmt_json_well_known_pb::*;
@@ -35,6 +46,46 @@ fn test_timestamp() {
     test_json_print_parse_message("{\"timestamp\": \"1970-01-01T00:00:01.000000001Z\"}", &m);
 }
 
+#[test]
+fn test_timestamp_duration_fraction_digits() {
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    m.mut_timestamp().seconds = 1;
+    m.mut_timestamp().nanos = 123_456_789;
+    m.mut_duration().seconds = -1;
+    m.mut_duration().nanos = -987_654_321;
+
+    // Digits which do not fit are truncated.
+    for (digits, timestamp, duration) in [
+        (None, "1970-01-01T00:00:01.123456789Z", "-1.987654321s"),
+        (Some(0), "1970-01-01T00:00:01Z", "-1s"),
+        (Some(3), "1970-01-01T00:00:01.123Z", "-1.987s"),
+        (Some(6), "1970-01-01T00:00:01.123456Z", "-1.987654s"),
+        (Some(9), "1970-01-01T00:00:01.123456789Z", "-1.987654321s"),
+    ] {
+        let print_options = PrintOptions {
+            timestamp_fraction_digits: digits,
+            ..Default::default()
+        };
+        let expected = format!(
+            "{{\"duration\": \"{}\", \"timestamp\": \"{}\"}}",
+            duration, timestamp
+        );
+        assert_eq!(
+            expected,
+            print_to_string_with_options(&m, &print_options).unwrap()
+        );
+        let json = protobuf_json_mapping::to_json_value_with_options(&m, &print_options).unwrap();
+        assert_eq!(Some(&timestamp.into()), json.pointer("/timestamp"));
+        assert_eq!(Some(&duration.into()), json.pointer("/duration"));
+    }
+
+    let print_options = PrintOptions {
+        timestamp_fraction_digits: Some(2),
+        ..Default::default()
+    };
+    assert!(print_to_string_with_options(&m, &print_options).is_err());
+}
+
 #[test]
 fn test_null_value() {
     let mut m = TestFmtJsonWellKnownTypes::new();
@@ -132,11 +183,107 @@ fn test_wrappers() {
     test_json_print_parse_message("{\"bytesValue\": \"YWI=\"}", &m);
 }
 
+#[test]
+fn test_wrappers_null() {
+    let m: TestFmtJsonWellKnownTypes = parse_from_str("{\"int32Value\": null}").unwrap();
+    assert!(m.int32_value.is_none());
+    let m: TestFmtJsonWellKnownTypes = parse_from_str("{\"stringValue\": null}").unwrap();
+    assert!(m.string_value.is_none());
+}
+
+#[test]
+fn test_repeated_wrappers() {
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    for v in [1, -2, 0] {
+        let mut w = Int32Value::new();
+        w.value = v;
+        m.repeated_int32_value.push(w);
+    }
+    test_json_print_parse_message("{\"repeatedInt32Value\": [1, -2, 0]}", &m);
+
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    let mut w = StringValue::new();
+    w.value = "ab".to_owned();
+    m.repeated_string_value.push(w);
+    test_json_print_parse_message("{\"repeatedStringValue\": [\"ab\"]}", &m);
+
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    let mut w = Int32Value::new();
+    w.value = 3;
+    m.map_int32_value.insert("a".to_owned(), w);
+    test_json_print_parse_message("{\"mapInt32Value\": {\"a\": 3}}", &m);
+}
+
+#[test]
+fn test_repeated_wrappers_null_element() {
+    // Wrappers in repeated fields and map values are always present,
+    // so `null` cannot be parsed into them.
+    for json in [
+        "{\"repeatedInt32Value\": [1, null]}",
+        "{\"repeatedStringValue\": [null]}",
+        "{\"mapInt32Value\": {\"a\": null}}",
+    ] {
+        let err = parse_from_str::<TestFmtJsonWellKnownTypes>(json).unwrap_err();
+        assert!(err.to_string().contains("null"), "{}: {}", json, err);
+    }
+
+    // `null` list is an empty list.
+    let m: TestFmtJsonWellKnownTypes = parse_from_str("{\"repeatedInt32Value\": null}").unwrap();
+    assert!(m.repeated_int32_value.is_empty());
+}
+
 #[test]
 fn test_any() {
+    let mut type_registry = TypeRegistry::new();
+    type_registry.add_message(Timestamp::descriptor());
+    type_registry.add_message(TestFmtJsonWellKnownTypes::descriptor());
+    let print_options = PrintOptions {
+        type_registry: type_registry.clone(),
+        ..Default::default()
+    };
+    let parse_options = ParseOptions {
+        type_registry,
+        ..Default::default()
+    };
+
+    let mut timestamp = Timestamp::new();
+    timestamp.seconds = 1_000_000_000;
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    m.set_any_value(Any::pack(&timestamp).unwrap());
+
+    let json = "{\"anyValue\": {\"@type\": \"type.googleapis.com/google.protobuf.Timestamp\", \"value\": \"2001-09-09T01:46:40.000000000Z\"}}";
+    assert_eq!(
+        json,
+        print_to_string_with_options(&m, &print_options).unwrap()
+    );
+    let parsed: TestFmtJsonWellKnownTypes =
+        parse_from_str_with_options(json, &parse_options).unwrap();
+    assert_eq!(m, parsed);
+    assert_eq!(
+        Some(timestamp),
+        parsed.any_value().unpack::<Timestamp>().unwrap()
+    );
+
+    // Regular message fields are inlined, `@type` is not necessarily first.
+    let mut inner = TestFmtJsonWellKnownTypes::new();
+    inner.mut_string_value().value = "ab".to_owned();
     let mut m = TestFmtJsonWellKnownTypes::new();
-    m.any_value.mut_or_insert_default();
-    // TODO
+    m.set_any_value(Any::pack(&inner).unwrap());
+    let json = "{\"anyValue\": {\"@type\": \"type.googleapis.com/TestFmtJsonWellKnownTypes\", \"stringValue\": \"ab\"}}";
+    assert_eq!(
+        json,
+        print_to_string_with_options(&m, &print_options).unwrap()
+    );
+    let parsed: TestFmtJsonWellKnownTypes = parse_from_str_with_options(
+        "{\"anyValue\": {\"stringValue\": \"ab\", \"@type\": \"type.googleapis.com/TestFmtJsonWellKnownTypes\"}}",
+        &parse_options,
+    )
+    .unwrap();
+    assert_eq!(m, parsed);
+
+    // Types must be registered.
+    assert!(print_to_string(&m).is_err());
+    assert!(parse_from_str::<TestFmtJsonWellKnownTypes>(json).is_err());
 }
 
 #[test]
@@ -160,3 +307,59 @@ fn test_field_mask() {
     });
     test_json_print_parse_message("{\"fieldMask\": \"ab,c.d.e\"}", &m);
 }
+
+#[test]
+fn test_to_json_value() {
+    let mut type_registry = TypeRegistry::new();
+    type_registry.add_message(Duration::descriptor());
+    type_registry.add_message(TestFmtJsonWellKnownTypes::descriptor());
+    let print_options = PrintOptions {
+        type_registry,
+        ..Default::default()
+    };
+
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    m.mut_duration().seconds = -2;
+    m.mut_timestamp().seconds = 1_000_000_000;
+    m.mut_field_mask().paths = vec!["a".to_owned(), "b.c".to_owned()];
+    m.null_values.push(NullValue::NULL_VALUE.into());
+    m.mut_struct_value().fields.insert("ab".to_owned(), {
+        let mut v = Value::new();
+        v.set_list_value(ListValue::new());
+        v
+    });
+    m.mut_uint64_value().value = 13;
+    m.mut_bytes_value().value = b"ab".to_vec();
+    let mut nested = TestFmtJsonWellKnownTypes::new();
+    nested.mut_int32_value().value = 5;
+    m.set_any_value(Any::pack(&nested).unwrap());
+
+    let json = protobuf_json_mapping::to_json_value_with_options(&m, &print_options).unwrap();
+    assert_eq!(Some(&"-2.000000000s".into()), json.pointer("/duration"));
+    assert_eq!(
+        Some(&"2001-09-09T01:46:40.000000000Z".into()),
+        json.pointer("/timestamp")
+    );
+    assert_eq!(Some(&"a,b.c".into()), json.pointer("/fieldMask"));
+    assert_eq!(
+        Some(&serde_json::Value::Null),
+        json.pointer("/nullValues/0")
+    );
+    assert_eq!(
+        Some(&serde_json::json!([])),
+        json.pointer("/structValue/ab")
+    );
+    assert_eq!(Some(&"13".into()), json.pointer("/uint64Value"));
+    assert_eq!(Some(&"YWI=".into()), json.pointer("/bytesValue"));
+    assert_eq!(
+        Some(&"type.googleapis.com/TestFmtJsonWellKnownTypes".into()),
+        json.pointer("/anyValue/@type")
+    );
+    assert_eq!(Some(&5.into()), json.pointer("/anyValue/int32Value"));
+
+    let printed = print_to_string_with_options(&m, &print_options).unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&printed).unwrap(),
+        json
+    );
+}
@@ -1,4 +1,8 @@
+use protobuf::text_format::parse_from_str;
+use protobuf::text_format::parse_from_str_dyn;
 use protobuf::text_format::print_to_string;
+use protobuf::EnumOrUnknown;
+use protobuf::Message;
 use protobuf::MessageField;
 use protobuf::MessageFull;
 use protobuf_test_common::*;
@@ -280,3 +284,109 @@ fn test_string_escaped() {
         &*format!("{}", m)
     );
 }
+
+#[test]
+fn test_unknown_fields() {
+    let bytes = [
+        0x08, 0x01, // const: true
+        0x28, 0x7b, // 5: varint
+        0x35, 0x0a, 0x00, 0x00, 0x00, // 6: fixed32
+        0x39, 0x0b, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // 7: fixed64
+        0x42, 0x02, b'a', b'b', // 8: length-delimited
+    ];
+    let m = TestTextFormatRustIdentifier::parse_from_bytes(&bytes).unwrap();
+
+    let text = print_to_string(&m);
+    assert_eq!(
+        "const: true 5: 123 6: 0x0000000a 7: 0x000000000000000b 8: \"ab\"",
+        text
+    );
+
+    let parsed: TestTextFormatRustIdentifier = parse_from_str(&text).unwrap();
+    assert_eq!(m, parsed);
+    assert_eq!(text, print_to_string(&parsed));
+}
+
+#[test]
+fn test_unknown_field_number_incorrect() {
+    assert!(parse_from_str::<TestTextFormatRustIdentifier>("0: 1").is_err());
+    assert!(parse_from_str::<TestTextFormatRustIdentifier>("536870912: 1").is_err());
+}
+
+#[test]
+fn test_repeated_list() {
+    test_text_format_str_descriptor("int32_repeated: []", &TestTypes::descriptor());
+    test_text_format_str_descriptor("int32_repeated: [1, -2, 3]", &TestTypes::descriptor());
+    test_text_format_str_descriptor(
+        "int32_repeated: [1] int32_repeated: 2",
+        &TestTypes::descriptor(),
+    );
+    test_text_format_str_descriptor(
+        "test_enum_repeated: [DARK, LIGHT]",
+        &TestTypes::descriptor(),
+    );
+    test_text_format_str_descriptor(
+        "test_message_repeated: [{ value: 1 }, < value: 2 >]",
+        &TestTypes::descriptor(),
+    );
+}
+
+#[test]
+fn test_enum_by_number() {
+    test_text_format_str_descriptor("test_enum_singular: 2", &TestTypes::descriptor());
+    test_text_format_str_descriptor("test_enum_repeated: [1, LIGHT]", &TestTypes::descriptor());
+}
+
+#[test]
+fn test_string_concatenation() {
+    test_text_format_str_descriptor("string_singular: \"ab\" 'cd'", &TestTypes::descriptor());
+    test_text_format_str_descriptor("bytes_singular: \"a\\xfe\" \"b\"", &TestTypes::descriptor());
+}
+
+#[test]
+fn test_colon_before_message() {
+    test_text_format_str_descriptor(
+        "test_message_singular: { value: 10 }",
+        &TestTypes::descriptor(),
+    );
+    test_text_format_str_descriptor(
+        "message_map_field: { key: \"a\" value: { value: 1 } }",
+        &TestTypes::descriptor(),
+    );
+}
+
+#[test]
+fn test_parse_dyn_nested() {
+    let fixture = "
+        # Fixture comment
+        string_singular: \"abc\"  # trailing comment
+        test_message_singular <
+            value: 10
+        >
+        test_message_repeated: [{ value: 20 }, { value: 30 }]
+        test_enum_repeated: [DARK, 2]
+        message_map_field { key: \"k\" value { value: 40 } }
+    ";
+
+    let message = parse_from_str_dyn(&TestTypes::descriptor(), fixture).unwrap();
+    let message: &TestTypes = message.downcast_ref().unwrap();
+
+    assert_eq!("abc", message.string_singular());
+    assert_eq!(10, message.test_message_singular.value());
+    assert_eq!(
+        vec![20, 30],
+        message
+            .test_message_repeated
+            .iter()
+            .map(|m| m.value())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            EnumOrUnknown::new(TestEnum::DARK),
+            EnumOrUnknown::new(TestEnum::LIGHT)
+        ],
+        message.test_enum_repeated
+    );
+    assert_eq!(40, message.message_map_field["k"].value());
+}
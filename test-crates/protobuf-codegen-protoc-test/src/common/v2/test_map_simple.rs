@@ -1,4 +1,8 @@
 use protobuf::text_format::print_to_string;
+use protobuf::CodedOutputStream;
+use protobuf::Message;
+use protobuf::MessageDyn;
+use protobuf_test_common::hex::decode_hex;
 use protobuf_test_common::*;
 
 use super::test_map_simple_pb::*;
@@ -63,6 +67,49 @@ fn test_map_unset_default_fields() {
     test_deserialize("0a 02 10 11", &m);
 }
 
+fn write_deterministic(m: &dyn MessageDyn) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut os = CodedOutputStream::vec(&mut bytes);
+    os.set_deterministic(true);
+    m.write_to_dyn(&mut os).unwrap();
+    os.flush().unwrap();
+    drop(os);
+    bytes
+}
+
+#[test]
+fn test_map_deterministic() {
+    let mut a = TestMap::new();
+    let mut b = TestMap::new();
+    for i in 0..100 {
+        a.mut_m().insert(format!("k{}", i), i);
+        b.mut_m().insert(format!("k{}", 99 - i), 99 - i);
+        a.mut_mi().insert(i, i);
+        b.mut_mi().insert(99 - i, 99 - i);
+    }
+    let bytes = write_deterministic(&a);
+    assert_eq!(bytes, write_deterministic(&b));
+    assert_eq!(bytes, write_deterministic(&a));
+    assert_eq!(bytes, write_deterministic(&*recreate_as_dynamic(&b)));
+    assert_eq!(a, TestMap::parse_from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn test_map_deterministic_int_keys() {
+    let mut map = TestMap::new();
+    map.mut_mi().insert(10, 0);
+    map.mut_mi().insert(-1, 0);
+    map.mut_mi().insert(2, 0);
+    let expected = "22 0d 08 ff ff ff ff ff ff ff ff ff 01 10 00 \
+                    22 04 08 02 10 00 \
+                    22 04 08 0a 10 00";
+    assert_eq!(decode_hex(expected), write_deterministic(&map));
+    assert_eq!(
+        write_deterministic(&map),
+        write_deterministic(&*recreate_as_dynamic(&map))
+    );
+}
+
 #[test]
 fn text_format() {
     let mut map = TestMap::new();
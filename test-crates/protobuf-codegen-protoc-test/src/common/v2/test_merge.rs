@@ -0,0 +1,125 @@
+use protobuf::Message;
+use protobuf_test_common::*;
+
+use super::test_merge_pb::*;
+
+fn inner(a: i32) -> MergeInner {
+    let mut inner = MergeInner::new();
+    inner.set_a(a);
+    inner
+}
+
+fn concat(messages: &[&MergeOuter]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for m in messages {
+        bytes.extend(m.write_to_bytes().unwrap());
+    }
+    bytes
+}
+
+/// Parse with dynamic message and convert back to generated message.
+fn parse_dynamic(bytes: &[u8]) -> MergeOuter {
+    let descriptor = dynamic_descriptor_for_descriptor::<MergeOuter>();
+    let dynamic = descriptor.parse_from_bytes(bytes).unwrap();
+    MergeOuter::parse_from_bytes(&dynamic.write_to_bytes_dyn().unwrap()).unwrap()
+}
+
+#[test]
+fn test_merge_concatenated() {
+    let mut first = MergeOuter::new();
+    first.set_scalar(1);
+    first.set_text("first".to_owned());
+    first.numbers = vec![1, 2];
+    first.messages.push(inner(1));
+    first.mut_inner().set_a(1);
+    first.mut_inner().b.push(1);
+    first.mut_inner().mut_inner().set_a(10);
+    first.map.insert("a".to_owned(), 1);
+    first.map.insert("b".to_owned(), 2);
+    first.mut_one_inner().set_a(1);
+
+    let mut second = MergeOuter::new();
+    second.set_scalar(2);
+    second.numbers = vec![3];
+    second.messages.push(inner(2));
+    second.mut_inner().b.push(2);
+    second.mut_inner().mut_inner().b.push(20);
+    second.map.insert("b".to_owned(), 3);
+    second.mut_one_inner().b.push(2);
+
+    let mut expected = MergeOuter::new();
+    // Last value wins for scalars.
+    expected.set_scalar(2);
+    expected.set_text("first".to_owned());
+    // Repeated fields are concatenated.
+    expected.numbers = vec![1, 2, 3];
+    expected.messages = vec![inner(1), inner(2)];
+    // Messages are merged recursively.
+    expected.mut_inner().set_a(1);
+    expected.mut_inner().b = vec![1, 2];
+    expected.mut_inner().mut_inner().set_a(10);
+    expected.mut_inner().mut_inner().b.push(20);
+    // Last value wins for map keys.
+    expected.map.insert("a".to_owned(), 1);
+    expected.map.insert("b".to_owned(), 3);
+    // Oneof message field is merged if the same field is set.
+    expected.mut_one_inner().set_a(1);
+    expected.mut_one_inner().b.push(2);
+
+    let bytes = concat(&[&first, &second]);
+    assert_eq!(expected, MergeOuter::parse_from_bytes(&bytes).unwrap());
+    assert_eq!(expected, parse_dynamic(&bytes));
+
+    let mut merged = MergeOuter::parse_from_bytes(&first.write_to_bytes().unwrap()).unwrap();
+    merged
+        .merge_from_bytes(&second.write_to_bytes().unwrap())
+        .unwrap();
+    assert_eq!(expected, merged);
+}
+
+#[test]
+fn test_merge_oneof_different_fields() {
+    let mut first = MergeOuter::new();
+    first.mut_one_inner().set_a(1);
+    let mut second = MergeOuter::new();
+    second.set_one_int(2);
+    let mut third = MergeOuter::new();
+    third.mut_one_inner().b.push(3);
+
+    // Other oneof field replaces previously set one.
+    let mut expected = MergeOuter::new();
+    expected.mut_one_inner().b.push(3);
+
+    let bytes = concat(&[&first, &second, &third]);
+    assert_eq!(expected, MergeOuter::parse_from_bytes(&bytes).unwrap());
+    assert_eq!(expected, parse_dynamic(&bytes));
+}
+
+#[test]
+fn test_clear_retains_capacity() {
+    let mut m = MergeOuter::new();
+    m.set_scalar(1);
+    m.set_text("text".to_owned());
+    m.numbers = (0..100).collect();
+    m.messages.push(inner(1));
+    m.mut_inner().set_a(1);
+    m.map.insert("a".to_owned(), 1);
+    m.set_one_int(2);
+    let bytes = m.write_to_bytes().unwrap();
+
+    let numbers_capacity = m.numbers.capacity();
+    let map_capacity = m.map.capacity();
+    m.clear();
+    assert_eq!(MergeOuter::new(), m);
+    assert_eq!(numbers_capacity, m.numbers.capacity());
+    assert_eq!(map_capacity, m.map.capacity());
+
+    // Cleared message can be reused.
+    m.merge_from_bytes(&bytes).unwrap();
+    assert_eq!(bytes, m.write_to_bytes().unwrap());
+
+    let descriptor = dynamic_descriptor_for_descriptor::<MergeOuter>();
+    let mut dynamic = descriptor.parse_from_bytes(&bytes).unwrap();
+    dynamic.clear_dyn();
+    assert!(dynamic == descriptor.new_instance());
+}
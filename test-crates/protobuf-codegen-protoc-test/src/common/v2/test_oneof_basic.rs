@@ -1,3 +1,4 @@
+use protobuf::well_known_types::duration::Duration;
 use protobuf::OneofFull;
 use protobuf_test_common::*;
 
@@ -61,6 +62,27 @@ fn test_types() {
     t(|o| o.set_bytes_field(vec![99, 100]));
     t(|o| o.set_enum_field(EnumForOneof::A));
     t(|o| o.mut_message_field().set_f(22));
+    t(|o| o.mut_duration_field().seconds = 23);
+}
+
+#[test]
+fn from_oneof() {
+    let m = TestOneof::from(test_oneof::One::Int32Field(10));
+    assert_eq!(10, m.int32_field());
+    assert_eq!(Some(&test_oneof::One::Int32Field(10)), m.one());
+
+    let mut message = MessageForOneof::new();
+    message.set_f(22);
+    let m: TestOneof = test_oneof::One::MessageField(message.clone()).into();
+    assert_eq!(&message, m.message_field());
+
+    let mut duration = Duration::new();
+    duration.seconds = 23;
+    let m = TestOneof::from(test_oneof::One::DurationField(duration.clone()));
+    assert_eq!(&duration, m.duration_field());
+    test_serialize_deserialize_no_hex(&m);
+
+    assert_eq!(None, TestOneof::new().one());
 }
 
 #[test]
@@ -69,3 +69,37 @@ fn test_dynamic() {
     let msg_empty = msg_desc.new_instance();
     assert_eq!(msg.to_string(), msg_empty.to_string());
 }
+
+#[test]
+fn test_has_field_after_set_and_clear() {
+    let msg_desc = TestMessage::descriptor();
+
+    let mut msg = msg_desc.new_instance();
+
+    for field in msg_desc.fields() {
+        assert!(!field.has_field(msg.as_ref()), "{}", field);
+    }
+
+    let a_field = msg_desc.field_by_name("a").unwrap();
+    let b_field = msg_desc.field_by_name("b").unwrap();
+    let c_field = msg_desc.field_by_name("c").unwrap();
+    let d_field = msg_desc.field_by_name("d").unwrap();
+    let e_field = msg_desc.field_by_name("e").unwrap();
+
+    a_field.set_singular_field(msg.as_mut(), 1.into());
+    b_field.set_singular_field(msg.as_mut(), "b".to_string().into());
+    c_field.set_singular_field(
+        msg.as_mut(),
+        ReflectValueBox::Message(Box::new(test_message::Nested::default())),
+    );
+    d_field.mut_repeated(msg.as_mut()).push(1.into());
+    e_field
+        .mut_map(msg.as_mut())
+        .insert("key".to_string().into(), "value".to_string().into());
+
+    for field in msg_desc.fields() {
+        assert!(field.has_field(msg.as_ref()), "{}", field);
+        field.clear_field(msg.as_mut());
+        assert!(!field.has_field(msg.as_ref()), "{}", field);
+    }
+}
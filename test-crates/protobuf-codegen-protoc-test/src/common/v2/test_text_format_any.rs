@@ -0,0 +1,91 @@
+use protobuf::reflect::DescriptorRegistry;
+use protobuf::text_format::merge_from_str;
+use protobuf::text_format::parse_from_str;
+use protobuf::text_format::parse_from_str_with_registry;
+use protobuf::text_format::print_to_string;
+use protobuf::text_format::print_to_string_pretty_with_registry;
+use protobuf::text_format::print_to_string_with_registry;
+use protobuf::well_known_types::any::Any;
+use protobuf::MessageFull;
+
+use super::test_text_format_any_pb::*;
+
+fn registry() -> DescriptorRegistry {
+    DescriptorRegistry::new(
+        Vec::new(),
+        &[Payload::descriptor().file_descriptor().clone()],
+    )
+    .unwrap()
+}
+
+fn with_any() -> WithAny {
+    let mut payload = Payload::new();
+    payload.set_value(10);
+    payload.set_name("ten".to_owned());
+
+    let mut message = WithAny::new();
+    message.any = Some(Any::pack(&payload).unwrap()).into();
+    message.anys.push(Any::pack(&Payload::new()).unwrap());
+    message
+}
+
+#[test]
+fn test_print_expanded() {
+    let message = with_any();
+    let text = print_to_string_with_registry(&message, &registry());
+    assert_eq!(
+        "any {[type.googleapis.com/test_text_format_any.Payload] {value: 10 name: \"ten\"}} \
+         anys {[type.googleapis.com/test_text_format_any.Payload] {}}",
+        text
+    );
+    assert_eq!(
+        message,
+        parse_from_str_with_registry(&text, &registry()).unwrap()
+    );
+
+    let pretty = print_to_string_pretty_with_registry(&message, &registry());
+    assert_eq!(
+        message,
+        parse_from_str_with_registry(&pretty, &registry()).unwrap()
+    );
+}
+
+#[test]
+fn test_unknown_type_is_not_expanded() {
+    let mut message = WithAny::new();
+    let any = message.any.mut_or_insert_default();
+    any.type_url = "type.googleapis.com/nope.Nope".to_owned();
+    any.value = vec![0x08, 0x01];
+    let text = print_to_string_with_registry(&message, &registry());
+    assert_eq!(print_to_string(&message), text);
+    assert!(text.contains("type_url: "), "{}", text);
+    assert_eq!(message, parse_from_str(&text).unwrap());
+}
+
+#[test]
+fn test_parse_both_forms() {
+    let message = with_any();
+    let plain = print_to_string(&message);
+    assert_eq!(
+        message,
+        parse_from_str_with_registry(&plain, &registry()).unwrap()
+    );
+
+    let expanded =
+        "any { [type.googleapis.com/test_text_format_any.Payload] { value: 10 name: \"ten\" } } \
+                    anys { [type.googleapis.com/test_text_format_any.Payload] {} }";
+    assert_eq!(
+        message,
+        parse_from_str_with_registry(expanded, &registry()).unwrap()
+    );
+}
+
+#[test]
+fn test_parse_expanded_unknown_type() {
+    let text = "any { [type.googleapis.com/nope.Nope] { value: 10 } }";
+    assert!(parse_from_str_with_registry::<WithAny>(text, &registry()).is_err());
+    // Expanded form requires a registry.
+    let mut message = WithAny::new();
+    let text = "any { [type.googleapis.com/test_text_format_any.Payload] { value: 10 } }";
+    assert!(merge_from_str(&mut message, text).is_err());
+}
@@ -1,5 +1,6 @@
 use bytes::Bytes;
 use protobuf::Chars;
+use protobuf::Message;
 use protobuf_test_common::*;
 
 use super::test_tokio_bytes_pb::*;
@@ -22,3 +23,19 @@ fn test() {
 
     test_serialize_deserialize_no_hex_with_dynamic(&m);
 }
+
+#[test]
+#[cfg_attr(miri, ignore)] // bytes violates SB, see https://github.com/tokio-rs/bytes/issues/522
+fn test_parse_shares_allocation() {
+    let mut m = TestTokioBytes::new();
+    m.set_b1(Bytes::from("aabb"));
+    m.set_br(vec![Bytes::from("bb1")]);
+
+    let bytes = Bytes::from(m.write_to_bytes().unwrap());
+    let parsed = TestTokioBytes::parse_from_tokio_bytes(&bytes).unwrap();
+    assert_eq!(m, parsed);
+
+    let range = bytes.as_ptr_range();
+    assert!(range.contains(&parsed.b1().as_ptr()));
+    assert!(range.contains(&parsed.br()[0].as_ptr()));
+}
@@ -17,3 +17,5 @@ mod google;
 mod interop;
 
 mod include_generated;
+
+mod single_file;
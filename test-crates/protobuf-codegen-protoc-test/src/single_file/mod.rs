@@ -0,0 +1,29 @@
+// Single mod.rs contains both modules inline
+include!(concat!(env!("OUT_DIR"), "/single_file/mod.rs"));
+
+use protobuf::Message;
+use single_file_a::point::Kind;
+use single_file_a::Point;
+use single_file_b::Line;
+
+#[test]
+fn test() {
+    let mut line = Line::new();
+    line.from.mut_or_insert_default().x = 1;
+    line.to = Some(Point::new()).into();
+    line.from_kind = Kind::KIND_ORIGIN.into();
+    line.labels.insert("a".to_owned(), Point::new());
+
+    let bytes = line.write_to_bytes().unwrap();
+    assert_eq!(line, Line::parse_from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn test_no_other_files() {
+    let dir = concat!(env!("OUT_DIR"), "/single_file");
+    let names: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert_eq!(vec!["mod.rs"], names);
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use super::test_builder_pb::*;
+
+#[test]
+fn test_build() {
+    let order = OrderBuilder::new()
+        .id(10)
+        .comment("fast".to_owned())
+        .color(Color::GREEN)
+        .main_item(ItemBuilder::new().name("a".to_owned()).build().unwrap())
+        .items(vec![ItemBuilder::new()
+            .name("b".to_owned())
+            .build()
+            .unwrap()])
+        .quantities(vec![1, 2])
+        .tags(HashMap::from([("k".to_owned(), 3)]))
+        .cash(17)
+        .note(
+            order::NoteBuilder::new()
+                .text("n".to_owned())
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let mut expected = Order::new();
+    expected.set_id(10);
+    expected.set_comment("fast".to_owned());
+    expected.set_color(Color::GREEN);
+    expected
+        .main_item
+        .mut_or_insert_default()
+        .set_name("a".to_owned());
+    expected.items.push(Item::new());
+    expected.items[0].set_name("b".to_owned());
+    expected.quantities = vec![1, 2];
+    expected.tags.insert("k".to_owned(), 3);
+    expected.set_cash(17);
+    expected
+        .note
+        .mut_or_insert_default()
+        .set_text("n".to_owned());
+
+    assert_eq!(expected, order);
+}
+
+#[test]
+fn test_build_missing_required() {
+    assert!(OrderBuilder::new().comment("c".to_owned()).build().is_err());
+    assert!(OrderBuilder::new().id(1).build().is_ok());
+
+    // Nested messages must be initialized too
+    let mut item = Item::new();
+    assert!(!item.has_name());
+    assert!(OrderBuilder::new()
+        .id(1)
+        .items(vec![item.clone()])
+        .build()
+        .is_err());
+    item.set_name("x".to_owned());
+    assert!(OrderBuilder::new().id(1).items(vec![item]).build().is_ok());
+}
@@ -1,3 +1,6 @@
+use protobuf::reflect::ReflectValueBox;
+use protobuf::MessageFull;
+
 use super::test_default_values_pb::*;
 
 #[test]
@@ -38,3 +41,55 @@ fn test_default_value_extreme() {
     assert_eq!(b"'", d.bquote1());
     assert_eq!(b"\"", d.bquote2());
 }
+
+#[test]
+fn test_default_value_negative() {
+    let d = TestNegativeDefaultValues::default();
+    assert_eq!(-42, d.int32_field());
+    assert_eq!(i64::MIN, d.int64_field());
+    assert_eq!(-3, d.sint32_field());
+    assert_eq!(-4, d.sfixed64_field());
+    assert_eq!(-1.5, d.double_field());
+    assert_eq!(-2e10, d.float_field());
+    assert_eq!("\u{e9}A", d.utf8_string());
+}
+
+#[test]
+fn test_default_value_is_not_serialized() {
+    let d = TestDefaultValues::default();
+    assert_eq!(3, d.int32_field());
+    assert_eq!(None, d.int32_field);
+    assert_eq!(0, protobuf::Message::compute_size(&d));
+}
+
+#[test]
+fn test_reflect_default_value() {
+    let descriptor = TestExtremeDefaultValues::descriptor();
+    let default_value = |name: &str| {
+        descriptor
+            .field_by_name(name)
+            .unwrap()
+            .default_value()
+            .unwrap()
+            .to_box()
+    };
+    assert_eq!(
+        ReflectValueBox::Bytes(b"\0\x01\x07\x08\x0c\n\r\t\x0b\\\'\"\xfe".to_vec()),
+        default_value("escaped_bytes")
+    );
+    assert_eq!(
+        ReflectValueBox::String("\"".to_owned()),
+        default_value("quote2")
+    );
+    assert_eq!(
+        ReflectValueBox::F64(f64::NEG_INFINITY),
+        default_value("neg_inf_double")
+    );
+
+    let descriptor = TestNegativeDefaultValues::descriptor();
+    let utf8_string = descriptor.field_by_name("utf8_string").unwrap();
+    assert_eq!(
+        Some("\u{e9}A"),
+        utf8_string.default_value().unwrap().to_str()
+    );
+}
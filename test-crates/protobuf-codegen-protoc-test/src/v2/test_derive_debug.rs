@@ -0,0 +1,23 @@
+use super::test_derive_debug_pb::*;
+
+#[test]
+fn test_derive_attr() {
+    let generated = include_str!("test_derive_debug_pb.rs");
+    assert!(generated.contains(
+        "// @@protoc_insertion_point(message:test_derive_debug.NoDebug)\n#[derive(PartialEq,Clone,Default)]\n"
+    ));
+    assert!(generated.contains(
+        "// @@protoc_insertion_point(message:test_derive_debug.WithDebug)\n#[derive(PartialEq,Clone,Default,Debug)]\n"
+    ));
+}
+
+#[test]
+fn test_manual_debug() {
+    let mut no_debug = NoDebug::new();
+    no_debug.set_a(10);
+    assert_eq!("NoDebug { .. }", format!("{:?}", no_debug));
+
+    let mut with_debug = WithDebug::new();
+    with_debug.no_debug = Some(no_debug).into();
+    assert!(format!("{:?}", with_debug).contains("NoDebug { .. }"));
+}
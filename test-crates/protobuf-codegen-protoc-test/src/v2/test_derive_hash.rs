@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use super::test_derive_hash_pb::*;
+
+fn key(id: i32, name: &str) -> Key {
+    let mut key = Key::new();
+    key.set_id(id);
+    key.set_name(name.to_owned());
+    key.tags.push(b"tag".to_vec());
+    key.set_color(Color::GREEN);
+    key.nested.mut_or_insert_default().set_flag(true);
+    key.set_text(name.to_owned());
+    key
+}
+
+#[test]
+fn test_hash_map_key() {
+    let mut map = HashMap::new();
+    map.insert(key(1, "one"), 1);
+    map.insert(key(2, "two"), 2);
+    map.insert(key(1, "one"), 3);
+    assert_eq!(2, map.len());
+    assert_eq!(Some(&3), map.get(&key(1, "one")));
+    assert_eq!(None, map.get(&key(1, "two")));
+}
+
+#[test]
+fn test_derive_attr() {
+    let generated = include_str!("test_derive_hash_pb.rs");
+    assert!(generated.contains(
+        "// @@protoc_insertion_point(message:test_derive_hash.Key)\n#[derive(PartialEq,Clone,Default,Debug,Eq,Hash)]\n"
+    ));
+    assert!(generated.contains(
+        "// @@protoc_insertion_point(message:test_derive_hash.NotHashable)\n#[derive(PartialEq,Clone,Default,Debug)]\n"
+    ));
+}
@@ -0,0 +1,12 @@
+use super::test_enum_non_exhaustive_pb::*;
+
+#[test]
+fn test_proto2_enum_is_exhaustive() {
+    let generated = include_str!("test_enum_non_exhaustive_pb.rs");
+    assert!(!generated.contains("#[non_exhaustive]"));
+    // Exhaustive match without a wildcard arm compiles.
+    match Closed::CLOSED_ONE {
+        Closed::CLOSED_ZERO => unreachable!(),
+        Closed::CLOSED_ONE => {}
+    }
+}
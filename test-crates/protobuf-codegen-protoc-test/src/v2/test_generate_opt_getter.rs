@@ -0,0 +1,11 @@
+use super::test_generate_opt_getter_pb::*;
+
+#[test]
+fn default_value() {
+    let mut m = TestOptGetterDefault::new();
+    assert_eq!(5, m.a());
+    assert_eq!(None, m.a_opt());
+
+    m.set_a(5);
+    assert_eq!(Some(5), m.a_opt());
+}
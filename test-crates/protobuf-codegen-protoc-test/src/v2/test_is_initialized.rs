@@ -1,9 +1,11 @@
 use protobuf::reflect::FileDescriptor;
 use protobuf::reflect::ReflectValueBox;
+use protobuf::Message;
 use protobuf::MessageDyn;
 
 use crate::v2::test_is_initialized_pb;
 use crate::v2::test_is_initialized_pb::TestIsInitialized;
+use crate::v2::test_is_initialized_pb::TestUninitializedPath;
 
 fn file_descriptor_dynamic() -> FileDescriptor {
     FileDescriptor::new_dynamic(
@@ -35,3 +37,61 @@ fn is_initialized_dynamic() {
     let mut message = message_descriptor.new_instance();
     test_is_initialized(&mut *message);
 }
+
+fn initialized() -> TestIsInitialized {
+    let mut message = TestIsInitialized::new();
+    message.set_a(1);
+    message
+}
+
+#[test]
+fn uninitialized_field_path() {
+    let mut message = TestUninitializedPath::new();
+    assert_eq!(None, message.uninitialized_field_path());
+    assert!(message.check_initialized().is_ok());
+
+    message.child = Some(initialized()).into();
+    message.children.push(TestUninitializedPath::new());
+    let mut nested = TestUninitializedPath::new();
+    nested.set_variant_child(TestIsInitialized::new());
+    message.children.push(nested);
+
+    let err = message.check_initialized().unwrap_err();
+    assert_eq!("children[1].variant_child.a", err.path());
+    assert_eq!("TestUninitializedPath", err.message_name());
+    assert_eq!(
+        "Message `TestUninitializedPath` is missing required field `children[1].variant_child.a`",
+        err.to_string()
+    );
+}
+
+#[test]
+fn uninitialized_field_path_dynamic() {
+    let file_descriptor = file_descriptor_dynamic();
+    let path_descriptor = file_descriptor
+        .message_by_package_relative_name("TestUninitializedPath")
+        .unwrap();
+    let required_descriptor = file_descriptor
+        .message_by_package_relative_name("TestIsInitialized")
+        .unwrap();
+
+    let mut message = path_descriptor.new_instance();
+    assert!(message.check_initialized_dyn().is_ok());
+
+    let mut nested = path_descriptor.new_instance();
+    path_descriptor
+        .field_by_name("variant_child")
+        .unwrap()
+        .set_singular_field(
+            &mut *nested,
+            ReflectValueBox::Message(required_descriptor.new_instance()),
+        );
+    let children = path_descriptor.field_by_name("children").unwrap();
+    let mut repeated = children.mut_repeated(&mut *message);
+    repeated.push(ReflectValueBox::Message(path_descriptor.new_instance()));
+    repeated.push(ReflectValueBox::Message(nested));
+
+    let err = message.check_initialized_dyn().unwrap_err();
+    assert_eq!("children[1].variant_child.a", err.path());
+    assert_eq!("TestUninitializedPath", err.message_name());
+}
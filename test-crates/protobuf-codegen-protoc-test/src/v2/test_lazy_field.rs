@@ -0,0 +1,142 @@
+use protobuf::Message;
+use protobuf::MessageFull;
+
+use super::test_lazy_field_pb::*;
+
+fn inner(a: i32) -> Inner {
+    let mut inner = Inner::new();
+    inner.set_a(a);
+    inner.set_b("bb".to_owned());
+    inner
+}
+
+#[test]
+fn test_not_parsed_until_accessed() {
+    let mut outer = Outer::new();
+    outer.lazy_inner.set(inner(10));
+    outer.set_c(3);
+    let bytes = outer.write_to_bytes().unwrap();
+
+    let parsed = Outer::parse_from_bytes(&bytes).unwrap();
+    assert!(parsed.lazy_inner.is_some());
+    assert!(!parsed.lazy_inner.is_parsed());
+    assert_eq!(3, parsed.c());
+
+    assert_eq!(10, parsed.lazy_inner.get().unwrap().unwrap().a());
+    assert!(parsed.lazy_inner.is_parsed());
+    assert_eq!(outer, parsed);
+}
+
+#[test]
+fn test_round_trip_unchanged() {
+    // Field `a` is specified twice, so decoding and encoding
+    // the inner message would produce different bytes.
+    let inner_bytes = [0x08, 0x01, 0x08, 0x02, 0x12, 0x00];
+    let mut bytes = vec![0x0a, inner_bytes.len() as u8];
+    bytes.extend_from_slice(&inner_bytes);
+    bytes.extend_from_slice(&[0x18, 0x05]);
+
+    let outer = Outer::parse_from_bytes(&bytes).unwrap();
+    assert_eq!(bytes, outer.write_to_bytes().unwrap());
+
+    assert_eq!(2, outer.lazy_inner.get().unwrap().unwrap().a());
+    assert_eq!(bytes, outer.write_to_bytes().unwrap());
+}
+
+#[test]
+fn test_modified_is_encoded() {
+    let mut outer = Outer::parse_from_bytes(&[0x0a, 0x04, 0x08, 0x01, 0x12, 0x00]).unwrap();
+    outer.lazy_inner.mut_or_insert_default().unwrap().set_a(7);
+    assert!(outer.lazy_inner.encoded_bytes().is_none());
+
+    let parsed = Outer::parse_from_bytes(&outer.write_to_bytes().unwrap()).unwrap();
+    assert_eq!(7, parsed.lazy_inner.get().unwrap().unwrap().a());
+}
+
+#[test]
+fn test_malformed_reported_on_access() {
+    let outer = Outer::parse_from_bytes(&[0x0a, 0x01, 0x08]).unwrap();
+    assert!(outer.lazy_inner.get().is_err());
+}
+
+#[test]
+fn test_is_initialized() {
+    let mut outer = Outer::new();
+    assert!(outer.is_initialized());
+    outer.lazy_inner.set(Inner::new());
+    assert!(!outer.is_initialized());
+    outer.lazy_inner.set(inner(1));
+    assert!(outer.is_initialized());
+}
+
+#[test]
+fn test_builder() {
+    let outer = OuterBuilder::new().lazy_inner(inner(4)).build().unwrap();
+    assert_eq!(4, outer.lazy_inner.get().unwrap().unwrap().a());
+}
+
+#[test]
+fn test_reflect() {
+    let field = Outer::descriptor().field_by_name("lazy_inner").unwrap();
+
+    let outer = Outer::parse_from_bytes(&[0x0a, 0x04, 0x08, 0x09, 0x12, 0x00]).unwrap();
+    assert!(field.has_field(&outer));
+    let inner = field.get_message(&outer);
+    assert_eq!(
+        9,
+        inner
+            .descriptor_dyn()
+            .field_by_name("a")
+            .unwrap()
+            .get_singular_field_or_default(&*inner)
+            .to_i32()
+            .unwrap()
+    );
+
+    let mut outer = outer;
+    field.clear_field(&mut outer);
+    assert!(outer.lazy_inner.is_none());
+}
+
+#[test]
+fn test_lazy_message_fields_disabled() {
+    let mut outer = OuterNotLazy::new();
+    outer.set_lazy_inner(inner(6));
+    let _: &protobuf::MessageField<Inner> = &outer.lazy_inner;
+
+    let parsed = OuterNotLazy::parse_from_bytes(&outer.write_to_bytes().unwrap()).unwrap();
+    assert_eq!(6, parsed.lazy_inner().a());
+}
+
+#[test]
+fn test_reflect_mut_malformed_keeps_bytes() {
+    let field = Outer::descriptor().field_by_name("lazy_inner").unwrap();
+
+    let mut outer = Outer::parse_from_bytes(&[0x0a, 0x01, 0x08]).unwrap();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        field.mut_message(&mut outer);
+    }));
+    assert!(result.is_err());
+    assert_eq!(Some(&[0x08][..]), outer.lazy_inner.encoded_bytes());
+}
+
+#[test]
+fn test_reflect_merge_into_malformed() {
+    let field = Outer::descriptor().field_by_name("lazy_inner").unwrap();
+    let malformed = [0x0a, 0x01, 0x08];
+
+    let mut outer = Outer::parse_from_bytes(&malformed).unwrap();
+    assert!(field.try_mut_message(&mut outer).is_err());
+
+    let mut src = Outer::new();
+    src.lazy_inner.set(inner(1));
+    assert!(Outer::descriptor().merge_from(&mut outer, &src).is_err());
+
+    let mut mask = protobuf::well_known_types::field_mask::FieldMask::new();
+    mask.paths.push("lazy_inner.a".to_owned());
+    assert!(mask.apply(&mut outer, &src).is_err());
+
+    assert!(protobuf::reflect::set_field_from_str(&mut outer, "lazy_inner.a", "2").is_err());
+
+    assert_eq!(Some(&[0x08][..]), outer.lazy_inner.encoded_bytes());
+}
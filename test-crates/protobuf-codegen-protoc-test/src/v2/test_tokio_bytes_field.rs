@@ -0,0 +1,40 @@
+use bytes::Bytes;
+use protobuf::Chars;
+use protobuf::Message;
+use protobuf_test_common::*;
+
+use super::test_tokio_bytes_field_pb::*;
+
+#[test]
+fn test_field_types() {
+    let mut m = TestTokioBytesField::new();
+    m.set_large(Bytes::from("large"));
+    m.set_small(b"small".to_vec());
+    m.large_repeated.push(Bytes::from("large repeated"));
+    m.set_large_string(Chars::from("large string"));
+    m.set_small_string("small string".to_owned());
+
+    let _: &Bytes = m.large.as_ref().unwrap();
+    let _: &Vec<u8> = m.small.as_ref().unwrap();
+    let _: &Vec<Bytes> = &m.large_repeated;
+    let _: &Chars = m.large_string.as_ref().unwrap();
+    let _: &String = m.small_string.as_ref().unwrap();
+
+    test_serialize_deserialize_no_hex_with_dynamic(&m);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // bytes violates SB, see https://github.com/tokio-rs/bytes/issues/522
+fn test_parse_shares_allocation() {
+    let mut m = TestTokioBytesField::new();
+    m.set_large(Bytes::from("large"));
+    m.set_small(b"small".to_vec());
+
+    let bytes = Bytes::from(m.write_to_bytes().unwrap());
+    let parsed = TestTokioBytesField::parse_from_tokio_bytes(&bytes).unwrap();
+    assert_eq!(m, parsed);
+
+    let range = bytes.as_ptr_range();
+    assert!(range.contains(&parsed.large().as_ptr()));
+    assert!(!range.contains(&parsed.small().as_ptr()));
+}
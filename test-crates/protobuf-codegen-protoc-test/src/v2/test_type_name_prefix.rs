@@ -0,0 +1,46 @@
+use protobuf::Message;
+use protobuf::MessageFull;
+
+use super::test_type_name_prefix_1_pb::shared::FirstNested;
+use super::test_type_name_prefix_1_pb::*;
+use super::test_type_name_prefix_2_pb::*;
+
+#[test]
+fn test_names() {
+    // Both files are glob imported, names do not collide.
+    let _: FirstShared = FirstShared::new();
+    // Nested modules are named after protobuf names.
+    let _: FirstNested = FirstNested::new();
+    let _: FirstKind = FirstKind::KIND_A;
+    let _: SecondSharedPb = SecondSharedPb::new();
+    let _: SecondKindPb = SecondKindPb::KIND_B;
+
+    // Protobuf names are not affected.
+    assert_eq!(
+        "type_name_prefix_1.Shared",
+        FirstShared::descriptor().full_name()
+    );
+    assert_eq!(
+        "type_name_prefix_2.Shared",
+        SecondSharedPb::descriptor().full_name()
+    );
+}
+
+#[test]
+fn test_cross_file_references() {
+    let mut first = FirstShared::new();
+    first.set_kind(FirstKind::KIND_A);
+    first.nested.mut_or_insert_default().set_n(10);
+
+    let mut second = SecondSharedPb::new();
+    second.set_kind(SecondKindPb::KIND_B);
+    second.first = Some(first.clone()).into();
+    second.set_first_kind(FirstKind::KIND_A);
+    second
+        .first_nested
+        .push(first.nested.get_or_default().clone());
+    second.first_map.insert("a".to_owned(), first);
+
+    let bytes = second.write_to_bytes().unwrap();
+    assert_eq!(second, SecondSharedPb::parse_from_bytes(&bytes).unwrap());
+}
@@ -0,0 +1,29 @@
+use protobuf::EnumOrUnknown;
+use protobuf::Message;
+
+use super::test_enum_non_exhaustive_pb::*;
+
+#[test]
+fn test_non_exhaustive_attr() {
+    let generated = include_str!("test_enum_non_exhaustive_pb.rs");
+    assert!(generated.contains(
+        "#[non_exhaustive]\n// @@protoc_insertion_point(enum:test_enum_non_exhaustive.Fruit)\n"
+    ));
+    assert!(!generated.contains("#[non_exhaustive]\n// @@protoc_insertion_point(enum:test_enum_non_exhaustive.Exhaustive)\n"));
+}
+
+#[test]
+fn test_unknown_value_round_trip() {
+    let mut basket = Basket::new();
+    basket.fruit = EnumOrUnknown::from_i32(17);
+    basket.fruits = vec![
+        EnumOrUnknown::new(Fruit::FRUIT_APPLE),
+        EnumOrUnknown::from_i32(-3),
+    ];
+
+    let parsed = Basket::parse_from_bytes(&basket.write_to_bytes().unwrap()).unwrap();
+    assert_eq!(basket, parsed);
+    assert_eq!(Err(17), parsed.fruit.enum_value());
+    assert_eq!(Ok(Fruit::FRUIT_APPLE), parsed.fruits[0].enum_value());
+    assert_eq!(Err(-3), parsed.fruits[1].enum_value());
+}
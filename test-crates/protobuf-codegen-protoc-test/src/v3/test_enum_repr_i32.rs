@@ -0,0 +1,27 @@
+use std::mem;
+
+use protobuf::Enum;
+
+use super::test_enum_repr_i32_pb::*;
+
+#[test]
+fn test_repr_attr() {
+    let generated = include_str!("test_enum_repr_i32_pb.rs");
+    assert!(generated
+        .contains("#[repr(i32)]\n// @@protoc_insertion_point(enum:test_enum_repr_i32.Sparse)\n"));
+    assert!(!generated
+        .contains("#[repr(i32)]\n// @@protoc_insertion_point(enum:test_enum_repr_i32.NoRepr)\n"));
+}
+
+#[test]
+fn test_discriminants_are_proto_numbers() {
+    assert_eq!(0, Sparse::SPARSE_ZERO as i32);
+    assert_eq!(10, Sparse::SPARSE_TEN as i32);
+    assert_eq!(-3, Sparse::SPARSE_NEGATIVE as i32);
+    assert_eq!(i32::MIN, Sparse::SPARSE_MIN as i32);
+    assert_eq!(i32::MAX, Sparse::SPARSE_MAX as i32);
+    for &value in Sparse::VALUES {
+        assert_eq!(value.value(), value as i32);
+    }
+    assert_eq!(mem::size_of::<i32>(), mem::size_of::<Sparse>());
+}
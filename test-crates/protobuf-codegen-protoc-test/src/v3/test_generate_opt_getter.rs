@@ -0,0 +1,42 @@
+use super::test_generate_opt_getter_pb::*;
+
+#[test]
+fn unset() {
+    let m = TestOptGetter::new();
+    assert_eq!(None, m.int32_field_opt());
+    assert_eq!(None, m.string_field_opt());
+    assert_eq!(None, m.bytes_field_opt());
+    assert_eq!(None, m.enum_field_opt());
+    assert_eq!(None, m.message_field_opt());
+}
+
+#[test]
+fn set() {
+    let mut m = TestOptGetter::new();
+    m.set_int32_field(0);
+    m.set_string_field("abc".to_owned());
+    m.set_bytes_field(b"de".to_vec());
+    m.set_enum_field(Color::RED);
+    m.mut_message_field().value = 10;
+
+    assert_eq!(Some(0), m.int32_field_opt());
+    assert_eq!(Some("abc"), m.string_field_opt());
+    assert_eq!(Some(&b"de"[..]), m.bytes_field_opt());
+    assert_eq!(Some(Color::RED), m.enum_field_opt());
+    assert_eq!(Some(10), m.message_field_opt().map(|m| m.value));
+}
+
+#[test]
+fn oneof() {
+    let mut m = TestOptGetterOneof::new();
+    assert_eq!(None, m.oneof_string_opt());
+    assert_eq!(None, m.oneof_uint64_opt());
+
+    m.set_oneof_uint64(11);
+    assert_eq!(None, m.oneof_string_opt());
+    assert_eq!(Some(11), m.oneof_uint64_opt());
+
+    m.set_oneof_string("fg".to_owned());
+    assert_eq!(Some("fg"), m.oneof_string_opt());
+    assert_eq!(None, m.oneof_uint64_opt());
+}
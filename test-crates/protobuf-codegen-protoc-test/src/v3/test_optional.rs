@@ -57,3 +57,36 @@ fn reflect_oneofs() {
         iii.get_singular(&mut message)
     );
 }
+
+#[test]
+fn reflect_has_field_and_clear() {
+    let descriptor = TestOptionalProto3::descriptor();
+    let iii = descriptor.field_by_name("iii").unwrap();
+    let non_optional = descriptor.field_by_name("non_optional").unwrap();
+
+    let mut message = TestOptionalProto3::new();
+    assert!(!iii.has_field(&message));
+
+    // Explicit presence: zero is set.
+    iii.set_singular_field(&mut message, 0.into());
+    assert!(iii.has_field(&message));
+    iii.clear_field(&mut message);
+    assert!(!iii.has_field(&message));
+    assert_eq!(None, message.iii);
+
+    // Implicit presence: zero is the same as unset.
+    non_optional.set_singular_field(&mut message, 0.into());
+    assert!(!non_optional.has_field(&message));
+    non_optional.set_singular_field(&mut message, 1.into());
+    assert!(non_optional.has_field(&message));
+    non_optional.clear_field(&mut message);
+    assert!(!non_optional.has_field(&message));
+
+    let mut message = descriptor.new_instance();
+    iii.set_singular_field(&mut *message, 0.into());
+    assert!(iii.has_field(&*message));
+    iii.clear_field(&mut *message);
+    assert!(!iii.has_field(&*message));
+    non_optional.set_singular_field(&mut *message, 0.into());
+    assert!(!non_optional.has_field(&*message));
+}
@@ -0,0 +1,112 @@
+//! Check `protobuf` crate can be used without `std`.
+//!
+//! CI builds this crate for a target without `std`.
+
+#![no_std]
+
+use protobuf::wire::Error;
+use protobuf::wire::SliceInputStream;
+use protobuf::wire::SliceOutputStream;
+use protobuf::wire::WireRead;
+use protobuf::wire::WireType;
+use protobuf::wire::WireWrite;
+
+/// Manually encoded message:
+///
+/// ```proto
+/// message Point {
+///     int32 x = 1;
+///     sint64 y = 2;
+///     string label = 3;
+/// }
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Point<'a> {
+    pub x: i32,
+    pub y: i64,
+    pub label: &'a str,
+}
+
+impl<'a> Point<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<Point<'a>, Error> {
+        let mut point = Point::default();
+        let mut is = SliceInputStream::new(bytes);
+        while !is.eof() {
+            match is.read_tag()? {
+                (1, WireType::Varint) => point.x = is.read_int32()?,
+                (2, WireType::Varint) => point.y = is.read_sint64()?,
+                (3, WireType::LengthDelimited) => point.label = is.read_string()?,
+                (_, wire_type) => is.skip_field(wire_type)?,
+            }
+        }
+        Ok(point)
+    }
+
+    /// Write the message into the buffer, return written bytes.
+    pub fn write_to<'b>(&self, buf: &'b mut [u8]) -> Result<&'b [u8], Error> {
+        let mut os = SliceOutputStream::new(buf);
+        if self.x != 0 {
+            os.write_int32(1, self.x)?;
+        }
+        if self.y != 0 {
+            os.write_sint64(2, self.y)?;
+        }
+        if !self.label.is_empty() {
+            os.write_string(3, self.label)?;
+        }
+        let len = os.pos();
+        Ok(&buf[..len])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use protobuf::wire::Error;
+
+    use super::Point;
+
+    #[test]
+    fn round_trip() {
+        let point = Point {
+            x: -1,
+            y: 300,
+            label: "p",
+        };
+        let mut buf = [0; 32];
+        let bytes = point.write_to(&mut buf).unwrap();
+        assert_eq!(
+            &[
+                0x08, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x10, 0xd8, 0x04,
+                0x1a, 0x01, b'p'
+            ],
+            bytes
+        );
+        assert_eq!(point, Point::parse(bytes).unwrap());
+    }
+
+    #[test]
+    fn skip_unknown_fields() {
+        // x = 1, field 4 = "zz", label = "a"
+        let bytes = [0x08, 0x01, 0x22, 0x02, b'z', b'z', 0x1a, 0x01, b'a'];
+        let point = Point::parse(&bytes).unwrap();
+        assert_eq!(
+            Point {
+                x: 1,
+                y: 0,
+                label: "a"
+            },
+            point
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Err(Error::UnexpectedEof), Point::parse(&[0x1a, 0x02, b'a']));
+        let mut buf = [0; 2];
+        let point = Point {
+            label: "long",
+            ..Point::default()
+        };
+        assert_eq!(Err(Error::BufferTooSmall), point.write_to(&mut buf));
+    }
+}
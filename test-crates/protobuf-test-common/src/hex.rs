@@ -58,7 +58,9 @@ mod test {
 
     #[test]
     fn test_decode_hex() {
-        assert_eq!(decode_hex(""), [].to_vec());
+        // `[].to_vec()` is ambiguous when `serde_json` is linked,
+        // because it implements `PartialEq<Value>` for `u8`.
+        assert_eq!(decode_hex(""), Vec::<u8>::new());
         assert_eq!(decode_hex("00"), [0x00u8].to_vec());
         assert_eq!(decode_hex("ff"), [0xffu8].to_vec());
         assert_eq!(decode_hex("AB"), [0xabu8].to_vec());